@@ -63,6 +63,22 @@ pub fn get_rg_prefetch_size() -> usize {
         .unwrap_or_else(|_| std::cmp::max(get_file_prefetch_size(), 128))
 }
 
+/// The memory budget in bytes of a cache inserted by common subplan elimination, a cached
+/// `DataFrame` that is larger is spilled to disk.
+pub fn get_cache_memory_budget() -> Option<usize> {
+    std::env::var("POLARS_CACHE_MEMORY_BUDGET")
+        .ok()
+        .map(|s| s.parse::<usize>().expect("integer"))
+}
+
+/// The number of rows below which the smaller input of an inner join is always broadcast: the hash
+/// table is built on it and the other input probes it, whichever side it is on.
+pub fn get_join_broadcast_rows() -> Option<usize> {
+    std::env::var("POLARS_JOIN_BROADCAST_ROWS")
+        .ok()
+        .map(|s| s.parse::<usize>().expect("integer"))
+}
+
 pub fn force_async() -> bool {
     std::env::var("POLARS_FORCE_ASYNC")
         .map(|value| value == "1")
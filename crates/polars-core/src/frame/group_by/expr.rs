@@ -5,4 +5,16 @@ pub trait PhysicalAggExpr {
     fn evaluate_on_groups(&self, df: &DataFrame, groups: &GroupPositions) -> PolarsResult<Series>;
 
     fn root_name(&self) -> PolarsResult<&PlSmallStr>;
+
+    /// Whether the aggregate of a group is the sum of the aggregates of any split of that group,
+    /// e.g. for `sum` and `count`.
+    fn is_additive(&self) -> bool {
+        false
+    }
+
+    /// Columns of the input that are passed to the aggregation after the aggregated column, e.g.
+    /// the weights of a weighted mean.
+    fn auxiliary_columns(&self) -> &[PlSmallStr] {
+        &[]
+    }
 }
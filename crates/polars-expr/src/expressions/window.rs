@@ -562,9 +562,14 @@ impl PhysicalExpr for WindowExpr {
                                 };
 
                                 PolarsResult::Ok(Arc::new(
-                                    left.hash_join_left(&right, JoinValidation::ManyToMany, true)
-                                        .unwrap()
-                                        .1,
+                                    left.hash_join_left(
+                                        &right,
+                                        JoinValidation::ManyToMany,
+                                        true,
+                                        None,
+                                    )
+                                    .unwrap()
+                                    .1,
                                 ))
                             } else {
                                 let df_right =
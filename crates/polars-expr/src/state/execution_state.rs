@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicI64;
 use std::sync::{Mutex, OnceLock, RwLock};
 use std::time::Duration;
@@ -7,6 +8,7 @@ use bitflags::bitflags;
 use polars_core::config::verbose;
 use polars_core::prelude::*;
 use polars_ops::prelude::ChunkJoinOptIds;
+use polars_utils::cancel::CancelToken;
 use polars_utils::relaxed_cell::RelaxedCell;
 use polars_utils::unique_id::UniqueId;
 
@@ -99,7 +101,23 @@ impl From<u8> for StateFlags {
     }
 }
 
-type CachedValue = Arc<(AtomicI64, OnceLock<DataFrame>)>;
+/// A `DataFrame` cached by a `.cache` call, see [`ExecutionState::get_df_cache`].
+pub enum CachedDataFrame {
+    InMemory(DataFrame),
+    /// Spilled to an IPC file because it exceeded the memory budget of the cache. The file is
+    /// removed when the cache is dropped.
+    Spilled(PathBuf),
+}
+
+impl Drop for CachedDataFrame {
+    fn drop(&mut self) {
+        if let Self::Spilled(path) = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+type CachedValue = Arc<(AtomicI64, OnceLock<CachedDataFrame>)>;
 
 /// State/ cache that is maintained during the Execution of the physical plan.
 #[derive(Clone)]
@@ -114,7 +132,7 @@ pub struct ExecutionState {
     pub flags: RelaxedCell<u8>,
     pub ext_contexts: Arc<Vec<DataFrame>>,
     node_timer: Option<NodeTimer>,
-    stop: Arc<RelaxedCell<bool>>,
+    stop: Arc<CancelToken>,
 }
 
 impl ExecutionState {
@@ -131,7 +149,7 @@ impl ExecutionState {
             flags: RelaxedCell::from(StateFlags::init().as_u8()),
             ext_contexts: Default::default(),
             node_timer: None,
-            stop: Arc::new(RelaxedCell::from(false)),
+            stop: Arc::new(CancelToken::new()),
         }
     }
 
@@ -162,11 +180,11 @@ impl ExecutionState {
     // This is wrong when the U64 overflows which will never happen.
     pub fn should_stop(&self) -> PolarsResult<()> {
         try_raise_keyboard_interrupt();
-        polars_ensure!(!self.stop.load(), ComputeError: "query interrupted");
+        polars_ensure!(!self.stop.is_cancelled(), ComputeError: "query interrupted");
         Ok(())
     }
 
-    pub fn cancel_token(&self) -> Arc<RelaxedCell<bool>> {
+    pub fn cancel_token(&self) -> Arc<CancelToken> {
         self.stop.clone()
     }
 
@@ -26,6 +26,7 @@ pub enum SpecializedColumnPredicate {
     StartsWith(Box<[u8]>),
     EndsWith(Box<[u8]>),
     StartEndsWith(Box<[u8]>, Box<[u8]>),
+    Contains(Box<[u8]>),
 }
 
 #[derive(Clone)]
@@ -64,6 +65,7 @@ impl ColumnPredicateExpr {
                 S::StartsWith(s) => P::StartsWith(s),
                 S::EndsWith(s) => P::EndsWith(s),
                 S::StartEndsWith(start, end) => P::StartEndsWith(start, end),
+                S::Contains(s) => P::Contains(s),
             })
         });
 
@@ -2,7 +2,7 @@ use std::sync::Mutex;
 use std::sync::mpsc::{Receiver, channel};
 
 use polars_core::POOL;
-use polars_utils::relaxed_cell::RelaxedCell;
+use polars_utils::cancel::CancelToken;
 
 use super::*;
 
@@ -45,13 +45,13 @@ impl LazyFrame {
 #[derive(Clone)]
 pub struct InProcessQuery {
     rx: Arc<Mutex<Receiver<PolarsResult<DataFrame>>>>,
-    token: Arc<RelaxedCell<bool>>,
+    token: Arc<CancelToken>,
 }
 
 impl InProcessQuery {
     /// Cancel the query at earliest convenience.
     pub fn cancel(&self) {
-        self.token.store(true)
+        self.token.cancel()
     }
 
     /// Fetch the result.
@@ -72,6 +72,6 @@ impl InProcessQuery {
 
 impl Drop for InProcessQuery {
     fn drop(&mut self) {
-        self.token.store(true);
+        self.token.cancel();
     }
 }
@@ -0,0 +1,73 @@
+//! Module containing an eager join on key expressions.
+//!
+//! [`DataFrameJoinOps::join`] only accepts column names as keys. The functions here evaluate the
+//! key expressions against both frames first, so derived keys don't have to be materialized as
+//! columns.
+
+use polars_core::prelude::*;
+use polars_ops::frame::{DataFrameJoinOps, JoinCoalesce};
+
+use crate::physical_plan::exotic::prepare_expression_for_schema;
+use crate::prelude::*;
+
+/// Join `df` with `other` on the keys computed by `left_on` and `right_on`.
+///
+/// If all keys are plain columns, coalescing behaves as in [`DataFrameJoinOps::join`]. If any key
+/// is computed there are no key columns to coalesce, so both frames keep all their columns.
+pub fn join_on(
+    df: &DataFrame,
+    other: &DataFrame,
+    left_on: &[Expr],
+    right_on: &[Expr],
+    mut args: JoinArgs,
+) -> PolarsResult<DataFrame> {
+    polars_ensure!(
+        left_on.len() == right_on.len(),
+        InvalidOperation: "the number of join keys on the left ({}) and right ({}) must match",
+        left_on.len(),
+        right_on.len()
+    );
+
+    let selected_left = evaluate_keys(df, left_on)?;
+    let selected_right = evaluate_keys(other, right_on)?;
+
+    // Coalescing drops the key columns by name, which is only correct if the keys are columns of
+    // the frames.
+    if !left_on
+        .iter()
+        .chain(right_on)
+        .all(|e| matches!(e, Expr::Column(_)))
+    {
+        args.coalesce = JoinCoalesce::KeepColumns;
+    }
+
+    df._join_impl(
+        other,
+        selected_left,
+        selected_right,
+        args,
+        None,
+        true,
+        false,
+    )
+}
+
+fn evaluate_keys(df: &DataFrame, exprs: &[Expr]) -> PolarsResult<Vec<Series>> {
+    let state = ExecutionState::new();
+    exprs
+        .iter()
+        .map(|expr| {
+            let phys_expr = prepare_expression_for_schema(expr, df.schema(), Context::Default)?;
+            let key = phys_expr.evaluate(df, &state)?;
+            let key = match key.len() {
+                len if len == df.height() => key,
+                1 => key.new_from_index(0, df.height()),
+                len => polars_bail!(
+                    ShapeMismatch: "join key '{}' has length {}, expected {}",
+                    key.name(), len, df.height()
+                ),
+            };
+            Ok(key.take_materialized_series())
+        })
+        .collect()
+}
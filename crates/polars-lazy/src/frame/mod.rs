@@ -6,6 +6,9 @@ mod cached_arenas;
 mod err;
 #[cfg(not(target_arch = "wasm32"))]
 mod exitable;
+pub mod join_on;
+#[cfg(feature = "new_streaming")]
+mod partition_write;
 #[cfg(feature = "pivot")]
 pub mod pivot;
 
@@ -23,6 +26,8 @@ pub use ipc::*;
 pub use ndjson::*;
 #[cfg(feature = "parquet")]
 pub use parquet::*;
+#[cfg(feature = "new_streaming")]
+pub use partition_write::{PartitionAndWrite, PartitionWriteOptions};
 use polars_compute::rolling::QuantileMethod;
 use polars_core::POOL;
 use polars_core::error::feature_gated;
@@ -30,7 +35,7 @@ use polars_core::prelude::*;
 use polars_expr::{ExpressionConversionState, create_physical_expr};
 use polars_io::RowIndex;
 use polars_mem_engine::{Executor, create_multiple_physical_plans, create_physical_plan};
-use polars_ops::frame::{JoinCoalesce, MaintainOrderJoin};
+use polars_ops::frame::{JoinCoalesce, JoinDedupRight, JoinRechunk, MaintainOrderJoin};
 #[cfg(feature = "is_between")]
 use polars_ops::prelude::ClosedInterval;
 pub use polars_plan::frame::{AllowedOptimizations, OptFlags};
@@ -155,6 +160,28 @@ impl LazyFrame {
         self
     }
 
+    /// Return an error if the optimizer changes the names of the output schema.
+    ///
+    /// This check always runs in debug builds.
+    pub fn with_verify_schema(mut self, toggle: bool) -> Self {
+        self.opt_state.set(OptFlags::VERIFY_SCHEMA, toggle);
+        self
+    }
+
+    /// Return an error if the optimization rules still change the plan after reaching a fixed
+    /// point.
+    pub fn with_verify_fixed_point(mut self, toggle: bool) -> Self {
+        self.opt_state.set(OptFlags::VERIFY_FIXED_POINT, toggle);
+        self
+    }
+
+    /// Report the filter predicates that are pushed into each scan, as they were written in the
+    /// query, see [`LazyFrame::optimize_with_report`].
+    pub fn with_predicate_lineage(mut self, toggle: bool) -> Self {
+        self.opt_state.set(OptFlags::PREDICATE_LINEAGE, toggle);
+        self
+    }
+
     /// Toggle predicate pushdown optimization.
     pub fn with_predicate_pushdown(mut self, toggle: bool) -> Self {
         self.opt_state.set(OptFlags::PREDICATE_PUSHDOWN, toggle);
@@ -253,6 +280,18 @@ impl LazyFrame {
         }
     }
 
+    /// Return a String describing the optimized logical plan, with a note above every node that
+    /// an optimization pass added or rewrote, e.g. `(predicate pushed down to here)`.
+    ///
+    /// Returns `Err` if optimizing the logical plan fails.
+    pub fn explain_optimized_annotated(&self) -> PolarsResult<String> {
+        let mut lf = self.clone();
+        let (mut lp_arena, mut expr_arena) = lf.get_arenas();
+        let (node, report) = lf.optimize_with_report(&mut lp_arena, &mut expr_arena)?;
+        let plan = IRPlan::new(node, lp_arena, expr_arena);
+        Ok(plan.as_ref().display_annotated(&report).to_string())
+    }
+
     /// Add a sort operation to the logical plan.
     ///
     /// Sorts the LazyFrame by the column name specified using the provided options.
@@ -505,12 +544,40 @@ impl LazyFrame {
         lp_arena: &mut Arena<IR>,
         expr_arena: &mut Arena<AExpr>,
     ) -> PolarsResult<Node> {
-        self.optimize_with_scratch(lp_arena, expr_arena, &mut vec![])
+        self.optimize_with_scratch(lp_arena, expr_arena, &mut vec![], None, None)
+    }
+
+    /// Optimize the plan like [`LazyFrame::optimize`], taking the row counts of the scans from
+    /// `row_counts` where it knows them.
+    ///
+    /// These row counts are used by [`estimate_cardinalities`] instead of the ones found in the
+    /// metadata of the files.
+    pub fn optimize_with_row_counts(
+        self,
+        lp_arena: &mut Arena<IR>,
+        expr_arena: &mut Arena<AExpr>,
+        row_counts: ScanRowCountOracle<'_>,
+    ) -> PolarsResult<Node> {
+        self.optimize_with_scratch(lp_arena, expr_arena, &mut vec![], None, Some(row_counts))
+    }
+
+    /// Optimize the plan like [`LazyFrame::optimize`], and report which optimization passes
+    /// changed it.
+    pub fn optimize_with_report(
+        self,
+        lp_arena: &mut Arena<IR>,
+        expr_arena: &mut Arena<AExpr>,
+    ) -> PolarsResult<(Node, OptimizationReport)> {
+        let mut report = OptimizationReport::default();
+        let node =
+            self.optimize_with_scratch(lp_arena, expr_arena, &mut vec![], Some(&mut report), None)?;
+        Ok((node, report))
     }
 
     pub fn to_alp_optimized(mut self) -> PolarsResult<IRPlan> {
         let (mut lp_arena, mut expr_arena) = self.get_arenas();
-        let node = self.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![])?;
+        let node =
+            self.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![], None, None)?;
 
         Ok(IRPlan::new(node, lp_arena, expr_arena))
     }
@@ -532,6 +599,8 @@ impl LazyFrame {
         lp_arena: &mut Arena<IR>,
         expr_arena: &mut Arena<AExpr>,
         scratch: &mut Vec<Node>,
+        report: Option<&mut OptimizationReport>,
+        row_counts: Option<ScanRowCountOracle<'_>>,
     ) -> PolarsResult<Node> {
         #[allow(unused_mut)]
         let mut opt_state = self.opt_state;
@@ -562,6 +631,8 @@ impl LazyFrame {
                 let io_expr = phys_expr_to_io_expr(phys_expr);
                 Some(io_expr)
             }),
+            report,
+            row_counts,
         )?;
 
         Ok(lp_top)
@@ -584,7 +655,8 @@ impl LazyFrame {
         let (mut lp_arena, mut expr_arena) = self.get_arenas();
 
         let mut scratch = vec![];
-        let lp_top = self.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut scratch)?;
+        let lp_top =
+            self.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut scratch, None, None)?;
 
         post_opt(
             lp_top,
@@ -966,6 +1038,8 @@ impl LazyFrame {
         sink_options: SinkOptions,
         per_partition_sort_by: Option<Vec<SortColumn>>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
+        expected_keys: Option<Arc<DataFrame>>,
     ) -> PolarsResult<Self> {
         self.sink(SinkType::Partition(PartitionSinkType {
             base_path,
@@ -976,6 +1050,8 @@ impl LazyFrame {
             cloud_options,
             per_partition_sort_by,
             finish_callback,
+            morsel_metrics_callback,
+            expected_keys,
         }))
     }
 
@@ -994,6 +1070,8 @@ impl LazyFrame {
         sink_options: SinkOptions,
         per_partition_sort_by: Option<Vec<SortColumn>>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
+        expected_keys: Option<Arc<DataFrame>>,
     ) -> PolarsResult<Self> {
         self.sink(SinkType::Partition(PartitionSinkType {
             base_path,
@@ -1004,6 +1082,8 @@ impl LazyFrame {
             cloud_options,
             per_partition_sort_by,
             finish_callback,
+            morsel_metrics_callback,
+            expected_keys,
         }))
     }
 
@@ -1022,6 +1102,8 @@ impl LazyFrame {
         sink_options: SinkOptions,
         per_partition_sort_by: Option<Vec<SortColumn>>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
+        expected_keys: Option<Arc<DataFrame>>,
     ) -> PolarsResult<Self> {
         self.sink(SinkType::Partition(PartitionSinkType {
             base_path,
@@ -1032,6 +1114,8 @@ impl LazyFrame {
             cloud_options,
             per_partition_sort_by,
             finish_callback,
+            morsel_metrics_callback,
+            expected_keys,
         }))
     }
 
@@ -1050,6 +1134,8 @@ impl LazyFrame {
         sink_options: SinkOptions,
         per_partition_sort_by: Option<Vec<SortColumn>>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
+        expected_keys: Option<Arc<DataFrame>>,
     ) -> PolarsResult<Self> {
         self.sink(SinkType::Partition(PartitionSinkType {
             base_path,
@@ -1060,6 +1146,8 @@ impl LazyFrame {
             cloud_options,
             per_partition_sort_by,
             finish_callback,
+            morsel_metrics_callback,
+            expected_keys,
         }))
     }
 
@@ -1096,6 +1184,8 @@ impl LazyFrame {
                     if !force_new_streaming
                         && auto_new_streaming
                         && e.downcast_ref::<&str>()
+                            .copied()
+                            .or_else(|| e.downcast_ref::<String>().map(|s| s.as_str()))
                             .map(|s| s.starts_with("not yet implemented"))
                             .unwrap_or(false)
                     {
@@ -1575,6 +1665,14 @@ impl LazyFrame {
             suffix,
             slice,
             nulls_equal,
+            nans_equal,
+            case_insensitive,
+            semi_anti_key_names,
+            semi_anti_from_right,
+            output_columns,
+            key_struct,
+            dedup_right,
+            rechunk,
             coalesce,
             maintain_order,
         } = args;
@@ -1591,15 +1689,32 @@ impl LazyFrame {
             .how(how)
             .validate(validation)
             .join_nulls(nulls_equal)
+            .join_nans(nans_equal)
+            .join_case_insensitive(case_insensitive)
+            .semi_anti_from_right(semi_anti_from_right)
+            .dedup_right(dedup_right)
+            .rechunk(rechunk)
             .coalesce(coalesce)
             .maintain_order(maintain_order);
 
+        if let Some(names) = semi_anti_key_names {
+            builder = builder.semi_anti_key_names(names);
+        }
+
         if let Some(suffix) = suffix {
             builder = builder.suffix(suffix);
         }
+        // Not supported on lazy joins, passed on so that resolving the plan raises an error.
+        builder.key_struct = key_struct;
 
         // Note: args.slice is set by the optimizer
-        builder.finish()
+        let out = builder.finish();
+
+        // The projection of a lazy join is pushed down by the optimizer instead.
+        match output_columns {
+            Some(columns) => out.select(columns.into_iter().map(col).collect::<Vec<_>>()),
+            None => out,
+        }
     }
 
     /// Consume `self` and return a [`JoinBuilder`] to customize a join on this LazyFrame.
@@ -2056,8 +2171,24 @@ impl LazyFrame {
         self.map_private(DslFunction::Unnest(cols))
     }
 
+    /// Merge two frames that are both sorted by the `key` column into a single frame sorted by it.
     #[cfg(feature = "merge_sorted")]
     pub fn merge_sorted<S>(self, other: LazyFrame, key: S) -> PolarsResult<LazyFrame>
+    where
+        S: Into<PlSmallStr>,
+    {
+        self.merge_sorted_with_options(other, key, MergeSortedOptions::default())
+    }
+
+    /// Like [`LazyFrame::merge_sorted`], with `options` describing how both frames are sorted,
+    /// e.g. in descending order or with the nulls last.
+    #[cfg(feature = "merge_sorted")]
+    pub fn merge_sorted_with_options<S>(
+        self,
+        other: LazyFrame,
+        key: S,
+        options: MergeSortedOptions,
+    ) -> PolarsResult<LazyFrame>
     where
         S: Into<PlSmallStr>,
     {
@@ -2067,6 +2198,8 @@ impl LazyFrame {
             input_left: Arc::new(self.logical_plan),
             input_right: Arc::new(other.logical_plan),
             key,
+            descending: options.descending,
+            nulls_last: options.nulls_last,
         };
         Ok(LazyFrame::from_logical_plan(lp, self.opt_state))
     }
@@ -2200,6 +2333,13 @@ pub struct JoinBuilder {
     suffix: Option<PlSmallStr>,
     validation: JoinValidation,
     nulls_equal: bool,
+    nans_equal: bool,
+    case_insensitive: bool,
+    semi_anti_key_names: Option<Vec<PlSmallStr>>,
+    semi_anti_from_right: bool,
+    key_struct: Option<PlSmallStr>,
+    dedup_right: JoinDedupRight,
+    rechunk: JoinRechunk,
     coalesce: JoinCoalesce,
     maintain_order: MaintainOrderJoin,
 }
@@ -2217,6 +2357,13 @@ impl JoinBuilder {
             suffix: None,
             validation: Default::default(),
             nulls_equal: false,
+            nans_equal: true,
+            case_insensitive: false,
+            semi_anti_key_names: None,
+            semi_anti_from_right: false,
+            key_struct: None,
+            dedup_right: Default::default(),
+            rechunk: Default::default(),
             coalesce: Default::default(),
             maintain_order: Default::default(),
         }
@@ -2283,6 +2430,47 @@ impl JoinBuilder {
         self
     }
 
+    /// Join NaN values on each other. By default NaN values match each other.
+    pub fn join_nans(mut self, nans_equal: bool) -> Self {
+        self.nans_equal = nans_equal;
+        self
+    }
+
+    /// Join string keys regardless of their case. The output keeps the original values.
+    pub fn join_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Rename the left key columns in the output of a semi or anti join, one name per key.
+    pub fn semi_anti_key_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<PlSmallStr>,
+    {
+        self.semi_anti_key_names = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Return the right rows with (semi) or without (anti) a match in the left table, instead of
+    /// the left rows.
+    pub fn semi_anti_from_right(mut self, from_right: bool) -> Self {
+        self.semi_anti_from_right = from_right;
+        self
+    }
+
+    /// Reduce the right table of a left join to one row per key, see [`JoinArgs::dedup_right`].
+    pub fn dedup_right(mut self, dedup_right: JoinDedupRight) -> Self {
+        self.dedup_right = dedup_right;
+        self
+    }
+
+    /// Whether the tables are rechunked before joining, see [`JoinArgs::rechunk`].
+    pub fn rechunk(mut self, rechunk: JoinRechunk) -> Self {
+        self.rechunk = rechunk;
+        self
+    }
+
     /// Suffix to add duplicate column names in join.
     /// Defaults to `"_right"` if this method is never called.
     pub fn suffix<S>(mut self, suffix: S) -> Self
@@ -2316,6 +2504,14 @@ impl JoinBuilder {
             suffix: self.suffix,
             slice: None,
             nulls_equal: self.nulls_equal,
+            nans_equal: self.nans_equal,
+            case_insensitive: self.case_insensitive,
+            semi_anti_key_names: self.semi_anti_key_names,
+            semi_anti_from_right: self.semi_anti_from_right,
+            output_columns: None,
+            key_struct: self.key_struct,
+            dedup_right: self.dedup_right,
+            rechunk: self.rechunk,
             coalesce: self.coalesce,
             maintain_order: self.maintain_order,
         };
@@ -2407,6 +2603,14 @@ impl JoinBuilder {
             suffix: self.suffix,
             slice: None,
             nulls_equal: self.nulls_equal,
+            nans_equal: self.nans_equal,
+            case_insensitive: self.case_insensitive,
+            semi_anti_key_names: self.semi_anti_key_names,
+            semi_anti_from_right: self.semi_anti_from_right,
+            output_columns: None,
+            key_struct: self.key_struct,
+            dedup_right: self.dedup_right,
+            rechunk: self.rechunk,
             coalesce: self.coalesce,
             maintain_order: self.maintain_order,
         };
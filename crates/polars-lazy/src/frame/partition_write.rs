@@ -0,0 +1,115 @@
+//! Eager partitioned writing of a [`DataFrame`].
+//!
+//! Writing a `DataFrame` partitioned by key otherwise requires building a lazy query ending in a
+//! partitioned sink, passing a finish callback to get the written partitions and running it on the
+//! streaming engine. [`PartitionAndWrite::partition_and_write`] does all of this in one call.
+
+use std::sync::{Arc, Mutex};
+
+use polars_core::prelude::*;
+use polars_io::cloud::CloudOptions;
+use polars_utils::plpath::PlPath;
+
+use crate::prelude::*;
+
+/// Options for [`PartitionAndWrite::partition_and_write`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartitionWriteOptions {
+    /// Write the key columns into the files.
+    pub include_key: bool,
+    /// Sort the rows of every partition by these columns before writing them.
+    pub per_partition_sort_by: Option<Vec<SortColumn>>,
+    /// Split the rows of a key over several files of at most this many rows.
+    pub max_rows_per_file: Option<IdxSize>,
+    pub sink_options: SinkOptions,
+    pub cloud_options: Option<CloudOptions>,
+}
+
+impl Default for PartitionWriteOptions {
+    fn default() -> Self {
+        Self {
+            include_key: true,
+            per_partition_sort_by: None,
+            max_rows_per_file: None,
+            sink_options: SinkOptions {
+                mkdir: true,
+                ..Default::default()
+            },
+            cloud_options: None,
+        }
+    }
+}
+
+pub trait PartitionAndWrite {
+    /// Write the rows into one directory of files per unique value of `key_cols` under
+    /// `base_path`, in the format and with the writer options of `format`.
+    ///
+    /// Returns the written partitions, with the keys and the metrics of every partition. Only
+    /// Parquet files report their metrics, for other formats no partitions are returned.
+    fn partition_and_write<I, S>(
+        &self,
+        base_path: PlPath,
+        key_cols: I,
+        format: FileType,
+        options: PartitionWriteOptions,
+    ) -> PolarsResult<DataFrame>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<PlSmallStr>;
+}
+
+impl PartitionAndWrite for DataFrame {
+    fn partition_and_write<I, S>(
+        &self,
+        base_path: PlPath,
+        key_cols: I,
+        format: FileType,
+        options: PartitionWriteOptions,
+    ) -> PolarsResult<DataFrame>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<PlSmallStr>,
+    {
+        let key_exprs: Vec<Expr> = key_cols.into_iter().map(|c| col(c.into())).collect();
+        polars_ensure!(
+            !key_exprs.is_empty(),
+            InvalidOperation: "partition_and_write needs at least one key column"
+        );
+
+        let written = Arc::new(Mutex::new(None));
+        let finish_callback = {
+            let written = written.clone();
+            SinkFinishCallback::Rust(SpecialEq::new(Arc::new(move |df: DataFrame| {
+                *written.lock().unwrap() = Some(df);
+                Ok(())
+            })))
+        };
+
+        self.clone()
+            .lazy()
+            .sink(SinkType::Partition(PartitionSinkType {
+                base_path: Arc::new(base_path),
+                file_path_cb: None,
+                sink_options: options.sink_options,
+                variant: PartitionVariant::ByKey {
+                    key_exprs,
+                    include_key: options.include_key,
+                    max_rows_per_file: options.max_rows_per_file,
+                    dry_run: false,
+                    separate_null_keys: false,
+                    flush_morsel_rows: None,
+                    strict_order: false,
+                },
+                file_type: format,
+                cloud_options: options.cloud_options,
+                per_partition_sort_by: options.per_partition_sort_by,
+                finish_callback: Some(finish_callback),
+                morsel_metrics_callback: None,
+                expected_keys: None,
+            }))?
+            .collect_with_engine(Engine::Streaming)?;
+
+        let written = written.lock().unwrap().take();
+        Ok(written.expect("finish callback should be called"))
+    }
+}
@@ -7,10 +7,16 @@
 //! We can do a pivot on an eager `DataFrame` as that is already materialized. The code for the
 //! pivot is here, because we want to be able to pass expressions to the pivot operation.
 //!
+//! If the distinct `on` values are known up front, [`pivot_lazy`] builds the pivot into the plan
+//! as a group by with an aggregation per output column. The schema is then known, so projection
+//! pushdown drops the aggregations of the output columns that aren't used afterwards, and these
+//! cells are never computed.
+//!
 
 use polars_core::frame::group_by::expr::PhysicalAggExpr;
 use polars_core::prelude::*;
-use polars_ops::pivot::PivotAgg;
+use polars_ops::pivot::{PivotAgg, PivotOptions};
+use polars_utils::format_pl_smallstr;
 
 use crate::physical_plan::exotic::{contains_column_refs, prepare_expression_for_context};
 use crate::prelude::*;
@@ -41,6 +47,13 @@ impl PhysicalAggExpr for PivotExpr {
     fn root_name(&self) -> PolarsResult<&PlSmallStr> {
         Ok(PlSmallStr::EMPTY_REF)
     }
+
+    fn is_additive(&self) -> bool {
+        matches!(
+            self.0,
+            Expr::Agg(AggExpr::Sum(_) | AggExpr::Count(_, _)) | Expr::Len
+        )
+    }
 }
 
 pub fn pivot<I0, I1, I2, S0, S1, S2>(
@@ -98,3 +111,213 @@ where
     let agg_expr = agg_expr.map(|ae| PivotAgg(Arc::new(PivotExpr(ae))));
     polars_ops::pivot::pivot_stable(df, on, index, values, sort_columns, agg_expr, separator)
 }
+
+#[allow(clippy::too_many_arguments)]
+pub fn pivot_stable_with_options<I0, I1, I2, S0, S1, S2>(
+    df: &DataFrame,
+    on: I0,
+    index: Option<I1>,
+    values: Option<I2>,
+    sort_columns: bool,
+    agg_expr: Option<Expr>,
+    // used as separator/delimiter in generated column names.
+    separator: Option<&str>,
+    options: &PivotOptions,
+) -> PolarsResult<DataFrame>
+where
+    I0: IntoIterator<Item = S0>,
+    I1: IntoIterator<Item = S1>,
+    I2: IntoIterator<Item = S2>,
+    S0: Into<PlSmallStr>,
+    S1: Into<PlSmallStr>,
+    S2: Into<PlSmallStr>,
+{
+    // we are strict:
+    // agg_expr can only access data as generated by the pivot operation through pl.element()
+    if agg_expr.as_ref().is_some_and(contains_column_refs) {
+        polars_bail!(InvalidOperation: "explicit column references are not allowed in aggregate_function");
+    }
+
+    let agg_expr = agg_expr.map(|ae| PivotAgg(Arc::new(PivotExpr(ae))));
+    polars_ops::pivot::pivot_stable_with_options(
+        df,
+        on,
+        index,
+        values,
+        sort_columns,
+        agg_expr,
+        separator,
+        options,
+    )
+}
+
+/// Pivot with an aggregation expression per suffix, see
+/// [`polars_ops::pivot::pivot_stable_multi_agg`].
+#[allow(clippy::too_many_arguments)]
+pub fn pivot_stable_multi_agg<I0, I1, I2, S0, S1, S2>(
+    df: &DataFrame,
+    on: I0,
+    index: Option<I1>,
+    values: Option<I2>,
+    sort_columns: bool,
+    agg_exprs: Vec<(PlSmallStr, Expr)>,
+    // used as separator/delimiter in generated column names.
+    separator: Option<&str>,
+    options: &PivotOptions,
+) -> PolarsResult<DataFrame>
+where
+    I0: IntoIterator<Item = S0>,
+    I1: IntoIterator<Item = S1>,
+    I2: IntoIterator<Item = S2>,
+    S0: Into<PlSmallStr>,
+    S1: Into<PlSmallStr>,
+    S2: Into<PlSmallStr>,
+{
+    // we are strict:
+    // agg_expr can only access data as generated by the pivot operation through pl.element()
+    if agg_exprs.iter().any(|(_, ae)| contains_column_refs(ae)) {
+        polars_bail!(InvalidOperation: "explicit column references are not allowed in aggregate_function");
+    }
+
+    let aggs = agg_exprs
+        .into_iter()
+        .map(|(suffix, ae)| (suffix, PivotAgg(Arc::new(PivotExpr(ae)))))
+        .collect::<Vec<_>>();
+    polars_ops::pivot::pivot_stable_multi_agg(
+        df,
+        on,
+        index,
+        values,
+        sort_columns,
+        &aggs,
+        separator,
+        options,
+    )
+}
+
+/// Group `df` by `index` and `on`, evaluate `aggs` on every group and pivot the results.
+///
+/// This is equivalent to a `group_by(index + on).agg(aggs)` followed by a `pivot` with `first`
+/// as aggregation, but the aggregated long frame is scattered directly into the wide layout
+/// instead of being grouped a second time by the pivot.
+pub fn pivot_group_by<I0, I1, S0, S1, E>(
+    df: &DataFrame,
+    on: I0,
+    index: I1,
+    aggs: E,
+    sort_columns: bool,
+    // used as separator/delimiter in generated column names.
+    separator: Option<&str>,
+) -> PolarsResult<DataFrame>
+where
+    I0: IntoIterator<Item = S0>,
+    I1: IntoIterator<Item = S1>,
+    S0: Into<PlSmallStr>,
+    S1: Into<PlSmallStr>,
+    E: AsRef<[Expr]>,
+{
+    let on = on.into_iter().map(Into::into).collect::<Vec<PlSmallStr>>();
+    let index = index
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<PlSmallStr>>();
+    let keys = index
+        .iter()
+        .chain(on.iter())
+        .map(|name| col(name.clone()))
+        .collect::<Vec<_>>();
+
+    let grouped = df
+        .clone()
+        .lazy()
+        .group_by_stable(keys)
+        .agg(aggs)
+        .collect()?;
+    let values = grouped
+        .get_column_names()
+        .into_iter()
+        .filter(|c| !(index.contains(c) | on.contains(c)))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let options = PivotOptions {
+        input_is_grouped: true,
+        ..Default::default()
+    };
+    polars_ops::pivot::pivot_stable_with_options(
+        &grouped,
+        on,
+        Some(index),
+        Some(values),
+        sort_columns,
+        None,
+        separator,
+        &options,
+    )
+}
+
+/// Pivot `lf` lazily, with a column per value in `on_columns` instead of per distinct value of
+/// `on`.
+///
+/// Every output cell is `agg_expr` evaluated on the `values` of the rows of its index group with
+/// that `on` value, like [`pivot_stable`] does. The output has a row per distinct `index` in order
+/// of appearance and the `on` values that aren't in `on_columns` are ignored. The columns are
+/// named like the eager pivot names them.
+///
+/// As the output schema is known, the output columns that aren't used by the rest of the query are
+/// never computed.
+pub fn pivot_lazy<I1, I2, S1, S2>(
+    lf: LazyFrame,
+    on: impl Into<PlSmallStr>,
+    on_columns: &Series,
+    index: I1,
+    values: I2,
+    agg_expr: Expr,
+    // used as separator/delimiter in generated column names.
+    separator: Option<&str>,
+) -> PolarsResult<LazyFrame>
+where
+    I1: IntoIterator<Item = S1>,
+    I2: IntoIterator<Item = S2>,
+    S1: Into<PlSmallStr>,
+    S2: Into<PlSmallStr>,
+{
+    // we are strict:
+    // agg_expr can only access data as generated by the pivot operation through pl.element()
+    if contains_column_refs(&agg_expr) {
+        polars_bail!(InvalidOperation: "explicit column references are not allowed in aggregate_function");
+    }
+
+    let on = on.into();
+    let index = index.into_iter().map(col).collect::<Vec<_>>();
+    let values = values
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<PlSmallStr>>();
+    polars_ensure!(!index.is_empty(), InvalidOperation: "lazy pivot needs at least one index column");
+    let sep = separator.unwrap_or("_");
+
+    let headers = on_columns.cast(&DataType::String)?;
+    let headers = headers.str()?;
+    let mut aggs = Vec::with_capacity(values.len() * on_columns.len());
+    for value in &values {
+        for (i, header) in headers.iter().enumerate() {
+            let key = Scalar::new(on_columns.dtype().clone(), on_columns.get(i)?.into_static());
+            let in_cell = col(on.clone()).eq_missing(lit(key));
+            let cell = agg_expr.clone().map_expr(|e| match e {
+                Expr::Column(name) if name.is_empty() => col(value.clone()).filter(in_cell.clone()),
+                e => e,
+            });
+
+            let header = header.unwrap_or("null");
+            let name = if values.len() > 1 {
+                format_pl_smallstr!("{value}{sep}{header}")
+            } else {
+                PlSmallStr::from_str(header)
+            };
+            aggs.push(cell.alias(name));
+        }
+    }
+
+    Ok(lf.group_by_stable(index).agg(aggs))
+}
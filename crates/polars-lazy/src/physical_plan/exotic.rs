@@ -3,6 +3,7 @@ use polars_expr::{ExpressionConversionState, create_physical_expr};
 
 use crate::prelude::*;
 
+#[cfg(feature = "pivot")]
 pub(crate) fn contains_column_refs(expr: &Expr) -> bool {
     for e in expr.into_iter() {
         match e {
@@ -24,19 +25,29 @@ pub(crate) fn contains_column_refs(expr: &Expr) -> bool {
     false
 }
 
+#[cfg(feature = "pivot")]
 pub(crate) fn prepare_expression_for_context(
     name: PlSmallStr,
     expr: &Expr,
     dtype: &DataType,
     ctxt: Context,
+) -> PolarsResult<Arc<dyn PhysicalExpr>> {
+    let input_schema = Schema::from_iter([Field::new(name, dtype.clone())]);
+    prepare_expression_for_schema(expr, &input_schema, ctxt)
+}
+
+/// Create a physical expression that can be evaluated on frames with the given schema.
+pub(crate) fn prepare_expression_for_schema(
+    expr: &Expr,
+    input_schema: &Schema,
+    ctxt: Context,
 ) -> PolarsResult<Arc<dyn PhysicalExpr>> {
     let mut lp_arena = Arena::with_capacity(8);
     let mut expr_arena = Arena::with_capacity(10);
 
     // create a dummy lazyframe and run a very simple optimization run so that
     // type coercion and simplify expression optimizations run.
-    let column = Series::full_null(name, 0, dtype);
-    let df = column.into_frame();
+    let df = DataFrame::empty_with_schema(input_schema);
     let input_schema = df.schema().clone();
     let lf = df
         .lazy()
@@ -1,2 +1 @@
-#[cfg(feature = "pivot")]
 pub(crate) mod exotic;
@@ -354,3 +354,108 @@ fn test_cse_prune_scan_filter_difference() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_cse_merge_scan_projections() -> PolarsResult<()> {
+    let lf = scan_foods_ipc();
+    let IRPlan {
+        lp_top: scan,
+        mut lp_arena,
+        expr_arena,
+    } = lf.clone().to_alp()?;
+
+    // Two scans of the same file that project different columns.
+    let mut project = |columns: [&str; 2]| {
+        let mut ir = lp_arena.get(scan).clone();
+        let IR::Scan {
+            file_info,
+            unified_scan_args,
+            output_schema,
+            ..
+        } = &mut ir
+        else {
+            panic!()
+        };
+        unified_scan_args.projection = Some(columns.into_iter().map(PlSmallStr::from).collect());
+        *output_schema = Some(Arc::new(file_info.schema.try_project(columns).unwrap()));
+        lp_arena.add(ir)
+    };
+    let inputs = vec![
+        project(["category", "calories"]),
+        project(["fats_g", "sugars_g"]),
+    ];
+    let schema: Schema = inputs
+        .iter()
+        .flat_map(|input| {
+            lp_arena
+                .get(*input)
+                .schema(&lp_arena)
+                .iter_fields()
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let top = lp_arena.add(IR::HConcat {
+        inputs,
+        schema: Arc::new(schema),
+        options: Default::default(),
+    });
+
+    let q = LazyFrame::from(DslPlan::IR {
+        node: Some(top),
+        dsl: Arc::new(lf.logical_plan.clone()),
+        version: lp_arena.version(),
+    })
+    .with_comm_subplan_elim(true);
+    q.set_cached_arena(lp_arena.clone(), expr_arena.clone());
+    assert_eq!(count_caches(q.clone()), 2);
+
+    q.set_cached_arena(lp_arena, expr_arena);
+    let out = q.collect()?;
+    let expected = scan_foods_ipc()
+        .select([
+            col("category"),
+            col("calories"),
+            col("fats_g"),
+            col("sugars_g"),
+        ])
+        .collect()?;
+    assert!(out.equals(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_cse_cache_spill() -> PolarsResult<()> {
+    let lf = scan_foods_ipc().with_column(col("category").str().to_uppercase());
+    let q = concat(
+        &[lf.clone(), lf],
+        UnionArgs {
+            rechunk: false,
+            parallel: false,
+            ..Default::default()
+        },
+    )?;
+
+    // A budget of 0 bytes spills every cache inserted by common subplan elimination.
+    unsafe { std::env::set_var("POLARS_CACHE_MEMORY_BUDGET", "0") };
+    let q_cse = q.clone().with_comm_subplan_elim(true);
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q_cse.clone().optimize(&mut lp_arena, &mut expr_arena);
+    let out = q_cse.collect();
+    unsafe { std::env::remove_var("POLARS_CACHE_MEMORY_BUDGET") };
+
+    let lp = lp?;
+    let budgets: Vec<_> = lp_arena
+        .iter(lp)
+        .filter_map(|(_, ir)| match ir {
+            IR::Cache { memory_budget, .. } => Some(*memory_budget),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(budgets, [Some(0), Some(0)]);
+
+    let expected = q.with_comm_subplan_elim(false).collect()?;
+    assert!(out?.equals_missing(&expected));
+
+    Ok(())
+}
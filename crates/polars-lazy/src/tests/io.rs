@@ -805,3 +805,103 @@ fn scan_small_dtypes() -> PolarsResult<()> {
     }
     Ok(())
 }
+
+#[test]
+#[cfg(all(feature = "parquet", feature = "new_streaming"))]
+fn test_partition_and_write() -> PolarsResult<()> {
+    let df = df![
+        "g" => ["a", "b", "a", "b", "a"],
+        "v" => [1i32, 5, 3, 4, 2],
+    ]?;
+    let base_path = std::env::temp_dir().join("polars_test_partition_and_write");
+    let _ = std::fs::remove_dir_all(&base_path);
+
+    let written = df.partition_and_write(
+        PlPath::new(base_path.to_str().unwrap()),
+        ["g"],
+        FileType::Parquet(ParquetWriteOptions::default()),
+        PartitionWriteOptions {
+            include_key: false,
+            per_partition_sort_by: Some(vec![SortColumn {
+                expr: col("v"),
+                descending: true,
+                nulls_last: false,
+            }]),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(written.height(), 2);
+    assert_eq!(written.column("num_rows")?.u64()?.sum(), Some(5));
+
+    let paths = written.column("path")?.str()?;
+    let keys = written.column("keys")?.struct_()?.fields_as_series();
+    let keys = keys[0].str()?;
+    for (path, key) in paths.into_no_null_iter().zip(keys.into_no_null_iter()) {
+        let args = ScanArgsParquet {
+            hive_options: polars_io::HiveOptions::new_disabled(),
+            ..Default::default()
+        };
+        let out = LazyFrame::scan_parquet(PlPath::new(path), args)?.collect()?;
+        let expected = df
+            .clone()
+            .lazy()
+            .filter(col("g").eq(lit(key)))
+            .select([col("v")])
+            .sort(
+                ["v"],
+                SortMultipleOptions::default().with_order_descending(true),
+            )
+            .collect()?;
+        assert!(out.equals(&expected));
+    }
+    std::fs::remove_dir_all(&base_path)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(all(
+    feature = "parquet",
+    feature = "new_streaming",
+    feature = "dtype-struct"
+))]
+fn test_partition_metrics_keys() -> PolarsResult<()> {
+    let s = df![
+        "x" => [1i64, 1, 2],
+        "y" => ["p", "p", "q"],
+    ]?
+    .into_struct("s".into())
+    .into_series();
+    let df = df![
+        "g" => [1i32, 1, 1],
+        "v" => [1.0f64, 2.0, 3.0],
+    ]?
+    .hstack(&[s.clone().into_column()])?;
+    let base_path = std::env::temp_dir().join("polars_test_partition_metrics_keys");
+    let _ = std::fs::remove_dir_all(&base_path);
+
+    let written = df
+        .partition_and_write(
+            PlPath::new(base_path.to_str().unwrap()),
+            ["g", "s"],
+            FileType::Parquet(ParquetWriteOptions::default()),
+            Default::default(),
+        )?
+        .sort(["num_rows"], Default::default())?;
+    // Every key is a field of the `keys` column with the dtype of the key.
+    let keys = written.column("keys")?.struct_()?.fields_as_series();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].name(), "g");
+    assert_eq!(keys[0].dtype(), &DataType::Int32);
+    assert_eq!(keys[1].name(), "s");
+    assert_eq!(keys[1].dtype(), s.dtype());
+    let expected = df![
+        "x" => [2i64, 1],
+        "y" => ["q", "p"],
+    ]?
+    .into_struct("s".into())
+    .into_series();
+    assert!(keys[1].equals(&expected));
+    assert_eq!(keys[0].i32()?.to_vec(), [Some(1), Some(1)]);
+    std::fs::remove_dir_all(&base_path)?;
+    Ok(())
+}
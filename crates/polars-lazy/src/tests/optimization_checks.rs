@@ -94,6 +94,26 @@ fn test_pred_pd_1() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_pred_pd_no_pushdown() -> PolarsResult<()> {
+    let df = fruits_cars();
+    let q = df.clone().lazy().join(
+        df.lazy().select([col("A"), col("B").alias("C")]),
+        [col("A")],
+        [col("A")],
+        JoinType::Inner.into(),
+    );
+
+    assert!(predicate_at_scan(q.clone().filter(col("B").gt(lit(1)))));
+
+    // The filter must stay above the join.
+    let q = q.filter(col("B").gt(lit(1)).no_pushdown());
+    assert!(!predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 4);
+
+    Ok(())
+}
+
 #[test]
 fn test_no_left_join_pass() -> PolarsResult<()> {
     let df1 = df![
@@ -242,6 +262,74 @@ pub fn test_slice_pushdown_sort() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_slice_pushdown_top_k_left_join() -> PolarsResult<()> {
+    use polars_ops::frame::MaintainOrderJoin;
+
+    let accounts = df![
+        "id" => [1, 2, 3, 4, 5],
+        "balance" => [30, 50, 10, 40, 20],
+    ]?
+    .lazy();
+    let transactions = df![
+        "id" => [2, 2, 4, 1, 5, 2],
+        "amount" => [1, 2, 3, 4, 5, 6],
+    ]?
+    .lazy();
+    let top_k = |q: LazyFrame| {
+        q.sort(
+            ["balance"],
+            SortMultipleOptions::default()
+                .with_order_descending(true)
+                .with_maintain_order(true),
+        )
+        .slice(0, 3)
+    };
+    let join = |how: JoinType| {
+        accounts.clone().join(
+            transactions.clone(),
+            [col("id")],
+            [col("id")],
+            JoinArgs {
+                maintain_order: MaintainOrderJoin::Left,
+                ..JoinArgs::new(how)
+            },
+        )
+    };
+    let has_top_k_on_left = |q: LazyFrame| {
+        let (mut expr_arena, mut lp_arena) = get_arenas();
+        let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+        lp_arena.iter(lp).any(|(_, lp)| match lp {
+            IR::Join { input_left, .. } => matches!(
+                lp_arena.get(*input_left),
+                IR::Sort {
+                    slice: Some((0, 3)),
+                    ..
+                }
+            ),
+            _ => false,
+        })
+    };
+
+    // The top-k by a left column is also computed on the left input.
+    let q = top_k(join(JoinType::Left));
+    assert!(has_top_k_on_left(q.clone()));
+    let expected = q.clone().with_slice_pushdown(false).collect()?;
+    assert!(q.collect()?.equals(&expected));
+
+    // The top-k by a right column needs all left rows.
+    let q = join(JoinType::Left)
+        .sort(["amount"], SortMultipleOptions::default())
+        .slice(0, 3);
+    assert!(!has_top_k_on_left(q));
+
+    // An inner join drops the left rows without a match.
+    let q = top_k(join(JoinType::Inner));
+    assert!(!has_top_k_on_left(q));
+
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "dtype-i16")]
 pub fn test_predicate_block_cast() -> PolarsResult<()> {
@@ -650,3 +738,628 @@ fn test_cluster_with_columns_chain() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_verify_schema() -> PolarsResult<()> {
+    let df = df!("a" => [1, 2, 3], "b" => [4, 5, 6])?;
+
+    let out = df
+        .lazy()
+        .with_verify_schema(true)
+        .with_columns([(col("a") + col("b")).alias("c")])
+        .filter(col("a").gt(lit(1)))
+        .select([col("c"), col("a")])
+        .collect()?;
+    assert_eq!(out.get_column_names(), &["c", "a"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_fixed_point() -> PolarsResult<()> {
+    let df = df!("a" => [1, 2, 3], "b" => [4, 5, 6])?;
+
+    let out = df
+        .lazy()
+        .with_verify_fixed_point(true)
+        .with_columns([(col("a") + lit(1) + lit(2)).alias("c")])
+        .filter(col("a").gt(lit(1)).and(lit(true)))
+        .select([col("c"), col("b").cast(DataType::Int64)])
+        .collect()?;
+    assert_eq!(out.get_column_names(), &["c", "b"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_reorder_predicates() -> PolarsResult<()> {
+    let df = df!("a" => [1, 2, 3], "b" => [4, 5, 6])?;
+
+    let q = df
+        .lazy()
+        .filter(col("a").gt(lit(1)).and(col("b").eq(lit(5))));
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let predicate = lp_arena
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            IR::Filter { predicate, .. } => Some(predicate.node()),
+            _ => None,
+        })
+        .unwrap();
+
+    // The equality is cheaper and more selective than the range, so it is evaluated first.
+    let terms = MintermIter::new(predicate, &expr_arena).collect::<Vec<_>>();
+    assert_eq!(terms.len(), 2);
+    assert!(matches!(
+        expr_arena.get(terms[0]),
+        AExpr::BinaryExpr {
+            op: Operator::Eq,
+            ..
+        }
+    ));
+
+    let out = q.collect()?;
+    assert_eq!(out.column("a")?.i32()?.get(0), Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_remove_noop_with_columns() -> PolarsResult<()> {
+    let df = df!("a" => [1, 2, 3], "b" => [4, 5, 6])?;
+
+    let q = df.clone().lazy().with_columns([col("a"), col("b")]);
+    let optimized = q.clone().to_alp_optimized()?.describe();
+    assert_eq!(num_occurrences(&optimized, "WITH_COLUMNS"), 0);
+    assert!(q.collect()?.equals(&df));
+
+    // Renames and casts change the output and have to be kept.
+    let q = df.clone().lazy().with_columns([
+        col("a"),
+        col("a").alias("c"),
+        col("b").cast(DataType::Float64),
+    ]);
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let names = lp_arena
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            IR::HStack { exprs, .. } => Some(
+                exprs
+                    .iter()
+                    .map(|e| e.output_name().clone())
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(names, ["c", "b"]);
+    let out = q.collect()?;
+    assert_eq!(out.column("b")?.dtype(), &DataType::Float64);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "semi_anti_join", feature = "diagonal_concat"))]
+fn test_collapse_reconcile_union() -> PolarsResult<()> {
+    let a = df!("key" => [1, 2, 3, 3], "a" => ["x", "y", "z", "w"])?.lazy();
+    let b = df!("key" => [3, 4, 4, 5], "b" => [10, 20, 30, 40])?.lazy();
+
+    let q = concat_lf_diagonal(
+        [
+            b.clone().anti_join(a.clone(), col("key"), col("key")),
+            a.clone().inner_join(b.clone(), col("key"), col("key")),
+            a.clone().anti_join(b.clone(), col("key"), col("key")),
+        ],
+        UnionArgs {
+            maintain_order: false,
+            ..Default::default()
+        },
+    )?;
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let joins = lp_arena
+        .iter(lp)
+        .filter_map(|(_, lp)| match lp {
+            IR::Join { options, .. } => Some(options.args.how.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(joins, [JoinType::Full]);
+
+    let sort = |df: DataFrame| {
+        df.sort(
+            ["key", "a", "b"],
+            SortMultipleOptions::default().with_nulls_last(true),
+        )
+    };
+    let out = sort(q.clone().collect()?)?;
+    let expected = sort(q.with_collapse_joins(false).collect()?)?;
+    assert_eq!(out.get_column_names(), &["key", "b", "a"]);
+    assert!(out.equals_missing(&expected));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "semi_anti_join", feature = "diagonal_concat"))]
+fn test_collapse_reconcile_union_shared_column() -> PolarsResult<()> {
+    // The inner join suffixes the right `val`, but the right-only rows fill the unsuffixed `val`
+    // in the union, so this isn't a full join.
+    let a = df!("key" => [1, 2, 3], "val" => [1, 2, 3])?.lazy();
+    let b = df!("key" => [3, 4, 5], "val" => [30, 40, 50])?.lazy();
+
+    let q = concat_lf_diagonal(
+        [
+            b.clone().anti_join(a.clone(), col("key"), col("key")),
+            a.clone().inner_join(b.clone(), col("key"), col("key")),
+            a.clone().anti_join(b.clone(), col("key"), col("key")),
+        ],
+        UnionArgs {
+            maintain_order: false,
+            ..Default::default()
+        },
+    )?;
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!(!lp_arena.iter(lp).any(|(_, lp)| matches!(
+        lp,
+        IR::Join { options, .. } if options.args.how == JoinType::Full
+    )));
+
+    let sort = |df: DataFrame| {
+        df.sort(
+            ["key", "val"],
+            SortMultipleOptions::default().with_nulls_last(true),
+        )
+    };
+    let out = sort(q.clone().collect()?)?;
+    let expected = sort(q.with_collapse_joins(false).collect()?)?;
+    assert!(out.equals_missing(&expected));
+    assert_eq!(
+        out.column("val")?.i32()?.into_iter().collect::<Vec<_>>(),
+        [Some(1), Some(2), Some(3), Some(40), Some(50)]
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "cross_join")]
+fn test_collapse_joins_filters_inputs() -> PolarsResult<()> {
+    let a = df!("key" => [1, 2, 3, 4], "x" => [1, 2, 3, 4])?.lazy();
+    let b = df!("key" => [2, 3, 4, 5], "x" => [5, 4, 3, 2])?.lazy();
+
+    let q = a
+        .cross_join(b, None)
+        .filter(
+            col("key")
+                .eq(col("key_right"))
+                .and(col("x").gt(lit(1)))
+                .and(col("x_right").lt(lit(5))),
+        )
+        .with_predicate_pushdown(false);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let (how, input_left, input_right) = lp_arena
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            IR::Join {
+                options,
+                input_left,
+                input_right,
+                ..
+            } => Some((options.args.how.clone(), *input_left, *input_right)),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(how, JoinType::Inner);
+    assert!(matches!(lp_arena.get(input_left), IR::Filter { .. }));
+    assert!(matches!(lp_arena.get(input_right), IR::Filter { .. }));
+
+    let sort = |df: DataFrame| df.sort(["key"], SortMultipleOptions::default());
+    let out = sort(q.clone().collect()?)?;
+    let expected = sort(q.with_collapse_joins(false).collect()?)?;
+    assert!(out.equals_missing(&expected));
+    assert_eq!(out.height(), 2);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "cum_agg")]
+fn test_slice_pushdown_partial_with_columns() -> PolarsResult<()> {
+    let df = df!("a" => [1, 2, 3, 4], "b" => [4, 5, 6, 7])?;
+
+    let q = df
+        .clone()
+        .lazy()
+        .with_columns([
+            (col("b") * lit(2)).alias("c"),
+            col("a").cum_sum(false),
+            col("b").alias("d"),
+        ])
+        .slice(1, 2);
+
+    // The elementwise expressions are computed above the slice, `cum_sum` below it.
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let hstack_names = |node| match lp_arena.get(node) {
+        IR::HStack { exprs, .. } => exprs
+            .iter()
+            .map(|e| e.output_name().clone())
+            .collect::<Vec<_>>(),
+        _ => panic!(),
+    };
+    let (upper, slice) = lp_arena
+        .iter(lp)
+        .find_map(|(node, lp)| match lp {
+            IR::HStack { input, .. } => Some((node, *input)),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(hstack_names(upper), ["c", "d"]);
+    let IR::Slice { input: lower, .. } = lp_arena.get(slice) else {
+        panic!()
+    };
+    assert_eq!(hstack_names(*lower), ["a"]);
+
+    let out = q.collect()?;
+    let expected = df!(
+        "a" => [3, 6],
+        "b" => [5, 6],
+        "c" => [10, 12],
+        "d" => [5, 6],
+    )?;
+    assert!(out.equals(&expected));
+
+    // `d` reads the input `a`, which `cum_sum` overwrites, so nothing can be split off.
+    let q = df
+        .lazy()
+        .with_columns([col("a").cum_sum(false), col("a").alias("d")])
+        .slice(1, 2);
+    let optimized = q.clone().to_alp_optimized()?.describe();
+    assert_eq!(num_occurrences(&optimized, "WITH_COLUMNS"), 1);
+    let out = q.collect()?;
+    assert!(out.column("d")?.equals(&Column::new("d".into(), [2, 3])));
+
+    Ok(())
+}
+
+#[test]
+fn test_push_distinct_below_with_columns() -> PolarsResult<()> {
+    let df = df!("a" => [1, 1, 2, 2], "b" => [1, 2, 3, 4])?;
+    let unique =
+        |lf: LazyFrame| lf.unique_stable(Some(by_name(["a"], true)), UniqueKeepStrategy::First);
+
+    let q = unique(
+        df.clone()
+            .lazy()
+            .with_column((col("b") * lit(10)).alias("c")),
+    );
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let IR::HStack { input, .. } = lp_arena.get(lp) else {
+        panic!()
+    };
+    assert!(matches!(lp_arena.get(*input), IR::Distinct { .. }));
+
+    let out = q.collect()?;
+    let expected = df!("a" => [1, 2], "b" => [1, 3], "c" => [10, 30])?;
+    assert!(out.equals(&expected));
+
+    // The subset column is overwritten, so the `unique` has to stay on top.
+    let q = unique(df.lazy().with_column(col("b").alias("a")));
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!(matches!(lp_arena.get(lp), IR::Distinct { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_prune_empty_plans() -> PolarsResult<()> {
+    let left = df!("a" => [1, 2, 3], "b" => [4, 5, 6])?.lazy();
+    let right = df!("a" => [1, 2], "c" => ["x", "y"])?.lazy();
+
+    let mut q = left
+        .filter(col("a").gt(lit(1)).and(lit(false)))
+        .inner_join(right, col("a"), col("a"))
+        .with_column((col("b") * lit(2)).alias("d"));
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!(matches!(lp_arena.get(lp), IR::DataFrameScan { df, .. } if df.height() == 0));
+
+    let out = q.clone().collect()?;
+    assert_eq!(out.height(), 0);
+    assert_eq!(out.schema(), &q.collect_schema()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_optimization_report() -> PolarsResult<()> {
+    let df = df!("a" => [1, 2, 3], "b" => [4, 5, 6])?;
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let (_, report) = df
+        .clone()
+        .lazy()
+        .optimize_with_report(&mut lp_arena, &mut expr_arena)?;
+    assert_eq!(report, OptimizationReport::default());
+
+    let q = df
+        .lazy()
+        .with_column((col("b") * lit(2)).alias("c"))
+        .filter(col("a").gt(lit(1)));
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let (_, report) = q.optimize_with_report(&mut lp_arena, &mut expr_arena)?;
+    let pass = report
+        .passes
+        .iter()
+        .find(|p| p.name == "predicate_pushdown")
+        .unwrap();
+    assert!(pass.nodes_changed > 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_explain_optimized_annotated() -> PolarsResult<()> {
+    let df = df!("a" => [1, 2, 3], "b" => [4, 5, 6])?;
+    let q = df
+        .lazy()
+        .with_column((col("b") * lit(2)).alias("c"))
+        .filter(col("a").gt(lit(1)));
+
+    let explained = q.explain_optimized_annotated()?;
+    let lines: Vec<_> = explained.lines().collect();
+    // The filter was pushed below the `with_columns`, the note is on the line above it.
+    let filter = lines.iter().position(|l| l.contains("FILTER")).unwrap();
+    assert!(filter > 0);
+    assert!(lines[filter - 1].contains("predicate pushed down to here"));
+    // Without annotations the plan is the same.
+    assert_eq!(
+        explained
+            .lines()
+            .filter(|l| !l.trim_start().starts_with('('))
+            .collect::<Vec<_>>(),
+        q.explain(true)?.lines().collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_predicate_lineage() -> PolarsResult<()> {
+    let q = scan_foods_csv()
+        .select([col("calories").alias("cal"), col("category")])
+        .filter(col("cal").gt(lit(100)))
+        .with_predicate_lineage(true);
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let (_, report) = q.optimize_with_report(&mut lp_arena, &mut expr_arena)?;
+
+    let [lineage] = report.predicate_lineage.as_slice() else {
+        panic!()
+    };
+    let IR::Scan {
+        predicate: Some(predicate),
+        ..
+    } = lp_arena.get(lineage.node)
+    else {
+        panic!()
+    };
+    // The scan filters on the renamed column, the lineage on the column of the filter.
+    assert_eq!(
+        aexpr_to_leaf_names(predicate.node(), &expr_arena),
+        ["calories"]
+    );
+    let [pushed] = lineage.predicates.as_slice() else {
+        panic!()
+    };
+    assert_eq!(aexpr_to_leaf_names(pushed.node(), &expr_arena), ["cal"]);
+
+    // Nothing is recorded without the flag.
+    let q = scan_foods_csv().filter(col("calories").gt(lit(100)));
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let (_, report) = q.optimize_with_report(&mut lp_arena, &mut expr_arena)?;
+    assert!(report.predicate_lineage.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_estimate_cardinalities() -> PolarsResult<()> {
+    let facts = df!("k" => (0..100).map(|i| i % 10).collect::<Vec<i32>>())?.lazy();
+    let dims = df!("k" => (0..10).collect::<Vec<i32>>(), "v" => vec![1; 10])?.lazy();
+    let estimate = |q: LazyFrame| -> PolarsResult<Option<usize>> {
+        let (mut expr_arena, mut lp_arena) = get_arenas();
+        let lp = q.optimize(&mut lp_arena, &mut expr_arena)?;
+        Ok(estimate_cardinalities(lp, &lp_arena, &expr_arena)[&lp])
+    };
+
+    assert_eq!(estimate(facts.clone())?, Some(100));
+    assert_eq!(
+        estimate(facts.clone().filter(col("k").eq(lit(1))))?,
+        Some(10)
+    );
+    assert_eq!(
+        estimate(facts.clone().group_by([col("k")]).agg([len()]))?,
+        Some(10)
+    );
+    assert_eq!(estimate(facts.clone().slice(10, 20))?, Some(20));
+
+    // Every row of the larger input is assumed to find one match.
+    let join = |left: LazyFrame, right: LazyFrame, how: JoinType| {
+        left.join(right, [col("k")], [col("k")], how.into())
+    };
+    assert_eq!(
+        estimate(join(facts.clone(), dims.clone(), JoinType::Inner))?,
+        Some(100)
+    );
+    assert_eq!(
+        estimate(join(dims.clone(), facts.clone(), JoinType::Inner))?,
+        Some(100)
+    );
+    assert_eq!(
+        estimate(join(
+            facts.clone(),
+            dims.clone().slice(0, 0),
+            JoinType::Inner
+        ))?,
+        Some(0)
+    );
+    assert_eq!(
+        estimate(join(facts.clone(), dims.clone(), JoinType::Full))?,
+        Some(110)
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_optimize_with_row_counts() -> PolarsResult<()> {
+    let row_counts = |source: &ScanSource| match source {
+        ScanSource::Path(path) if path.to_str().ends_with("foods1.csv") => Some(1000),
+        _ => None,
+    };
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp =
+        scan_foods_csv().optimize_with_row_counts(&mut lp_arena, &mut expr_arena, &row_counts)?;
+    let estimates = estimate_cardinalities(lp, &lp_arena, &expr_arena);
+    assert_eq!(estimates[&lp], Some(1000));
+
+    // The predicate is pushed into the scan and scales its row count.
+    let q = scan_foods_csv().filter(col("calories").eq(lit(100)));
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.optimize_with_row_counts(&mut lp_arena, &mut expr_arena, &row_counts)?;
+    let estimates = estimate_cardinalities(lp, &lp_arena, &expr_arena);
+    assert_eq!(estimates[&lp], Some(100));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_push_cast_to_scan() -> PolarsResult<()> {
+    let mut df = df!("a" => [1i32, 2, 3], "b" => [1.5f32, 2.5, 3.5])?;
+    let path = std::env::temp_dir().join("polars_test_push_cast_to_scan.parquet");
+    ParquetWriter::new(std::fs::File::create(&path)?).finish(&mut df)?;
+    let scan = || {
+        LazyFrame::scan_parquet(
+            PlPath::new(path.to_str().unwrap()),
+            ScanArgsParquet::default(),
+        )
+        .unwrap()
+    };
+    let scan_dtypes = |q: LazyFrame| {
+        let (mut expr_arena, mut lp_arena) = get_arenas();
+        let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+        let has_hstack = lp_arena
+            .iter(lp)
+            .any(|(_, lp)| matches!(lp, IR::HStack { .. }));
+        let (_, IR::Scan { file_info, .. }) = lp_arena
+            .iter(lp)
+            .find(|(_, lp)| matches!(lp, IR::Scan { .. }))
+            .unwrap()
+        else {
+            unreachable!()
+        };
+        let schema = file_info.schema.clone();
+        (has_hstack, schema)
+    };
+
+    // Lossless upcasts are read as the target dtype.
+    let q = scan().with_columns([
+        col("a").cast(DataType::Int64),
+        col("b").cast(DataType::Float64),
+    ]);
+    let (has_hstack, schema) = scan_dtypes(q.clone());
+    assert!(!has_hstack);
+    assert_eq!(schema.get("a"), Some(&DataType::Int64));
+    assert_eq!(schema.get("b"), Some(&DataType::Float64));
+    let out = q.collect()?;
+    assert_eq!(out, df!("a" => [1i64, 2, 3], "b" => [1.5f64, 2.5, 3.5])?);
+
+    // A lossy cast stays on top of the scan.
+    let q = scan().with_column(col("a").cast(DataType::Int8));
+    let (has_hstack, schema) = scan_dtypes(q.clone());
+    assert!(has_hstack);
+    assert_eq!(schema.get("a"), Some(&DataType::Int32));
+    assert_eq!(q.collect()?.column("a")?.dtype(), &DataType::Int8);
+
+    // The column is used by another expression, which must see the file dtype.
+    let q = scan().with_columns([
+        col("a").cast(DataType::Int64),
+        (col("a") * lit(2)).alias("c"),
+    ]);
+    let (has_hstack, schema) = scan_dtypes(q);
+    assert!(has_hstack);
+    assert_eq!(schema.get("a"), Some(&DataType::Int32));
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pivot")]
+fn test_pivot_lazy_projection_pushdown() -> PolarsResult<()> {
+    use crate::frame::pivot::{pivot_lazy, pivot_stable};
+
+    let df = df![
+        "i" => [1, 1, 2, 2, 3],
+        "k" => ["a", "b", "c", "b", "a"],
+        "v" => [1, 2, 3, 4, 5],
+    ]?;
+    let on_columns = Series::new("k".into(), ["a", "b", "c"]);
+    let q = pivot_lazy(
+        df.clone().lazy(),
+        "k",
+        &on_columns,
+        ["i"],
+        ["v"],
+        col("").sum(),
+        None,
+    )?
+    .select([col("i"), col("b")]);
+
+    // Only the aggregation of the selected column is left.
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let aggs = lp_arena
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            IR::GroupBy { aggs, .. } => Some(
+                aggs.iter()
+                    .map(|e| e.output_name().clone())
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(aggs, ["b"]);
+
+    let out = q.collect()?;
+    let expected = pivot_stable(
+        &df,
+        ["k"],
+        Some(["i"]),
+        Some(["v"]),
+        false,
+        Some(col("").sum()),
+        None,
+    )?
+    .select(["i", "b"])?;
+    assert!(out.equals_missing(&expected));
+
+    Ok(())
+}
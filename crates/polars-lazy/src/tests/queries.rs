@@ -1970,3 +1970,107 @@ fn test_over_with_options_empty_join() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_morsel_memory_budget_join_zip() -> PolarsResult<()> {
+    let _guard = SINGLE_LOCK.lock().unwrap();
+    // Every morsel exceeds the budget, so the sources send them one at a time. The join buffers
+    // samples of both inputs and the zip buffers the heads of its inputs, neither may stall the
+    // sources.
+    polars_stream::set_morsel_memory_budget(Some(1));
+    let n = 250_000i64;
+    let left = df![
+        "a" => (0..n).collect::<Vec<_>>(),
+    ]?;
+    let right = df![
+        "a" => (0..n).rev().collect::<Vec<_>>(),
+        "b" => (0..n).rev().map(|a| 2 * a).collect::<Vec<_>>(),
+    ]?;
+    let joined = left
+        .clone()
+        .lazy()
+        .inner_join(right.lazy(), col("a"), col("a"));
+    let other = left.lazy().select([col("a").alias("c")]);
+    let out = concat_lf_horizontal([joined, other], UnionArgs::default())
+        .and_then(|lf| lf.with_new_streaming(true).collect());
+    polars_stream::set_morsel_memory_budget(None);
+    let out = out?;
+
+    assert_eq!(out.height(), n as usize);
+    let sum = |name: &str| out.column(name)?.as_materialized_series().sum::<i64>();
+    assert_eq!(sum("b")?, 2 * sum("a")?);
+    assert_eq!(sum("c")?, sum("a")?);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_morsel_memory_budget_cse_self_join() -> PolarsResult<()> {
+    let _guard = SINGLE_LOCK.lock().unwrap();
+    // The common subplan is read once and multiplexed to both sides of the join. The multiplexer
+    // buffers the morsels for the probe side while the build side is consumed, those may not
+    // stall the source.
+    polars_stream::set_morsel_memory_budget(Some(1));
+    let n = 250_000i64;
+    let lf = df![
+        "a" => (0..n).collect::<Vec<_>>(),
+    ]?
+    .lazy()
+    .with_column((col("a") * lit(2i64)).alias("b"));
+    let out = lf
+        .clone()
+        .inner_join(lf, col("a"), col("a"))
+        .with_comm_subplan_elim(true)
+        .with_new_streaming(true)
+        .collect();
+    polars_stream::set_morsel_memory_budget(None);
+    let out = out?;
+
+    assert_eq!(out.height(), n as usize);
+    let sum = |name: &str| out.column(name)?.as_materialized_series().sum::<i64>();
+    assert_eq!(sum("b")?, sum("b_right")?);
+    assert_eq!(sum("b")?, 2 * sum("a")?);
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "new_streaming", feature = "asof_join"))]
+fn test_streaming_asof_join_by_multiple_morsels() -> PolarsResult<()> {
+    use polars_ops::frame::{AsOfOptions, AsofStrategy};
+
+    // Both sides span several morsels, so the right rows arrive while the left side is joined.
+    // The right keys have runs of equal values across groups.
+    let n = 250_000i64;
+    let left = df![
+        "t" => (0..n).map(|i| i * 3).collect::<Vec<_>>(),
+        "g" => (0..n).map(|i| i % 5).collect::<Vec<_>>(),
+    ]?;
+    let right = df![
+        "t" => (0..n).map(|i| i / 2 * 7).collect::<Vec<_>>(),
+        "g" => (0..n).map(|i| i % 3).collect::<Vec<_>>(),
+        "v" => (0..n).collect::<Vec<_>>(),
+    ]?;
+
+    for strategy in [
+        AsofStrategy::Backward,
+        AsofStrategy::Forward,
+        AsofStrategy::Nearest,
+    ] {
+        let args = JoinArgs::new(JoinType::AsOf(Box::new(AsOfOptions {
+            strategy,
+            left_by: Some(vec!["g".into()]),
+            right_by: Some(vec!["g".into()]),
+            allow_eq: true,
+            ..Default::default()
+        })));
+        let lf = left
+            .clone()
+            .lazy()
+            .join(right.clone().lazy(), [col("t")], [col("t")], args);
+        let expected = lf.clone().collect()?;
+        let out = lf.with_new_streaming(true).collect()?;
+        assert!(out.equals_missing(&expected), "{strategy:?}");
+    }
+    Ok(())
+}
@@ -1,17 +1,60 @@
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering};
 
+use polars_expr::state::CachedDataFrame;
 #[cfg(feature = "async")]
 use polars_io::pl_async;
 use polars_utils::unique_id::UniqueId;
 
 use super::*;
 
+#[cfg_attr(not(feature = "ipc"), allow(unused))]
+static NEXT_SPILL_FILE_ID: AtomicU64 = AtomicU64::new(0);
+
 pub struct CacheExec {
     pub input: Option<Box<dyn Executor>>,
     pub id: UniqueId,
     /// `(cache_hits_before_drop - 1)`
     pub count: u32,
     pub is_new_streaming_scan: bool,
+    /// Spill the cached `DataFrame` to an IPC file if its estimated size exceeds this.
+    pub memory_budget: Option<usize>,
+}
+
+impl CacheExec {
+    #[cfg(feature = "ipc")]
+    fn spill(&self, mut df: DataFrame) -> PolarsResult<CachedDataFrame> {
+        use polars_io::SerWriter;
+        use polars_io::ipc::IpcWriter;
+
+        let path = polars_io::path_utils::POLARS_TEMP_DIR_BASE_PATH.join(format!(
+            "cache-spill-{}-{}.ipc",
+            std::process::id(),
+            NEXT_SPILL_FILE_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        // Create the entry first, so the file is removed if writing fails.
+        let cached = CachedDataFrame::Spilled(path);
+        IpcWriter::new(file).finish(&mut df)?;
+        Ok(cached)
+    }
+
+    #[cfg(not(feature = "ipc"))]
+    fn spill(&self, df: DataFrame) -> PolarsResult<CachedDataFrame> {
+        Ok(CachedDataFrame::InMemory(df))
+    }
+
+    #[cfg(feature = "ipc")]
+    fn read_spilled(path: &std::path::Path) -> PolarsResult<DataFrame> {
+        use polars_io::SerReader;
+        use polars_io::ipc::IpcReader;
+
+        IpcReader::new(std::fs::File::open(path)?).finish()
+    }
+
+    #[cfg(not(feature = "ipc"))]
+    fn read_spilled(_path: &std::path::Path) -> PolarsResult<DataFrame> {
+        unreachable!()
+    }
 }
 
 impl Executor for CacheExec {
@@ -23,7 +66,10 @@ impl Executor for CacheExec {
                     eprintln!("CACHE HIT: cache id: {:?}", self.id);
                 }
                 let cache = state.get_df_cache(&self.id, self.count);
-                let out = cache.1.get().expect("prefilled").clone();
+                let out = match cache.1.get().expect("prefilled") {
+                    CachedDataFrame::InMemory(df) => df.clone(),
+                    CachedDataFrame::Spilled(path) => Self::read_spilled(path)?,
+                };
                 let previous = cache.0.fetch_sub(1, Ordering::Relaxed);
                 if previous == 0 {
                     if state.verbose() {
@@ -40,8 +86,17 @@ impl Executor for CacheExec {
                     eprintln!("CACHE SET: cache id: {:?}", self.id);
                 }
                 let df = input.execute(state)?;
+                let df = match self.memory_budget {
+                    Some(budget) if df.estimated_size() > budget => {
+                        if state.verbose() {
+                            eprintln!("CACHE SPILL: cache id: {:?}", self.id);
+                        }
+                        self.spill(df)?
+                    },
+                    _ => CachedDataFrame::InMemory(df),
+                };
                 let cache = state.get_df_cache(&self.id, self.count);
-                cache.1.set(df).expect("should be empty");
+                assert!(cache.1.set(df).is_ok(), "should be empty");
                 Ok(DataFrame::empty())
             },
         }
@@ -6,6 +6,8 @@ pub(crate) struct MergeSorted {
     pub(crate) input_left: Box<dyn Executor>,
     pub(crate) input_right: Box<dyn Executor>,
     pub(crate) key: PlSmallStr,
+    pub(crate) descending: bool,
+    pub(crate) nulls_last: bool,
 }
 
 impl Executor for MergeSorted {
@@ -38,7 +40,10 @@ impl Executor for MergeSorted {
                     &right,
                     lhs.as_materialized_series(),
                     rhs.as_materialized_series(),
+                    None,
                     true,
+                    self.descending,
+                    self.nulls_last,
                 )
             },
             profile_name,
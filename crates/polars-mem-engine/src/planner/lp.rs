@@ -372,6 +372,7 @@ fn create_physical_plan_impl(
                             id,
                             count: 0,
                             is_new_streaming_scan: false,
+                            memory_budget: None,
                         }),
                     );
 
@@ -384,6 +385,7 @@ fn create_physical_plan_impl(
                         input: None,
                         count: Default::default(),
                         is_new_streaming_scan: false,
+                        memory_budget: None,
                     }))
                 },
             }
@@ -524,6 +526,7 @@ fn create_physical_plan_impl(
                             // This is (n_hits - 1), because the drop logic is `fetch_sub(1) == 0`.
                             count: 0,
                             is_new_streaming_scan: true,
+                            memory_budget: None,
                         }),
                     );
 
@@ -536,6 +539,7 @@ fn create_physical_plan_impl(
                         input: None,
                         count: Default::default(),
                         is_new_streaming_scan: true,
+                        memory_budget: None,
                     }))
                 },
                 #[allow(unreachable_patterns)]
@@ -611,6 +615,7 @@ fn create_physical_plan_impl(
             input,
             id,
             cache_hits,
+            memory_budget,
         } => {
             state.has_cache_parent = true;
             state.has_cache_child = true;
@@ -623,6 +628,7 @@ fn create_physical_plan_impl(
                     input: Some(input),
                     count: cache_hits,
                     is_new_streaming_scan: false,
+                    memory_budget,
                 });
 
                 cache_nodes.insert(id, cache);
@@ -633,6 +639,7 @@ fn create_physical_plan_impl(
                 input: None,
                 count: cache_hits,
                 is_new_streaming_scan: false,
+                memory_budget,
             }))
         },
         Distinct { input, options } => {
@@ -878,6 +885,8 @@ fn create_physical_plan_impl(
             input_left,
             input_right,
             key,
+            descending,
+            nulls_last,
         } => {
             let (input_left, input_right) = state.with_new_branch(|new_state| {
                 (
@@ -892,6 +901,8 @@ fn create_physical_plan_impl(
                 input_left,
                 input_right,
                 key,
+                descending,
+                nulls_last,
             };
             Ok(Box::new(exec))
         },
@@ -1105,6 +1116,7 @@ mod tests {
             input: scan,
             id: UniqueId::new(),
             cache_hits: 1,
+            memory_budget: None,
         });
 
         let left_sink = ir.add(IR::Sink {
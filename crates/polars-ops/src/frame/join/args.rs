@@ -1,3 +1,5 @@
+use polars_utils::format_pl_smallstr;
+
 use super::*;
 
 pub(super) type JoinIds = Vec<IdxSize>;
@@ -19,7 +21,7 @@ pub type ChunkJoinIds = Vec<IdxSize>;
 use serde::{Deserialize, Serialize};
 use strum_macros::IntoStaticStr;
 
-#[derive(Clone, PartialEq, Debug, Hash, Default)]
+#[derive(Clone, PartialEq, Debug, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
 pub struct JoinArgs {
@@ -28,10 +30,56 @@ pub struct JoinArgs {
     pub suffix: Option<PlSmallStr>,
     pub slice: Option<(i64, usize)>,
     pub nulls_equal: bool,
+    /// Whether NaN float keys match each other.
+    ///
+    /// When `true` all NaN bit patterns are considered equal, when `false` a NaN key never
+    /// matches anything. This is independent of `nulls_equal`. `-0.0` and `+0.0` are always
+    /// considered equal.
+    pub nans_equal: bool,
+    /// Whether string keys match regardless of case.
+    ///
+    /// The keys of both sides are lowercased before they are hashed, the output keeps the
+    /// original values. Only string keys are supported.
+    pub case_insensitive: bool,
+    /// The names of the left key columns in the output of a semi or anti join, one per key.
+    ///
+    /// Only left key columns that are part of the output are renamed. By default they keep their
+    /// names.
+    pub semi_anti_key_names: Option<Vec<PlSmallStr>>,
+    /// Evaluate a semi or anti join from the right frame, i.e. return the right rows with (semi)
+    /// or without (anti) a match in the left frame.
+    ///
+    /// This is the same as swapping the frames and their keys. [`JoinArgs::semi_anti_key_names`]
+    /// then renames the right key columns.
+    pub semi_anti_from_right: bool,
+    /// The output columns of an inner join, in order.
+    ///
+    /// Only these columns are gathered, which saves the work of gathering columns that are
+    /// dropped right after the join. Right columns are selected by their name in the output, i.e.
+    /// with the suffix if they clash with a left column. Only supported on eager joins.
+    pub output_columns: Option<Vec<PlSmallStr>>,
+    /// Nest the key columns of an inner join that doesn't coalesce them into a struct column of
+    /// this name, with a `left` and a `right` field, instead of keeping them as separate columns.
+    ///
+    /// With several keys, both fields are structs of the key columns of their side under their
+    /// input names. The struct takes the place of the first left key column. Only supported on
+    /// eager joins.
+    pub key_struct: Option<PlSmallStr>,
+    /// Reduce the right side of a left join to one row per key before probing it, so that every
+    /// left row occurs exactly once in the output.
+    pub dedup_right: JoinDedupRight,
+    /// When the frames are rechunked before joining, see [`JoinRechunk`].
+    pub rechunk: JoinRechunk,
     pub coalesce: JoinCoalesce,
     pub maintain_order: MaintainOrderJoin,
 }
 
+impl Default for JoinArgs {
+    fn default() -> Self {
+        Self::new(JoinType::default())
+    }
+}
+
 impl JoinArgs {
     pub fn should_coalesce(&self) -> bool {
         self.coalesce.coalesce(&self.how)
@@ -93,6 +141,42 @@ impl JoinCoalesce {
     }
 }
 
+/// How the right rows with a duplicate key are handled by a left join, see
+/// [`JoinArgs::dedup_right`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default, IntoStaticStr)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+#[strum(serialize_all = "snake_case")]
+pub enum JoinDedupRight {
+    /// Every right row with a matching key is joined.
+    #[default]
+    None,
+    /// Only the first right row of every key is joined.
+    KeepFirst,
+    /// Only the first right row of every key is joined, and it is an error if the rows of a key
+    /// differ in any of the non-key columns.
+    KeepFirstIfEqual,
+}
+
+/// Whether the frames of a join are rechunked before joining, see [`JoinArgs::rechunk`].
+///
+/// Joins other than left joins can't produce chunked row ids, so they rechunk frames whose columns
+/// have misaligned chunks. Left joins never rechunk unless it is forced.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub enum JoinRechunk {
+    /// Rechunk a frame if its chunks are misaligned, unless `POLARS_NO_CHUNKED_JOIN` is set.
+    #[default]
+    Auto,
+    /// Like [`JoinRechunk::Auto`], but a frame with fewer chunks than this is never rechunked.
+    MinChunks(usize),
+    /// Rechunk every frame that has more than one chunk, for all join types.
+    Always,
+    /// Never rechunk.
+    Never,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default, IntoStaticStr)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
@@ -126,21 +210,49 @@ impl JoinArgs {
             suffix: None,
             slice: None,
             nulls_equal: false,
+            nans_equal: true,
+            case_insensitive: false,
+            semi_anti_key_names: None,
+            semi_anti_from_right: false,
+            output_columns: None,
+            key_struct: None,
+            dedup_right: Default::default(),
+            rechunk: Default::default(),
             coalesce: Default::default(),
             maintain_order: Default::default(),
         }
     }
 
+    /// The arguments of a semi or anti join from the right frame, see
+    /// [`JoinArgs::semi_anti_from_right`], for the join with the frames swapped.
+    pub fn swap_semi_anti_from_right(mut self) -> Self {
+        debug_assert!(self.semi_anti_from_right);
+        self.semi_anti_from_right = false;
+        self.validation = self.validation.swap(true);
+        self.maintain_order = self.maintain_order.flip();
+        self
+    }
+
     pub fn with_coalesce(mut self, coalesce: JoinCoalesce) -> Self {
         self.coalesce = coalesce;
         self
     }
 
+    /// Set the suffix appended to the colliding column names of the right table.
+    ///
+    /// A `{}` placeholder in the suffix is replaced by the column name instead, e.g. a suffix of
+    /// `"{}_r"` renames `amount` to `amount_r`.
     pub fn with_suffix(mut self, suffix: Option<PlSmallStr>) -> Self {
         self.suffix = suffix;
         self
     }
 
+    /// Prepend `prefix` to the colliding column names of the right table instead of appending a
+    /// suffix.
+    pub fn with_prefix(self, prefix: &str) -> Self {
+        self.with_suffix(Some(format_pl_smallstr!("{prefix}{{}}")))
+    }
+
     pub fn suffix(&self) -> &PlSmallStr {
         const DEFAULT: &PlSmallStr = &PlSmallStr::from_static("_right");
         self.suffix.as_ref().unwrap_or(DEFAULT)
@@ -1,11 +1,10 @@
 use arrow::array::Array;
 use arrow::bitmap::Bitmap;
-use num_traits::Zero;
 use polars_core::prelude::*;
-use polars_utils::abs_diff::AbsDiff;
 
 use super::{
     AsofJoinBackwardState, AsofJoinForwardState, AsofJoinNearestState, AsofJoinState, AsofStrategy,
+    tolerance_filter,
 };
 
 fn join_asof_impl<'a, T, S, F>(
@@ -114,6 +113,7 @@ pub(crate) fn join_asof_numeric<T: PolarsNumericType>(
     other: &Series,
     strategy: AsofStrategy,
     tolerance: Option<AnyValue<'static>>,
+    tolerance_relative: bool,
     allow_eq: bool,
 ) -> PolarsResult<IdxCa> {
     let other = input_ca.unpack_series_matching_type(other)?;
@@ -124,9 +124,7 @@ pub(crate) fn join_asof_numeric<T: PolarsNumericType>(
     let right = other.downcast_as_array();
 
     let out = if let Some(t) = tolerance {
-        let native_tolerance = t.try_extract::<T::Native>()?;
-        let abs_tolerance = native_tolerance.abs_diff(T::Native::zero());
-        let filter = |l: T::Native, r: T::Native| l.abs_diff(r) <= abs_tolerance;
+        let filter = tolerance_filter::<T::Native>(t, tolerance_relative)?;
         match strategy {
             AsofStrategy::Forward => join_asof_forward::<T, _>(left, right, filter, allow_eq),
             AsofStrategy::Backward => join_asof_backward::<T, _>(left, right, filter, allow_eq),
@@ -168,7 +166,10 @@ where
         AsofStrategy::Backward => {
             join_asof_impl::<T, AsofJoinBackwardState, _>(left, right, filter, allow_eq)
         },
-        AsofStrategy::Nearest => unimplemented!(),
+        AsofStrategy::Nearest => polars_bail!(
+            InvalidOperation:
+            "asof join with 'nearest' strategy is only supported on numeric/temporal keys"
+        ),
     })
 }
 
@@ -234,4 +235,31 @@ mod test {
         assert_eq!(tuples.len(), a.len());
         assert_eq!(tuples.to_vec(), &[Some(0), Some(0), Some(1), Some(2), None]);
     }
+
+    #[test]
+    fn test_asof_disallow_eq_duplicates() {
+        let a = PrimitiveArray::from_slice([1, 2, 2, 3, 4]);
+        let b = PrimitiveArray::from_slice([1, 2, 2, 2, 3, 5]);
+
+        let tuples = join_asof_backward::<Int32Type, _>(&a, &b, |_, _| true, false);
+        assert_eq!(tuples.to_vec(), &[None, Some(0), Some(0), Some(3), Some(4)]);
+
+        let tuples = join_asof_forward::<Int32Type, _>(&a, &b, |_, _| true, false);
+        assert_eq!(
+            tuples.to_vec(),
+            &[Some(1), Some(4), Some(4), Some(5), Some(5)]
+        );
+
+        let tuples = join_asof_nearest::<Int32Type, _>(&a, &b, |_, _| true, false);
+        assert_eq!(
+            tuples.to_vec(),
+            &[Some(3), Some(4), Some(4), Some(3), Some(5)]
+        );
+
+        let tuples = join_asof_nearest::<Int32Type, _>(&a, &b, |_, _| true, true);
+        assert_eq!(
+            tuples.to_vec(),
+            &[Some(0), Some(3), Some(3), Some(4), Some(5)]
+        );
+    }
 }
@@ -1,13 +1,11 @@
 use std::hash::Hash;
 
-use num_traits::Zero;
 use polars_core::hashing::_HASHMAP_INIT_SIZE;
 use polars_core::prelude::*;
 use polars_core::series::BitRepr;
 use polars_core::utils::flatten::flatten_nullable;
 use polars_core::utils::split_and_flatten;
 use polars_core::{POOL, with_match_physical_float_polars_type};
-use polars_utils::abs_diff::AbsDiff;
 use polars_utils::hashing::{DirtyHash, hash_to_partition};
 use polars_utils::nulls::IsNull;
 use polars_utils::total_ord::{ToTotalOrd, TotalEq, TotalHash};
@@ -307,8 +305,8 @@ where
         // TODO: @scalar-opt.
         let left_by_series: Vec<_> = left_by.materialized_column_iter().cloned().collect();
         let right_by_series: Vec<_> = right_by.materialized_column_iter().cloned().collect();
-        let lhs_keys = prepare_keys_multiple(&left_by_series, false)?;
-        let rhs_keys = prepare_keys_multiple(&right_by_series, false)?;
+        let lhs_keys = prepare_keys_multiple(&left_by_series, false, true)?;
+        let rhs_keys = prepare_keys_multiple(&right_by_series, false, true)?;
         asof_join_by_binary::<BinaryOffsetType, T, A, F>(
             &lhs_keys, &rhs_keys, left_asof, right_asof, filter, allow_eq,
         )
@@ -338,7 +336,10 @@ where
         AsofStrategy::Forward => dispatch_join_by_type::<T, AsofJoinForwardState, _>(
             left_asof, right_asof, left_by, right_by, filter, allow_eq,
         ),
-        AsofStrategy::Nearest => unimplemented!(),
+        AsofStrategy::Nearest => polars_bail!(
+            InvalidOperation:
+            "asof join with 'nearest' strategy is only supported on numeric/temporal keys"
+        ),
     }
 }
 
@@ -350,14 +351,13 @@ fn dispatch_join_strategy_numeric<T: PolarsNumericType>(
     right_by: &mut DataFrame,
     strategy: AsofStrategy,
     tolerance: Option<AnyValue<'static>>,
+    tolerance_relative: bool,
     allow_eq: bool,
 ) -> PolarsResult<IdxArr> {
     let right_ca = left_asof.unpack_series_matching_type(right_asof)?;
 
     if let Some(tol) = tolerance {
-        let native_tolerance: T::Native = tol.try_extract()?;
-        let abs_tolerance = native_tolerance.abs_diff(T::Native::zero());
-        let filter = |a: T::Native, b: T::Native| a.abs_diff(b) <= abs_tolerance;
+        let filter = tolerance_filter::<T::Native>(tol, tolerance_relative)?;
         match strategy {
             AsofStrategy::Backward => dispatch_join_by_type::<T, AsofJoinBackwardState, _>(
                 left_asof, right_ca, left_by, right_by, filter, allow_eq,
@@ -393,50 +393,100 @@ fn dispatch_join_type(
     right_by: &mut DataFrame,
     strategy: AsofStrategy,
     tolerance: Option<AnyValue<'static>>,
+    tolerance_relative: bool,
     allow_eq: bool,
 ) -> PolarsResult<IdxArr> {
     match left_asof.dtype() {
         DataType::Int64 => {
             let ca = left_asof.i64().unwrap();
             dispatch_join_strategy_numeric(
-                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+                ca,
+                right_asof,
+                left_by,
+                right_by,
+                strategy,
+                tolerance,
+                tolerance_relative,
+                allow_eq,
             )
         },
         DataType::Int32 => {
             let ca = left_asof.i32().unwrap();
             dispatch_join_strategy_numeric(
-                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+                ca,
+                right_asof,
+                left_by,
+                right_by,
+                strategy,
+                tolerance,
+                tolerance_relative,
+                allow_eq,
             )
         },
         DataType::UInt64 => {
             let ca = left_asof.u64().unwrap();
             dispatch_join_strategy_numeric(
-                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+                ca,
+                right_asof,
+                left_by,
+                right_by,
+                strategy,
+                tolerance,
+                tolerance_relative,
+                allow_eq,
             )
         },
         DataType::UInt32 => {
             let ca = left_asof.u32().unwrap();
             dispatch_join_strategy_numeric(
-                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+                ca,
+                right_asof,
+                left_by,
+                right_by,
+                strategy,
+                tolerance,
+                tolerance_relative,
+                allow_eq,
             )
         },
         #[cfg(feature = "dtype-i128")]
         DataType::Int128 => {
             let ca = left_asof.i128().unwrap();
             dispatch_join_strategy_numeric(
-                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+                ca,
+                right_asof,
+                left_by,
+                right_by,
+                strategy,
+                tolerance,
+                tolerance_relative,
+                allow_eq,
             )
         },
         DataType::Float32 => {
             let ca = left_asof.f32().unwrap();
             dispatch_join_strategy_numeric(
-                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+                ca,
+                right_asof,
+                left_by,
+                right_by,
+                strategy,
+                tolerance,
+                tolerance_relative,
+                allow_eq,
             )
         },
         DataType::Float64 => {
             let ca = left_asof.f64().unwrap();
             dispatch_join_strategy_numeric(
-                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+                ca,
+                right_asof,
+                left_by,
+                right_by,
+                strategy,
+                tolerance,
+                tolerance_relative,
+                allow_eq,
             )
         },
         DataType::Boolean => {
@@ -474,6 +524,7 @@ fn dispatch_join_type(
                 right_by,
                 strategy,
                 tolerance,
+                tolerance_relative,
                 allow_eq,
             )
         },
@@ -493,11 +544,13 @@ pub trait AsofJoinBy: IntoDf {
         right_by: Vec<PlSmallStr>,
         strategy: AsofStrategy,
         tolerance: Option<AnyValue<'static>>,
+        tolerance_relative: bool,
         suffix: Option<PlSmallStr>,
         slice: Option<(i64, usize)>,
         coalesce: bool,
         allow_eq: bool,
         check_sortedness: bool,
+        index_column: Option<PlSmallStr>,
     ) -> PolarsResult<DataFrame> {
         let (self_sliced_slot, left_slice_s); // Keeps temporaries alive.
         let (self_df, other_df, left_key, right_key);
@@ -548,6 +601,7 @@ pub trait AsofJoinBy: IntoDf {
             &mut right_by,
             strategy,
             tolerance,
+            tolerance_relative,
             allow_eq,
         )?;
 
@@ -567,11 +621,10 @@ pub trait AsofJoinBy: IntoDf {
         let left = self_df.clone();
 
         // SAFETY: join tuples are in bounds.
-        let right_df = unsafe {
-            proj_other_df.take_unchecked(&IdxCa::with_chunk(PlSmallStr::EMPTY, right_join_tuples))
-        };
+        let take_idx = IdxCa::with_chunk(PlSmallStr::EMPTY, right_join_tuples);
+        let right_df = unsafe { proj_other_df.take_unchecked(&take_idx) };
 
-        _finish_join(left, right_df, suffix)
+        finish_asof_join(left, right_df, suffix, take_idx, index_column)
     }
 
     /// This is similar to a left-join except that we match on nearest key
@@ -608,11 +661,70 @@ pub trait AsofJoinBy: IntoDf {
             right_by,
             strategy,
             tolerance,
+            false,
             None,
             None,
             true,
             allow_eq,
             check_sortedness,
+            None,
+        )
+    }
+
+    /// Join every row with the most recent prior row of the same `by` group, at most `tolerance`
+    /// before it.
+    ///
+    /// This is an asof self-join on `on`. A row never matches itself or another row with the same
+    /// `on` value, only rows strictly before it. The `columns` of the matched row are added with
+    /// `suffix`, or null if there is no such row. Selecting `on` adds the key of the matched row,
+    /// the `by` columns are equal for both rows and are not added again.
+    fn join_asof_prior<I, S, J, T>(
+        &self,
+        on: &str,
+        by: I,
+        columns: J,
+        tolerance: Option<AnyValue<'static>>,
+        suffix: Option<PlSmallStr>,
+    ) -> PolarsResult<DataFrame>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        J: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let self_df = self.to_df();
+        let by: Vec<PlSmallStr> = by.into_iter().map(|s| s.as_ref().into()).collect();
+        let on = PlSmallStr::from_str(on);
+
+        let mut keep_on = false;
+        let mut projection = by.clone();
+        projection.push(on.clone());
+        for name in columns {
+            let name = name.as_ref();
+            if name == on {
+                keep_on = true;
+            } else if !projection.iter().any(|c| c == name) {
+                projection.push(name.into());
+            }
+        }
+        let prior = self_df.select(projection)?;
+
+        let key = self_df.column(&on)?.as_materialized_series();
+        self_df._join_asof_by(
+            &prior,
+            key,
+            key,
+            by.clone(),
+            by,
+            AsofStrategy::Backward,
+            tolerance,
+            false,
+            suffix,
+            None,
+            !keep_on,
+            false,
+            true,
+            None,
         )
     }
 }
@@ -860,4 +972,43 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_asof_prior() -> PolarsResult<()> {
+        let df = df![
+            "id" => ["a", "b", "a", "a", "b", "a"],
+            "t" => [1i64, 2, 3, 3, 6, 10],
+            "v" => [1, 2, 3, 4, 5, 6]
+        ]?;
+
+        let out = df.join_asof_prior(
+            "t",
+            ["id"],
+            ["t", "v"],
+            Some(AnyValue::Int64(7)),
+            Some("_prior".into()),
+        )?;
+        assert_eq!(
+            out.get_column_names(),
+            &["id", "t", "v", "t_prior", "v_prior"]
+        );
+        // A row doesn't match the row with the same key, only strictly earlier rows.
+        let t = out.column("t_prior")?.i64()?;
+        assert_eq!(
+            Vec::from(t),
+            &[None, None, Some(1), Some(1), Some(2), Some(3)]
+        );
+        let v = out.column("v_prior")?.i32()?;
+        assert_eq!(
+            Vec::from(v),
+            &[None, None, Some(1), Some(1), Some(2), Some(4)]
+        );
+
+        // Beyond the tolerance there is no prior row.
+        let out = df.join_asof_prior("t", ["id"], ["v"], Some(AnyValue::Int64(5)), None)?;
+        assert_eq!(out.get_column_names(), &["id", "t", "v", "v_right"]);
+        let v = out.column("v_right")?.i32()?;
+        assert_eq!(Vec::from(v), &[None, None, Some(1), Some(1), Some(2), None]);
+        Ok(())
+    }
 }
@@ -2,6 +2,8 @@ mod default;
 mod groups;
 use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use default::*;
 pub use groups::AsofJoinBy;
@@ -112,18 +114,50 @@ impl<T: PartialOrd> AsofJoinState<T> for AsofJoinBackwardState {
 
 #[derive(Default)]
 struct AsofJoinNearestState {
-    // best_bound is the nearest value to left_val, with ties broken towards the last element.
-    best_bound: Option<IdxSize>,
+    // below is the greatest right index with a value < left_val.
+    below: Option<IdxSize>,
+    // All right values before scan_offset are < left_val.
     scan_offset: IdxSize,
     allow_eq: bool,
+    // The first and last index of the last run of equal right values that was looked up.
+    run: Option<(IdxSize, IdxSize)>,
+}
+
+impl AsofJoinNearestState {
+    /// The last index of the run of values equal to the value at `start`.
+    fn run_end<T: PartialEq, F: FnMut(IdxSize) -> Option<T>>(
+        &mut self,
+        start: IdxSize,
+        start_val: &T,
+        right: &mut F,
+        n_right: IdxSize,
+    ) -> IdxSize {
+        if let Some((run_start, run_end)) = self.run {
+            if run_start == start {
+                return run_end;
+            }
+        }
+
+        let mut end = start;
+        let mut offset = start + 1;
+        while offset < n_right {
+            match right(offset) {
+                Some(val) if val == *start_val => end = offset,
+                Some(_) => break,
+                None => {},
+            }
+            offset += 1;
+        }
+        self.run = Some((start, end));
+        end
+    }
 }
 
 impl<T: NumericNative> AsofJoinState<T> for AsofJoinNearestState {
     fn new(allow_eq: bool) -> Self {
         AsofJoinNearestState {
-            scan_offset: Default::default(),
-            best_bound: Default::default(),
             allow_eq,
+            ..Default::default()
         }
     }
     #[inline]
@@ -133,52 +167,53 @@ impl<T: NumericNative> AsofJoinState<T> for AsofJoinNearestState {
         mut right: F,
         n_right: IdxSize,
     ) -> Option<IdxSize> {
-        // Skipping ahead to the first value greater than left_val. This is
-        // cheaper than computing differences.
+        // Skipping ahead to the first value not less than left_val. This is cheaper than
+        // computing differences.
         while self.scan_offset < n_right {
-            if let Some(scan_right_val) = right(self.scan_offset) {
-                if lt_allow_eq(&scan_right_val, left_val, self.allow_eq) {
-                    self.best_bound = Some(self.scan_offset);
+            if let Some(right_val) = right(self.scan_offset) {
+                if right_val < *left_val {
+                    self.below = Some(self.scan_offset);
                 } else {
-                    // Now we must compute a difference to see if scan_right_val
-                    // is closer than our current best bound.
-                    let scan_is_better = if let Some(best_idx) = self.best_bound {
-                        let best_right_val = unsafe { right(best_idx).unwrap_unchecked() };
-                        let best_diff = left_val.abs_diff(best_right_val);
-                        let scan_diff = left_val.abs_diff(scan_right_val);
-
-                        lt_allow_eq(&scan_diff, &best_diff, self.allow_eq)
-                    } else {
-                        true
-                    };
-
-                    if scan_is_better {
-                        self.best_bound = Some(self.scan_offset);
-                        self.scan_offset += 1;
-
-                        // It is possible there are later elements equal to our
-                        // scan, so keep going on.
-                        while self.scan_offset < n_right {
-                            if let Some(next_right_val) = right(self.scan_offset) {
-                                if next_right_val == scan_right_val && self.allow_eq {
-                                    self.best_bound = Some(self.scan_offset);
-                                } else {
-                                    break;
-                                }
-                            }
-
-                            self.scan_offset += 1;
-                        }
-                    }
-
                     break;
                 }
             }
-
             self.scan_offset += 1;
         }
 
-        self.best_bound
+        // The right values from here on are >= left_val. Those equal to left_val are an exact
+        // match if allowed, otherwise the nearest greater value comes after all of them. We don't
+        // move scan_offset past them, they are less than the next left values.
+        let mut offset = self.scan_offset;
+        let mut above = None;
+        while offset < n_right {
+            if let Some(right_val) = right(offset) {
+                let end = self.run_end(offset, &right_val, &mut right, n_right);
+                if right_val != *left_val {
+                    // Ties between equally near values are broken towards the last one.
+                    above = Some((end, right_val));
+                    break;
+                }
+                if self.allow_eq {
+                    return Some(end);
+                }
+                offset = end;
+            }
+            offset += 1;
+        }
+
+        match (self.below, above) {
+            (Some(below), Some((above, above_val))) => {
+                // SAFETY: below was a valid, non-null index.
+                let below_val = unsafe { right(below).unwrap_unchecked() };
+                if left_val.abs_diff(above_val) <= left_val.abs_diff(below_val) {
+                    Some(above)
+                } else {
+                    Some(below)
+                }
+            },
+            (below, None) => below,
+            (None, Some((above, _))) => Some(above),
+        }
     }
 }
 
@@ -189,6 +224,11 @@ pub struct AsOfOptions {
     pub strategy: AsofStrategy,
     /// A tolerance in the same unit as the asof column
     pub tolerance: Option<Scalar>,
+    /// Interpret `tolerance` as a fraction of the left asof value instead of an absolute distance,
+    /// e.g. a tolerance of `0.01` only matches right values within 1% of the left value.
+    ///
+    /// This only makes sense for positive numeric asof keys.
+    pub tolerance_relative: bool,
     /// A time duration specified as a string, for example:
     /// - "5m"
     /// - "2h15m"
@@ -199,6 +239,113 @@ pub struct AsOfOptions {
     /// Allow equal matches
     pub allow_eq: bool,
     pub check_sortedness: bool,
+    /// Sort both inputs by their asof key before the join instead of requiring sorted inputs.
+    /// The output follows the sorted left rows, unless the join maintains the left order, in
+    /// which case the rows are moved back to the original left order.
+    pub auto_sort: bool,
+    /// Append the index of the matched right row under this name, or null if a left row has no
+    /// match.
+    pub index_column: Option<PlSmallStr>,
+    /// The order of string asof keys. Both inputs must be sorted in this order, unless
+    /// `auto_sort` is set.
+    pub string_collation: AsofStringCollation,
+}
+
+/// The order in which an asof join compares string keys, see
+/// [`AsOfOptions::string_collation`].
+#[derive(Clone, Debug, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub enum AsofStringCollation {
+    /// Compare the UTF-8 bytes.
+    #[default]
+    Binary,
+    /// Compare the lowercase strings.
+    CaseInsensitive,
+    /// Compare the sort keys produced by a function, e.g. the collation keys of a locale. Can't
+    /// be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "dsl-schema", schemars(skip))]
+    Custom(AsofCollationKey),
+}
+
+/// Writes the sort key of a string into the buffer.
+pub type CollationKeyFn = dyn Fn(&str, &mut Vec<u8>) + Send + Sync;
+
+/// Writes the sort key of a string into the buffer. The strings are ordered by the bytes of
+/// their keys.
+#[derive(Clone)]
+pub struct AsofCollationKey(pub Arc<CollationKeyFn>);
+
+impl PartialEq for AsofCollationKey {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for AsofCollationKey {}
+
+impl Hash for AsofCollationKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).cast::<()>().hash(state)
+    }
+}
+
+impl fmt::Debug for AsofCollationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AsofCollationKey")
+    }
+}
+
+impl AsofStringCollation {
+    /// Replace string keys by binary keys that order the same in byte order, so the join, the
+    /// sortedness check and `auto_sort` can compare bytes. Returns `None` for other keys or
+    /// if the strings already compare by their bytes.
+    pub(super) fn collation_keys(&self, key: &Series) -> PolarsResult<Option<Series>> {
+        if matches!(self, Self::Binary) || key.dtype() != &DataType::String {
+            return Ok(None);
+        }
+        let keys: BinaryChunked =
+            key.str()?
+                .apply_nonnull_values_generic(DataType::Binary, |s| -> Vec<u8> {
+                    match self {
+                        Self::Binary => unreachable!(),
+                        Self::CaseInsensitive => s.to_lowercase().into_bytes(),
+                        Self::Custom(key) => {
+                            let mut buf = vec![];
+                            key.0(s, &mut buf);
+                            buf
+                        },
+                    }
+                });
+        Ok(Some(keys.with_name(key.name().clone()).into_series()))
+    }
+}
+
+/// The filter that accepts a match of a left and a right asof value within `tolerance`, see
+/// [`AsOfOptions::tolerance_relative`].
+fn tolerance_filter<T: NumericNative>(
+    tolerance: AnyValue<'static>,
+    relative: bool,
+) -> PolarsResult<impl Fn(T, T) -> bool + Copy> {
+    let (abs_tolerance, rel_tolerance) = if relative {
+        let fraction: f64 = tolerance.try_extract()?;
+        polars_ensure!(
+            fraction >= 0.0,
+            InvalidOperation: "relative asof tolerance must not be negative, got {}", fraction
+        );
+        (None, fraction)
+    } else {
+        let native_tolerance: T = tolerance.try_extract()?;
+        (Some(native_tolerance.abs_diff(T::zero())), 0.0)
+    };
+    Ok(move |l: T, r: T| match abs_tolerance {
+        Some(abs_tolerance) => l.abs_diff(r) <= abs_tolerance,
+        None => {
+            let (l, r): (f64, f64) = (l.as_(), r.as_());
+            (l - r).abs() <= rel_tolerance * l.abs()
+        },
+    })
 }
 
 fn check_asof_columns(
@@ -232,6 +379,7 @@ fn check_asof_columns(
         if by_groups_present {
             polars_warn!("Sortedness of columns cannot be checked when 'by' groups provided");
         } else {
+            // String keys compare by their UTF-8 bytes, the same order the join uses for them.
             a.ensure_sorted_arg("asof_join")?;
             b.ensure_sorted_arg("asof_join")?;
         }
@@ -239,6 +387,76 @@ fn check_asof_columns(
     Ok(())
 }
 
+/// Finish an asof join that took the `take_idx` rows of the right frame, see
+/// [`AsOfOptions::index_column`].
+fn finish_asof_join(
+    left: DataFrame,
+    right: DataFrame,
+    suffix: Option<PlSmallStr>,
+    take_idx: IdxCa,
+    index_column: Option<PlSmallStr>,
+) -> PolarsResult<DataFrame> {
+    let mut out = _finish_join(left, right, suffix)?;
+    if let Some(name) = index_column {
+        out.hstack_mut(&[take_idx.with_name(name).into_column()])?;
+    }
+    Ok(out)
+}
+
+/// Run the asof `join` on both inputs sorted by their key, see [`AsOfOptions::auto_sort`].
+///
+/// Sorting the whole input by the key also sorts the key within every `by` group, so this works
+/// for [`AsofJoinBy`] as well.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn join_asof_sorted<F>(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_key: &Series,
+    right_key: &Series,
+    slice: Option<(i64, usize)>,
+    maintain_left_order: bool,
+    index_column: Option<&PlSmallStr>,
+    join: F,
+) -> PolarsResult<DataFrame>
+where
+    F: FnOnce(&DataFrame, &DataFrame, &Series, &Series) -> PolarsResult<DataFrame>,
+{
+    let sort_options = SortOptions::default()
+        .with_nulls_last(true)
+        .with_maintain_order(true);
+    let left_idx = left_key.arg_sort(sort_options);
+    let right_idx = right_key.arg_sort(sort_options);
+
+    // SAFETY: the sort indices are in bounds.
+    let (left, left_key, right, right_key) = unsafe {
+        (
+            left.take_unchecked(&left_idx),
+            left_key.take_unchecked(&left_idx),
+            right.take_unchecked(&right_idx),
+            right_key.take_unchecked(&right_idx),
+        )
+    };
+    let mut out = join(&left, &right, &left_key, &right_key)?;
+    if let Some(name) = index_column {
+        // Map the indices into the sorted right frame back to the original right rows.
+        let sorted_idx = out.column(name)?.idx()?;
+        let idx = right_idx.take(sorted_idx)?;
+        out.with_column(idx.with_name(name.clone()))?;
+    }
+
+    if maintain_left_order {
+        // Every left row has exactly one output row, so the inverse of the left sort restores
+        // the left order.
+        let restore_idx = left_idx.arg_sort(SortOptions::default());
+        // SAFETY: the inverse of a permutation is in bounds.
+        out = unsafe { out.take_unchecked(&restore_idx) };
+    }
+    if let Some((offset, len)) = slice {
+        out = out.slice(offset, len);
+    }
+    Ok(out)
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
@@ -262,11 +480,13 @@ pub trait AsofJoin: IntoDf {
         right_key: &Series,
         strategy: AsofStrategy,
         tolerance: Option<AnyValue<'static>>,
+        tolerance_relative: bool,
         suffix: Option<PlSmallStr>,
         slice: Option<(i64, usize)>,
         coalesce: bool,
         allow_eq: bool,
         check_sortedness: bool,
+        index_column: Option<PlSmallStr>,
     ) -> PolarsResult<DataFrame> {
         let self_df = self.to_df();
 
@@ -283,32 +503,81 @@ pub trait AsofJoin: IntoDf {
         let mut take_idx = match left_key.dtype() {
             DataType::Int64 => {
                 let ca = left_key.i64().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             DataType::Int32 => {
                 let ca = left_key.i32().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             #[cfg(feature = "dtype-i128")]
             DataType::Int128 => {
                 let ca = left_key.i128().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             DataType::UInt64 => {
                 let ca = left_key.u64().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             DataType::UInt32 => {
                 let ca = left_key.u32().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             DataType::Float32 => {
                 let ca = left_key.f32().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             DataType::Float64 => {
                 let ca = left_key.f64().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             DataType::Boolean => {
                 let ca = left_key.bool().unwrap();
@@ -327,7 +596,14 @@ pub trait AsofJoin: IntoDf {
                 let left_key = left_key.cast(&DataType::Int32).unwrap();
                 let right_key = right_key.cast(&DataType::Int32).unwrap();
                 let ca = left_key.i32().unwrap();
-                join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
+                join_asof_numeric(
+                    ca,
+                    &right_key,
+                    strategy,
+                    tolerance,
+                    tolerance_relative,
+                    allow_eq,
+                )
             },
             dt => polars_bail!(opq = asof_join, dt),
         }?;
@@ -349,7 +625,7 @@ pub trait AsofJoin: IntoDf {
         // SAFETY: join tuples are in bounds.
         let right_df = unsafe { other.take_unchecked(&take_idx) };
 
-        _finish_join(left, right_df, suffix)
+        finish_asof_join(left, right_df, suffix, take_idx, index_column)
     }
 }
 
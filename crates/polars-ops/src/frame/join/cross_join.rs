@@ -24,7 +24,10 @@ fn slice_take(
 fn take_left(total_rows: IdxSize, n_rows_right: IdxSize, slice: Option<(i64, usize)>) -> IdxCa {
     fn inner(offset: IdxSize, total_rows: IdxSize, n_rows_right: IdxSize) -> IdxCa {
         let mut take: NoNull<IdxCa> = (offset..total_rows)
-            .map(|i| i / n_rows_right)
+            .map(|i| {
+                maybe_raise_keyboard_interrupt(i as usize);
+                i / n_rows_right
+            })
             .collect_trusted();
         take.set_sorted_flag(IsSorted::Ascending);
         take.into_inner()
@@ -35,7 +38,10 @@ fn take_left(total_rows: IdxSize, n_rows_right: IdxSize, slice: Option<(i64, usi
 fn take_right(total_rows: IdxSize, n_rows_right: IdxSize, slice: Option<(i64, usize)>) -> IdxCa {
     fn inner(offset: IdxSize, total_rows: IdxSize, n_rows_right: IdxSize) -> IdxCa {
         let take: NoNull<IdxCa> = (offset..total_rows)
-            .map(|i| i % n_rows_right)
+            .map(|i| {
+                maybe_raise_keyboard_interrupt(i as usize);
+                i % n_rows_right
+            })
             .collect_trusted();
         take.into_inner()
     }
@@ -69,16 +75,44 @@ pub trait CrossJoin: IntoDf {
     }
 
     /// Creates the Cartesian product from both frames, preserves the order of the left keys.
+    ///
+    /// If the product would have more than `max_rows` rows (after applying `slice`), an error is
+    /// returned before anything is materialized.
     fn cross_join(
         &self,
         other: &DataFrame,
         suffix: Option<PlSmallStr>,
         slice: Option<(i64, usize)>,
+        max_rows: Option<usize>,
     ) -> PolarsResult<DataFrame> {
+        if let Some(max_rows) = max_rows {
+            let (n_rows_left, n_rows_right) = (self.to_df().height(), other.height());
+            let mut n_rows = n_rows_left.saturating_mul(n_rows_right);
+            if let Some((offset, len)) = slice {
+                n_rows = slice_offsets(offset, len, n_rows).1;
+            }
+            polars_ensure!(
+                n_rows <= max_rows,
+                ComputeError: "cross join would produce {} rows ({} x {}), which is more than \
+                `max_rows` ({})",
+                n_rows, n_rows_left, n_rows_right, max_rows
+            );
+        }
         let (l_df, r_df) = cross_join_dfs(self.to_df(), other, slice, true)?;
 
         _finish_join(l_df, r_df, suffix)
     }
+
+    /// Lazily enumerates the Cartesian product as `(left_row, right_row)` index pairs, in the
+    /// order of [`CrossJoin::cross_join`].
+    ///
+    /// Nothing is materialized, so the caller can stop early or take rows from the frames as
+    /// needed.
+    fn cross_join_pairs(&self, other: &DataFrame) -> impl Iterator<Item = (usize, usize)> {
+        let n_rows_right = other.height();
+        (0..self.to_df().height())
+            .flat_map(move |left| (0..n_rows_right).map(move |right| (left, right)))
+    }
 }
 
 impl CrossJoin for DataFrame {}
@@ -166,6 +200,7 @@ pub(super) fn fused_cross_filter(
     let dfs = POOL
         .install(|| {
             cartesian_prod.par_iter().map(|(left, right)| {
+                try_raise_keyboard_interrupt();
                 let (mut left, right) = cross_join_dfs(left, right, None, false)?;
                 let mut right_columns = right.take_columns();
 
@@ -42,12 +42,26 @@ pub fn materialize_left_join_from_series(
     verbose: bool,
     drop_names: Option<Vec<PlSmallStr>>,
 ) -> PolarsResult<(DataFrame, DataFrame)> {
+    // The current sort_or_hash_left implementation preserves the Left DataFrame order so skip left for now.
+    let requires_ordering = matches!(
+        args.maintain_order,
+        MaintainOrderJoin::Right | MaintainOrderJoin::RightLeft
+    );
+
+    // Every left row produces at least one output row, so if the output follows the left order
+    // the slice is satisfied by the first `offset + len` left rows and by the first
+    // `offset + len` join tuples.
+    let limit = args
+        .slice
+        .filter(|(offset, _)| *offset >= 0 && !requires_ordering)
+        .map(|(offset, len)| (offset as usize).saturating_add(len));
+
     let mut s_left = s_left.clone();
     // Eagerly limit left if possible.
-    if let Some((offset, len)) = args.slice {
-        if offset == 0 {
-            left = left.slice(0, len);
-            s_left = s_left.slice(0, len);
+    if let Some(limit) = limit {
+        if limit < left.height() {
+            left = left.slice(0, limit);
+            s_left = s_left.slice(0, limit);
         }
     }
 
@@ -65,11 +79,6 @@ pub fn materialize_left_join_from_series(
         s_right = s_right.rechunk();
     }
 
-    // The current sort_or_hash_left implementation preserves the Left DataFrame order so skip left for now.
-    let requires_ordering = matches!(
-        args.maintain_order,
-        MaintainOrderJoin::Right | MaintainOrderJoin::RightLeft
-    );
     if requires_ordering {
         // When ordering we rechunk the series so we don't get ChunkIds as output
         s_left = s_left.rechunk();
@@ -82,6 +91,7 @@ pub fn materialize_left_join_from_series(
         verbose,
         args.validation,
         args.nulls_equal,
+        limit,
     )?;
 
     let right = if let Some(drop_names) = drop_names {
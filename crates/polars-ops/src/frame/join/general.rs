@@ -1,10 +1,28 @@
 use polars_utils::format_pl_smallstr;
 
 use super::*;
-use crate::series::coalesce_columns;
 
+/// Placeholder for the column name in a join suffix that is used as a template.
+const JOIN_SUFFIX_PLACEHOLDER: &str = "{}";
+
+/// Rename a column of the right table that collides with a column of the left table.
+///
+/// If `suffix` contains a `{}` placeholder it is used as a template for the new name, e.g.
+/// `"right_{}"` renames `amount` to `right_amount`. Otherwise `suffix` is appended to the name.
 pub fn _join_suffix_name(name: &str, suffix: &str) -> PlSmallStr {
-    format_pl_smallstr!("{name}{suffix}")
+    match suffix.split_once(JOIN_SUFFIX_PLACEHOLDER) {
+        Some((prefix, suffix)) => format_pl_smallstr!("{prefix}{name}{suffix}"),
+        None => format_pl_smallstr!("{name}{suffix}"),
+    }
+}
+
+/// Inverse of [`_join_suffix_name`]. Returns `None` if `name` can't have been created with
+/// `suffix`.
+pub fn _join_strip_suffix<'a>(name: &'a str, suffix: &str) -> Option<&'a str> {
+    match suffix.split_once(JOIN_SUFFIX_PLACEHOLDER) {
+        Some((prefix, suffix)) => name.strip_prefix(prefix)?.strip_suffix(suffix),
+        None => name.strip_suffix(suffix),
+    }
 }
 
 fn get_suffix(suffix: Option<PlSmallStr>) -> PlSmallStr {
@@ -48,6 +66,276 @@ pub fn _finish_join(
     Ok(df_left)
 }
 
+/// Return an error if the keys of a join with `args` can't be nested with
+/// [`JoinArgs::key_struct`].
+pub(super) fn ensure_key_struct_supported(args: &JoinArgs) -> PolarsResult<()> {
+    polars_ensure!(
+        matches!(args.how, JoinType::Inner) && !args.should_coalesce(),
+        InvalidOperation: "the join keys can only be nested into a struct for inner joins that don't coalesce them"
+    );
+    polars_ensure!(
+        args.output_columns.is_none(),
+        InvalidOperation: "the join keys can't be nested into a struct if the output columns are set"
+    );
+    Ok(())
+}
+
+/// The output names of the `right_on` key columns of a join that keeps them, i.e. with the
+/// suffix if they clash with a `left` column.
+pub(super) fn right_key_output_names(
+    left: &Schema,
+    right_on: &[PlSmallStr],
+    suffix: &str,
+) -> Vec<PlSmallStr> {
+    right_on
+        .iter()
+        .map(|name| {
+            if left.contains(name) {
+                _join_suffix_name(name, suffix)
+            } else {
+                name.clone()
+            }
+        })
+        .collect()
+}
+
+/// Move the `left_keys` and `right_keys` columns of a join output into the struct column `name`,
+/// see [`JoinArgs::key_struct`].
+#[cfg(feature = "dtype-struct")]
+pub(super) fn nest_join_keys(
+    df: DataFrame,
+    left_keys: &[PlSmallStr],
+    right_keys: &[PlSmallStr],
+    name: PlSmallStr,
+) -> PolarsResult<DataFrame> {
+    let side = |keys: &[PlSmallStr], field: PlSmallStr| -> PolarsResult<Column> {
+        let columns = keys
+            .iter()
+            .map(|key| df.column(key).cloned())
+            .collect::<PolarsResult<Vec<_>>>()?;
+        Ok(match columns.as_slice() {
+            [column] => column.clone().with_name(field),
+            _ => StructChunked::from_columns(field, df.height(), &columns)?.into_column(),
+        })
+    };
+    let fields = [
+        side(left_keys, PlSmallStr::from_static("left"))?,
+        side(right_keys, PlSmallStr::from_static("right"))?,
+    ];
+    let keys = StructChunked::from_columns(name, df.height(), &fields)?.into_column();
+
+    let position = left_keys
+        .iter()
+        .filter_map(|key| df.get_column_index(key))
+        .min()
+        .unwrap_or(0);
+    let mut df = df.drop_many(left_keys.iter().chain(right_keys).cloned());
+    df.insert_column(position, keys)?;
+    Ok(df)
+}
+
+#[cfg(not(feature = "dtype-struct"))]
+pub(super) fn nest_join_keys(
+    _df: DataFrame,
+    _left_keys: &[PlSmallStr],
+    _right_keys: &[PlSmallStr],
+    _name: PlSmallStr,
+) -> PolarsResult<DataFrame> {
+    polars_bail!(InvalidOperation: "activate 'dtype-struct' feature to nest the join keys into a struct")
+}
+
+/// The schema of a join output after [`nest_join_keys`].
+#[cfg(feature = "dtype-struct")]
+fn nest_join_keys_schema(
+    schema: Schema,
+    left_keys: &[PlSmallStr],
+    right_keys: &[PlSmallStr],
+    name: &PlSmallStr,
+) -> PolarsResult<Schema> {
+    let side = |keys: &[PlSmallStr], field: PlSmallStr| -> PolarsResult<Field> {
+        let fields = keys
+            .iter()
+            .map(|key| Ok(Field::new(key.clone(), schema.try_get(key)?.clone())))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        Ok(match fields.as_slice() {
+            [key] => Field::new(field, key.dtype().clone()),
+            _ => Field::new(field, DataType::Struct(fields)),
+        })
+    };
+    let keys = DataType::Struct(vec![
+        side(left_keys, PlSmallStr::from_static("left"))?,
+        side(right_keys, PlSmallStr::from_static("right"))?,
+    ]);
+
+    let position = left_keys
+        .iter()
+        .filter_map(|key| schema.index_of(key))
+        .min()
+        .unwrap_or(0);
+    polars_ensure!(
+        !schema.contains(name) || left_keys.contains(name) || right_keys.contains(name),
+        Duplicate: "column with name {:?} is already present in the DataFrame", name
+    );
+    let mut out = Schema::with_capacity(schema.len());
+    for (i, (column, dtype)) in schema.iter().enumerate() {
+        if i == position {
+            out.insert(name.clone(), keys.clone());
+        }
+        if !(left_keys.contains(column) || right_keys.contains(column)) {
+            out.insert(column.clone(), dtype.clone());
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(not(feature = "dtype-struct"))]
+fn nest_join_keys_schema(
+    _schema: Schema,
+    _left_keys: &[PlSmallStr],
+    _right_keys: &[PlSmallStr],
+    _name: &PlSmallStr,
+) -> PolarsResult<Schema> {
+    polars_bail!(InvalidOperation: "activate 'dtype-struct' feature to nest the join keys into a struct")
+}
+
+/// The columns of a join result that don't appear under their input name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JoinColumnReport {
+    /// Key columns that were coalesced into the key columns of the other side. These are right
+    /// columns, except for a right join which keeps the right keys and drops the left ones.
+    pub dropped: Vec<PlSmallStr>,
+    /// Right columns that clashed with a left column, with their suffixed output name.
+    pub renamed: Vec<(PlSmallStr, PlSmallStr)>,
+}
+
+/// Determine which columns a join of `left` with `right` drops and renames, following the same
+/// rules as the join itself.
+pub(super) fn join_column_report(
+    left: &Schema,
+    right: &Schema,
+    left_on: &[PlSmallStr],
+    right_on: &[PlSmallStr],
+    args: &JoinArgs,
+) -> JoinColumnReport {
+    #[cfg(feature = "semi_anti_join")]
+    if matches!(args.how, JoinType::Semi | JoinType::Anti) {
+        return JoinColumnReport::default();
+    }
+
+    let should_coalesce = args.should_coalesce();
+    let dropped = match &args.how {
+        #[cfg(feature = "asof_join")]
+        JoinType::AsOf(options) => {
+            let mut dropped = options.right_by.clone().unwrap_or_default();
+            if should_coalesce && left_on == right_on {
+                dropped.extend_from_slice(right_on);
+            }
+            dropped
+        },
+        JoinType::Right if should_coalesce => left_on.to_vec(),
+        _ if should_coalesce => right_on.to_vec(),
+        _ => vec![],
+    };
+    let is_dropped = |name: &PlSmallStr, from_left: bool| {
+        (from_left == (args.how == JoinType::Right)) && dropped.contains(name)
+    };
+
+    let suffix = get_suffix(args.suffix.clone());
+    let renamed = right
+        .iter_names()
+        .filter(|name| {
+            !is_dropped(name, false) && left.contains(name.as_str()) && !is_dropped(name, true)
+        })
+        .map(|name| (name.clone(), _join_suffix_name(name, suffix.as_str())))
+        .collect();
+    JoinColumnReport { dropped, renamed }
+}
+
+/// Determine the output schema of a join of `left` with `right` without executing it, following
+/// the same rules as the join itself.
+pub(super) fn join_schema(
+    left: &Schema,
+    right: &Schema,
+    left_on: &[PlSmallStr],
+    right_on: &[PlSmallStr],
+    args: &JoinArgs,
+) -> PolarsResult<Schema> {
+    polars_ensure!(
+        left_on.len() == right_on.len(),
+        InvalidOperation: "the number of left ({}) and right ({}) join keys must match",
+        left_on.len(), right_on.len()
+    );
+    for name in left_on {
+        left.try_get(name)?;
+    }
+    for name in right_on {
+        right.try_get(name)?;
+    }
+    polars_ensure!(
+        !args.semi_anti_from_right || args.how.is_semi_anti(),
+        InvalidOperation: "only semi and anti joins can be evaluated from the right frame"
+    );
+
+    if args.how.is_semi_anti() {
+        let (schema, key_names) = if args.semi_anti_from_right {
+            (right, right_on)
+        } else {
+            (left, left_on)
+        };
+        let mut schema = schema.clone();
+        if let Some(names) = &args.semi_anti_key_names {
+            polars_ensure!(
+                names.len() == key_names.len(),
+                InvalidOperation: "expected {} output key names, got {}",
+                key_names.len(), names.len()
+            );
+            for (key_name, name) in key_names.iter().zip(names) {
+                schema.rename(key_name, name.clone());
+            }
+        }
+        return Ok(schema);
+    }
+    polars_ensure!(
+        args.semi_anti_key_names.is_none(),
+        InvalidOperation: "output key names can only be set for semi and anti joins"
+    );
+    polars_ensure!(
+        args.output_columns.is_none() || matches!(args.how, JoinType::Inner),
+        InvalidOperation: "output columns can only be set for inner joins"
+    );
+
+    let JoinColumnReport { dropped, renamed } =
+        join_column_report(left, right, left_on, right_on, args);
+    let is_right = args.how == JoinType::Right;
+
+    let mut schema = Schema::with_capacity(left.len() + right.len());
+    for (name, dtype) in left.iter() {
+        if !(is_right && dropped.contains(name)) {
+            schema.insert(name.clone(), dtype.clone());
+        }
+    }
+    for (name, dtype) in right.iter() {
+        if !is_right && dropped.contains(name) {
+            continue;
+        }
+        let name = renamed
+            .iter()
+            .find_map(|(old, new)| (old == name).then_some(new))
+            .unwrap_or(name);
+        schema.insert(name.clone(), dtype.clone());
+    }
+
+    if let Some(name) = &args.key_struct {
+        ensure_key_struct_supported(args)?;
+        let right_keys = right_key_output_names(left, right_on, args.suffix());
+        return nest_join_keys_schema(schema, left_on, &right_keys, name);
+    }
+    match &args.output_columns {
+        Some(output_columns) => schema.try_project(output_columns),
+        None => Ok(schema),
+    }
+}
+
 pub fn _coalesce_full_join(
     mut df: DataFrame,
     keys_left: &[PlSmallStr],
@@ -65,25 +353,40 @@ pub fn _coalesce_full_join(
     };
 
     let schema = df.schema().clone();
-    let mut to_remove = Vec::with_capacity(keys_right.len());
+    let suffix = get_suffix(suffix);
+    let positions = keys_left
+        .iter()
+        .zip(keys_right.iter())
+        .map(|(l, r)| {
+            let pos_l = schema.get_full(l.as_str()).unwrap().0;
+
+            let r = if l == r || schema_left.contains(r.as_str()) {
+                _join_suffix_name(r.as_str(), suffix.as_str())
+            } else {
+                r.clone()
+            };
+            let pos_r = schema.get_full(&r).unwrap().0;
+            (pos_l, pos_r)
+        })
+        .collect::<Vec<_>>();
 
     // SAFETY: we maintain invariants.
     let columns = unsafe { df.get_columns_mut() };
-    let suffix = get_suffix(suffix);
-    for (l, r) in keys_left.iter().zip(keys_right.iter()) {
-        let pos_l = schema.get_full(l.as_str()).unwrap().0;
 
-        let r = if l == r || schema_left.contains(r.as_str()) {
-            _join_suffix_name(r.as_str(), suffix.as_str())
-        } else {
-            r.clone()
-        };
-        let pos_r = schema.get_full(&r).unwrap().0;
-
-        let l = columns[pos_l].clone();
-        let r = columns[pos_r].clone();
+    // Take all keys of a row from the side it came from, so that the keys form a tuple of that
+    // side. A row comes from the left if any of its left keys is valid, rows with only null left
+    // keys have no left match so their right keys (if any) are taken.
+    let mask = positions
+        .iter()
+        .map(|(pos_l, _)| columns[*pos_l].is_not_null())
+        .reduce(|acc, valid| &acc | &valid)
+        .unwrap();
+    let mut to_remove = Vec::with_capacity(keys_right.len());
+    for (pos_l, pos_r) in positions {
+        let l = columns[pos_l].as_materialized_series();
+        let r = columns[pos_r].as_materialized_series();
 
-        columns[pos_l] = coalesce_columns(&[l, r]).unwrap();
+        columns[pos_l] = l.zip_with_same_type(&mask, r).unwrap().into_column();
         to_remove.push(pos_r);
     }
     // sort in reverse order, so the indexes remain correct if we remove.
@@ -18,6 +18,7 @@ pub trait SeriesJoin: SeriesSealed + Sized {
         other: &Series,
         validate: JoinValidation,
         nulls_equal: bool,
+        limit: Option<usize>,
     ) -> PolarsResult<LeftJoinIds> {
         let s_self = self.as_series();
         let (lhs, rhs) = (s_self.to_physical_repr(), other.to_physical_repr());
@@ -45,6 +46,7 @@ pub trait SeriesJoin: SeriesSealed + Sized {
                     validate,
                     nulls_equal,
                     build_null_count,
+                    limit,
                 )
             },
             T::BinaryOffset => {
@@ -63,30 +65,31 @@ pub trait SeriesJoin: SeriesSealed + Sized {
                     validate,
                     nulls_equal,
                     build_null_count,
+                    limit,
                 )
             },
             T::List(_) => {
                 let lhs = &encode_rows_unordered(&[lhs.into_owned().into()])?.into_series();
                 let rhs = &encode_rows_unordered(&[rhs.into_owned().into()])?.into_series();
-                lhs.hash_join_left(rhs, validate, nulls_equal)
+                lhs.hash_join_left(rhs, validate, nulls_equal, limit)
             },
             #[cfg(feature = "dtype-array")]
             T::Array(_, _) => {
                 let lhs = &encode_rows_unordered(&[lhs.into_owned().into()])?.into_series();
                 let rhs = &encode_rows_unordered(&[rhs.into_owned().into()])?.into_series();
-                lhs.hash_join_left(rhs, validate, nulls_equal)
+                lhs.hash_join_left(rhs, validate, nulls_equal, limit)
             },
             #[cfg(feature = "dtype-struct")]
             T::Struct(_) => {
                 let lhs = &encode_rows_unordered(&[lhs.into_owned().into()])?.into_series();
                 let rhs = &encode_rows_unordered(&[rhs.into_owned().into()])?.into_series();
-                lhs.hash_join_left(rhs, validate, nulls_equal)
+                lhs.hash_join_left(rhs, validate, nulls_equal, limit)
             },
             x if x.is_float() => {
                 with_match_physical_float_polars_type!(lhs.dtype(), |$T| {
                     let lhs: &ChunkedArray<$T> = lhs.as_ref().as_ref().as_ref();
                     let rhs: &ChunkedArray<$T> = rhs.as_ref().as_ref().as_ref();
-                    num_group_join_left(lhs, rhs, validate, nulls_equal)
+                    num_group_join_left(lhs, rhs, validate, nulls_equal, limit)
                 })
             },
             _ => {
@@ -101,16 +104,16 @@ pub trait SeriesJoin: SeriesSealed + Sized {
                 match (lhs, rhs) {
                     (B::U32(lhs), B::U32(rhs)) => {
                         // Turbofish: see #17137.
-                        num_group_join_left::<UInt32Type>(&lhs, &rhs, validate, nulls_equal)
+                        num_group_join_left::<UInt32Type>(&lhs, &rhs, validate, nulls_equal, limit)
                     },
                     (B::U64(lhs), B::U64(rhs)) => {
                         // Turbofish: see #17137.
-                        num_group_join_left::<UInt64Type>(&lhs, &rhs, validate, nulls_equal)
+                        num_group_join_left::<UInt64Type>(&lhs, &rhs, validate, nulls_equal, limit)
                     },
                     #[cfg(feature = "dtype-i128")]
                     (B::I128(lhs), B::I128(rhs)) => {
                         // Turbofish: see #17137.
-                        num_group_join_left::<Int128Type>(&lhs, &rhs, validate, nulls_equal)
+                        num_group_join_left::<Int128Type>(&lhs, &rhs, validate, nulls_equal, limit)
                     },
                     _ => {
                         polars_bail!(
@@ -571,6 +574,7 @@ fn num_group_join_left<T>(
     right: &ChunkedArray<T>,
     validate: JoinValidation,
     nulls_equal: bool,
+    limit: Option<usize>,
 ) -> PolarsResult<LeftJoinIds>
 where
     T: PolarsNumericType,
@@ -591,7 +595,7 @@ where
         (0, 0, 1, 1) => {
             let keys_a = chunks_as_slices(&splitted_a);
             let keys_b = chunks_as_slices(&splitted_b);
-            hash_join_tuples_left(keys_a, keys_b, None, None, validate, nulls_equal, 0)
+            hash_join_tuples_left(keys_a, keys_b, None, None, validate, nulls_equal, 0, limit)
         },
         (0, 0, _, _) => {
             let keys_a = chunks_as_slices(&splitted_a);
@@ -607,6 +611,7 @@ where
                 validate,
                 nulls_equal,
                 0,
+                limit,
             )
         },
         _ => {
@@ -623,6 +628,7 @@ where
                 validate,
                 nulls_equal,
                 build_null_count,
+                limit,
             )
         },
     }
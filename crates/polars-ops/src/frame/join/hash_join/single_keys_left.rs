@@ -103,6 +103,7 @@ pub(super) fn flatten_left_join_ids(result: Vec<LeftJoinIds>) -> LeftJoinIds {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn hash_join_tuples_left<T, I>(
     probe: Vec<I>,
     build: Vec<I>,
@@ -114,6 +115,9 @@ pub(super) fn hash_join_tuples_left<T, I>(
     nulls_equal: bool,
     // We should know the number of nulls to avoid extra calculation
     build_null_count: usize,
+    // Every probe partition stops once it produced this many join tuples. The concatenated result
+    // then still starts with the first `limit` join tuples.
+    limit: Option<usize>,
 ) -> PolarsResult<LeftJoinIds>
 where
     I: IntoIterator<Item = T>,
@@ -141,6 +145,7 @@ where
 
     // we determine the offset so that we later know which index to store in the join tuples
     let offsets = probe_to_offsets(&probe);
+    let limit = limit.unwrap_or(usize::MAX);
 
     // next we probe the other relation
     let result: Vec<LeftJoinIds> = POOL.install(move || {
@@ -157,7 +162,10 @@ where
                 let mut result_idx_left = Vec::with_capacity(probe.size_hint().1.unwrap());
                 let mut result_idx_right = Vec::with_capacity(probe.size_hint().1.unwrap());
 
-                probe.enumerate().for_each(|(idx_a, k)| {
+                for (idx_a, k) in probe.enumerate() {
+                    if result_idx_left.len() >= limit {
+                        break;
+                    }
                     let k = k.to_total_ord();
                     let idx_a = (idx_a + offset) as IdxSize;
                     // probe table that contains the hashed value
@@ -180,7 +188,7 @@ where
                             result_idx_right.push(NullableIdxSize::null());
                         },
                     }
-                });
+                }
                 finish_left_join_mappings(
                     result_idx_left,
                     result_idx_right,
@@ -118,8 +118,79 @@ where
     T: TotalHash + TotalEq + DirtyHash + ToTotalOrd,
     <T as ToTotalOrd>::TotalOrdItem: Send + Sync + Hash + Eq + DirtyHash + IsNull,
 {
+    if num_keys(&probe) * SHORT_CIRCUIT_SEMI_RATIO < num_keys(&build) {
+        return hash_join_tuples_left_semi_short_circuit(probe, build, nulls_equal);
+    }
+
     let par_iter = semi_anti_impl(probe, build, nulls_equal)
         .filter(|tpls| tpls.1)
         .map(|tpls| tpls.0);
     POOL.install(|| par_iter.collect())
 }
+
+/// How many times larger the right side has to be than the left side of a semi join to scan it
+/// sequentially for the left keys, see [`hash_join_tuples_left_semi_short_circuit`].
+const SHORT_CIRCUIT_SEMI_RATIO: usize = 8;
+
+fn num_keys<T, I>(keys: &[I]) -> usize
+where
+    I: IntoIterator<Item = T> + Copy,
+{
+    keys.iter().map(|k| k.into_iter().size_hint().0).sum()
+}
+
+/// Semi join for a left side that is smaller than the right side.
+///
+/// Rather than hashing the right side, the distinct left keys are hashed and the right side is
+/// scanned until all of them are found. If the right side densely covers the left keys, this stops
+/// long before the end of the right side.
+fn hash_join_tuples_left_semi_short_circuit<T, I>(
+    probe: Vec<I>,
+    build: Vec<I>,
+    nulls_equal: bool,
+) -> Vec<IdxSize>
+where
+    I: IntoIterator<Item = T> + Copy,
+    T: ToTotalOrd,
+    <T as ToTotalOrd>::TotalOrdItem: Hash + Eq + IsNull,
+{
+    // Whether every distinct left key has been found on the right.
+    let mut matched: PlHashMap<T::TotalOrdItem, bool> = PlHashMap::new();
+    for keys in &probe {
+        for k in keys.into_iter() {
+            let k = k.to_total_ord();
+            if !k.is_null() || nulls_equal {
+                matched.insert(k, false);
+            }
+        }
+    }
+
+    let mut num_unmatched = matched.len();
+    let mut iteration = 0;
+    'scan: for keys in build {
+        if num_unmatched == 0 {
+            break;
+        }
+        for k in keys {
+            maybe_raise_keyboard_interrupt(iteration);
+            iteration += 1;
+            if let Some(found) = matched.get_mut(&k.to_total_ord()) {
+                if !*found {
+                    *found = true;
+                    num_unmatched -= 1;
+                    if num_unmatched == 0 {
+                        break 'scan;
+                    }
+                }
+            }
+        }
+    }
+
+    probe
+        .into_iter()
+        .flat_map(|keys| keys.into_iter())
+        .enumerate()
+        .filter(|(_, k)| matched.get(&k.to_total_ord()).copied().unwrap_or(false))
+        .map(|(idx, _)| idx as IdxSize)
+        .collect()
+}
@@ -223,6 +223,28 @@ pub(crate) fn _sort_or_hash_inner(
             }
             Ok((par_sorted_merge_inner_no_nulls(s_left, s_right), true))
         },
+        // Nulls of a sorted key are all at the start or at the end. If they don't match each
+        // other we can merge join the non-null parts instead of building a hash table.
+        (IsSorted::Ascending, IsSorted::Ascending, false) if is_numeric && !nulls_equal => {
+            if verbose {
+                eprintln!("inner join: keys are sorted: use sorted merge join on non-null keys");
+            }
+            let (offset_left, len_left) = sorted_non_null_range(s_left);
+            let (offset_right, len_right) = sorted_non_null_range(s_right);
+            let (mut left, mut right) = par_sorted_merge_inner_no_nulls(
+                &s_left.slice(offset_left as i64, len_left),
+                &s_right.slice(offset_right as i64, len_right),
+            );
+
+            POOL.install(|| {
+                rayon::join(
+                    || add_offset(&mut left, offset_left),
+                    || add_offset(&mut right, offset_right),
+                )
+            });
+
+            Ok(((left, right), true))
+        },
         (IsSorted::Ascending, _, true)
             if is_numeric && size_factor_rhs < size_factor_acceptable =>
         {
@@ -284,6 +306,25 @@ pub(crate) fn _sort_or_hash_inner(
     }
 }
 
+/// The offset and length of the non-null values of a key that is sorted.
+#[cfg(feature = "performant")]
+fn sorted_non_null_range(s: &Series) -> (usize, usize) {
+    let null_count = s.null_count();
+    let len = s.len() - null_count;
+    if null_count > 0 && s.is_null().get(0) == Some(true) {
+        (null_count, len)
+    } else {
+        (0, len)
+    }
+}
+
+#[cfg(feature = "performant")]
+fn add_offset(idx: &mut [IdxSize], offset: usize) {
+    if offset > 0 {
+        idx.par_iter_mut().for_each(|i| *i += offset as IdxSize);
+    }
+}
+
 #[cfg(not(feature = "performant"))]
 pub(crate) fn sort_or_hash_left(
     s_left: &Series,
@@ -291,8 +332,9 @@ pub(crate) fn sort_or_hash_left(
     _verbose: bool,
     validate: JoinValidation,
     nulls_equal: bool,
+    limit: Option<usize>,
 ) -> PolarsResult<LeftJoinIds> {
-    s_left.hash_join_left(s_right, validate, nulls_equal)
+    s_left.hash_join_left(s_right, validate, nulls_equal, limit)
 }
 
 #[cfg(feature = "performant")]
@@ -302,9 +344,10 @@ pub(crate) fn sort_or_hash_left(
     verbose: bool,
     validate: JoinValidation,
     nulls_equal: bool,
+    limit: Option<usize>,
 ) -> PolarsResult<LeftJoinIds> {
     if validate.needs_checks() {
-        return s_left.hash_join_left(s_right, validate, nulls_equal);
+        return s_left.hash_join_left(s_right, validate, nulls_equal, limit);
     }
 
     let size_factor_rhs = s_right.len() as f32 / s_left.len() as f32;
@@ -356,6 +399,6 @@ pub(crate) fn sort_or_hash_left(
             Ok(to_left_join_ids(left, right))
         },
         // don't reverse sort a left join key yet. Have to figure out how to set sorted flag
-        _ => s_left.hash_join_left(s_right, validate, nulls_equal),
+        _ => s_left.hash_join_left(s_right, validate, nulls_equal, limit),
     }
 }
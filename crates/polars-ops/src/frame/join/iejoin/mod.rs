@@ -13,7 +13,7 @@ use polars_core::prelude::*;
 use polars_core::series::IsSorted;
 use polars_core::utils::{_set_partition_size, split};
 use polars_core::{POOL, with_match_physical_numeric_polars_type};
-use polars_error::{PolarsResult, polars_err};
+use polars_error::{PolarsResult, polars_bail, polars_err};
 use polars_utils::IdxSize;
 use polars_utils::binary_search::ExponentialSearch;
 use polars_utils::itertools::Itertools;
@@ -22,7 +22,8 @@ use rayon::prelude::*;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::frame::_finish_join;
+use super::maybe_raise_keyboard_interrupt;
+use crate::frame::{_finish_join, MaintainOrderJoin};
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -76,7 +77,8 @@ fn ie_join_impl_t<T: PolarsNumericType>(
         // To handle inclusive comparisons in x and duplicate x values we also need the
         // sort of l1 to be stable, so that the left hand side entries come before the right
         // hand side entries (as we mark visited entries from the right hand side).
-        for &p in l2_order {
+        for (i, &p) in l2_order.iter().enumerate() {
+            maybe_raise_keyboard_interrupt(i);
             match_count += unsafe {
                 l1_array.process_entry(
                     p as usize,
@@ -100,6 +102,7 @@ fn ie_join_impl_t<T: PolarsNumericType>(
         let mut run_start = 0;
 
         for i in 0..l2_array.len() {
+            maybe_raise_keyboard_interrupt(i);
             // Elide bound checks
             unsafe {
                 let item = l2_array.get_unchecked(i);
@@ -158,6 +161,7 @@ where
     let mut match_count = 0;
 
     while left_idx < left_ca.len() {
+        maybe_raise_keyboard_interrupt(left_idx);
         debug_assert!(left_ca.get(left_idx).is_some());
         let left_val = unsafe { left_ca.value_unchecked(left_idx) };
         while right_idx < right_ca.len() {
@@ -202,6 +206,50 @@ where
     Ok((left_row_idx, right_row_idx))
 }
 
+/// Compute the row indices of the left and right rows matched by an inequality join, without
+/// gathering the joined frame.
+///
+/// `selected_left` and `selected_right` hold the key columns the operators of `options` compare,
+/// one for a piecewise merge join and two for an IEJoin.
+pub fn iejoin_row_indices(
+    selected_left: Vec<Series>,
+    selected_right: Vec<Series>,
+    options: &IEJoinOptions,
+    slice: Option<(i64, usize)>,
+    maintain_order: MaintainOrderJoin,
+) -> PolarsResult<(IdxCa, IdxCa)> {
+    if let Some((l, r)) = selected_left
+        .iter()
+        .zip(&selected_right)
+        .find(|(l, r)| l.dtype() != r.dtype())
+    {
+        polars_bail!(
+            ComputeError: "datatypes of join keys don't match - `{}`: {} on left does not match `{}`: {} on right",
+            l.name(), l.dtype(), r.name(), r.dtype()
+        );
+    }
+
+    let is_empty = |s: &[Series]| s.first().is_none_or(|s| s.is_empty());
+    if POOL.current_num_threads() > 1 && !is_empty(&selected_left) && !is_empty(&selected_right) {
+        iejoin_par_row_indices(
+            selected_left,
+            selected_right,
+            options,
+            slice,
+            maintain_order,
+        )
+    } else {
+        iejoin_serial_row_indices(
+            selected_left,
+            selected_right,
+            options,
+            slice,
+            maintain_order,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(super) fn iejoin_par(
     left: &DataFrame,
     right: &DataFrame,
@@ -210,7 +258,25 @@ pub(super) fn iejoin_par(
     options: &IEJoinOptions,
     suffix: Option<PlSmallStr>,
     slice: Option<(i64, usize)>,
+    maintain_order: MaintainOrderJoin,
 ) -> PolarsResult<DataFrame> {
+    let (left_idx, right_idx) = iejoin_par_row_indices(
+        selected_left,
+        selected_right,
+        options,
+        slice,
+        maintain_order,
+    )?;
+    unsafe { materialize_join(left, right, &left_idx, &right_idx, suffix) }
+}
+
+fn iejoin_par_row_indices(
+    selected_left: Vec<Series>,
+    selected_right: Vec<Series>,
+    options: &IEJoinOptions,
+    slice: Option<(i64, usize)>,
+    maintain_order: MaintainOrderJoin,
+) -> PolarsResult<(IdxCa, IdxCa)> {
     let l1_descending = matches!(
         options.operator1,
         InequalityOperator::Gt | InequalityOperator::GtEq
@@ -244,6 +310,7 @@ pub(super) fn iejoin_par(
         .collect::<Vec<_>>();
 
     let iter = cartesian_prod.par_iter().map(|(l_l1_idx, r_l1_idx)| {
+        try_raise_keyboard_interrupt();
         if l_l1_idx.is_empty() || r_l1_idx.is_empty() {
             return Ok(None);
         }
@@ -331,14 +398,17 @@ pub(super) fn iejoin_par(
         left_idx.append(&l)?;
         right_idx.append(&r)?;
     }
+    if maintain_order != MaintainOrderJoin::None {
+        (left_idx, right_idx) = sort_row_indices(left_idx, right_idx, maintain_order)?;
+    }
     if let Some((offset, end)) = slice {
         left_idx = left_idx.slice(offset, end);
         right_idx = right_idx.slice(offset, end);
     }
-
-    unsafe { materialize_join(left, right, &left_idx, &right_idx, suffix) }
+    Ok((left_idx, right_idx))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn iejoin(
     left: &DataFrame,
     right: &DataFrame,
@@ -347,13 +417,72 @@ pub(super) fn iejoin(
     options: &IEJoinOptions,
     suffix: Option<PlSmallStr>,
     slice: Option<(i64, usize)>,
+    maintain_order: MaintainOrderJoin,
 ) -> PolarsResult<DataFrame> {
-    let (left_row_idx, right_row_idx) = if options.operator2.is_some() {
-        iejoin_tuples(selected_left, selected_right, options, slice)
+    let (left_row_idx, right_row_idx) = iejoin_serial_row_indices(
+        selected_left,
+        selected_right,
+        options,
+        slice,
+        maintain_order,
+    )?;
+    unsafe { materialize_join(left, right, &left_row_idx, &right_row_idx, suffix) }
+}
+
+fn iejoin_serial_row_indices(
+    selected_left: Vec<Series>,
+    selected_right: Vec<Series>,
+    options: &IEJoinOptions,
+    slice: Option<(i64, usize)>,
+    maintain_order: MaintainOrderJoin,
+) -> PolarsResult<(IdxCa, IdxCa)> {
+    // The slice can only be applied while computing the tuples if we don't reorder them afterwards.
+    let tuples_slice = slice.filter(|_| maintain_order == MaintainOrderJoin::None);
+    let (mut left_row_idx, mut right_row_idx) = if options.operator2.is_some() {
+        iejoin_tuples(selected_left, selected_right, options, tuples_slice)
     } else {
-        piecewise_merge_join_tuples(selected_left, selected_right, options, slice)
+        piecewise_merge_join_tuples(selected_left, selected_right, options, tuples_slice)
     }?;
-    unsafe { materialize_join(left, right, &left_row_idx, &right_row_idx, suffix) }
+    if maintain_order != MaintainOrderJoin::None {
+        (left_row_idx, right_row_idx) =
+            sort_row_indices(left_row_idx, right_row_idx, maintain_order)?;
+        if let Some((offset, len)) = slice {
+            left_row_idx = left_row_idx.slice(offset, len);
+            right_row_idx = right_row_idx.slice(offset, len);
+        }
+    }
+    Ok((left_row_idx, right_row_idx))
+}
+
+/// Reorder the matched row indices so the output follows the order of the input frame(s)
+/// requested by `maintain_order`.
+fn sort_row_indices(
+    left_row_idx: IdxCa,
+    right_row_idx: IdxCa,
+    maintain_order: MaintainOrderJoin,
+) -> PolarsResult<(IdxCa, IdxCa)> {
+    let mut df = DataFrame::new(vec![
+        left_row_idx.with_name("a".into()).into_series().into(),
+        right_row_idx.with_name("b".into()).into_series().into(),
+    ])?;
+
+    let options = SortMultipleOptions::new()
+        .with_order_descending(false)
+        .with_maintain_order(true);
+
+    let columns = match maintain_order {
+        MaintainOrderJoin::Left => vec!["a"],
+        MaintainOrderJoin::LeftRight => vec!["a", "b"],
+        MaintainOrderJoin::Right => vec!["b"],
+        MaintainOrderJoin::RightLeft => vec!["b", "a"],
+        MaintainOrderJoin::None => unreachable!(),
+    };
+
+    df.sort_in_place(columns, options)?;
+
+    let left_row_idx = df.column("a").unwrap().idx().unwrap().clone();
+    let right_row_idx = df.column("b").unwrap().idx().unwrap().clone();
+    Ok((left_row_idx, right_row_idx))
 }
 
 unsafe fn materialize_join(
@@ -0,0 +1,144 @@
+use std::hash::BuildHasher;
+
+use polars_compute::binview_index_map::{BinaryViewIndexMap, Entry};
+use polars_utils::aliases::PlRandomState;
+use polars_utils::idx_vec::IdxVec;
+use polars_utils::unitvec;
+
+use super::*;
+
+/// The hash table of the build side of an inner join, which can be probed many times.
+///
+/// Joining the same (small) frame against many other frames normally rebuilds its hash table on
+/// every join. A `JoinIndex` builds it once, [`JoinIndex::inner_join`] then only has to probe it.
+///
+/// The index owns the build frame. As the columns of a [`DataFrame`] are reference counted, this
+/// doesn't copy any data.
+pub struct JoinIndex {
+    /// The build frame without its key columns.
+    payload: DataFrame,
+    keys: Vec<Field>,
+    nulls_equal: bool,
+    random_state: PlRandomState,
+    /// The row-encoded keys mapped to the rows of the build frame with that key.
+    table: BinaryViewIndexMap<IdxVec>,
+}
+
+impl JoinIndex {
+    /// Build the hash table of `frame` on the `keys` columns.
+    ///
+    /// If `nulls_equal` is set null keys match null keys, otherwise they never match.
+    pub fn build(
+        frame: &DataFrame,
+        keys: impl IntoIterator<Item = impl Into<PlSmallStr>>,
+        nulls_equal: bool,
+    ) -> PolarsResult<Self> {
+        let keys = frame.select_columns(keys)?;
+        polars_ensure!(!keys.is_empty(), ComputeError: "cannot build a join index without keys");
+        let encoded = encode_keys(&keys, nulls_equal)?;
+
+        let random_state = PlRandomState::default();
+        let mut table = BinaryViewIndexMap::<IdxVec>::new();
+        table.reserve(encoded.len());
+        for (idx, key) in encoded.iter().enumerate() {
+            let Some(key) = key else {
+                continue;
+            };
+            let hash = random_state.hash_one(key);
+            match table.entry(hash, key) {
+                Entry::Occupied(o) => o.into_mut().push(idx as IdxSize),
+                Entry::Vacant(v) => {
+                    v.insert(unitvec![idx as IdxSize]);
+                },
+            }
+        }
+
+        Ok(Self {
+            payload: frame.drop_many(keys.iter().map(|c| c.name().clone())),
+            keys: keys.iter().map(|c| c.field().into_owned()).collect(),
+            nulls_equal,
+            random_state,
+            table,
+        })
+    }
+
+    /// Inner join `probe` on its `probe_keys` columns against the build frame.
+    ///
+    /// The result equals that of an inner join of `probe` with the build frame which maintains
+    /// the order of `probe`. The key columns are those of `probe`, clashing names of the build
+    /// frame get the default suffix.
+    pub fn inner_join(
+        &self,
+        probe: &DataFrame,
+        probe_keys: impl IntoIterator<Item = impl Into<PlSmallStr>>,
+    ) -> PolarsResult<DataFrame> {
+        let keys = probe.select_columns(probe_keys)?;
+        polars_ensure!(
+            keys.len() == self.keys.len(),
+            ComputeError: "the number of probe keys ({}) does not match the number of build keys ({})",
+            keys.len(), self.keys.len()
+        );
+        if let Some((l, r)) = keys
+            .iter()
+            .zip(&self.keys)
+            .find(|(l, r)| l.dtype() != r.dtype())
+        {
+            polars_bail!(
+                ComputeError:
+                    "datatypes of join keys don't match - `{}`: {} on left does not match `{}`: {} on right",
+                    l.name(), l.dtype(), r.name(), r.dtype()
+            );
+        }
+        let encoded = encode_keys(&keys, self.nulls_equal)?;
+
+        let mut offset = 0;
+        let chunks = encoded
+            .downcast_iter()
+            .map(|arr| {
+                let chunk_offset = offset;
+                offset += arr.len() as IdxSize;
+                (chunk_offset, arr)
+            })
+            .collect::<Vec<_>>();
+        let matches = POOL.install(|| {
+            chunks
+                .into_par_iter()
+                .map(|(offset, arr)| {
+                    let mut probe_idx = vec![];
+                    let mut build_idx = vec![];
+                    for (i, key) in arr.iter().enumerate() {
+                        let Some(key) = key else {
+                            continue;
+                        };
+                        let hash = self.random_state.hash_one(key);
+                        if let Some(rows) = self.table.get(hash, key) {
+                            probe_idx
+                                .extend(std::iter::repeat_n(offset + i as IdxSize, rows.len()));
+                            build_idx.extend_from_slice(rows);
+                        }
+                    }
+                    (probe_idx, build_idx)
+                })
+                .collect::<Vec<_>>()
+        });
+        let (probe_idx, build_idx): (Vec<_>, Vec<_>) = matches.into_iter().unzip();
+        let probe_idx = probe_idx.concat();
+        let build_idx = build_idx.concat();
+        try_raise_keyboard_interrupt();
+
+        // SAFETY: the indices are rows of the probed and built frames.
+        let (left, right) = POOL.join(
+            || unsafe { probe._take_unchecked_slice(&probe_idx, true) },
+            || unsafe { self.payload._take_unchecked_slice(&build_idx, true) },
+        );
+        _finish_join(left, right, None)
+    }
+}
+
+fn encode_keys(keys: &[Column], nulls_equal: bool) -> PolarsResult<BinaryOffsetChunked> {
+    let keys = keys
+        .iter()
+        .map(|c| c.as_materialized_series().clone())
+        .collect::<Vec<_>>();
+    prepare_keys_multiple(&keys, nulls_equal, true)
+}
@@ -1,17 +1,33 @@
+use std::cmp::Ordering;
+
 use arrow::legacy::utils::{CustomIterTools, FromTrustedLenIterator};
+use polars_core::chunked_array::ops::row_encode::_get_rows_encoded_ca;
 use polars_core::prelude::*;
+use polars_core::series::IsSorted;
 use polars_core::{with_match_categorical_physical_type, with_match_physical_numeric_polars_type};
 
+/// Merge two frames that are both sorted by their key column into a single sorted frame.
+///
+/// If `descending` is set, both keys are expected to be sorted in descending order. `nulls_last`
+/// sets where the nulls of both keys are expected to be placed.
+///
+/// If `check` is set, the schemas of both frames are validated, as well as the sort order and the
+/// null placement of both keys.
+///
+/// Rows with equal keys are taken from `left` first, unless a `tie_break` key is given for both
+/// frames. The rows with equal keys are then ordered by the tie-break key, so both frames must be
+/// sorted by the key and then by the tie-break key, in the same order.
+#[allow(clippy::too_many_arguments)]
 pub fn _merge_sorted_dfs(
     left: &DataFrame,
     right: &DataFrame,
     left_s: &Series,
     right_s: &Series,
-    check_schema: bool,
+    tie_break: Option<(&Series, &Series)>,
+    check: bool,
+    descending: bool,
+    nulls_last: bool,
 ) -> PolarsResult<DataFrame> {
-    if check_schema {
-        left.schema_equal(right)?;
-    }
     let dtype_lhs = left_s.dtype();
     let dtype_rhs = right_s.dtype();
 
@@ -20,6 +36,53 @@ pub fn _merge_sorted_dfs(
         ComputeError: "merge-sort datatype mismatch: {} != {}", dtype_lhs, dtype_rhs
     );
 
+    let expected_order = if descending {
+        IsSorted::Descending
+    } else {
+        IsSorted::Ascending
+    };
+    if check {
+        left.schema_equal(right)?;
+        for s in [left_s, right_s] {
+            // A flag of `Not` only means the order is unknown, so we can only catch inputs that are
+            // known to be sorted in the opposite direction.
+            let order = s.is_sorted_flag();
+            polars_ensure!(
+                s.len() <= 1 || order == IsSorted::Not || order == expected_order,
+                InvalidOperation: "merge-sort expected key '{}' to be sorted {}, but it is sorted {}",
+                s.name(),
+                if descending { "descending" } else { "ascending" },
+                if descending { "ascending" } else { "descending" },
+            );
+
+            // In a sorted key the nulls are all at one end, so it suffices to look at the end
+            // where they are expected.
+            let null_count = s.null_count();
+            if null_count > 0 && null_count < s.len() {
+                let expected_null_idx = if nulls_last { s.len() - 1 } else { 0 };
+                polars_ensure!(
+                    s.get(expected_null_idx)?.is_null(),
+                    InvalidOperation: "merge-sort expected the nulls of key '{}' to be placed {}",
+                    s.name(),
+                    if nulls_last { "last" } else { "first" },
+                );
+            }
+        }
+    }
+
+    if let Some((left_tie_break, right_tie_break)) = tie_break {
+        polars_ensure!(
+            left_tie_break.dtype() == right_tie_break.dtype(),
+            ComputeError: "merge-sort tie-break datatype mismatch: {} != {}",
+            left_tie_break.dtype(), right_tie_break.dtype()
+        );
+        polars_ensure!(
+            left_tie_break.len() == left_s.len() && right_tie_break.len() == right_s.len(),
+            ShapeMismatch: "merge-sort tie-break key '{}' must have the same length as key '{}'",
+            left_tie_break.name(), left_s.name()
+        );
+    }
+
     // If one frame is empty, we can return the other immediately.
     if right_s.is_empty() {
         return Ok(left.clone());
@@ -27,8 +90,25 @@ pub fn _merge_sorted_dfs(
         return Ok(right.clone());
     }
 
-    let merge_indicator = series_to_merge_indicator(left_s, right_s)?;
-    let new_columns = left
+    let merge_indicator = match tie_break {
+        None => series_to_merge_indicator(left_s, right_s, descending, nulls_last)?,
+        Some((left_tie_break, right_tie_break)) => {
+            // The row encoding orders by the key and then by the tie-break key, and already
+            // accounts for the sort order and the placement of the nulls.
+            let encode = |key: &Series, tie_break: &Series| {
+                _get_rows_encoded_ca(
+                    PlSmallStr::EMPTY,
+                    &[key.clone().into_column(), tie_break.clone().into_column()],
+                    &[descending; 2],
+                    &[nulls_last; 2],
+                )
+            };
+            let lhs = encode(left_s, left_tie_break)?;
+            let rhs = encode(right_s, right_tie_break)?;
+            get_ordered_merge_indicator(lhs.into_iter(), rhs.into_iter(), false, false)
+        },
+    };
+    let mut new_columns: Vec<Column> = left
         .get_columns()
         .iter()
         .zip(right.get_columns())
@@ -48,6 +128,13 @@ pub fn _merge_sorted_dfs(
         })
         .collect::<PolarsResult<_>>()?;
 
+    // The merged key is only known to be sorted if both inputs were known to be sorted.
+    if left_s.is_sorted_flag() == expected_order && right_s.is_sorted_flag() == expected_order {
+        if let Some(key) = new_columns.iter_mut().find(|c| c.name() == left_s.name()) {
+            key.set_sorted_flag(expected_order);
+        }
+    }
+
     Ok(unsafe { DataFrame::new_no_checks(left.height() + right.height(), new_columns) })
 }
 
@@ -134,12 +221,17 @@ where
     unsafe { iter.trust_my_length(total_len).collect_trusted() }
 }
 
-fn series_to_merge_indicator(lhs: &Series, rhs: &Series) -> PolarsResult<Vec<bool>> {
+fn series_to_merge_indicator(
+    lhs: &Series,
+    rhs: &Series,
+    descending: bool,
+    nulls_last: bool,
+) -> PolarsResult<Vec<bool>> {
     if let Ok(cat_phys) = lhs.dtype().cat_physical() {
         with_match_categorical_physical_type!(cat_phys, |$C| {
             let lhs = lhs.cat::<$C>().unwrap();
             let rhs = rhs.cat::<$C>().unwrap();
-            return Ok(get_merge_indicator(lhs.iter_str(), rhs.iter_str()));
+            return Ok(get_ordered_merge_indicator(lhs.iter_str(), rhs.iter_str(), descending, nulls_last));
         })
     }
 
@@ -150,31 +242,31 @@ fn series_to_merge_indicator(lhs: &Series, rhs: &Series) -> PolarsResult<Vec<boo
         DataType::Boolean => {
             let lhs = lhs_s.bool().unwrap();
             let rhs = rhs_s.bool().unwrap();
-            get_merge_indicator(lhs.into_iter(), rhs.into_iter())
+            get_ordered_merge_indicator(lhs.into_iter(), rhs.into_iter(), descending, nulls_last)
         },
         DataType::String => {
             let lhs = lhs.str().unwrap().as_binary();
             let rhs = rhs.str().unwrap().as_binary();
-            get_merge_indicator(lhs.into_iter(), rhs.into_iter())
+            get_ordered_merge_indicator(lhs.into_iter(), rhs.into_iter(), descending, nulls_last)
         },
         DataType::Binary => {
             let lhs = lhs_s.binary().unwrap();
             let rhs = rhs_s.binary().unwrap();
-            get_merge_indicator(lhs.into_iter(), rhs.into_iter())
+            get_ordered_merge_indicator(lhs.into_iter(), rhs.into_iter(), descending, nulls_last)
         },
         #[cfg(feature = "dtype-struct")]
         DataType::Struct(_) => {
             let options = SortOptions::default();
             let lhs = lhs_s.struct_().unwrap().get_row_encoded(options)?;
             let rhs = rhs_s.struct_().unwrap().get_row_encoded(options)?;
-            get_merge_indicator(lhs.into_iter(), rhs.into_iter())
+            get_ordered_merge_indicator(lhs.into_iter(), rhs.into_iter(), descending, nulls_last)
         },
         _ => {
             with_match_physical_numeric_polars_type!(lhs_s.dtype(), |$T| {
                     let lhs: &ChunkedArray<$T> = lhs_s.as_ref().as_ref().as_ref();
                     let rhs: &ChunkedArray<$T> = rhs_s.as_ref().as_ref().as_ref();
 
-                    get_merge_indicator(lhs.into_iter(), rhs.into_iter())
+                    get_ordered_merge_indicator(lhs.into_iter(), rhs.into_iter(), descending, nulls_last)
 
             })
         },
@@ -182,6 +274,61 @@ fn series_to_merge_indicator(lhs: &Series, rhs: &Series) -> PolarsResult<Vec<boo
     Ok(out)
 }
 
+/// A key value that compares according to the requested sort order and null placement.
+#[derive(Clone, Copy)]
+struct MergeKey<T> {
+    value: Option<T>,
+    descending: bool,
+    nulls_last: bool,
+}
+
+impl<T> Default for MergeKey<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            descending: false,
+            nulls_last: false,
+        }
+    }
+}
+
+impl<T: PartialOrd> PartialEq for MergeKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for MergeKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (&self.value, &other.value) {
+            (None, None) => Some(Ordering::Equal),
+            (None, Some(_)) if self.nulls_last => Some(Ordering::Greater),
+            (None, Some(_)) => Some(Ordering::Less),
+            (Some(_), None) if self.nulls_last => Some(Ordering::Less),
+            (Some(_), None) => Some(Ordering::Greater),
+            (Some(l), Some(r)) if self.descending => r.partial_cmp(l),
+            (Some(l), Some(r)) => l.partial_cmp(r),
+        }
+    }
+}
+
+fn get_ordered_merge_indicator<T>(
+    a_iter: impl ExactSizeIterator<Item = Option<T>>,
+    b_iter: impl ExactSizeIterator<Item = Option<T>>,
+    descending: bool,
+    nulls_last: bool,
+) -> Vec<bool>
+where
+    T: PartialOrd + Copy,
+{
+    let to_key = |value| MergeKey {
+        value,
+        descending,
+        nulls_last,
+    };
+    get_merge_indicator(a_iter.map(to_key), b_iter.map(to_key))
+}
+
 // get a boolean values, left: true, right: false
 // that indicate from which side we should take a value
 fn get_merge_indicator<T>(
@@ -283,3 +430,111 @@ fn test_merge_sorted() {
     let expected = [true, true, true, false, false, false, false];
     assert_eq!(out, expected);
 }
+
+#[test]
+fn test_merge_sorted_descending() {
+    let a = [9, 6, 4, 2, 1];
+    let b = [10, 5, 4, 3, 2];
+
+    let out = get_ordered_merge_indicator(
+        a.iter().copied().map(Some),
+        b.iter().copied().map(Some),
+        true,
+        false,
+    );
+    let expected = [
+        false, true, true, false, true, false, false, true, false, true,
+    ];
+    //                       10    9     6     5      4     4      3      2     2      1
+    assert_eq!(out, expected);
+
+    let left = df!["a" => a].unwrap();
+    let right = df!["a" => b].unwrap();
+    let mut left_s = left.column("a").unwrap().as_materialized_series().clone();
+    left_s.set_sorted_flag(IsSorted::Descending);
+    let mut right_s = right.column("a").unwrap().as_materialized_series().clone();
+
+    // Only one of the inputs is known to be sorted, so neither is the output.
+    let out = _merge_sorted_dfs(&left, &right, &left_s, &right_s, None, true, true, false).unwrap();
+    assert_eq!(out.column("a").unwrap().is_sorted_flag(), IsSorted::Not);
+
+    right_s.set_sorted_flag(IsSorted::Descending);
+    let out = _merge_sorted_dfs(&left, &right, &left_s, &right_s, None, true, true, false).unwrap();
+    let out = out.column("a").unwrap();
+    assert_eq!(out.is_sorted_flag(), IsSorted::Descending);
+    assert_eq!(
+        out.i32().unwrap().cont_slice().unwrap(),
+        [10, 9, 6, 5, 4, 4, 3, 2, 2, 1]
+    );
+
+    // Inputs that are known to be sorted in the other direction are rejected.
+    assert!(_merge_sorted_dfs(&left, &right, &left_s, &right_s, None, true, false, false).is_err());
+}
+
+#[test]
+fn test_merge_sorted_nulls_last() {
+    let a = [Some(1), Some(4), None];
+    let b = [Some(2), None, None];
+
+    let out = get_ordered_merge_indicator(a.iter().copied(), b.iter().copied(), false, true);
+    let expected = [true, false, true, true, false, false];
+    //                      1     2      4     null  null   null
+    assert_eq!(out, expected);
+
+    let left = df!["a" => a].unwrap();
+    let right = df!["a" => b].unwrap();
+    let left_s = left.column("a").unwrap().as_materialized_series();
+    let right_s = right.column("a").unwrap().as_materialized_series();
+
+    let out = _merge_sorted_dfs(&left, &right, left_s, right_s, None, true, false, true).unwrap();
+    let expected = df!["a" => [Some(1), Some(2), Some(4), None, None, None]].unwrap();
+    assert!(out.equals_missing(&expected));
+
+    // Inputs with their nulls at the other end are rejected.
+    assert!(_merge_sorted_dfs(&left, &right, left_s, right_s, None, true, false, false).is_err());
+}
+
+#[test]
+fn test_merge_sorted_tie_break() {
+    let left = df!["a" => [1, 1, 2], "b" => [0, 2, 1], "side" => ["l", "l", "l"]].unwrap();
+    let right = df!["a" => [1, 1, 2], "b" => [1, 3, 0], "side" => ["r", "r", "r"]].unwrap();
+    let column = |df: &DataFrame, name| df.column(name).unwrap().as_materialized_series().clone();
+    let (left_s, right_s) = (column(&left, "a"), column(&right, "a"));
+    let tie_break = (&column(&left, "b"), &column(&right, "b"));
+
+    let out = _merge_sorted_dfs(
+        &left,
+        &right,
+        &left_s,
+        &right_s,
+        Some(tie_break),
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+    let expected = df![
+        "a" => [1, 1, 1, 1, 2, 2],
+        "b" => [0, 1, 2, 3, 0, 1],
+        "side" => ["l", "r", "l", "r", "r", "l"],
+    ]
+    .unwrap();
+    assert!(out.equals(&expected));
+
+    // The tie-break key must line up with the key.
+    let short = column(&left, "b").slice(0, 2);
+    let tie_break = (&short, tie_break.1);
+    assert!(
+        _merge_sorted_dfs(
+            &left,
+            &right,
+            &left_s,
+            &right_s,
+            Some(tie_break),
+            true,
+            false,
+            false
+        )
+        .is_err()
+    );
+}
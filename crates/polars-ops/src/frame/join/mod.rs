@@ -7,6 +7,7 @@ mod general;
 mod hash_join;
 #[cfg(feature = "iejoin")]
 mod iejoin;
+mod index;
 #[cfg(feature = "merge_sorted")]
 mod merge_sorted;
 
@@ -15,19 +16,27 @@ use std::fmt::{Debug, Display, Formatter};
 use std::hash::Hash;
 
 pub use args::*;
+use arrow::array::PrimitiveArray;
+use arrow::bitmap::Bitmap;
+use arrow::compute::utils::combine_validities_and;
 use arrow::trusted_len::TrustedLen;
 #[cfg(feature = "asof_join")]
-pub use asof::{AsOfOptions, AsofJoin, AsofJoinBy, AsofStrategy};
+pub use asof::{
+    AsOfOptions, AsofCollationKey, AsofJoin, AsofJoinBy, AsofStrategy, AsofStringCollation,
+};
 pub use cross_join::CrossJoin;
 #[cfg(feature = "chunked_ids")]
 use either::Either;
 #[cfg(feature = "chunked_ids")]
 use general::create_chunked_index_mapping;
-pub use general::{_coalesce_full_join, _finish_join, _join_suffix_name};
+pub use general::{
+    _coalesce_full_join, _finish_join, _join_strip_suffix, _join_suffix_name, JoinColumnReport,
+};
 pub use hash_join::*;
 use hashbrown::hash_map::{Entry, RawEntryMut};
 #[cfg(feature = "iejoin")]
-pub use iejoin::{IEJoinOptions, InequalityOperator};
+pub use iejoin::{IEJoinOptions, InequalityOperator, iejoin_row_indices};
+pub use index::JoinIndex;
 #[cfg(feature = "merge_sorted")]
 pub use merge_sorted::_merge_sorted_dfs;
 use polars_core::POOL;
@@ -37,6 +46,7 @@ use polars_core::chunked_array::ops::row_encode::{
 };
 use polars_core::hashing::_HASHMAP_INIT_SIZE;
 use polars_core::prelude::*;
+use polars_core::series::BitRepr;
 pub(super) use polars_core::series::IsSorted;
 use polars_core::utils::slice_offsets;
 #[allow(unused_imports)]
@@ -47,6 +57,17 @@ use rayon::prelude::*;
 use self::cross_join::fused_cross_filter;
 use super::IntoDf;
 
+/// Number of iterations between keyboard interrupt checks in long-running join loops.
+const INTERRUPT_CHECK_INTERVAL: usize = 1 << 16;
+
+/// Check for a keyboard interrupt once every [`INTERRUPT_CHECK_INTERVAL`] iterations.
+#[inline(always)]
+fn maybe_raise_keyboard_interrupt(iteration: usize) {
+    if iteration % INTERRUPT_CHECK_INTERVAL == 0 {
+        try_raise_keyboard_interrupt();
+    }
+}
+
 pub trait DataFrameJoinOps: IntoDf {
     /// Generic join method. Can be used to join on multiple columns.
     ///
@@ -115,6 +136,52 @@ pub trait DataFrameJoinOps: IntoDf {
         )
     }
 
+    /// Join like [`DataFrameJoinOps::join`], and also report the columns that were dropped by
+    /// coalescing the keys or renamed with the suffix to not clash with a left column.
+    fn join_with_report(
+        &self,
+        other: &DataFrame,
+        left_on: impl IntoIterator<Item = impl Into<PlSmallStr>>,
+        right_on: impl IntoIterator<Item = impl Into<PlSmallStr>>,
+        args: JoinArgs,
+        options: Option<JoinTypeOptions>,
+    ) -> PolarsResult<(DataFrame, JoinColumnReport)> {
+        let left_on = left_on.into_iter().map(Into::into).collect::<Vec<_>>();
+        let right_on = right_on.into_iter().map(Into::into).collect::<Vec<_>>();
+        let report = general::join_column_report(
+            self.to_df().schema(),
+            other.schema(),
+            &left_on,
+            &right_on,
+            &args,
+        );
+        let out = self.join(other, left_on, right_on, args, options)?;
+        Ok((out, report))
+    }
+
+    /// The schema [`DataFrameJoinOps::join`] would produce when joining with a frame of
+    /// `other_schema`, without executing the join.
+    ///
+    /// This includes dropping the coalesced key columns and renaming the right columns that clash
+    /// with a left column.
+    fn join_schema(
+        &self,
+        other_schema: &Schema,
+        left_on: impl IntoIterator<Item = impl Into<PlSmallStr>>,
+        right_on: impl IntoIterator<Item = impl Into<PlSmallStr>>,
+        args: &JoinArgs,
+    ) -> PolarsResult<Schema> {
+        let left_on = left_on.into_iter().map(Into::into).collect::<Vec<_>>();
+        let right_on = right_on.into_iter().map(Into::into).collect::<Vec<_>>();
+        general::join_schema(
+            self.to_df().schema(),
+            other_schema,
+            &left_on,
+            &right_on,
+            args,
+        )
+    }
+
     #[doc(hidden)]
     #[allow(clippy::too_many_arguments)]
     #[allow(unused_mut)]
@@ -136,7 +203,80 @@ pub trait DataFrameJoinOps: IntoDf {
                 assert!(args.slice.is_none());
                 return fused_cross_filter(left_df, other, args.suffix.clone(), cross_options);
             }
-            return left_df.cross_join(other, args.suffix.clone(), args.slice);
+            return left_df.cross_join(other, args.suffix.clone(), args.slice, None);
+        }
+
+        if args.semi_anti_from_right {
+            polars_ensure!(
+                args.how.is_semi_anti(),
+                InvalidOperation: "only semi and anti joins can be evaluated from the right frame"
+            );
+            return other._join_impl(
+                left_df,
+                selected_right,
+                selected_left,
+                args.swap_semi_anti_from_right(),
+                options,
+                _check_rechunk,
+                _verbose,
+            );
+        }
+
+        #[cfg(feature = "semi_anti_join")]
+        if let Some(names) = args.semi_anti_key_names.take() {
+            polars_ensure!(
+                args.how.is_semi_anti(),
+                InvalidOperation: "output key names can only be set for semi and anti joins"
+            );
+            polars_ensure!(
+                names.len() == selected_left.len(),
+                InvalidOperation: "expected {} output key names, got {}",
+                selected_left.len(), names.len()
+            );
+            let key_names = selected_left
+                .iter()
+                .map(|s| s.name().clone())
+                .collect::<Vec<_>>();
+            let mut out = self._join_impl(
+                other,
+                selected_left,
+                selected_right,
+                args,
+                options,
+                _check_rechunk,
+                _verbose,
+            )?;
+            rename_semi_anti_keys(&mut out, &key_names, &names)?;
+            return Ok(out);
+        }
+
+        polars_ensure!(
+            args.output_columns.is_none() || matches!(args.how, JoinType::Inner),
+            InvalidOperation: "output columns can only be set for inner joins"
+        );
+
+        if let Some(name) = args.key_struct.take() {
+            general::ensure_key_struct_supported(&args)?;
+            let left_keys = selected_left
+                .iter()
+                .map(|s| s.name().clone())
+                .collect::<Vec<_>>();
+            let right_keys = selected_right
+                .iter()
+                .map(|s| s.name().clone())
+                .collect::<Vec<_>>();
+            let right_keys =
+                general::right_key_output_names(left_df.schema(), &right_keys, args.suffix());
+            let out = self._join_impl(
+                other,
+                selected_left,
+                selected_right,
+                args,
+                options,
+                _check_rechunk,
+                _verbose,
+            )?;
+            return general::nest_join_keys(out, &left_keys, &right_keys, name);
         }
 
         // Clear literals if a frame is empty. Otherwise we could get an oob
@@ -162,13 +302,26 @@ pub trait DataFrameJoinOps: IntoDf {
             // a left join create chunked-ids
             // the others not yet.
             // TODO! change this to other join types once they support chunked-id joins
-            if _check_rechunk
-                && !(matches!(args.how, JoinType::Left)
-                    || std::env::var("POLARS_NO_CHUNKED_JOIN").is_ok())
-            {
+            let check_rechunk = match args.rechunk {
+                JoinRechunk::Auto | JoinRechunk::MinChunks(_) => {
+                    !(matches!(args.how, JoinType::Left)
+                        || std::env::var("POLARS_NO_CHUNKED_JOIN").is_ok())
+                },
+                JoinRechunk::Always => true,
+                JoinRechunk::Never => false,
+            };
+            let should_rechunk = |df: &DataFrame| match args.rechunk {
+                JoinRechunk::Auto => df.should_rechunk(),
+                JoinRechunk::MinChunks(min_chunks) => {
+                    df.max_n_chunks() >= min_chunks && df.should_rechunk()
+                },
+                JoinRechunk::Always => df.max_n_chunks() > 1,
+                JoinRechunk::Never => false,
+            };
+            if _check_rechunk && check_rechunk {
                 let mut left = Cow::Borrowed(left_df);
                 let mut right = Cow::Borrowed(other);
-                if left_df.should_rechunk() {
+                if should_rechunk(left_df) {
                     if _verbose {
                         eprintln!(
                             "{:?} join triggered a rechunk of the left DataFrame: {} columns are affected",
@@ -181,7 +334,7 @@ pub trait DataFrameJoinOps: IntoDf {
                     tmp_left.as_single_chunk_par();
                     left = Cow::Owned(tmp_left);
                 }
-                if other.should_rechunk() {
+                if should_rechunk(other) {
                     if _verbose {
                         eprintln!(
                             "{:?} join triggered a rechunk of the right DataFrame: {} columns are affected",
@@ -219,6 +372,32 @@ pub trait DataFrameJoinOps: IntoDf {
             );
         };
 
+        if args.case_insensitive {
+            selected_left = lowercase_keys(selected_left)?;
+            selected_right = lowercase_keys(selected_right)?;
+        }
+
+        if args.dedup_right != JoinDedupRight::None {
+            polars_ensure!(
+                matches!(args.how, JoinType::Left),
+                InvalidOperation: "deduplicating the right keys is only supported for left joins"
+            );
+            let (other, selected_right) =
+                dedup_right_keys(other, &selected_right, args.nulls_equal, args.dedup_right)?;
+            args.dedup_right = JoinDedupRight::None;
+            // The keys are already lowercased.
+            args.case_insensitive = false;
+            return left_df._join_impl(
+                &other,
+                selected_left,
+                selected_right,
+                args,
+                options,
+                false,
+                _verbose,
+            );
+        }
+
         #[cfg(feature = "iejoin")]
         if let JoinType::IEJoin = args.how {
             let Some(JoinTypeOptions::IEJoin(options)) = options else {
@@ -238,16 +417,26 @@ pub trait DataFrameJoinOps: IntoDf {
                 &options,
                 args.suffix,
                 args.slice,
+                args.maintain_order,
             );
         }
 
+        // NaN keys that must never match are masked out after row encoding, so they take the
+        // multiple keys path.
+        let mask_nans = !args.nans_equal
+            && !args.how.is_asof()
+            && keys_have_nans(&selected_left, &selected_right);
+
+        let validation = args.validation;
+        let nulls_equal = args.nulls_equal;
+
         // Single keys.
-        if selected_left.len() == 1 {
+        if selected_left.len() == 1 && !mask_nans {
             let s_left = &selected_left[0];
             let s_right = &selected_right[0];
             let drop_names: Option<Vec<PlSmallStr>> =
                 if should_coalesce { None } else { Some(vec![]) };
-            return match args.how {
+            let out = match args.how {
                 JoinType::Inner => left_df
                     ._inner_join_from_series(other, s_left, s_right, args, _verbose, drop_names),
                 JoinType::Left => dispatch_left_right::left_join_from_series(
@@ -286,36 +475,80 @@ pub trait DataFrameJoinOps: IntoDf {
                     args.nulls_equal,
                 ),
                 #[cfg(feature = "asof_join")]
-                JoinType::AsOf(options) => match (options.left_by, options.right_by) {
-                    (Some(left_by), Some(right_by)) => left_df._join_asof_by(
-                        other,
-                        s_left,
-                        s_right,
-                        left_by,
-                        right_by,
-                        options.strategy,
-                        options.tolerance.map(|v| v.into_value()),
-                        args.suffix.clone(),
-                        args.slice,
-                        should_coalesce,
-                        options.allow_eq,
-                        options.check_sortedness,
-                    ),
-                    (None, None) => left_df._join_asof(
-                        other,
-                        s_left,
-                        s_right,
-                        options.strategy,
-                        options.tolerance.map(|v| v.into_value()),
-                        args.suffix,
-                        args.slice,
-                        should_coalesce,
-                        options.allow_eq,
-                        options.check_sortedness,
-                    ),
-                    _ => {
-                        panic!("expected by arguments on both sides")
-                    },
+                JoinType::AsOf(options) => {
+                    let left_collated = options.string_collation.collation_keys(s_left)?;
+                    let right_collated = options.string_collation.collation_keys(s_right)?;
+                    let s_left = left_collated.as_ref().unwrap_or(s_left);
+                    let s_right = right_collated.as_ref().unwrap_or(s_right);
+                    let join = |left: &DataFrame,
+                                right: &DataFrame,
+                                left_key: &Series,
+                                right_key: &Series,
+                                slice: Option<(i64, usize)>,
+                                check_sortedness: bool| {
+                        match (&options.left_by, &options.right_by) {
+                            (Some(left_by), Some(right_by)) => left._join_asof_by(
+                                right,
+                                left_key,
+                                right_key,
+                                left_by.clone(),
+                                right_by.clone(),
+                                options.strategy,
+                                options.tolerance.clone().map(|v| v.into_value()),
+                                options.tolerance_relative,
+                                args.suffix.clone(),
+                                slice,
+                                should_coalesce,
+                                options.allow_eq,
+                                check_sortedness,
+                                options.index_column.clone(),
+                            ),
+                            (None, None) => left._join_asof(
+                                right,
+                                left_key,
+                                right_key,
+                                options.strategy,
+                                options.tolerance.clone().map(|v| v.into_value()),
+                                options.tolerance_relative,
+                                args.suffix.clone(),
+                                slice,
+                                should_coalesce,
+                                options.allow_eq,
+                                check_sortedness,
+                                options.index_column.clone(),
+                            ),
+                            _ => {
+                                panic!("expected by arguments on both sides")
+                            },
+                        }
+                    };
+                    if options.auto_sort {
+                        let maintain_left_order = matches!(
+                            args.maintain_order,
+                            MaintainOrderJoin::Left | MaintainOrderJoin::LeftRight
+                        );
+                        asof::join_asof_sorted(
+                            left_df,
+                            other,
+                            s_left,
+                            s_right,
+                            args.slice,
+                            maintain_left_order,
+                            options.index_column.as_ref(),
+                            |left, right, left_key, right_key| {
+                                join(left, right, left_key, right_key, None, false)
+                            },
+                        )
+                    } else {
+                        join(
+                            left_df,
+                            other,
+                            s_left,
+                            s_right,
+                            args.slice,
+                            options.check_sortedness,
+                        )
+                    }
                 },
                 #[cfg(feature = "iejoin")]
                 JoinType::IEJoin => {
@@ -325,20 +558,40 @@ pub trait DataFrameJoinOps: IntoDf {
                     unreachable!()
                 },
             };
+            return explain_validation_error(
+                out,
+                validation,
+                &selected_left,
+                &selected_right,
+                nulls_equal,
+            );
         }
-        let (lhs_keys, rhs_keys) =
-            if (left_df.is_empty() || other.is_empty()) && matches!(&args.how, JoinType::Inner) {
-                // Fast path for empty inner joins.
-                // Return 2 dummies so that we don't row-encode.
-                let a = Series::full_null("".into(), 0, &DataType::Null);
-                (a.clone(), a)
-            } else {
-                // Row encode the keys.
-                (
-                    prepare_keys_multiple(&selected_left, args.nulls_equal)?.into_series(),
-                    prepare_keys_multiple(&selected_right, args.nulls_equal)?.into_series(),
-                )
-            };
+        let (lhs_keys, rhs_keys) = if (left_df.is_empty() || other.is_empty())
+            && matches!(&args.how, JoinType::Inner)
+        {
+            // Fast path for empty inner joins.
+            // Return 2 dummies so that we don't row-encode.
+            let a = Series::full_null("".into(), 0, &DataType::Null);
+            (a.clone(), a)
+        } else if let Some(keys) =
+            pack_fixed_width_keys(&selected_left, &selected_right, args.nulls_equal)?
+        {
+            // All keys are integers that fit in a single integer, so we can hash those instead of
+            // row encoding.
+            keys
+        } else {
+            // Row encode the keys.
+            let keys = (
+                prepare_keys_multiple(&selected_left, args.nulls_equal, !mask_nans)?.into_series(),
+                prepare_keys_multiple(&selected_right, args.nulls_equal, !mask_nans)?.into_series(),
+            );
+            if mask_nans {
+                // Null keys were already encoded as values if they should match, the only
+                // remaining nulls are the NaN keys.
+                args.nulls_equal = false;
+            }
+            keys
+        };
 
         let drop_names = if should_coalesce {
             if args.how == JoinType::Right {
@@ -357,7 +610,7 @@ pub trait DataFrameJoinOps: IntoDf {
         };
 
         // Multiple keys.
-        match args.how {
+        let out = match args.how {
             #[cfg(feature = "asof_join")]
             JoinType::AsOf(_) => polars_bail!(
                 ComputeError: "asof join not supported for join on multiple keys"
@@ -426,7 +679,14 @@ pub trait DataFrameJoinOps: IntoDf {
                 _check_rechunk,
                 _verbose,
             ),
-        }
+        };
+        explain_validation_error(
+            out,
+            validation,
+            &selected_left,
+            &selected_right,
+            nulls_equal,
+        )
     }
 
     /// Perform an inner join on two DataFrames.
@@ -542,8 +802,19 @@ trait DataFrameJoinOpsPrivate: IntoDf {
         drop_names: Option<Vec<PlSmallStr>>,
     ) -> PolarsResult<DataFrame> {
         let left_df = self.to_df();
-        let ((join_tuples_left, join_tuples_right), sorted) =
-            _sort_or_hash_inner(s_left, s_right, verbose, args.validation, args.nulls_equal)?;
+        let broadcast = polars_core::config::get_join_broadcast_rows()
+            .is_some_and(|max_rows| s_left.len().min(s_right.len()) <= max_rows);
+        let ((join_tuples_left, join_tuples_right), sorted) = if broadcast {
+            // The hash join builds its table on the smaller input and swaps the join tuples back
+            // if that is the left input, skip the sort-merge strategies which may sort the larger
+            // input.
+            if verbose {
+                eprintln!("inner join: broadcast the smaller input");
+            }
+            s_left.hash_join_inner(s_right, args.validation, args.nulls_equal)?
+        } else {
+            _sort_or_hash_inner(s_left, s_right, verbose, args.validation, args.nulls_equal)?
+        };
 
         let mut join_tuples_left = &*join_tuples_left;
         let mut join_tuples_right = &*join_tuples_right;
@@ -559,6 +830,16 @@ trait DataFrameJoinOpsPrivate: IntoDf {
             other.drop(s_right.name()).unwrap()
         };
 
+        let output_columns = args.output_columns.as_deref();
+        let (left_df, other) = match output_columns {
+            Some(output_columns) => {
+                let (left, right) =
+                    project_join_output(left_df, &other, output_columns, args.suffix())?;
+                (Cow::Owned(left), right)
+            },
+            None => (Cow::Borrowed(left_df), other),
+        };
+
         let mut left = unsafe { IdxCa::mmap_slice("a".into(), join_tuples_left) };
         if sorted {
             left.set_sorted_flag(IsSorted::Ascending);
@@ -609,14 +890,195 @@ trait DataFrameJoinOpsPrivate: IntoDf {
                 )
             };
 
-        _finish_join(df_left, df_right, args.suffix)
+        let out = _finish_join(df_left, df_right, args.suffix.clone())?;
+        match output_columns {
+            Some(output_columns) => out.select(output_columns.iter().cloned()),
+            None => Ok(out),
+        }
     }
 }
 
+/// Select the columns of both sides of a join that are part of `output_columns`. The right
+/// columns get their name in the output, so they are matched with the suffix if they clash with a
+/// left column, whether or not that column is selected.
+fn project_join_output(
+    left: &DataFrame,
+    right: &DataFrame,
+    output_columns: &[PlSmallStr],
+    suffix: &str,
+) -> PolarsResult<(DataFrame, DataFrame)> {
+    let left_schema = left.schema();
+    let right_columns = right
+        .get_columns()
+        .iter()
+        .filter_map(|c| {
+            let name = if left_schema.contains(c.name()) {
+                _join_suffix_name(c.name(), suffix)
+            } else {
+                c.name().clone()
+            };
+            output_columns
+                .contains(&name)
+                .then(|| c.clone().with_name(name))
+        })
+        .collect::<Vec<_>>();
+    let left = left.select(
+        output_columns
+            .iter()
+            .filter(|name| left_schema.contains(name))
+            .cloned(),
+    )?;
+    // SAFETY: the columns come from a frame of the same height.
+    let right = unsafe { DataFrame::new_no_checks(right.height(), right_columns) };
+    Ok((left, right))
+}
+
 impl DataFrameJoinOps for DataFrame {}
 impl DataFrameJoinOpsPrivate for DataFrame {}
 
-fn prepare_keys_multiple(s: &[Series], nulls_equal: bool) -> PolarsResult<BinaryOffsetChunked> {
+/// Rename the left key columns that are in the output of a semi or anti join.
+#[cfg(feature = "semi_anti_join")]
+fn rename_semi_anti_keys(
+    df: &mut DataFrame,
+    key_names: &[PlSmallStr],
+    names: &[PlSmallStr],
+) -> PolarsResult<()> {
+    for (key_name, name) in key_names.iter().zip(names) {
+        if df.get_column_index(key_name).is_some() {
+            df.rename(key_name, name.clone())?;
+        }
+    }
+    Ok(())
+}
+
+/// Keep only the first row of every key in the right frame of a left join.
+fn dedup_right_keys(
+    other: &DataFrame,
+    selected_right: &[Series],
+    nulls_equal: bool,
+    dedup_right: JoinDedupRight,
+) -> PolarsResult<(DataFrame, Vec<Series>)> {
+    let keys = prepare_keys_multiple(selected_right, nulls_equal, true)?.into_series();
+
+    if dedup_right == JoinDedupRight::KeepFirstIfEqual {
+        // Rows with a null key never match if nulls aren't equal, so they can't conflict.
+        let valid = keys.is_not_null();
+        let mut by = Vec::with_capacity(other.width() + 1);
+        by.push(keys.clone().into_column());
+        by.extend(other.get_columns().iter().cloned());
+        let rows = encode_rows_vertical_par_unordered(&by)?.into_series();
+        polars_ensure!(
+            rows.filter(&valid)?.n_unique()? == keys.filter(&valid)?.n_unique()?,
+            ComputeError: "the right rows of a duplicate join key differ in their non-key columns"
+        );
+    }
+
+    let firsts = keys.group_tuples(true, true)?.take_group_firsts();
+    if firsts.len() == other.height() {
+        return Ok((other.clone(), selected_right.to_vec()));
+    }
+    let idx = IdxCa::from_vec(PlSmallStr::EMPTY, firsts);
+    let other = other.take(&idx)?;
+    let selected_right = selected_right
+        .iter()
+        .map(|s| s.take(&idx))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Ok((other, selected_right))
+}
+
+/// Lowercase the string join keys, so that they match regardless of case.
+fn lowercase_keys(keys: Vec<Series>) -> PolarsResult<Vec<Series>> {
+    keys.into_iter()
+        .map(|s| {
+            polars_ensure!(
+                s.dtype() == &DataType::String,
+                InvalidOperation: "case-insensitive joins require string keys, got `{}`: {}",
+                s.name(), s.dtype()
+            );
+            let out = s
+                .str()?
+                .apply_into_string_amortized(|v, buf| buf.push_str(&v.to_lowercase()));
+            Ok(out.into_series())
+        })
+        .collect()
+}
+
+/// Whether any float join key contains a NaN.
+fn keys_have_nans(left: &[Series], right: &[Series]) -> bool {
+    left.iter()
+        .chain(right)
+        .any(|s| s.dtype().is_float() && s.is_nan().unwrap().any())
+}
+
+/// Row encode the join keys.
+///
+/// Floats are canonicalized first, so `-0.0` and `+0.0` always encode equal, as do all NaN bit
+/// patterns. If `nans_equal` is false, rows with a NaN key are set to null instead.
+/// The validation checks only find out that some key is duplicated. If a join failed that way,
+/// report the first duplicated key and how often it occurs.
+fn explain_validation_error(
+    out: PolarsResult<DataFrame>,
+    validation: JoinValidation,
+    selected_left: &[Series],
+    selected_right: &[Series],
+    nulls_equal: bool,
+) -> PolarsResult<DataFrame> {
+    let Err(err) = out else {
+        return out;
+    };
+    let sides = match validation {
+        JoinValidation::ManyToMany => vec![],
+        JoinValidation::ManyToOne => vec![("right", selected_right)],
+        JoinValidation::OneToMany => vec![("left", selected_left)],
+        JoinValidation::OneToOne => vec![("left", selected_left), ("right", selected_right)],
+    };
+    for (side, keys) in sides {
+        if let Some((key, count)) = find_duplicate_key(keys, nulls_equal)? {
+            polars_bail!(
+                ComputeError: "join keys did not fulfill {} validation: key {} occurs {} times in the {} dataset",
+                validation, key, count, side
+            );
+        }
+    }
+    Err(err)
+}
+
+/// The first key that occurs more than once, formatted, and the number of times it occurs.
+fn find_duplicate_key(
+    keys: &[Series],
+    nulls_equal: bool,
+) -> PolarsResult<Option<(String, IdxSize)>> {
+    let encoded = prepare_keys_multiple(keys, nulls_equal, true)?;
+    let groups = encoded.group_tuples(true, true)?;
+    let duplicate = groups.iter().find_map(|g| {
+        let (first, len) = match g {
+            GroupsIndicator::Idx((first, idx)) => (first, idx.len() as IdxSize),
+            GroupsIndicator::Slice([first, len]) => (first, len),
+        };
+        // Null keys don't match each other if they aren't equal.
+        (len > 1 && encoded.get(first as usize).is_some()).then_some((first as usize, len))
+    });
+    let Some((idx, count)) = duplicate else {
+        return Ok(None);
+    };
+
+    let values = keys
+        .iter()
+        .map(|s| s.get(idx).map(|av| av.to_string()))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let key = if values.len() == 1 {
+        values.into_iter().next().unwrap()
+    } else {
+        format!("({})", values.join(", "))
+    };
+    Ok(Some((key, count)))
+}
+
+fn prepare_keys_multiple(
+    s: &[Series],
+    nulls_equal: bool,
+    nans_equal: bool,
+) -> PolarsResult<BinaryOffsetChunked> {
     let keys = s
         .iter()
         .map(|s| {
@@ -629,12 +1091,162 @@ fn prepare_keys_multiple(s: &[Series], nulls_equal: bool) -> PolarsResult<Binary
         })
         .collect::<Vec<_>>();
 
-    if nulls_equal {
+    let encoded = if nulls_equal {
         encode_rows_vertical_par_unordered(&keys)
     } else {
         encode_rows_vertical_par_unordered_broadcast_nulls(&keys)
+    }?;
+    if nans_equal {
+        return Ok(encoded);
+    }
+
+    let mut not_nan: Option<BooleanChunked> = None;
+    for s in s.iter().filter(|s| s.dtype().is_float()) {
+        let mask = s.is_not_nan()?.fill_null_with_values(true)?;
+        not_nan = Some(match not_nan {
+            Some(acc) => &acc & &mask,
+            None => mask,
+        });
+    }
+    let Some(not_nan) = not_nan else {
+        return Ok(encoded);
+    };
+
+    let mut encoded = encoded.rechunk().into_owned();
+    let not_nan = not_nan.rechunk();
+    let validity = combine_validities_and(
+        encoded.rechunk_validity().as_ref(),
+        Some(not_nan.downcast_as_array().values()),
+    );
+    encoded.with_validities(&[validity]);
+    Ok(encoded)
+}
+/// The number of bits of a fixed-width integer key, `None` for any other type.
+fn fixed_width_key_bits(dtype: &DataType) -> Option<u32> {
+    match dtype.to_physical() {
+        DataType::Int8 | DataType::UInt8 => Some(8),
+        DataType::Int16 | DataType::UInt16 => Some(16),
+        DataType::Int32 | DataType::UInt32 => Some(32),
+        DataType::Int64 | DataType::UInt64 => Some(64),
+        _ => None,
+    }
+}
+
+/// Pack multiple integer keys into a single `UInt64` (or `Int128`) key per row, so the join can
+/// use the integer hash tables instead of hashing row-encoded bytes.
+///
+/// Returns `None` if the keys don't all have a fixed-width integer type, if they don't fit
+/// together, or if null keys must match each other. In those cases the keys must be row encoded.
+fn pack_fixed_width_keys(
+    left: &[Series],
+    right: &[Series],
+    nulls_equal: bool,
+) -> PolarsResult<Option<(Series, Series)>> {
+    let Some(bits) = left
+        .iter()
+        .map(|s| fixed_width_key_bits(s.dtype()))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(None);
+    };
+    // A packed null can't tell which of the keys was null.
+    if nulls_equal && left.iter().chain(right).any(|s| s.has_nulls()) {
+        return Ok(None);
+    }
+
+    let total_bits = bits.iter().sum::<u32>();
+    if total_bits <= 64 {
+        return Ok(Some((
+            pack_keys::<u64>(left, &bits)?,
+            pack_keys::<u64>(right, &bits)?,
+        )));
+    }
+    #[cfg(feature = "dtype-i128")]
+    if total_bits <= 128 {
+        return Ok(Some((
+            pack_keys::<u128>(left, &bits)?,
+            pack_keys::<u128>(right, &bits)?,
+        )));
+    }
+    Ok(None)
+}
+
+/// An integer that multiple keys can be packed into.
+trait PackedKey: Copy + Default {
+    fn from_bits(v: u64) -> Self;
+    fn shl_or(self, bits: u32, v: Self) -> Self;
+    fn into_series(values: Vec<Self>, validity: Option<Bitmap>) -> Series;
+}
+
+impl PackedKey for u64 {
+    fn from_bits(v: u64) -> Self {
+        v
+    }
+
+    fn shl_or(self, bits: u32, v: Self) -> Self {
+        self.checked_shl(bits).unwrap_or(0) | v
+    }
+
+    fn into_series(values: Vec<Self>, validity: Option<Bitmap>) -> Series {
+        let arr = PrimitiveArray::from_vec(values).with_validity(validity);
+        UInt64Chunked::with_chunk(PlSmallStr::EMPTY, arr).into_series()
+    }
+}
+
+#[cfg(feature = "dtype-i128")]
+impl PackedKey for u128 {
+    fn from_bits(v: u64) -> Self {
+        v as u128
+    }
+
+    fn shl_or(self, bits: u32, v: Self) -> Self {
+        self.checked_shl(bits).unwrap_or(0) | v
+    }
+
+    fn into_series(values: Vec<Self>, validity: Option<Bitmap>) -> Series {
+        // Only the bit pattern matters for the join.
+        let values = values.into_iter().map(|v| v as i128).collect();
+        let arr = PrimitiveArray::from_vec(values).with_validity(validity);
+        Int128Chunked::with_chunk(PlSmallStr::EMPTY, arr).into_series()
     }
 }
+
+/// Pack the keys, the first key ends up in the most significant bits. A packed key is null if
+/// any of its keys is null.
+fn pack_keys<T: PackedKey>(keys: &[Series], bits: &[u32]) -> PolarsResult<Series> {
+    let mut packed = vec![T::default(); keys[0].len()];
+    let mut validity = None;
+    for (s, &bits) in keys.iter().zip(bits) {
+        let mask = u64::MAX >> (64 - bits);
+        let mut pack = |values: &mut dyn Iterator<Item = u64>| {
+            for (p, v) in packed.iter_mut().zip(values) {
+                *p = p.shl_or(bits, T::from_bits(v & mask));
+            }
+        };
+        let key_validity = match s.to_physical_repr().bit_repr() {
+            Some(BitRepr::U32(ca)) => {
+                pack(
+                    &mut ca
+                        .downcast_iter()
+                        .flat_map(|arr| arr.values_iter().map(|&v| v as u64)),
+                );
+                ca.rechunk_validity()
+            },
+            Some(BitRepr::U64(ca)) => {
+                pack(
+                    &mut ca
+                        .downcast_iter()
+                        .flat_map(|arr| arr.values_iter().copied()),
+                );
+                ca.rechunk_validity()
+            },
+            _ => unreachable!("fixed-width keys have a 32 or 64 bit representation"),
+        };
+        validity = combine_validities_and(validity.as_ref(), key_validity.as_ref());
+    }
+    Ok(T::into_series(packed, validity))
+}
+
 pub fn private_left_join_multiple_keys(
     a: &DataFrame,
     b: &DataFrame,
@@ -652,7 +1264,7 @@ pub fn private_left_join_multiple_keys(
         .map(|c| c.as_materialized_series().clone())
         .collect::<Vec<_>>();
 
-    let a = prepare_keys_multiple(&a_cols, nulls_equal)?.into_series();
-    let b = prepare_keys_multiple(&b_cols, nulls_equal)?.into_series();
-    sort_or_hash_left(&a, &b, false, JoinValidation::ManyToMany, nulls_equal)
+    let a = prepare_keys_multiple(&a_cols, nulls_equal, true)?.into_series();
+    let b = prepare_keys_multiple(&b_cols, nulls_equal, true)?.into_series();
+    sort_or_hash_left(&a, &b, false, JoinValidation::ManyToMany, nulls_equal, None)
 }
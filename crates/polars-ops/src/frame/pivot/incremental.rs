@@ -0,0 +1,307 @@
+use polars_core::chunked_array::ops::row_encode::encode_rows_vertical_par_unordered;
+use polars_core::with_match_physical_numeric_polars_type;
+use polars_utils::aliases::PlHashMap;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::*;
+use crate::chunked_array::ChunkedSet;
+
+/// The aggregations a [`PivotState`] supports.
+///
+/// These can be updated from the aggregate of only the new rows, without looking at the rows
+/// that were already ingested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PivotReducer {
+    Sum,
+    /// The number of non-null values.
+    Count,
+    Min,
+    Max,
+}
+
+impl PivotReducer {
+    /// Combine the aggregates of cells that already have values with the aggregates of the new
+    /// rows, which are non-null.
+    fn combine(self, old: &Series, new: &Series) -> PolarsResult<Series> {
+        match self {
+            // Cells without values are zero, unless the empty aggregate of the dtype is null.
+            PivotReducer::Sum | PivotReducer::Count => {
+                &old.fill_null(FillNullStrategy::Zero)? + new
+            },
+            // Cells without values are null, those always take the new value.
+            PivotReducer::Min | PivotReducer::Max => {
+                let take_new = match self {
+                    PivotReducer::Min => new.lt(old)?,
+                    _ => new.gt(old)?,
+                };
+                new.zip_with(&(take_new.fill_null_with_values(true)?), old)
+            },
+        }
+    }
+}
+
+impl PhysicalAggExpr for PivotReducer {
+    fn evaluate_on_groups(&self, df: &DataFrame, groups: &GroupPositions) -> PolarsResult<Series> {
+        let c = &df.get_columns()[0];
+        // SAFETY: the groups are created by the pivot on this frame.
+        let out = unsafe {
+            match self {
+                PivotReducer::Sum => c.agg_sum(groups),
+                PivotReducer::Count => c.agg_valid_count(groups),
+                PivotReducer::Min => c.agg_min(groups),
+                PivotReducer::Max => c.agg_max(groups),
+            }
+        };
+        Ok(out.take_materialized_series())
+    }
+
+    fn root_name(&self) -> PolarsResult<&PlSmallStr> {
+        Ok(PlSmallStr::EMPTY_REF)
+    }
+}
+
+impl From<PivotReducer> for PivotAgg {
+    fn from(value: PivotReducer) -> Self {
+        PivotAgg(Arc::new(value))
+    }
+}
+
+/// An output value column of a [`PivotState`].
+struct StateColumn {
+    name: PlSmallStr,
+    dtype: DataType,
+    /// The value of cells without values.
+    missing: Scalar,
+    /// The physical aggregate of every row, `missing` if the cell has no values yet.
+    values: Series,
+}
+
+impl StateColumn {
+    /// Add cells without values for the output rows that are new since the last update.
+    fn grow(&mut self, n_rows: usize) -> PolarsResult<()> {
+        let n_new_rows = n_rows - self.values.len();
+        if n_new_rows > 0 {
+            let missing = Column::new_scalar(PlSmallStr::EMPTY, self.missing.clone(), n_new_rows)
+                .take_materialized_series()
+                .to_physical_repr()
+                .into_owned();
+            self.values.append_owned(missing)?;
+        }
+        Ok(())
+    }
+
+    /// Overwrite the cells at `idx` with `new`.
+    ///
+    /// Numeric cells are patched in place, unless a previously emitted frame still shares the
+    /// buffer. Other columns are rebuilt.
+    fn scatter(&mut self, idx: &[IdxSize], new: Series) -> PolarsResult<()> {
+        let dtype = self.values.dtype().clone();
+        self.values = if dtype.is_primitive_numeric() {
+            with_match_physical_numeric_polars_type!(dtype, |$T| {
+                // Drop the series first, so the state holds the only reference to the buffer.
+                let mut values: ChunkedArray<$T> = {
+                    let values = std::mem::take(&mut self.values);
+                    let values: &ChunkedArray<$T> = values.as_ref().as_ref();
+                    values.clone()
+                };
+                let new: &ChunkedArray<$T> = new.as_ref().as_ref();
+                values.scatter(idx, new.iter())?
+            })
+        } else {
+            let mut positions = vec![None; self.values.len()];
+            for (i, &row) in idx.iter().enumerate() {
+                positions[row as usize] = Some(i as IdxSize);
+            }
+            let positions = IdxCa::from_iter_options(PlSmallStr::EMPTY, positions.into_iter());
+            let changed = positions.is_not_null();
+            new.take(&positions)?.zip_with(&changed, &self.values)?
+        };
+        Ok(())
+    }
+}
+
+/// A stable pivot that can ingest new rows without recomputing the existing cells.
+///
+/// The state is initialized from a base frame with [`PivotState::new`], after which
+/// [`PivotState::update`] merges the aggregates of new rows into the affected cells. The output of
+/// [`PivotState::emit`] equals that of [`pivot_stable`] on all rows ingested so far, without
+/// sorting the columns. Every value column is kept as a typed column in which an update only
+/// overwrites the changed cells, so emitting doesn't copy any values.
+pub struct PivotState {
+    on: Vec<PlSmallStr>,
+    index: Vec<PlSmallStr>,
+    values: Vec<PlSmallStr>,
+    reducer: PivotReducer,
+    separator: PlSmallStr,
+    /// The row-encoded index values of every output row.
+    row_positions: PlHashMap<Vec<u8>, usize>,
+    /// The index columns of the output.
+    index_df: DataFrame,
+    /// The output columns of every value column, in order of appearance.
+    columns: Vec<Vec<StateColumn>>,
+    column_positions: Vec<PlHashMap<PlSmallStr, usize>>,
+}
+
+impl PivotState {
+    /// Create the state of a pivot over `base`.
+    ///
+    /// See [`pivot_stable`] for the meaning of the arguments.
+    pub fn new<I0, I1, I2, S0, S1, S2>(
+        base: &DataFrame,
+        on: I0,
+        index: Option<I1>,
+        values: Option<I2>,
+        reducer: PivotReducer,
+        separator: Option<&str>,
+    ) -> PolarsResult<Self>
+    where
+        I0: IntoIterator<Item = S0>,
+        I1: IntoIterator<Item = S1>,
+        I2: IntoIterator<Item = S2>,
+        S0: Into<PlSmallStr>,
+        S1: Into<PlSmallStr>,
+        S2: Into<PlSmallStr>,
+    {
+        let on = on.into_iter().map(Into::into).collect::<Vec<_>>();
+        let (index, values) = assign_remaining_columns(base, &on, index, values, &[])?;
+        polars_ensure!(!index.is_empty(), ComputeError: "index cannot be zero length");
+        polars_ensure!(!on.is_empty(), ComputeError: "`on` cannot be zero length");
+
+        let index_df = base.select(index.iter().cloned())?.clear();
+        let mut state = Self {
+            on,
+            index,
+            columns: values.iter().map(|_| vec![]).collect(),
+            column_positions: values.iter().map(|_| PlHashMap::default()).collect(),
+            values,
+            reducer,
+            separator: separator.unwrap_or("_").into(),
+            row_positions: PlHashMap::default(),
+            index_df,
+        };
+        state.update(base)?;
+        Ok(state)
+    }
+
+    /// Merge the aggregates of `new_rows` into the state.
+    pub fn update(&mut self, new_rows: &DataFrame) -> PolarsResult<()> {
+        if new_rows.is_empty() {
+            return Ok(());
+        }
+
+        let options = PivotOptions {
+            missing_as_null: true,
+            ..Default::default()
+        };
+        let partial = pivot_stable_with_options(
+            new_rows,
+            self.on.iter().cloned(),
+            Some(self.index.iter().cloned()),
+            Some(self.values.iter().cloned()),
+            false,
+            Some(self.reducer.into()),
+            Some(self.separator.as_str()),
+            &options,
+        )?;
+
+        // Map the rows of the partial pivot to the output rows.
+        let (partial_index, partial_values) = partial.get_columns().split_at(self.index.len());
+        let encoded = encode_rows_vertical_par_unordered(partial_index)?;
+        let mut new_index_rows = vec![];
+        let rows = encoded
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let n_rows = self.row_positions.len();
+                *self
+                    .row_positions
+                    .entry(key.unwrap().to_vec())
+                    .or_insert_with(|| {
+                        new_index_rows.push(i as IdxSize);
+                        n_rows
+                    })
+            })
+            .collect::<Vec<_>>();
+        if !new_index_rows.is_empty() {
+            let idx = IdxCa::from_vec(PlSmallStr::EMPTY, new_index_rows);
+            let new_index = partial.select(self.index.iter().cloned())?.take(&idx)?;
+            self.index_df.vstack_mut_owned(new_index)?;
+        }
+
+        // Every value column has an output column for each key in the partial pivot.
+        let n_keys = partial_values.len() / self.values.len();
+        for (value_idx, partial_cols) in partial_values.chunks(n_keys.max(1)).enumerate() {
+            let value_dtype = new_rows.column(&self.values[value_idx])?.dtype();
+            for partial_col in partial_cols {
+                self.update_column(value_idx, value_dtype, partial_col, &rows)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_column(
+        &mut self,
+        value_idx: usize,
+        value_dtype: &DataType,
+        partial: &Column,
+        rows: &[usize],
+    ) -> PolarsResult<()> {
+        let n_rows = self.row_positions.len();
+        let columns = &mut self.columns[value_idx];
+        let position = match self.column_positions[value_idx].get(partial.name()) {
+            Some(position) => *position,
+            None => {
+                // Cells without values get the aggregate of an empty group, like in `pivot`.
+                let input = Column::new_empty(PlSmallStr::EMPTY, value_dtype).into_frame();
+                let missing = empty_group_aggregate(&self.reducer.into(), &input)?
+                    .cast(partial.dtype())
+                    .into_static();
+                let dtype = partial.dtype().clone();
+                columns.push(StateColumn {
+                    name: partial.name().clone(),
+                    values: Series::new_empty(PlSmallStr::EMPTY, &dtype.to_physical()),
+                    missing: Scalar::new(dtype.clone(), missing),
+                    dtype,
+                });
+                self.column_positions[value_idx].insert(partial.name().clone(), columns.len() - 1);
+                columns.len() - 1
+            },
+        };
+        let column = &mut columns[position];
+        column.grow(n_rows)?;
+
+        // Only the cells that get a value from the new rows change.
+        let partial = partial
+            .strict_cast(&column.dtype)?
+            .take_materialized_series()
+            .to_physical_repr()
+            .into_owned();
+        let has_value = partial.is_not_null();
+        let idx = rows
+            .iter()
+            .zip(has_value.iter())
+            .filter_map(|(&row, has_value)| has_value.unwrap().then_some(row as IdxSize))
+            .collect::<Vec<_>>();
+        if idx.is_empty() {
+            return Ok(());
+        }
+        let new = partial.filter(&has_value)?;
+        let old = column.values.take_slice(&idx)?;
+        let combined = self.reducer.combine(&old, &new)?;
+        column.scatter(&idx, combined)
+    }
+
+    /// Return the pivoted frame of all rows ingested so far.
+    ///
+    /// The value columns share their buffers with the state until the next update.
+    pub fn emit(&mut self) -> PolarsResult<DataFrame> {
+        let mut out = self.index_df.get_columns().to_vec();
+        for column in self.columns.iter_mut().flatten() {
+            column.grow(self.row_positions.len())?;
+            // SAFETY: the values are the physical representation of the column dtype.
+            let values = unsafe { column.values.from_physical_unchecked(&column.dtype)? };
+            out.push(values.with_name(column.name.clone()).into_column());
+        }
+        DataFrame::new(out)
+    }
+}
@@ -1,8 +1,10 @@
+mod incremental;
 mod positioning;
 mod unpivot;
 
 use std::borrow::Cow;
 
+pub use incremental::{PivotReducer, PivotState};
 use polars_core::frame::group_by::expr::PhysicalAggExpr;
 use polars_core::prelude::*;
 use polars_core::utils::_split_offsets;
@@ -11,11 +13,199 @@ use polars_utils::format_pl_smallstr;
 use rayon::prelude::*;
 pub use unpivot::UnpivotDF;
 
+use crate::series::{NullStrategy, sum_horizontal};
+
 const HASHMAP_INIT_SIZE: usize = 512;
 
 #[derive(Clone)]
 pub struct PivotAgg(pub Arc<dyn PhysicalAggExpr + Send + Sync>);
 
+impl PivotAgg {
+    /// Aggregate every cell with a closure over the [`Series`] of its values.
+    ///
+    /// The closure is called for one cell after the other on a single thread. It must return
+    /// values of the same dtype for every cell, otherwise the pivot returns an error. Like for
+    /// the other aggregations, cells without values get the result of the closure on an empty
+    /// [`Series`], unless [`PivotOptions::missing_as_null`] is set.
+    pub fn closure<F>(f: F) -> Self
+    where
+        F: Fn(&Series) -> AnyValue<'static> + Send + Sync + 'static,
+    {
+        PivotAgg(Arc::new(ClosureAgg(f)))
+    }
+
+    /// Collect the values of every cell into a list, so every output value column is a `List`
+    /// column. Cells without values get an empty list, unless [`PivotOptions::missing_as_null`]
+    /// is set.
+    pub fn list() -> Self {
+        PivotAgg(Arc::new(ListAgg))
+    }
+
+    /// The mean of the values of every cell weighted by the `weight_col` column of the input,
+    /// i.e. `(value * weight).sum() / weight.sum()` over the rows where both are not null. Cells
+    /// without values or with a total weight of zero are null.
+    ///
+    /// The weight column is not used as an index or value column when these are inferred.
+    pub fn weighted_mean(weight_col: impl Into<PlSmallStr>) -> Self {
+        PivotAgg(Arc::new(WeightedAgg {
+            weight_col: [weight_col.into()],
+            mean: true,
+        }))
+    }
+
+    /// The sum of the values of every cell weighted by the `weight_col` column of the input, i.e.
+    /// `(value * weight).sum()`. Cells without values are `0`.
+    ///
+    /// The weight column is not used as an index or value column when these are inferred.
+    pub fn weighted_sum(weight_col: impl Into<PlSmallStr>) -> Self {
+        PivotAgg(Arc::new(WeightedAgg {
+            weight_col: [weight_col.into()],
+            mean: false,
+        }))
+    }
+}
+
+/// The [`PhysicalAggExpr`] of [`PivotAgg::weighted_mean`] and [`PivotAgg::weighted_sum`].
+struct WeightedAgg {
+    weight_col: [PlSmallStr; 1],
+    mean: bool,
+}
+
+impl PhysicalAggExpr for WeightedAgg {
+    fn evaluate_on_groups(&self, df: &DataFrame, groups: &GroupPositions) -> PolarsResult<Series> {
+        let [values, weights] = df.get_columns() else {
+            unreachable!()
+        };
+        let values = values.cast(&DataType::Float64)?;
+        let weights = weights.cast(&DataType::Float64)?;
+        let (values, weights) = (values.f64()?, weights.f64()?);
+        let weighted = (values * weights).into_column();
+
+        // SAFETY: the groups are created by the pivot on this frame.
+        let sums = unsafe { weighted.agg_sum(groups) };
+        if !self.mean {
+            return Ok(sums.take_materialized_series());
+        }
+        // Only the weights of rows with a value count towards the total weight.
+        let weights = weights
+            .zip_with(
+                &values.is_not_null(),
+                &Float64Chunked::full_null(PlSmallStr::EMPTY, weights.len()),
+            )?
+            .into_column();
+        // SAFETY: the groups are created by the pivot on this frame.
+        let total_weights = unsafe { weights.agg_sum(groups) };
+        let means: Float64Chunked = sums
+            .f64()?
+            .into_iter()
+            .zip(total_weights.f64()?)
+            .map(|(sum, weight)| match (sum, weight) {
+                (Some(sum), Some(weight)) if weight != 0.0 => Some(sum / weight),
+                _ => None,
+            })
+            .collect();
+        Ok(means.into_series())
+    }
+
+    fn root_name(&self) -> PolarsResult<&PlSmallStr> {
+        Ok(PlSmallStr::EMPTY_REF)
+    }
+
+    fn is_additive(&self) -> bool {
+        !self.mean
+    }
+
+    fn auxiliary_columns(&self) -> &[PlSmallStr] {
+        &self.weight_col
+    }
+}
+
+/// The [`PhysicalAggExpr`] of [`PivotAgg::list`].
+struct ListAgg;
+
+impl PhysicalAggExpr for ListAgg {
+    fn evaluate_on_groups(&self, df: &DataFrame, groups: &GroupPositions) -> PolarsResult<Series> {
+        let s = df.get_columns()[0].as_materialized_series();
+        // SAFETY: the groups are created by the pivot on this frame.
+        Ok(unsafe { s.agg_list(groups) })
+    }
+
+    fn root_name(&self) -> PolarsResult<&PlSmallStr> {
+        Ok(PlSmallStr::EMPTY_REF)
+    }
+}
+
+/// The [`PhysicalAggExpr`] of [`PivotAgg::closure`].
+struct ClosureAgg<F>(F);
+
+impl<F> PhysicalAggExpr for ClosureAgg<F>
+where
+    F: Fn(&Series) -> AnyValue<'static> + Send + Sync,
+{
+    fn evaluate_on_groups(&self, df: &DataFrame, groups: &GroupPositions) -> PolarsResult<Series> {
+        let s = df.get_columns()[0].as_materialized_series();
+        let values = groups
+            .iter()
+            .map(|g| {
+                let cell = match g {
+                    // SAFETY: the groups are created by the pivot on this frame.
+                    GroupsIndicator::Idx((_, idx)) => unsafe { s.take_slice_unchecked(idx) },
+                    GroupsIndicator::Slice([first, len]) => s.slice(first as i64, len as usize),
+                };
+                (self.0)(&cell)
+            })
+            .collect::<Vec<_>>();
+        Series::from_any_values(s.name().clone(), &values, true)
+    }
+
+    fn root_name(&self) -> PolarsResult<&PlSmallStr> {
+        Ok(PlSmallStr::EMPTY_REF)
+    }
+}
+
+/// Additional options that control the output of [`pivot_stable_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct PivotOptions {
+    /// Fill the cells of (index, on) combinations that have no values with null, instead of with
+    /// the result of the aggregation on an empty group (e.g. `0` for `sum` or `""` for
+    /// `str.join`).
+    pub missing_as_null: bool,
+    /// Omit output value columns that are entirely null after aggregation. The index columns are
+    /// always kept.
+    pub drop_all_null_columns: bool,
+    /// Return an error if an (index, on) combination has more than one value, instead of
+    /// aggregating the values. This turns the pivot into a pure reshape.
+    pub require_unique_cells: bool,
+    /// The input already has at most one row per (index, on) combination, e.g. because it is the
+    /// output of a `group_by` on these columns. The values are scattered into the output as is,
+    /// without grouping them again. This is only validated together with `require_unique_cells`,
+    /// with an error if a combination occurs more than once. Otherwise the last value of such a
+    /// combination wins.
+    pub input_is_grouped: bool,
+    /// Order the output value columns by the value of their `on` key in its own dtype, e.g.
+    /// numerically for integer keys, instead of lexically by column name. This takes precedence
+    /// over `sort_columns`.
+    pub sort_columns_by_value: bool,
+    /// Name the output value columns of temporal (date, datetime and time) `on` keys with this
+    /// strftime format, e.g. `%Y-%m` or `%Y-Q%q`, instead of the default rendering. The keys are
+    /// formatted before grouping, so keys that get the same name, e.g. dates in the same month,
+    /// share a column and their values are aggregated together.
+    pub column_name_format: Option<String>,
+    /// Append a `Total` column with the total of every row and a `Total` row with the total of
+    /// every value column, the cell at their intersection holds the grand total. With multiple
+    /// `values` every value gets its own `{value}{separator}Total` column. The first index column
+    /// is cast to `String` to hold the `Total` label, the other index columns are null in that
+    /// row. The totals are sums of the cells, so this requires an additive aggregation such as
+    /// `sum` or `count`.
+    pub margins: bool,
+    /// With multiple `on` columns, nest the output value columns by their keys instead of naming
+    /// them after the combination of keys, e.g. `{"large","egg"}`. There is a struct column for
+    /// every value of the first `on` column, with a field for every value of the second `on`
+    /// column it occurs with, and so on. With multiple `values` the outer columns are named
+    /// `{value}{separator}{key}`. This can't be combined with `margins`.
+    pub struct_columns: bool,
+}
+
 fn restore_logical_type(s: &Series, logical_type: &DataType) -> Series {
     // restore logical type
     match (logical_type, s.dtype()) {
@@ -86,7 +276,8 @@ where
     S2: Into<PlSmallStr>,
 {
     let on = on.into_iter().map(Into::into).collect::<Vec<_>>();
-    let (index, values) = assign_remaining_columns(pivot_df, &on, index, values)?;
+    let (index, values) =
+        assign_remaining_columns(pivot_df, &on, index, values, auxiliary_columns(&agg_fn))?;
     pivot_impl(
         pivot_df,
         &on,
@@ -96,6 +287,7 @@ where
         sort_columns,
         false,
         separator,
+        &PivotOptions::default(),
     )
 }
 
@@ -122,7 +314,46 @@ where
     S2: Into<PlSmallStr>,
 {
     let on = on.into_iter().map(Into::into).collect::<Vec<_>>();
-    let (index, values) = assign_remaining_columns(pivot_df, &on, index, values)?;
+    let (index, values) =
+        assign_remaining_columns(pivot_df, &on, index, values, auxiliary_columns(&agg_fn))?;
+    pivot_impl(
+        pivot_df,
+        on.as_slice(),
+        index.as_slice(),
+        values.as_slice(),
+        agg_fn,
+        sort_columns,
+        true,
+        separator,
+        &PivotOptions::default(),
+    )
+}
+
+/// Do a stable pivot operation with additional [`PivotOptions`].
+///
+/// See [`pivot_stable`] for the meaning of the other arguments.
+#[allow(clippy::too_many_arguments)]
+pub fn pivot_stable_with_options<I0, I1, I2, S0, S1, S2>(
+    pivot_df: &DataFrame,
+    on: I0,
+    index: Option<I1>,
+    values: Option<I2>,
+    sort_columns: bool,
+    agg_fn: Option<PivotAgg>,
+    separator: Option<&str>,
+    options: &PivotOptions,
+) -> PolarsResult<DataFrame>
+where
+    I0: IntoIterator<Item = S0>,
+    I1: IntoIterator<Item = S1>,
+    I2: IntoIterator<Item = S2>,
+    S0: Into<PlSmallStr>,
+    S1: Into<PlSmallStr>,
+    S2: Into<PlSmallStr>,
+{
+    let on = on.into_iter().map(Into::into).collect::<Vec<_>>();
+    let (index, values) =
+        assign_remaining_columns(pivot_df, &on, index, values, auxiliary_columns(&agg_fn))?;
     pivot_impl(
         pivot_df,
         on.as_slice(),
@@ -132,19 +363,114 @@ where
         sort_columns,
         true,
         separator,
+        options,
     )
 }
 
+/// Do a stable pivot with several aggregations at once.
+///
+/// Every aggregation in `aggs` is paired with a suffix. For every output value column of a
+/// single pivot, e.g. `large`, there is one column per aggregation, named
+/// `{column}{separator}{suffix}`, e.g. `large_sum` and `large_mean`. The columns of the
+/// aggregations of one pivot key are next to each other in the order of `aggs`, the keys are in
+/// the order of a single pivot. The suffixes must be unique.
+///
+/// See [`pivot_stable_with_options`] for the meaning of the other arguments.
+#[allow(clippy::too_many_arguments)]
+pub fn pivot_stable_multi_agg<I0, I1, I2, S0, S1, S2>(
+    pivot_df: &DataFrame,
+    on: I0,
+    index: Option<I1>,
+    values: Option<I2>,
+    sort_columns: bool,
+    aggs: &[(PlSmallStr, PivotAgg)],
+    separator: Option<&str>,
+    options: &PivotOptions,
+) -> PolarsResult<DataFrame>
+where
+    I0: IntoIterator<Item = S0>,
+    I1: IntoIterator<Item = S1>,
+    I2: IntoIterator<Item = S2>,
+    S0: Into<PlSmallStr>,
+    S1: Into<PlSmallStr>,
+    S2: Into<PlSmallStr>,
+{
+    polars_ensure!(
+        !aggs.is_empty(),
+        InvalidOperation: "`pivot` with multiple aggregations requires at least one aggregation"
+    );
+    let mut suffixes = PlHashSet::with_capacity(aggs.len());
+    for (suffix, _) in aggs {
+        polars_ensure!(
+            suffixes.insert(suffix),
+            Duplicate: "the suffix '{suffix}' is used for more than one aggregation in `pivot`"
+        );
+    }
+    let sep = separator.unwrap_or("_");
+    let on = on.into_iter().map(Into::into).collect::<Vec<_>>();
+    let auxiliary = aggs
+        .iter()
+        .flat_map(|(_, agg_fn)| agg_fn.0.auxiliary_columns().iter().cloned())
+        .collect::<Vec<_>>();
+    let (index, values) = assign_remaining_columns(pivot_df, &on, index, values, &auxiliary)?;
+
+    // The input is grouped once for all aggregations, so their outputs have the same rows.
+    let agg_fns = aggs
+        .iter()
+        .map(|(_, agg_fn)| Some(agg_fn.clone()))
+        .collect::<Vec<_>>();
+    let outputs = pivot_impl_multi_agg(
+        pivot_df,
+        &on,
+        &index,
+        &values,
+        &agg_fns,
+        sort_columns,
+        true,
+        separator,
+        options,
+    )?;
+
+    // With `drop_all_null_columns` the aggregations can have different columns, so a key gets a
+    // column for the aggregations that have it, at the position it first occurs at.
+    let mut keys = PlIndexSet::new();
+    for out in &outputs {
+        keys.extend(
+            out.get_columns()[index.len()..]
+                .iter()
+                .map(|c| c.name().clone()),
+        );
+    }
+    let mut columns = outputs[0].get_columns()[..index.len()].to_vec();
+    for key in &keys {
+        for ((suffix, _), out) in aggs.iter().zip(&outputs) {
+            if let Ok(column) = out.column(key) {
+                let name = format_pl_smallstr!("{key}{sep}{suffix}");
+                columns.push(column.clone().with_name(name));
+            }
+        }
+    }
+    DataFrame::new(columns)
+}
+
+fn auxiliary_columns(agg_fn: &Option<PivotAgg>) -> &[PlSmallStr] {
+    agg_fn
+        .as_ref()
+        .map_or(&[], |agg_fn| agg_fn.0.auxiliary_columns())
+}
+
 /// Ensure both `index` and `values` are populated with `Vec<String>`.
 ///
 /// - If `index` is None, assign columns not in `on` and `values` to it.
 /// - If `values` is None, assign columns not in `on` and `index` to it.
 /// - At least one of `index` and `values` must be non-null.
+/// - The `auxiliary` columns of the aggregations are never assigned.
 fn assign_remaining_columns<I1, I2, S1, S2>(
     df: &DataFrame,
     on: &[PlSmallStr],
     index: Option<I1>,
     values: Option<I2>,
+    auxiliary: &[PlSmallStr],
 ) -> PolarsResult<(Vec<PlSmallStr>, Vec<PlSmallStr>)>
 where
     I1: IntoIterator<Item = S1>,
@@ -163,7 +489,7 @@ where
             let values = df
                 .get_column_names()
                 .into_iter()
-                .filter(|c| !(index.contains(c) | on.contains(c)))
+                .filter(|c| !(index.contains(c) | on.contains(c) | auxiliary.contains(c)))
                 .cloned()
                 .collect();
             Ok((index, values))
@@ -173,7 +499,7 @@ where
             let index = df
                 .get_column_names()
                 .into_iter()
-                .filter(|c| !(values.contains(c) | on.contains(c)))
+                .filter(|c| !(values.contains(c) | on.contains(c) | auxiliary.contains(c)))
                 .cloned()
                 .collect();
             Ok((index, values))
@@ -186,6 +512,34 @@ where
 
 #[allow(clippy::too_many_arguments)]
 fn pivot_impl(
+    pivot_df: &DataFrame,
+    on: &[PlSmallStr],
+    index: &[PlSmallStr],
+    values: &[PlSmallStr],
+    agg_fn: Option<PivotAgg>,
+    sort_columns: bool,
+    stable: bool,
+    separator: Option<&str>,
+    options: &PivotOptions,
+) -> PolarsResult<DataFrame> {
+    let mut outputs = pivot_impl_multi_agg(
+        pivot_df,
+        on,
+        index,
+        values,
+        &[agg_fn],
+        sort_columns,
+        stable,
+        separator,
+        options,
+    )?;
+    Ok(outputs.pop().unwrap())
+}
+
+/// Pivot with every aggregation in `agg_fns`, returning a frame per aggregation. The input is
+/// only grouped once.
+#[allow(clippy::too_many_arguments)]
+fn pivot_impl_multi_agg(
     pivot_df: &DataFrame,
     // keys of the first group_by operation
     on: &[PlSmallStr],
@@ -194,18 +548,27 @@ fn pivot_impl(
     // these columns will be used for a nested group_by
     // the rows of this nested group_by will be pivoted as header column values
     values: &[PlSmallStr],
-    // aggregation function
-    agg_fn: Option<PivotAgg>,
+    // aggregation functions
+    agg_fns: &[Option<PivotAgg>],
     sort_columns: bool,
     stable: bool,
     // used as separator/delimiter in generated column names.
     separator: Option<&str>,
-) -> PolarsResult<DataFrame> {
+    options: &PivotOptions,
+) -> PolarsResult<Vec<DataFrame>> {
     polars_ensure!(!index.is_empty(), ComputeError: "index cannot be zero length");
     polars_ensure!(!on.is_empty(), ComputeError: "`on` cannot be zero length");
     if !stable {
         println!("unstable pivot not yet supported, using stable pivot");
     };
+    let formatted_df;
+    let pivot_df = match &options.column_name_format {
+        Some(format) => {
+            formatted_df = format_temporal_columns(pivot_df, on, format)?;
+            &formatted_df
+        },
+        None => pivot_df,
+    };
     if on.len() > 1 {
         let schema = Arc::new(pivot_df.schema());
         let binding = pivot_df.select_with_schema(on.iter().cloned(), &schema)?;
@@ -226,9 +589,10 @@ fn pivot_impl(
             index,
             &column,
             values,
-            agg_fn,
+            agg_fns,
             sort_columns,
             separator,
+            options,
         )
     } else {
         pivot_impl_single_column(
@@ -236,144 +600,418 @@ fn pivot_impl(
             index,
             unsafe { on.get_unchecked(0) },
             values,
-            agg_fn,
+            agg_fns,
             sort_columns,
             separator,
+            options,
         )
     }
 }
 
+/// Replace the temporal columns in `names` by their values formatted with `format`.
+fn format_temporal_columns(
+    df: &DataFrame,
+    names: &[PlSmallStr],
+    format: &str,
+) -> PolarsResult<DataFrame> {
+    let mut df = df.clone();
+    for name in names {
+        if let Some(formatted) = format_temporal(df.column(name)?.as_materialized_series(), format)?
+        {
+            df.replace(name, formatted)?;
+        }
+    }
+    Ok(df)
+}
+
+#[cfg_attr(
+    not(any(
+        feature = "dtype-date",
+        feature = "dtype-datetime",
+        feature = "dtype-time"
+    )),
+    allow(unused_variables)
+)]
+fn format_temporal(s: &Series, format: &str) -> PolarsResult<Option<Series>> {
+    let name = s.name().clone();
+    match s.dtype() {
+        #[cfg(feature = "dtype-date")]
+        DataType::Date => Ok(Some(
+            s.date()
+                .unwrap()
+                .to_string(format)?
+                .with_name(name)
+                .into_series(),
+        )),
+        #[cfg(feature = "dtype-datetime")]
+        DataType::Datetime(_, _) => Ok(Some(
+            s.datetime()
+                .unwrap()
+                .to_string(format)?
+                .with_name(name)
+                .into_series(),
+        )),
+        #[cfg(feature = "dtype-time")]
+        DataType::Time => Ok(Some(
+            s.time()
+                .unwrap()
+                .to_string(format)
+                .with_name(name)
+                .into_series(),
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Return an error naming the first (index, on) combination that has more than one row, and
+/// `reason` why that isn't allowed.
+fn ensure_unique_cells(
+    pivot_df: &DataFrame,
+    index: &[PlSmallStr],
+    column: &PlSmallStr,
+    groups: &GroupsType,
+    reason: &str,
+) -> PolarsResult<()> {
+    let Some(group) = groups.iter().find(|g| g.len() > 1) else {
+        return Ok(());
+    };
+    let row = group.first() as usize;
+
+    let index_values = index
+        .iter()
+        .map(|name| Ok(pivot_df.column(name)?.get(row)?.to_string()))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let column_value = pivot_df.column(column)?.get(row)?;
+
+    polars_bail!(
+        ComputeError: "found {} values for index ({}) and column {} in `pivot`, but {}",
+        group.len(), index_values.join(", "), column_value, reason
+    )
+}
+
+/// The frame `agg_fn` is evaluated on: the `value_col` followed by the auxiliary columns of
+/// `agg_fn`.
+fn aggregation_input(
+    pivot_df: &DataFrame,
+    agg_fn: &PivotAgg,
+    mut value_col: Column,
+) -> PolarsResult<DataFrame> {
+    value_col.rename(agg_fn.0.root_name()?.clone());
+    let mut columns = vec![value_col];
+    for name in agg_fn.0.auxiliary_columns() {
+        columns.push(pivot_df.column(name)?.clone());
+    }
+    // SAFETY: all columns have the height of `pivot_df`. The aggregations access their input by
+    // position, so a name clash doesn't matter.
+    Ok(unsafe { DataFrame::new_no_checks(pivot_df.height(), columns) })
+}
+
+/// The result of `agg_fn` on an empty group of its `input` frame.
+fn empty_group_aggregate(agg_fn: &PivotAgg, input: &DataFrame) -> PolarsResult<AnyValue<'static>> {
+    let empty_df = input.clear();
+    let empty_group = GroupsIdx::new_empty();
+    let groups_from_empty = GroupsType::from(empty_group).into_sliceable();
+    let agg_on_empty = Column::from(agg_fn.0.evaluate_on_groups(&empty_df, &groups_from_empty)?);
+    Ok(agg_on_empty.get(0).unwrap_or_default().into_static())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn pivot_impl_single_column(
     pivot_df: &DataFrame,
     index: &[PlSmallStr],
     column: &PlSmallStr,
     values: &[PlSmallStr],
-    agg_fn: Option<PivotAgg>,
+    agg_fns: &[Option<PivotAgg>],
     sort_columns: bool,
     separator: Option<&str>,
-) -> PolarsResult<DataFrame> {
+    options: &PivotOptions,
+) -> PolarsResult<Vec<DataFrame>> {
     let sep = separator.unwrap_or("_");
-    let mut final_cols = vec![];
-    let mut count = 0;
+    if options.margins
+        && !agg_fns
+            .iter()
+            .all(|agg_fn| agg_fn.as_ref().is_some_and(|agg_fn| agg_fn.0.is_additive()))
+    {
+        polars_bail!(
+            InvalidOperation: "`margins` in `pivot` require an additive aggregation such as `sum` or `count`"
+        )
+    }
+    polars_ensure!(
+        !(options.margins && options.struct_columns),
+        InvalidOperation: "`margins` and `struct_columns` in `pivot` cannot be combined"
+    );
+    let mut outputs = Vec::with_capacity(agg_fns.len());
     let out: PolarsResult<()> = POOL.install(|| {
         let mut group_by = index.to_vec();
         group_by.push(column.clone());
+        let groups = if options.input_is_grouped {
+            // Grouping the input is what this option skips, so the input is only checked if the
+            // cells are required to be unique anyway.
+            if options.require_unique_cells {
+                let groups = pivot_df.group_by_stable(group_by)?.take_groups();
+                ensure_unique_cells(
+                    pivot_df,
+                    index,
+                    column,
+                    &groups,
+                    "the input is declared to be grouped",
+                )?;
+            }
 
-        let groups = pivot_df.group_by_stable(group_by)?.take_groups();
+            // Every row is its own group.
+            let groups = (0..pivot_df.height() as IdxSize).map(|i| [i, 1]).collect();
+            GroupsType::Slice {
+                groups,
+                rolling: false,
+            }
+            .into_sliceable()
+        } else {
+            let groups = pivot_df.group_by_stable(group_by)?.take_groups();
+            if options.require_unique_cells {
+                ensure_unique_cells(
+                    pivot_df,
+                    index,
+                    column,
+                    &groups,
+                    "every cell is required to be unique",
+                )?;
+            }
+            groups
+        };
 
         let (col, row) = POOL.join(
             || positioning::compute_col_idx(pivot_df, column, &groups),
-            || positioning::compute_row_idx(pivot_df, index, &groups, count),
+            || positioning::compute_row_idx(pivot_df, index, &groups, 0),
         );
         let (col_locations, column_agg) = col?;
-        let (row_locations, n_rows, mut row_index) = row?;
-
-        for value_col_name in values {
-            let value_col = pivot_df.column(value_col_name)?;
-
-            // Aggregate the expression on a value column
-            let value_agg = unsafe {
-                match &agg_fn {
-                    None => match value_col.len() > groups.len() {
-                        true => polars_bail!(
-                            ComputeError:
-                            "found multiple elements in the same group, \
-                            please specify an aggregation function"
-                        ),
-                        false => value_col.agg_first(&groups),
-                    },
-                    Some(agg_fn) => {
-                        let expr = agg_fn.0.clone();
-                        let name = expr.root_name()?.clone();
-                        let mut value_col = value_col.clone();
-                        value_col.rename(name);
-                        let tmp_df = value_col.into_frame();
-                        let mut aggregated =
-                            Column::from(expr.evaluate_on_groups(&tmp_df, &groups)?);
-                        aggregated.rename(value_col_name.clone());
-                        aggregated
-                    },
+        let (row_locations, n_rows, row_index) = row?;
+        let row_index = row_index.unwrap();
+        let keys = column_agg.unique_stable()?;
+
+        // Every aggregation is applied to the same groups, so the outputs share the index columns.
+        for agg_fn in agg_fns {
+            let mut final_cols = row_index.clone();
+            for value_col_name in values {
+                let value_col = pivot_df.column(value_col_name)?;
+
+                // Aggregate the expression on a value column
+                let value_agg = unsafe {
+                    match agg_fn {
+                        None => match value_col.len() > groups.len() {
+                            true => polars_bail!(
+                                ComputeError:
+                                "found multiple elements in the same group, \
+                                please specify an aggregation function"
+                            ),
+                            false => value_col.agg_first(&groups),
+                        },
+                        Some(agg_fn) => {
+                            let tmp_df = aggregation_input(pivot_df, agg_fn, value_col.clone())?;
+                            let mut aggregated =
+                                Column::from(agg_fn.0.evaluate_on_groups(&tmp_df, &groups)?);
+                            aggregated.rename(value_col_name.clone());
+                            aggregated
+                        },
+                    }
+                };
+
+                // For any combination of 'index' and 'on' for which there is no entry in the df,
+                // the default value is defined as the result of the agg_fn on the empty column,
+                // unless the user explicitly asked for nulls.
+                let default_val = {
+                    match agg_fn {
+                        Some(agg_fn) if !options.missing_as_null => {
+                            let input = aggregation_input(pivot_df, agg_fn, value_col.clone())?;
+                            empty_group_aggregate(agg_fn, &input)?
+                        },
+                        _ => AnyValue::Null,
+                    }
+                };
+
+                let headers = keys.cast(&DataType::String)?;
+                let mut headers = headers.str().unwrap().clone();
+                if values.len() > 1 {
+                    headers =
+                        headers.apply_values(|v| Cow::from(format!("{value_col_name}{sep}{v}")))
                 }
-            };
-
-            // For any combination of 'index' and 'on' for which there is no entry in the df,
-            // the default value is defined as the result of the agg_fn on the empty column.
-            let default_val = {
-                match &agg_fn {
-                    None => AnyValue::Null,
-                    Some(agg_fn) => {
-                        let empty_col = Column::new_empty(PlSmallStr::EMPTY, value_col.dtype());
-                        let empty_df = empty_col.clone().into_frame();
-                        let empty_group = GroupsIdx::new_empty();
-                        let groups_from_empty = GroupsType::from(empty_group).into_sliceable();
-                        let expr = agg_fn.0.clone();
-                        let agg_on_empty =
-                            Column::from(expr.evaluate_on_groups(&empty_df, &groups_from_empty)?);
-                        agg_on_empty.get(0).unwrap_or_default().into_static()
-                    },
+
+                let n_cols = headers.len();
+                let value_agg_phys = value_agg.to_physical_repr();
+                let logical_type = value_agg.dtype();
+
+                debug_assert_eq!(row_locations.len(), col_locations.len());
+                debug_assert_eq!(value_agg_phys.len(), row_locations.len());
+
+                let mut cols = if value_agg_phys.dtype().is_primitive_numeric() {
+                    macro_rules! dispatch {
+                        ($ca:expr) => {{
+                            let default_val = default_val.extract();
+                            positioning::position_aggregates_numeric(
+                                n_rows,
+                                n_cols,
+                                &row_locations,
+                                &col_locations,
+                                $ca,
+                                logical_type,
+                                &headers,
+                                default_val,
+                            )
+                        }};
+                    }
+                    downcast_as_macro_arg_physical!(value_agg_phys, dispatch)
+                } else {
+                    positioning::position_aggregates(
+                        n_rows,
+                        n_cols,
+                        &row_locations,
+                        &col_locations,
+                        value_agg_phys.as_materialized_series(),
+                        logical_type,
+                        &headers,
+                        &default_val,
+                    )
+                };
+
+                // The columns are in the order of `keys`.
+                if options.sort_columns_by_value {
+                    let order = keys.arg_sort(SortOptions::default());
+                    cols = order
+                        .into_no_null_iter()
+                        .map(|i| cols[i as usize].clone())
+                        .collect();
                 }
-            };
 
-            let headers = column_agg.unique_stable()?.cast(&DataType::String)?;
-            let mut headers = headers.str().unwrap().clone();
-            if values.len() > 1 {
-                headers = headers.apply_values(|v| Cow::from(format!("{value_col_name}{sep}{v}")))
-            }
+                if options.drop_all_null_columns {
+                    cols.retain(|c| c.is_empty() || c.null_count() < c.len());
+                }
 
-            let n_cols = headers.len();
-            let value_agg_phys = value_agg.to_physical_repr();
-            let logical_type = value_agg.dtype();
-
-            debug_assert_eq!(row_locations.len(), col_locations.len());
-            debug_assert_eq!(value_agg_phys.len(), row_locations.len());
-
-            let mut cols = if value_agg_phys.dtype().is_primitive_numeric() {
-                macro_rules! dispatch {
-                    ($ca:expr) => {{
-                        let default_val = default_val.extract();
-                        positioning::position_aggregates_numeric(
-                            n_rows,
-                            n_cols,
-                            &row_locations,
-                            &col_locations,
-                            $ca,
-                            logical_type,
-                            &headers,
-                            default_val,
-                        )
-                    }};
+                if sort_columns && !options.sort_columns_by_value {
+                    cols.sort_unstable_by(|a, b| a.name().partial_cmp(b.name()).unwrap());
+                }
+
+                if options.struct_columns && matches!(keys.dtype(), DataType::Struct(_)) {
+                    let prefix = (values.len() > 1).then(|| format!("{value_col_name}{sep}"));
+                    cols = nest_columns(cols, &headers, &keys, prefix.as_deref(), n_rows)?;
+                }
+
+                if options.margins {
+                    let name = if values.len() > 1 {
+                        format_pl_smallstr!("{value_col_name}{sep}Total")
+                    } else {
+                        PlSmallStr::from_static("Total")
+                    };
+                    let total = match sum_horizontal(&cols, NullStrategy::Ignore)? {
+                        Some(total) => total.with_name(name),
+                        None => Column::full_null(name, n_rows, logical_type),
+                    };
+                    cols.push(total);
                 }
-                downcast_as_macro_arg_physical!(value_agg_phys, dispatch)
-            } else {
-                positioning::position_aggregates(
-                    n_rows,
-                    n_cols,
-                    &row_locations,
-                    &col_locations,
-                    value_agg_phys.as_materialized_series(),
-                    logical_type,
-                    &headers,
-                    &default_val,
-                )
-            };
-
-            if sort_columns {
-                cols.sort_unstable_by(|a, b| a.name().partial_cmp(b.name()).unwrap());
-            }
 
-            let cols = if count == 0 {
-                let mut final_cols = row_index.take().unwrap();
                 final_cols.extend(cols);
-                final_cols
-            } else {
-                cols
-            };
-            count += 1;
-            final_cols.extend_from_slice(&cols);
+            }
+
+            let mut out = DataFrame::new(final_cols)?;
+            if options.margins {
+                append_totals_row(&mut out, index)?;
+            }
+            outputs.push(out);
         }
         Ok(())
     });
     out?;
 
-    DataFrame::new(final_cols)
+    Ok(outputs)
+}
+
+/// Nest the value `cols` by the fields of their struct `keys`, see
+/// [`PivotOptions::struct_columns`].
+///
+/// The column of `keys[i]` is named `headers[i]`, the outer columns are named with `prefix`.
+fn nest_columns(
+    cols: Vec<Column>,
+    headers: &StringChunked,
+    keys: &Column,
+    prefix: Option<&str>,
+    n_rows: usize,
+) -> PolarsResult<Vec<Column>> {
+    let key_idx: PlHashMap<&str, usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (h.unwrap(), i))
+        .collect();
+    let levels = keys
+        .struct_()?
+        .fields_as_series()
+        .iter()
+        .map(|s| Ok(s.cast(&DataType::String)?.str()?.clone()))
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    // The columns can be sorted or dropped, so look up their keys by name.
+    let idxs = cols
+        .iter()
+        .map(|c| key_idx[c.name().as_str()])
+        .collect::<Vec<_>>();
+    let cols = PlHashMap::from_iter(idxs.iter().copied().zip(cols));
+
+    let mut out = nest_level(&cols, &idxs, &levels, n_rows)?;
+    if let Some(prefix) = prefix {
+        for c in &mut out {
+            let name = format_pl_smallstr!("{prefix}{}", c.name());
+            c.rename(name);
+        }
+    }
+    Ok(out)
+}
+
+/// Group the columns of the keys `idxs` by the value of their key in `levels[0]`, nesting the
+/// next levels into every group.
+fn nest_level(
+    cols: &PlHashMap<usize, Column>,
+    idxs: &[usize],
+    levels: &[StringChunked],
+    n_rows: usize,
+) -> PolarsResult<Vec<Column>> {
+    let (level, inner_levels) = levels.split_first().unwrap();
+    let mut groups: PlIndexMap<&str, Vec<usize>> = PlIndexMap::default();
+    for &i in idxs {
+        let name = level.get(i).unwrap_or("null");
+        groups.entry(name).or_default().push(i);
+    }
+
+    groups
+        .into_iter()
+        .map(|(name, group)| {
+            let name = PlSmallStr::from_str(name);
+            if inner_levels.is_empty() {
+                // The keys are unique, so there is a single column left.
+                return Ok(cols[&group[0]].clone().with_name(name));
+            }
+            let fields = nest_level(cols, &group, inner_levels, n_rows)?;
+            Ok(StructChunked::from_columns(name, n_rows, &fields)?.into_column())
+        })
+        .collect()
+}
+
+/// Append the `Total` row of [`PivotOptions::margins`] to the pivoted `df`.
+fn append_totals_row(df: &mut DataFrame, index: &[PlSmallStr]) -> PolarsResult<()> {
+    let label = &index[0];
+    let labels = df.column(label)?.cast(&DataType::String)?;
+    df.with_column(labels)?;
+
+    let totals = df
+        .get_columns()
+        .iter()
+        .map(|c| {
+            let name = c.name().clone();
+            if name == label {
+                Ok(Column::new(name, ["Total"]))
+            } else if index.contains(&name) {
+                Ok(Column::full_null(name, 1, c.dtype()))
+            } else {
+                Column::new_scalar(name, c.sum_reduce()?, 1).strict_cast(c.dtype())
+            }
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    df.vstack_mut_owned(DataFrame::new(totals)?)?;
+    Ok(())
 }
@@ -100,6 +100,37 @@ impl utils::Decoded for DecodedStateTuple {
     }
 }
 
+/// Evaluate a predicate that matches on the bytes of a value, and decode the matching values if
+/// they are needed.
+fn decode_matching_plain(
+    max_num_values: usize,
+    values: &[u8],
+    is_match: impl Fn(&[u8]) -> bool,
+    include_values: bool,
+    target: &mut MutableBinaryViewArray<[u8]>,
+    pred_true_mask: &mut BitmapBuilder,
+    verify_utf8: bool,
+) -> ParquetResult<()> {
+    let mut mask = BitmapBuilder::with_capacity(max_num_values);
+    predicate::decode_matching(max_num_values, values, is_match, &mut mask)?;
+    let mask = mask.freeze();
+
+    if include_values && mask.set_bits() > 0 {
+        // The matching values are decoded in a second pass, which takes care of the UTF-8
+        // verification.
+        let mut is_selected = mask.iter();
+        decode_plain_generic(
+            values,
+            target,
+            max_num_values,
+            || Some((true, is_selected.next()?)),
+            verify_utf8,
+        )?;
+    }
+    pred_true_mask.extend_from_bitmap(&mask);
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn decode_plain(
     values: &[u8],
@@ -234,6 +265,27 @@ pub fn decode_plain(
                     target.set_total_bytes_len(new_total_bytes_len);
                 }
             },
+            SpecializedParquetColumnExpr::StartsWith(prefix) => decode_matching_plain(
+                max_num_values,
+                values,
+                |v| v.starts_with(prefix),
+                p.include_values,
+                target,
+                pred_true_mask,
+                verify_utf8,
+            )?,
+            SpecializedParquetColumnExpr::Contains(needle) => {
+                let finder = memchr::memmem::Finder::new(needle);
+                decode_matching_plain(
+                    max_num_values,
+                    values,
+                    |v| finder.find(v).is_some(),
+                    p.include_values,
+                    target,
+                    pred_true_mask,
+                    verify_utf8,
+                )?
+            },
             _ => unreachable!(),
         }
 
@@ -510,6 +562,8 @@ impl utils::Decoder for BinViewDecoder {
                 Some(
                     SpecializedParquetColumnExpr::Equal(_)
                         | SpecializedParquetColumnExpr::EqualOneOf(_)
+                        | SpecializedParquetColumnExpr::StartsWith(_)
+                        | SpecializedParquetColumnExpr::Contains(_)
                 )
             );
 
@@ -6,6 +6,38 @@ use polars_utils::aliases::PlIndexSet;
 
 use crate::parquet::error::ParquetResult;
 
+/// Create a mask for when `is_match` holds for a value.
+pub fn decode_matching(
+    num_expected_values: usize,
+    mut values: &[u8],
+    is_match: impl Fn(&[u8]) -> bool,
+    pred_true_mask: &mut BitmapBuilder,
+) -> ParquetResult<()> {
+    pred_true_mask.reserve(num_expected_values);
+    for _ in 0..num_expected_values {
+        if values.len() < 4 {
+            return Err(super::invalid_input_err());
+        }
+
+        let length;
+        (length, values) = values.split_at(4);
+        let length: &[u8; 4] = unsafe { length.try_into().unwrap_unchecked() };
+        let length = u32::from_le_bytes(*length);
+
+        if values.len() < length as usize {
+            return Err(super::invalid_input_err());
+        }
+
+        let value;
+        (value, values) = values.split_at(length as usize);
+
+        // SAFETY: We reserved enough just before the loop.
+        unsafe { pred_true_mask.push_unchecked(is_match(value)) };
+    }
+
+    Ok(())
+}
+
 /// Create a mask for when a value is equal to the `needle`.
 pub fn decode_equals(
     num_expected_values: usize,
@@ -95,6 +95,7 @@ pub enum SpecializedParquetColumnExpr {
     StartsWith(Box<[u8]>),
     EndsWith(Box<[u8]>),
     StartEndsWith(Box<[u8]>, Box<[u8]>),
+    Contains(Box<[u8]>),
 }
 
 pub type ParquetColumnExprRef = Arc<dyn ParquetColumnExpr>;
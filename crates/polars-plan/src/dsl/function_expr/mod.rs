@@ -289,6 +289,8 @@ pub enum FunctionExpr {
         seed: Option<u64>,
     },
     SetSortedFlag(IsSorted),
+    /// Marks a predicate that must not be pushed down.
+    NoPushdown,
     #[cfg(feature = "ffi_plugin")]
     /// Creating this node is unsafe
     /// This will lead to calls over FFI.
@@ -625,6 +627,7 @@ impl Hash for FunctionExpr {
             RLEID => {},
             ToPhysical => {},
             SetSortedFlag(is_sorted) => is_sorted.hash(state),
+            NoPushdown => {},
             #[cfg(feature = "ewma")]
             EwmMean { options } => options.hash(state),
             #[cfg(feature = "ewma_by")]
@@ -828,6 +831,7 @@ impl Display for FunctionExpr {
             #[cfg(feature = "random")]
             Random { method, .. } => method.into(),
             SetSortedFlag(_) => "set_sorted",
+            NoPushdown => "no_pushdown",
             #[cfg(feature = "ffi_plugin")]
             FfiPlugin { lib, symbol, .. } => return write!(f, "{lib}:{symbol}"),
             FoldHorizontal { .. } => "fold",
@@ -1534,6 +1534,14 @@ impl Expr {
         self.map_unary(FunctionExpr::SetSortedFlag(sorted))
     }
 
+    /// Prevent the optimizer from pushing a predicate containing this expression down.
+    ///
+    /// A filter on this expression stays where it is in the query plan, e.g. it is not pushed
+    /// below a join. The values are passed through unchanged.
+    pub fn no_pushdown(self) -> Expr {
+        self.map_unary(FunctionExpr::NoPushdown)
+    }
+
     #[cfg(feature = "row_hash")]
     /// Compute the hash of every element.
     pub fn hash(self, k0: u64, k1: u64, k2: u64, k3: u64) -> Expr {
@@ -274,6 +274,29 @@ pub struct UnionOptions {
     pub maintain_order: bool,
 }
 
+/// The order both inputs of a merge sorted are sorted in.
+#[cfg(feature = "merge_sorted")]
+#[derive(Clone, Debug, Copy, Default, Eq, PartialEq, Hash)]
+pub struct MergeSortedOptions {
+    pub descending: bool,
+    pub nulls_last: bool,
+}
+
+#[cfg(feature = "merge_sorted")]
+impl MergeSortedOptions {
+    /// Whether the inputs are sorted in descending order. Default `false`.
+    pub fn with_order_descending(mut self, descending: bool) -> Self {
+        self.descending = descending;
+        self
+    }
+
+    /// Whether the nulls are placed last in the inputs. Default `false`.
+    pub fn with_nulls_last(mut self, nulls_last: bool) -> Self {
+        self.nulls_last = nulls_last;
+        self
+    }
+}
+
 #[derive(Clone, Debug, Copy, Default, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
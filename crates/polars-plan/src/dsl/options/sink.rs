@@ -321,6 +321,8 @@ impl SinkFinishCallback {
 pub enum PartitionTargetCallbackResult {
     Str(String),
     Dyn(DynSinkTarget),
+    /// Don't write the partition, its rows are discarded.
+    Skip,
 }
 
 impl PartitionTargetCallback {
@@ -481,6 +483,11 @@ pub struct PartitionSinkType {
     pub cloud_options: Option<polars_io::cloud::CloudOptions>,
     pub per_partition_sort_by: Option<Vec<SortColumn>>,
     pub finish_callback: Option<SinkFinishCallback>,
+    /// Called with a sample of every morsel that was sent to one of the files.
+    pub morsel_metrics_callback: Option<SinkFinishCallback>,
+    /// Keys that get a file even if no rows have them, which is then empty. Only for partitioning
+    /// by key, the columns are the key columns.
+    pub expected_keys: Option<Arc<DataFrame>>,
 }
 
 #[cfg_attr(feature = "ir_serde", derive(serde::Serialize, serde::Deserialize))]
@@ -494,6 +501,8 @@ pub struct PartitionSinkTypeIR {
     pub cloud_options: Option<polars_io::cloud::CloudOptions>,
     pub per_partition_sort_by: Option<Vec<SortColumnIR>>,
     pub finish_callback: Option<SinkFinishCallback>,
+    pub morsel_metrics_callback: Option<SinkFinishCallback>,
+    pub expected_keys: Option<Arc<DataFrame>>,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -517,6 +526,24 @@ pub enum PartitionVariant {
     ByKey {
         key_exprs: Vec<Expr>,
         include_key: bool,
+        /// Split the rows of a key over several files of at most this many rows.
+        max_rows_per_file: Option<IdxSize>,
+        /// Only resolve the partitions and their paths, without writing any files.
+        dry_run: bool,
+        /// Route the rows whose keys are all null to their own partition, and write null keys as
+        /// `__NULL__` instead of `__HIVE_DEFAULT_PARTITION__` in the default paths.
+        separate_null_keys: bool,
+        /// Send the buffered rows of a partition to its file in morsels of this many rows,
+        /// instead of in the frames they were buffered as.
+        flush_morsel_rows: Option<IdxSize>,
+        /// Write the rows of every partition in the order of the input, regardless of
+        /// `maintain_order`.
+        ///
+        /// The morsels are put back in their input order before they are partitioned, which means
+        /// a morsel that is done early waits until all morsels before it are done. This adds
+        /// latency and holds up to a linearizer buffer of morsels per pipeline in memory, while
+        /// files are written in the order the rows arrived.
+        strict_order: bool,
     },
 }
 
@@ -531,6 +558,11 @@ pub enum PartitionVariantIR {
     ByKey {
         key_exprs: Vec<ExprIR>,
         include_key: bool,
+        max_rows_per_file: Option<IdxSize>,
+        dry_run: bool,
+        separate_null_keys: bool,
+        flush_morsel_rows: Option<IdxSize>,
+        strict_order: bool,
     },
 }
 
@@ -585,11 +617,27 @@ impl PartitionVariantIR {
             | Self::ByKey {
                 key_exprs,
                 include_key,
+                ..
             } => {
                 include_key.hash(state);
                 for key_expr in key_exprs.as_slice() {
                     key_expr.traverse_and_hash(expr_arena, state);
                 }
+                if let Self::ByKey {
+                    max_rows_per_file,
+                    dry_run,
+                    separate_null_keys,
+                    flush_morsel_rows,
+                    strict_order,
+                    ..
+                } = self
+                {
+                    max_rows_per_file.hash(state);
+                    dry_run.hash(state);
+                    separate_null_keys.hash(state);
+                    flush_morsel_rows.hash(state);
+                    strict_order.hash(state);
+                }
             },
         }
     }
@@ -23,6 +23,7 @@ const DSL_SCHEMA_HASH: SchemaHash<'static> = SchemaHash::from_hash_file();
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+#[allow(clippy::large_enum_variant)]
 pub enum DslPlan {
     #[cfg(feature = "python")]
     PythonScan {
@@ -147,6 +148,8 @@ pub enum DslPlan {
         input_left: Arc<DslPlan>,
         input_right: Arc<DslPlan>,
         key: PlSmallStr,
+        descending: bool,
+        nulls_last: bool,
     },
     IR {
         // Keep the original Dsl around as we need that for serialization.
@@ -186,7 +189,7 @@ impl Clone for DslPlan {
             Self::Sink { input, payload } => Self::Sink { input: input.clone(), payload: payload.clone() },
             Self::SinkMultiple { inputs } => Self::SinkMultiple { inputs: inputs.clone() },
             #[cfg(feature = "merge_sorted")]
-            Self::MergeSorted { input_left, input_right, key } => Self::MergeSorted { input_left: input_left.clone(), input_right: input_right.clone(), key: key.clone() },
+            Self::MergeSorted { input_left, input_right, key, descending, nulls_last } => Self::MergeSorted { input_left: input_left.clone(), input_right: input_right.clone(), key: key.clone(), descending: *descending, nulls_last: *nulls_last },
             Self::IR {node, dsl, version} => Self::IR {node: *node, dsl: dsl.clone(), version: *version},
         }
     }
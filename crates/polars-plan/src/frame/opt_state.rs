@@ -39,6 +39,15 @@ bitflags! {
         /// Check if operations are order dependent and unset maintaining_order if
         /// the order would not be observed.
         const CHECK_ORDER_OBSERVE = 1 << 16;
+        /// Return an error if the optimizer changed the names of the output schema. This check
+        /// always runs in debug builds, this flag enables it in release builds.
+        const VERIFY_SCHEMA = 1 << 17;
+        /// Return an error if an extra pass of the optimization rules still changes the plan
+        /// after they reached a fixed point.
+        const VERIFY_FIXED_POINT = 1 << 18;
+        /// Keep a copy of the filter predicates that predicate pushdown pushes into the scans, so
+        /// they can be reported after optimization.
+        const PREDICATE_LINEAGE = 1 << 19;
     }
 }
 
@@ -78,11 +87,19 @@ impl OptFlags {
     pub fn fast_projection(&self) -> bool {
         self.contains(OptFlags::FAST_PROJECTION)
     }
+    pub fn predicate_lineage(&self) -> bool {
+        self.contains(OptFlags::PREDICATE_LINEAGE)
+    }
 }
 
 impl Default for OptFlags {
     fn default() -> Self {
-        Self::from_bits_truncate(u32::MAX) & !Self::NEW_STREAMING & !Self::EAGER
+        Self::from_bits_truncate(u32::MAX)
+            & !Self::NEW_STREAMING
+            & !Self::EAGER
+            & !Self::VERIFY_SCHEMA
+            & !Self::VERIFY_FIXED_POINT
+            & !Self::PREDICATE_LINEAGE
     }
 }
 
@@ -57,6 +57,10 @@ pub(super) fn set_sorted_flag(s: &Column, sorted: IsSorted) -> PolarsResult<Colu
     Ok(s)
 }
 
+pub(super) fn no_pushdown(s: &Column) -> PolarsResult<Column> {
+    Ok(s.clone())
+}
+
 #[cfg(feature = "timezones")]
 pub(super) fn replace_time_zone(
     s: &[Column],
@@ -341,6 +341,8 @@ pub enum IRFunctionExpr {
         seed: Option<u64>,
     },
     SetSortedFlag(IsSorted),
+    /// Marks a predicate that must not be pushed down.
+    NoPushdown,
     #[cfg(feature = "ffi_plugin")]
     /// Creating this node is unsafe
     /// This will lead to calls over FFI.
@@ -682,6 +684,7 @@ impl Hash for IRFunctionExpr {
             RLEID => {},
             ToPhysical => {},
             SetSortedFlag(is_sorted) => is_sorted.hash(state),
+            NoPushdown => {},
             #[cfg(feature = "ewma")]
             EwmMean { options } => options.hash(state),
             #[cfg(feature = "ewma_by")]
@@ -887,6 +890,7 @@ impl Display for IRFunctionExpr {
             #[cfg(feature = "random")]
             Random { method, .. } => method.into(),
             SetSortedFlag(_) => "set_sorted",
+            NoPushdown => "no_pushdown",
             #[cfg(feature = "ffi_plugin")]
             FfiPlugin { lib, symbol, .. } => return write!(f, "{lib}:{symbol}"),
 
@@ -1312,6 +1316,7 @@ impl From<IRFunctionExpr> for SpecialEq<Arc<dyn ColumnsUdf>> {
                 }
             },
             SetSortedFlag(sorted) => map!(dispatch::set_sorted_flag, sorted),
+            NoPushdown => map!(dispatch::no_pushdown),
             #[cfg(feature = "ffi_plugin")]
             FfiPlugin {
                 flags: _,
@@ -1568,7 +1573,7 @@ impl IRFunctionExpr {
                 method: IRRandomMethod::Shuffle,
                 ..
             } => FunctionOptions::length_preserving(),
-            F::SetSortedFlag(_) => FunctionOptions::elementwise(),
+            F::SetSortedFlag(_) | F::NoPushdown => FunctionOptions::elementwise(),
             #[cfg(feature = "ffi_plugin")]
             F::FfiPlugin { flags, .. } => *flags,
             F::MaxHorizontal | F::MinHorizontal => FunctionOptions::elementwise().with_flags(|f| {
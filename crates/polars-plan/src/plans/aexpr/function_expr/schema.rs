@@ -352,7 +352,7 @@ impl IRFunctionExpr {
             ToPhysical => mapper.to_physical_type(),
             #[cfg(feature = "random")]
             Random { .. } => mapper.with_same_dtype(),
-            SetSortedFlag(_) => mapper.with_same_dtype(),
+            SetSortedFlag(_) | NoPushdown => mapper.with_same_dtype(),
             #[cfg(feature = "ffi_plugin")]
             FfiPlugin {
                 flags: _,
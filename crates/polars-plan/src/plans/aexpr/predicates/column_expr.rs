@@ -16,6 +16,8 @@ use crate::plans::aexpr::evaluate::{constant_evaluate, into_column};
 use crate::plans::{
     AExpr, IRBooleanFunction, IRFunctionExpr, MintermIter, aexpr_to_leaf_names_iter,
 };
+#[cfg(feature = "strings")]
+use crate::plans::{ExprIR, IRStringFunction};
 
 pub struct ColumnPredicates {
     pub predicates: PlHashMap<PlSmallStr, (Node, Option<SpecializedColumnPredicate>)>,
@@ -199,6 +201,25 @@ pub fn aexpr_to_column_predicates(
 
                                 Some(SpecializedColumnPredicate::EqualOneOf(values))
                             },
+                            #[cfg(feature = "strings")]
+                            AExpr::Function {
+                                input,
+                                function: IRFunctionExpr::StringExpr(IRStringFunction::StartsWith),
+                                options: _,
+                            } => {
+                                let prefix = string_pattern(input, expr_arena, schema, &dtype)?;
+                                Some(SpecializedColumnPredicate::StartsWith(prefix))
+                            },
+                            // A regex is evaluated by the generic predicate.
+                            #[cfg(all(feature = "strings", feature = "regex"))]
+                            AExpr::Function {
+                                input,
+                                function: IRFunctionExpr::StringExpr(IRStringFunction::Contains { literal: true, strict: _ }),
+                                options: _,
+                            } => {
+                                let needle = string_pattern(input, expr_arena, schema, &dtype)?;
+                                Some(SpecializedColumnPredicate::Contains(needle))
+                            },
                             AExpr::Function {
                                 input,
                                 function: IRFunctionExpr::Boolean(IRBooleanFunction::Not),
@@ -260,6 +281,27 @@ pub fn aexpr_to_column_predicates(
     }
 }
 
+/// The literal pattern of a string function on a `String` column, e.g. the prefix of
+/// `str.starts_with`.
+#[cfg(feature = "strings")]
+fn string_pattern(
+    input: &[ExprIR],
+    expr_arena: &Arena<AExpr>,
+    schema: &Schema,
+    dtype: &DataType,
+) -> Option<Box<[u8]>> {
+    if dtype != &DataType::String {
+        return None;
+    }
+    into_column(input[0].node(), expr_arena, schema, 0)?;
+    let pattern = constant_evaluate(input[1].node(), expr_arena, schema, 0)??;
+    match pattern.to_any_value()? {
+        AnyValue::String(s) => Some(s.as_bytes().into()),
+        AnyValue::StringOwned(s) => Some(s.as_bytes().into()),
+        _ => None,
+    }
+}
+
 fn is_between(
     dtype: &DataType,
     low: Option<Scalar>,
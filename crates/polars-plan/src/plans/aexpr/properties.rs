@@ -286,8 +286,15 @@ impl ExprPushdownGroup {
                     *self = ExprPushdownGroup::Fallible;
                 }
 
-                // Downgrade to barrier if non-elementwise
-                if !is_elementwise(stack, ae, expr_arena) {
+                // Downgrade to barrier if non-elementwise or explicitly marked by the user
+                if matches!(
+                    ae,
+                    AExpr::Function {
+                        function: IRFunctionExpr::NoPushdown,
+                        ..
+                    }
+                ) || !is_elementwise(stack, ae, expr_arena)
+                {
                     *self = ExprPushdownGroup::Barrier
                 }
             },
@@ -913,6 +913,7 @@ pub(super) fn convert_functions(
             }
         },
         F::SetSortedFlag(is_sorted) => I::SetSortedFlag(is_sorted),
+        F::NoPushdown => I::NoPushdown,
         #[cfg(feature = "ffi_plugin")]
         F::FfiPlugin {
             flags,
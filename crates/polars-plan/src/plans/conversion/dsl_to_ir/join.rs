@@ -34,6 +34,30 @@ pub fn resolve_join(
     mut options: JoinOptionsIR,
     ctxt: &mut DslConversionContext,
 ) -> PolarsResult<(Node, Node)> {
+    polars_ensure!(
+        options.args.output_columns.is_none(),
+        InvalidOperation: "the output columns of a lazy join can't be set, select them after the join instead"
+    );
+    polars_ensure!(
+        options.args.key_struct.is_none(),
+        InvalidOperation: "the keys of a lazy join can't be nested into a struct, use `as_struct` after the join instead"
+    );
+    if options.args.semi_anti_from_right {
+        polars_ensure!(
+            options.args.how.is_semi_anti(),
+            InvalidOperation: "only semi and anti joins can be evaluated from the right frame"
+        );
+        options.args = options.args.swap_semi_anti_from_right();
+        return resolve_join(
+            input_right,
+            input_left,
+            right_on,
+            left_on,
+            predicates,
+            options,
+            ctxt,
+        );
+    }
     if !predicates.is_empty() {
         feature_gated!("iejoin", {
             debug_assert!(left_on.is_empty() && right_on.is_empty());
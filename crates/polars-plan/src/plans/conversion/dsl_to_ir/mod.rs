@@ -440,6 +440,7 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                 input,
                 id,
                 cache_hits: crate::constants::UNLIMITED_CACHE,
+                memory_budget: None,
             }
         },
         DslPlan::GroupBy {
@@ -919,6 +920,12 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
             let input =
                 to_alp_impl(owned(input), ctxt).map_err(|e| e.context(failed_here!(sink)))?;
             let input_schema = ctxt.lp_arena.get(input).schema(ctxt.lp_arena);
+            if let SinkType::Partition(f) = &payload {
+                polars_ensure!(
+                    f.expected_keys.is_none() || matches!(f.variant, PartitionVariant::ByKey { .. }),
+                    InvalidOperation: "expected partition keys are only supported when partitioning by key"
+                );
+            }
             let payload = match payload {
                 SinkType::Memory => SinkTypeIR::Memory,
                 SinkType::File(f) => SinkTypeIR::File(f),
@@ -954,7 +961,20 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                         PartitionVariant::ByKey {
                             key_exprs,
                             include_key,
+                            max_rows_per_file,
+                            dry_run,
+                            separate_null_keys,
+                            flush_morsel_rows,
+                            strict_order,
                         } => {
+                            polars_ensure!(
+                                max_rows_per_file != Some(0),
+                                InvalidOperation: "`max_rows_per_file` must be larger than 0"
+                            );
+                            polars_ensure!(
+                                flush_morsel_rows != Some(0),
+                                InvalidOperation: "`flush_morsel_rows` must be larger than 0"
+                            );
                             let eirs = to_expr_irs(
                                 key_exprs,
                                 &mut ExprToIRContext::new_with_opt_eager(
@@ -969,6 +989,11 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                             PartitionVariantIR::ByKey {
                                 key_exprs: eirs,
                                 include_key,
+                                max_rows_per_file,
+                                dry_run,
+                                separate_null_keys,
+                                flush_morsel_rows,
+                                strict_order,
                             }
                         },
                     },
@@ -999,6 +1024,8 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                         ),
                     },
                     finish_callback: f.finish_callback,
+                    morsel_metrics_callback: f.morsel_metrics_callback,
+                    expected_keys: f.expected_keys,
                 }),
             };
 
@@ -1018,6 +1045,8 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
             input_left,
             input_right,
             key,
+            descending,
+            nulls_last,
         } => {
             let input_left = to_alp_impl(owned(input_left), ctxt)
                 .map_err(|e| e.context(failed_here!(merge_sorted)))?;
@@ -1028,6 +1057,8 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                 input_left,
                 input_right,
                 key,
+                descending,
+                nulls_last,
             }
         },
         DslPlan::IR { node, dsl, version } => {
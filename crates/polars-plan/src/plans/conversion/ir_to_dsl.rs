@@ -995,6 +995,7 @@ pub fn ir_function_to_dsl(input: Vec<Expr>, function: IRFunctionExpr) -> Expr {
             }
         },
         IF::SetSortedFlag(s) => F::SetSortedFlag(s),
+        IF::NoPushdown => F::NoPushdown,
         #[cfg(feature = "ffi_plugin")]
         IF::FfiPlugin {
             flags,
@@ -163,6 +163,7 @@ impl OptimizationRule for TypeCheckRule {
                         if let PartitionVariantIR::ByKey {
                             key_exprs,
                             include_key,
+                            ..
                         }
                         | PartitionVariantIR::Parted {
                             key_exprs,
@@ -306,6 +306,7 @@ impl<'a> IRDotDisplay<'a> {
                 input_left,
                 input_right,
                 key,
+                ..
             } => {
                 self.with_root(*input_left)._format(f, Some(id), last)?;
                 self.with_root(*input_right)._format(f, Some(id), last)?;
@@ -15,6 +15,7 @@ const INDENT_INCREMENT: usize = 2;
 
 pub struct IRDisplay<'a> {
     lp: IRPlanRef<'a>,
+    annotations: Option<&'a OptimizationReport>,
 }
 
 #[derive(Clone, Copy)]
@@ -113,7 +114,17 @@ fn write_scan(
 
 impl<'a> IRDisplay<'a> {
     pub fn new(lp: IRPlanRef<'a>) -> Self {
-        Self { lp }
+        Self {
+            lp,
+            annotations: None,
+        }
+    }
+
+    pub fn with_annotations(self, report: &'a OptimizationReport) -> Self {
+        Self {
+            annotations: Some(report),
+            ..self
+        }
     }
 
     fn root(&self) -> &IR {
@@ -123,6 +134,7 @@ impl<'a> IRDisplay<'a> {
     fn with_root(&self, root: Node) -> Self {
         Self {
             lp: self.lp.with_root(root),
+            annotations: self.annotations,
         }
     }
 
@@ -141,11 +153,30 @@ impl<'a> IRDisplay<'a> {
         }
     }
 
+    /// Write the notes of the passes that added or rewrote the root on a line of their own.
+    fn write_notes(&self, f: &mut Formatter, indent: usize) -> fmt::Result {
+        let Some(names) = self
+            .annotations
+            .and_then(|report| report.annotations.get(&self.lp.lp_top))
+        else {
+            return Ok(());
+        };
+        write!(f, "{:indent$}(", "")?;
+        for (i, name) in names.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", annotation_note(name))?;
+        }
+        writeln!(f, ")")
+    }
+
     #[recursive]
     fn _format(&self, f: &mut Formatter, indent: usize) -> fmt::Result {
         if indent != 0 {
             writeln!(f)?;
         }
+        self.write_notes(f, indent)?;
 
         let sub_indent = indent + INDENT_INCREMENT;
         use IR::*;
@@ -241,7 +272,7 @@ impl<'a> IRDisplay<'a> {
             MergeSorted {
                 input_left,
                 input_right,
-                key: _,
+                ..
             } => {
                 write_ir_non_recursive(f, ir_node, self.lp.expr_arena, output_schema, indent)?;
                 write!(f, ":")?;
@@ -796,11 +827,18 @@ pub fn write_ir_non_recursive(
             input: _,
             id,
             cache_hits,
-        } => write!(
-            f,
-            "{:indent$}CACHE[id: {}, cache_hits: {}]",
-            "", *id, *cache_hits
-        ),
+            memory_budget,
+        } => {
+            write!(
+                f,
+                "{:indent$}CACHE[id: {}, cache_hits: {}",
+                "", *id, *cache_hits
+            )?;
+            if let Some(memory_budget) = memory_budget {
+                write!(f, ", memory_budget: {memory_budget}")?;
+            }
+            write!(f, "]")
+        },
         IR::GroupBy {
             input: _,
             keys,
@@ -900,7 +938,18 @@ pub fn write_ir_non_recursive(
             input_left: _,
             input_right: _,
             key,
-        } => write!(f, "{:indent$}MERGE SORTED ON '{key}'", ""),
+            descending,
+            nulls_last,
+        } => {
+            write!(f, "{:indent$}MERGE SORTED ON '{key}'", "")?;
+            if *descending {
+                write!(f, " DESCENDING")?;
+            }
+            if *nulls_last {
+                write!(f, " NULLS LAST")?;
+            }
+            Ok(())
+        },
         IR::Invalid => write!(f, "{:indent$}INVALID", ""),
     }
 }
@@ -95,6 +95,8 @@ pub enum IR {
         id: UniqueId,
         /// How many hits the cache must be saved in memory.
         cache_hits: u32,
+        /// Spill the cached `DataFrame` to disk if its estimated size in bytes exceeds this.
+        memory_budget: Option<usize>,
     },
     GroupBy {
         input: Node,
@@ -157,6 +159,8 @@ pub enum IR {
         input_left: Node,
         input_right: Node,
         key: PlSmallStr,
+        descending: bool,
+        nulls_last: bool,
     },
     #[default]
     Invalid,
@@ -217,6 +221,11 @@ impl<'a> IRPlanRef<'a> {
         format::IRDisplay::new(self)
     }
 
+    /// Display the plan with a note above every node that `report` has annotations for.
+    pub fn display_annotated(self, report: &'a OptimizationReport) -> format::IRDisplay<'a> {
+        format::IRDisplay::new(self).with_annotations(report)
+    }
+
     pub fn display_dot(self) -> dot::IRDotDisplay<'a> {
         dot::IRDotDisplay::new(self)
     }
@@ -241,6 +241,7 @@ impl<'a> TreeFmtNode<'a> {
                         input,
                         id,
                         cache_hits,
+                        memory_budget: _,
                     } => ND(
                         wh(
                             h,
@@ -378,6 +379,7 @@ impl<'a> TreeFmtNode<'a> {
                         input_left,
                         input_right,
                         key,
+                        ..
                     } => ND(
                         wh(h, &format!("MERGE SORTED ON '{key}")),
                         [self.lp_node(Some("LEFT PLAN:".to_string()), *input_left)]
@@ -0,0 +1,279 @@
+use polars_core::prelude::*;
+use polars_ops::prelude::JoinType;
+
+use crate::prelude::*;
+
+/// Fraction of the rows that are assumed to survive a predicate we know nothing about.
+const DEFAULT_SELECTIVITY: f64 = 0.5;
+/// Fraction of the rows that are assumed to survive an equality predicate.
+const EQUALITY_SELECTIVITY: f64 = 0.1;
+/// Fraction of the rows that are assumed to survive a range predicate.
+const RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+/// Fraction of the rows that are assumed to be unique in a `group_by` or `unique`.
+const DISTINCT_FRACTION: f64 = 0.1;
+
+/// Estimated number of output rows of every node in a plan, see [`estimate_cardinalities`].
+pub type CardinalityEstimates = PlHashMap<Node, Option<usize>>;
+
+/// Returns the number of rows of a scan source if it is known, e.g. from a catalog.
+pub type ScanRowCountOracle<'a> = &'a dyn Fn(&ScanSource) -> Option<usize>;
+
+/// Set the row counts of the scans reachable from `lp_top` to the ones given by `oracle`.
+///
+/// A scan is only updated if the oracle knows the row count of all of its sources, the other
+/// scans keep the row counts that were found while resolving their schema.
+pub(super) fn apply_row_count_oracle(
+    lp_top: Node,
+    lp_arena: &mut Arena<IR>,
+    oracle: ScanRowCountOracle<'_>,
+) {
+    let scans = lp_arena
+        .iter(lp_top)
+        .filter_map(|(node, ir)| matches!(ir, IR::Scan { .. }).then_some(node))
+        .collect::<Vec<_>>();
+
+    for node in scans {
+        let IR::Scan {
+            sources, file_info, ..
+        } = lp_arena.get_mut(node)
+        else {
+            unreachable!()
+        };
+        let rows = sources
+            .iter()
+            .map(|source| oracle(&source.into_owned().ok()?))
+            .sum::<Option<usize>>();
+        if let Some(rows) = rows {
+            file_info.row_estimation = (Some(rows), rows);
+        }
+    }
+}
+
+/// Estimate the number of output rows of every node reachable from `lp_top`.
+///
+/// The estimates are derived from the row counts known at the scans and propagated upwards using
+/// simple selectivity heuristics. Nodes for which no estimate can be made (e.g. because a scan
+/// has no statistics) get `None`, as do all nodes depending on them.
+pub fn estimate_cardinalities(
+    lp_top: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> CardinalityEstimates {
+    let mut estimates = CardinalityEstimates::default();
+    estimate_node(lp_top, lp_arena, expr_arena, &mut estimates);
+    estimates
+}
+
+fn estimate_node(
+    node: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+    estimates: &mut CardinalityEstimates,
+) -> Option<usize> {
+    // Caches can be reached through multiple paths.
+    if let Some(estimate) = estimates.get(&node) {
+        return *estimate;
+    }
+
+    let mut input = |node: Node| estimate_node(node, lp_arena, expr_arena, estimates);
+
+    use IR::*;
+    let estimate = match lp_arena.get(node) {
+        #[cfg(feature = "python")]
+        PythonScan { .. } => None,
+        DataFrameScan { df, .. } => Some(df.height()),
+        Scan {
+            file_info,
+            predicate,
+            unified_scan_args,
+            ..
+        } => {
+            let rows = match file_info.row_estimation {
+                (Some(known), _) => Some(known),
+                (None, usize::MAX) => None,
+                (None, estimated) => Some(estimated),
+            };
+            let rows = match &unified_scan_args.pre_slice {
+                Some(slice) => rows.map(|n| n.min(slice.len())),
+                None => rows,
+            };
+            match predicate {
+                Some(predicate) => {
+                    rows.map(|n| scale(n, predicate_selectivity(predicate.node(), expr_arena)))
+                },
+                None => rows,
+            }
+        },
+        Slice {
+            input: i,
+            offset,
+            len,
+        } => input(*i).map(|n| apply_slice(n, Some((*offset, *len as usize)))),
+        Filter {
+            input: i,
+            predicate,
+        } => {
+            let selectivity = predicate_selectivity(predicate.node(), expr_arena);
+            input(*i).map(|n| scale(n, selectivity))
+        },
+        Select { input: i, expr, .. } => {
+            let rows = input(*i);
+            if !expr.is_empty() && expr.iter().all(|e| is_scalar_ae(e.node(), expr_arena)) {
+                Some(1)
+            } else {
+                rows
+            }
+        },
+        Sort {
+            input: i, slice, ..
+        } => input(*i).map(|n| apply_slice(n, *slice)),
+        GroupBy { input: i, keys, .. } => {
+            if keys.is_empty() {
+                Some(1)
+            } else {
+                input(*i).map(|n| scale(n, DISTINCT_FRACTION))
+            }
+        },
+        Distinct { input: i, options } => input(*i)
+            .map(|n| scale(n, DISTINCT_FRACTION))
+            .map(|n| apply_slice(n, options.slice)),
+        Join {
+            input_left,
+            input_right,
+            options,
+            ..
+        } => {
+            let left = input(*input_left);
+            let right = input(*input_right);
+            estimate_join(left, right, &options.args.how)
+                .map(|n| apply_slice(n, options.args.slice))
+        },
+        Union { inputs, options } => {
+            let rows = inputs.iter().map(|i| input(*i)).sum::<Option<usize>>();
+            rows.map(|n| apply_slice(n, options.slice))
+        },
+        HConcat { inputs, .. } => inputs
+            .iter()
+            .map(|i| input(*i))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|rows| rows.into_iter().max()),
+        #[cfg(feature = "merge_sorted")]
+        MergeSorted {
+            input_left,
+            input_right,
+            ..
+        } => {
+            let left = input(*input_left);
+            let right = input(*input_right);
+            left.zip(right).map(|(l, r)| l.saturating_add(r))
+        },
+        MapFunction { input: i, function } => {
+            let rows = input(*i);
+            match function {
+                FunctionIR::Rechunk | FunctionIR::RowIndex { .. } | FunctionIR::Unnest { .. } => {
+                    rows
+                },
+                FunctionIR::FastCount { .. } => Some(1),
+                _ => None,
+            }
+        },
+        SimpleProjection { input: i, .. }
+        | HStack { input: i, .. }
+        | Cache { input: i, .. }
+        | ExtContext { input: i, .. }
+        | Sink { input: i, .. } => input(*i),
+        SinkMultiple { inputs } => {
+            for i in inputs {
+                input(*i);
+            }
+            None
+        },
+        Invalid => unreachable!(),
+    };
+
+    estimates.insert(node, estimate);
+    estimate
+}
+
+fn estimate_join(left: Option<usize>, right: Option<usize>, how: &JoinType) -> Option<usize> {
+    match how {
+        JoinType::Left => left,
+        JoinType::Right => right,
+        #[cfg(feature = "asof_join")]
+        JoinType::AsOf(_) => left,
+        #[cfg(feature = "semi_anti_join")]
+        JoinType::Semi | JoinType::Anti => left.map(|n| scale(n, DEFAULT_SELECTIVITY)),
+        JoinType::Full => left.zip(right).map(|(l, r)| l.saturating_add(r)),
+        JoinType::Cross => left.zip(right).map(|(l, r)| l.saturating_mul(r)),
+        #[cfg(feature = "iejoin")]
+        JoinType::IEJoin => left
+            .zip(right)
+            .map(|(l, r)| scale(l.saturating_mul(r), RANGE_SELECTIVITY)),
+        JoinType::Inner => left.zip(right).map(|(l, r)| estimate_equi_join(l, r)),
+    }
+}
+
+/// Estimate the output rows of an inner equi-join as `l * r / max(ndv_l, ndv_r)`.
+///
+/// We have no statistics on the number of distinct keys (ndv), so we assume a foreign key join:
+/// the keys of the smaller input are unique and contain the keys of the larger input. Then
+/// `max(ndv_l, ndv_r) = min(l, r)` and every row of the larger input finds a single match.
+fn estimate_equi_join(l: usize, r: usize) -> usize {
+    let max_ndv = l.min(r);
+    if max_ndv == 0 {
+        return 0;
+    }
+    l.saturating_mul(r) / max_ndv
+}
+
+/// Estimate the fraction of rows for which the boolean expression at `node` is true.
+pub(super) fn predicate_selectivity(node: Node, expr_arena: &Arena<AExpr>) -> f64 {
+    match expr_arena.get(node) {
+        AExpr::BinaryExpr { left, op, right } => match op {
+            Operator::And | Operator::LogicalAnd => {
+                predicate_selectivity(*left, expr_arena) * predicate_selectivity(*right, expr_arena)
+            },
+            Operator::Or | Operator::LogicalOr => {
+                let l = predicate_selectivity(*left, expr_arena);
+                let r = predicate_selectivity(*right, expr_arena);
+                l + r - l * r
+            },
+            Operator::Eq | Operator::EqValidity => EQUALITY_SELECTIVITY,
+            Operator::NotEq | Operator::NotEqValidity => 1.0 - EQUALITY_SELECTIVITY,
+            Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq => RANGE_SELECTIVITY,
+            _ => DEFAULT_SELECTIVITY,
+        },
+        AExpr::Function {
+            input,
+            function: IRFunctionExpr::Boolean(function),
+            ..
+        } => match function {
+            IRBooleanFunction::Not => 1.0 - predicate_selectivity(input[0].node(), expr_arena),
+            IRBooleanFunction::IsNull => EQUALITY_SELECTIVITY,
+            IRBooleanFunction::IsNotNull => 1.0 - EQUALITY_SELECTIVITY,
+            #[cfg(feature = "is_between")]
+            IRBooleanFunction::IsBetween { .. } => RANGE_SELECTIVITY * RANGE_SELECTIVITY,
+            #[cfg(feature = "is_in")]
+            IRBooleanFunction::IsIn { .. } => EQUALITY_SELECTIVITY,
+            _ => DEFAULT_SELECTIVITY,
+        },
+        AExpr::Literal(lv) => match lv.bool() {
+            Some(true) => 1.0,
+            Some(false) => 0.0,
+            None => DEFAULT_SELECTIVITY,
+        },
+        _ => DEFAULT_SELECTIVITY,
+    }
+}
+
+fn scale(n: usize, fraction: f64) -> usize {
+    (n as f64 * fraction).ceil() as usize
+}
+
+fn apply_slice(n: usize, slice: Option<(i64, usize)>) -> usize {
+    match slice {
+        None => n,
+        Some((offset, len)) if offset >= 0 => n.saturating_sub(offset as usize).min(len),
+        Some((offset, len)) => n.min(offset.unsigned_abs() as usize).min(len),
+    }
+}
@@ -68,6 +68,47 @@ impl OptimizationRule for SimpleProjectionAndCollapse {
                     Ok(None)
                 }
             },
+            // Remove `with_columns` entries that select an input column under its own name, these
+            // don't change the output.
+            HStack {
+                input,
+                exprs,
+                schema,
+                options,
+            } if !self.processed.contains(&node) => {
+                let input_schema = lp_arena.get(*input).schema(lp_arena);
+                let is_noop = |e: &ExprIR| {
+                    matches!(expr_arena.get(e.node()), AExpr::Column(name) if e.output_name() == name)
+                        && input_schema.contains(e.output_name())
+                        // Another expression with the same output name could overwrite this one.
+                        && exprs
+                            .iter()
+                            .filter(|other| other.output_name() == e.output_name())
+                            .count()
+                            == 1
+                };
+
+                if !exprs.iter().any(is_noop) {
+                    self.processed.insert(node);
+                    return Ok(None);
+                }
+
+                let exprs = exprs
+                    .iter()
+                    .filter(|e| !is_noop(e))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if exprs.is_empty() {
+                    return Ok(Some(lp_arena.get(*input).clone()));
+                }
+
+                Ok(Some(HStack {
+                    input: *input,
+                    exprs,
+                    schema: schema.clone(),
+                    options: *options,
+                }))
+            },
             SimpleProjection { columns, input } if !self.eager => {
                 match lp_arena.get(*input) {
                     // If there are 2 subsequent fast projections, flatten them and only take the last
@@ -114,6 +155,7 @@ impl OptimizationRule for SimpleProjectionAndCollapse {
                     input: prev_input,
                     id,
                     cache_hits,
+                    memory_budget,
                 } = lp_arena.get(*input)
                 {
                     Ok(Some(Cache {
@@ -121,6 +163,7 @@ impl OptimizationRule for SimpleProjectionAndCollapse {
                         id: *id,
                         // ensure the counts are updated
                         cache_hits: cache_hits.saturating_add(*outer_cache_hits),
+                        memory_budget: *memory_budget,
                     }))
                 } else {
                     Ok(None)
@@ -11,7 +11,7 @@ use polars_ops::frame::{IEJoinOptions, InequalityOperator};
 use polars_ops::frame::{JoinCoalesce, JoinType, MaintainOrderJoin};
 use polars_utils::arena::{Arena, Node};
 
-use super::{AExpr, ExprOrigin, IR, JoinOptionsIR, aexpr_to_leaf_names_iter};
+use super::{AExpr, ExprOrigin, IR, JoinOptionsIR, aexpr_to_leaf_names_iter, is_elementwise_rec};
 use crate::dsl::{JoinTypeOptionsIR, Operator};
 use crate::plans::optimizer::join_utils::remove_suffix;
 use crate::plans::{ExprIR, MintermIter};
@@ -29,6 +29,7 @@ pub fn optimize(
     lp_arena: &mut Arena<IR>,
     expr_arena: &mut Arena<AExpr>,
     streaming: bool,
+    pushdown_maintain_errors: bool,
 ) {
     let mut predicates = Vec::with_capacity(4);
 
@@ -39,6 +40,9 @@ pub fn optimize(
     #[cfg(feature = "iejoin")]
     let mut ie_op = Vec::new();
     let mut remaining_predicates = Vec::new();
+    // Predicates that only use the columns of one input, these are applied to that input.
+    let mut left_predicates = Vec::new();
+    let mut right_predicates = Vec::new();
 
     let mut ir_stack = Vec::with_capacity(16);
     ir_stack.push(root);
@@ -85,6 +89,8 @@ pub fn optimize(
                 }
 
                 remaining_predicates.clear();
+                left_predicates.clear();
+                right_predicates.clear();
 
                 #[cfg(feature = "iejoin")]
                 fn to_inequality_operator(op: &Operator) -> Option<InequalityOperator> {
@@ -105,6 +111,32 @@ pub fn optimize(
 
                 for (_, predicate_node) in &predicates {
                     for node in MintermIter::new(*predicate_node, expr_arena) {
+                        // Filtering an input of a cross join filters the same rows as filtering
+                        // its output. Pushing it down could raise errors of rows that wouldn't be
+                        // filtered otherwise, e.g. if the other input is empty.
+                        if !pushdown_maintain_errors && is_elementwise_rec(node, expr_arena) {
+                            let origin = ExprOrigin::get_expr_origin(
+                                node,
+                                expr_arena,
+                                left_schema,
+                                right_schema,
+                                suffix.as_str(),
+                                None,
+                            )
+                            .unwrap();
+                            match origin {
+                                ExprOrigin::Left => {
+                                    left_predicates.push(node);
+                                    continue;
+                                },
+                                ExprOrigin::Right => {
+                                    right_predicates.push(node);
+                                    continue;
+                                },
+                                _ => {},
+                            }
+                        }
+
                         let AExpr::BinaryExpr { left, op, right } = expr_arena.get(node) else {
                             remaining_predicates.push(node);
                             continue;
@@ -234,6 +266,20 @@ pub fn optimize(
                 }
 
                 if can_simplify_join {
+                    for node in right_predicates.iter_mut() {
+                        let mut e = ExprIR::from_node(*node, expr_arena);
+                        remove_suffix(&mut e, expr_arena, right_schema, suffix.as_str());
+                        *node = e.node();
+                    }
+                    let options = options.as_ref().clone();
+                    let schema = schema.clone();
+                    let (input_left, input_right) = (*input_left, *input_right);
+
+                    let input_left =
+                        filter_input(input_left, &left_predicates, lp_arena, expr_arena);
+                    let input_right =
+                        filter_input(input_right, &right_predicates, lp_arena, expr_arena);
+
                     let new_join = insert_fitting_join(
                         eq_left_on,
                         eq_right_on,
@@ -246,10 +292,10 @@ pub fn optimize(
                         &remaining_predicates,
                         lp_arena,
                         expr_arena,
-                        options.as_ref().clone(),
-                        *input_left,
-                        *input_right,
-                        schema.clone(),
+                        options,
+                        input_left,
+                        input_right,
+                        schema,
                         streaming,
                     );
 
@@ -265,6 +311,25 @@ pub fn optimize(
     }
 }
 
+fn filter_input(
+    input: Node,
+    predicates: &[Node],
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &mut Arena<AExpr>,
+) -> Node {
+    let Some(predicate) = predicates
+        .iter()
+        .copied()
+        .reduce(|left, right| and_expr(left, right, expr_arena))
+    else {
+        return input;
+    };
+    lp_arena.add(IR::Filter {
+        input,
+        predicate: ExprIR::from_node(predicate, expr_arena),
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn insert_fitting_join(
     eq_left_on: Vec<ExprIR>,
@@ -389,3 +454,232 @@ fn insert_fitting_join(
         join_node
     }
 }
+
+/// Collapse the reconciliation idiom
+///
+/// `concat([a.join(b, how='anti'), b.join(a, how='anti'), a.join(b, how='inner')], how='diagonal')`
+///
+/// into `a.join(b, how='full', coalesce=True)`. The three parts are exactly the left-only,
+/// right-only and matching rows of the full join, so this builds a single hash table instead of
+/// three.
+///
+/// This only applies if the union doesn't maintain order, both sides of the joins are the same
+/// plans and the diagonal concatenation lines up with the columns of the full join.
+///
+/// Returns whether the plan was changed.
+#[cfg(all(feature = "semi_anti_join", feature = "cse"))]
+pub fn collapse_reconcile_unions(
+    root: Node,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    let mut changed = false;
+    let mut ir_stack = Vec::with_capacity(16);
+    ir_stack.push(root);
+
+    while let Some(current) = ir_stack.pop() {
+        if let Some(ir) = reconcile_union_to_full_join(current, lp_arena, expr_arena) {
+            lp_arena.replace(current, ir);
+            changed = true;
+        }
+        lp_arena.get(current).copy_inputs(&mut ir_stack);
+    }
+
+    changed
+}
+
+#[cfg(all(feature = "semi_anti_join", feature = "cse"))]
+fn reconcile_union_to_full_join(
+    node: Node,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<IR> {
+    use crate::prelude::visitor::IRNode;
+
+    let IR::Union { inputs, options } = lp_arena.get(node) else {
+        return None;
+    };
+    if inputs.len() != 3 || options.maintain_order || options.slice.is_some() {
+        return None;
+    }
+
+    let joins = inputs
+        .iter()
+        .map(|input| unwrap_diagonal_projection(*input, lp_arena, expr_arena))
+        .collect::<Option<Vec<_>>>()?;
+    let inner = joins.iter().copied().find(|n| {
+        matches!(lp_arena.get(*n), IR::Join { options, .. } if options.args.how == JoinType::Inner)
+    })?;
+    let IR::Join {
+        input_left,
+        input_right,
+        schema,
+        left_on,
+        right_on,
+        options: join_options,
+    } = lp_arena.get(inner)
+    else {
+        unreachable!()
+    };
+    if !join_options.args.should_coalesce() {
+        return None;
+    }
+    let keys = equi_join_keys(lp_arena.get(inner), expr_arena)?;
+
+    let same_plan = |l: Node, r: Node| {
+        IRNode::new(l).hashable_and_cmp(lp_arena, expr_arena)
+            == IRNode::new(r).hashable_and_cmp(lp_arena, expr_arena)
+    };
+    // One anti join must give the left-only rows and the other the right-only rows.
+    let mut has_left_only = false;
+    let mut has_right_only = false;
+    for &join in joins.iter().filter(|n| **n != inner) {
+        let ir = lp_arena.get(join);
+        let IR::Join {
+            input_left: anti_left,
+            input_right: anti_right,
+            options,
+            ..
+        } = ir
+        else {
+            return None;
+        };
+        if options.args.how != JoinType::Anti
+            || options.args.nulls_equal != join_options.args.nulls_equal
+            || equi_join_keys(ir, expr_arena).as_ref() != Some(&keys)
+        {
+            return None;
+        }
+
+        if !has_left_only
+            && same_plan(*anti_left, *input_left)
+            && same_plan(*anti_right, *input_right)
+        {
+            has_left_only = true;
+        } else if !has_right_only
+            && same_plan(*anti_left, *input_right)
+            && same_plan(*anti_right, *input_left)
+        {
+            has_right_only = true;
+        } else {
+            return None;
+        }
+    }
+    if !(has_left_only && has_right_only) {
+        return None;
+    }
+
+    // The right-only rows fill the right columns of the union by name, but the full join
+    // suffixes the right columns that are also on the left. So no suffix may apply, that is the
+    // non-key columns of both sides have to be disjoint.
+    let left_schema = lp_arena.get(*input_left).schema(lp_arena);
+    let right_schema = lp_arena.get(*input_right).schema(lp_arena);
+    if right_schema
+        .iter_names()
+        .any(|name| !keys.contains(name) && left_schema.contains(name))
+    {
+        return None;
+    }
+
+    // The coalesced full join has the same columns as the inner join.
+    let union_schema = lp_arena.get(node).schema(lp_arena).into_owned();
+    if union_schema.len() != schema.len()
+        || !union_schema
+            .iter()
+            .all(|(name, dtype)| schema.get(name) == Some(dtype))
+    {
+        return None;
+    }
+    let same_order = union_schema.iter_names().eq(schema.iter_names());
+
+    let mut options = (**join_options).clone();
+    options.args.how = JoinType::Full;
+    options.args.coalesce = JoinCoalesce::CoalesceColumns;
+    options.args.maintain_order = MaintainOrderJoin::None;
+    let join = IR::Join {
+        input_left: *input_left,
+        input_right: *input_right,
+        schema: schema.clone(),
+        left_on: left_on.clone(),
+        right_on: right_on.clone(),
+        options: Arc::new(options),
+    };
+
+    if same_order {
+        Some(join)
+    } else {
+        Some(IR::SimpleProjection {
+            input: lp_arena.add(join),
+            columns: union_schema,
+        })
+    }
+}
+
+/// The key names of a join on plain columns without slice, validation or extra join options. The
+/// keys have to have the same name on both sides, so that the right keys of the right-only rows
+/// end up in the coalesced key columns.
+#[cfg(all(feature = "semi_anti_join", feature = "cse"))]
+fn equi_join_keys(
+    ir: &IR,
+    expr_arena: &Arena<AExpr>,
+) -> Option<Vec<polars_utils::pl_str::PlSmallStr>> {
+    let IR::Join {
+        left_on,
+        right_on,
+        options,
+        ..
+    } = ir
+    else {
+        return None;
+    };
+    if options.options.is_some()
+        || options.args.slice.is_some()
+        || options.args.validation.needs_checks()
+    {
+        return None;
+    }
+
+    let column_name = |e: &ExprIR| match expr_arena.get(e.node()) {
+        AExpr::Column(name) if name == e.output_name() => Some(name.clone()),
+        _ => None,
+    };
+    let keys = left_on
+        .iter()
+        .map(column_name)
+        .collect::<Option<Vec<_>>>()?;
+    let right_keys = right_on
+        .iter()
+        .map(column_name)
+        .collect::<Option<Vec<_>>>()?;
+    (keys == right_keys).then_some(keys)
+}
+
+/// Look through the projections that a diagonal concatenation adds to line up its inputs. These
+/// may only reorder the columns and add null columns.
+#[cfg(all(feature = "semi_anti_join", feature = "cse"))]
+fn unwrap_diagonal_projection(
+    node: Node,
+    lp_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<Node> {
+    let IR::SimpleProjection { input, columns } = lp_arena.get(node) else {
+        return Some(node);
+    };
+    let (input, n_added) = match lp_arena.get(*input) {
+        IR::HStack { input, exprs, .. } => {
+            let all_null = exprs
+                .iter()
+                .all(|e| matches!(expr_arena.get(e.node()), AExpr::Literal(lv) if lv.is_null()));
+            if !all_null {
+                return None;
+            }
+            (*input, exprs.len())
+        },
+        _ => (*input, 0),
+    };
+
+    let input_schema = lp_arena.get(input).schema(lp_arena);
+    (columns.len() == input_schema.len() + n_added
+        && input_schema.iter_names().all(|name| columns.contains(name)))
+    .then_some(input)
+}
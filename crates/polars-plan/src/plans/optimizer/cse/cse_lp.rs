@@ -1,6 +1,7 @@
 use std::hash::BuildHasher;
 
 use hashbrown::hash_map::RawEntryMut;
+use polars_core::config::get_cache_memory_budget;
 use polars_utils::unique_id::UniqueId;
 
 use super::*;
@@ -264,6 +265,8 @@ struct CommonSubPlanRewriter<'a> {
     cache_id: IdentifierMap<UniqueId>,
     // Maps cache_id : (cache_count and cache_nodes)
     cache_id_to_caches: CacheId2Caches,
+    // The memory budget of the inserted caches.
+    memory_budget: Option<usize>,
 }
 
 impl<'a> CommonSubPlanRewriter<'a> {
@@ -276,6 +279,7 @@ impl<'a> CommonSubPlanRewriter<'a> {
             rewritten: false,
             cache_id: Default::default(),
             cache_id_to_caches: Default::default(),
+            memory_budget: get_cache_memory_budget(),
         }
     }
 }
@@ -352,6 +356,7 @@ impl RewritingVisitor for CommonSubPlanRewriter<'_> {
             input: node.node(),
             id: cache_id,
             cache_hits: cache_count - 1,
+            memory_budget: self.memory_budget,
         };
         node.assign(cache_node, &mut arena.0);
         let (_count, nodes) = self
@@ -0,0 +1,170 @@
+//! Merge scans of the same source that only differ in their projection.
+//!
+//! Common subplan elimination only caches subplans that are equal, so two scans of one file that
+//! project different columns open the file twice. Such scans are rewritten to scan the union of
+//! their columns, with a projection on top that selects the columns of the original scan. The
+//! scans are then equal and common subplan elimination inserts a cache for them.
+
+use super::*;
+use crate::plans::visitor::opt_expr_ir_eq;
+
+/// Whether the scans `l` and `r` read the same data, apart from the projected columns.
+fn same_scan_source(l: &IR, r: &IR, expr_arena: &Arena<AExpr>) -> bool {
+    let (
+        IR::Scan {
+            sources: sources_l,
+            predicate: predicate_l,
+            scan_type: scan_type_l,
+            unified_scan_args: args_l,
+            ..
+        },
+        IR::Scan {
+            sources: sources_r,
+            predicate: predicate_r,
+            scan_type: scan_type_r,
+            unified_scan_args: args_r,
+            ..
+        },
+    ) = (l, r)
+    else {
+        return false;
+    };
+    let without_projection = |args: &UnifiedScanArgs| UnifiedScanArgs {
+        projection: None,
+        ..args.clone()
+    };
+    sources_l == sources_r
+        && scan_type_l == scan_type_r
+        && opt_expr_ir_eq(predicate_l, predicate_r, expr_arena)
+        && without_projection(args_l) == without_projection(args_r)
+}
+
+/// Rewrite the scans below `root` that read the same source with a different projection to
+/// scan the union of their columns. Returns whether any scan was rewritten.
+pub(crate) fn merge_scan_projections(
+    root: Node,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    // Groups of scans of the same source.
+    let mut groups: Vec<Vec<Node>> = vec![];
+    for (node, ir) in lp_arena.iter(root) {
+        if !matches!(ir, IR::Scan { .. }) {
+            continue;
+        }
+        match groups
+            .iter_mut()
+            .find(|group| same_scan_source(lp_arena.get(group[0]), ir, expr_arena))
+        {
+            // A node can be reached more than once if its plan is shared.
+            Some(group) if group.contains(&node) => {},
+            Some(group) => group.push(node),
+            None => groups.push(vec![node]),
+        }
+    }
+
+    let mut changed = false;
+    for group in groups {
+        if group.len() < 2 {
+            continue;
+        }
+        let output_schemas: Vec<Option<SchemaRef>> = group
+            .iter()
+            .map(|node| match lp_arena.get(*node) {
+                IR::Scan { output_schema, .. } => output_schema.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        if output_schemas.iter().all(|s| s == &output_schemas[0]) {
+            // Already equal, nothing to merge.
+            continue;
+        }
+
+        let IR::Scan {
+            file_info,
+            unified_scan_args,
+            ..
+        } = lp_arena.get(group[0])
+        else {
+            unreachable!()
+        };
+        let file_schema = file_info.schema.clone();
+        // The row index and file path columns are added by the scan, they are part of the output
+        // schema but not of the projection.
+        let added_by_scan = [
+            unified_scan_args
+                .row_index
+                .as_ref()
+                .map(|ri| ri.name.clone()),
+            unified_scan_args.include_file_paths.clone(),
+        ];
+
+        // The union of the columns in the order of the source, a scan without projection reads
+        // all of them.
+        let output_schema = if output_schemas.iter().any(Option::is_none) {
+            None
+        } else {
+            let output_schema: Schema = file_schema
+                .iter()
+                .filter(|(name, _)| {
+                    output_schemas
+                        .iter()
+                        .flatten()
+                        .any(|schema| schema.contains(name))
+                })
+                .map(|(name, dtype)| (name.clone(), dtype.clone()))
+                .collect();
+            Some(Arc::new(output_schema))
+        };
+        let projection = output_schema.as_ref().map(|schema| {
+            schema
+                .iter_names()
+                .filter(|name| !added_by_scan.iter().flatten().any(|n| n == *name))
+                .cloned()
+                .collect::<Arc<[_]>>()
+        });
+
+        for node in group {
+            let ir = lp_arena.get(node);
+            let schema = ir.schema(lp_arena).into_owned();
+            let IR::Scan {
+                sources,
+                file_info,
+                hive_parts,
+                predicate,
+                scan_type,
+                unified_scan_args,
+                ..
+            } = ir
+            else {
+                unreachable!()
+            };
+            let mut unified_scan_args = unified_scan_args.clone();
+            unified_scan_args.projection = projection.clone();
+            let scan = IR::Scan {
+                sources: sources.clone(),
+                file_info: file_info.clone(),
+                hive_parts: hive_parts.clone(),
+                predicate: predicate.clone(),
+                scan_type: scan_type.clone(),
+                output_schema: output_schema.clone(),
+                unified_scan_args,
+            };
+            if schema == *output_schema.as_ref().unwrap_or(&file_schema) {
+                lp_arena.replace(node, scan);
+            } else {
+                // Select the columns of the original scan from the merged scan.
+                let input = lp_arena.add(scan);
+                lp_arena.replace(
+                    node,
+                    IR::SimpleProjection {
+                        input,
+                        columns: schema,
+                    },
+                );
+            }
+        }
+        changed = true;
+    }
+    changed
+}
@@ -1,9 +1,11 @@
 mod cse_expr;
 mod cse_lp;
+mod cse_scan;
 
 pub(super) use cse_expr::CommonSubExprOptimizer;
 pub use cse_expr::NaiveExprMerger;
 pub(super) use cse_lp::{elim_cmn_subplans, prune_unused_caches};
+pub(super) use cse_scan::merge_scan_projections;
 
 use super::*;
 
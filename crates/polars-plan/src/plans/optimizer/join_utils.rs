@@ -1,5 +1,6 @@
 use polars_core::error::{PolarsResult, polars_bail};
 use polars_core::schema::*;
+use polars_ops::frame::_join_strip_suffix;
 use polars_utils::arena::{Arena, Node};
 use polars_utils::pl_str::PlSmallStr;
 
@@ -74,9 +75,7 @@ impl ExprOrigin {
             {
                 ExprOrigin::Left
             } else if right_schema.contains(column_name)
-                || column_name
-                    .strip_suffix(suffix)
-                    .is_some_and(|x| right_schema.contains(x))
+                || _join_strip_suffix(column_name, suffix).is_some_and(|x| right_schema.contains(x))
             {
                 ExprOrigin::Right
             } else {
@@ -118,9 +117,10 @@ pub(super) fn remove_suffix<'a>(
     expr.set_node(node);
 
     if let OutputName::ColumnLhs(colname) = expr.output_name_inner() {
-        if colname.ends_with(suffix) && !schema.contains(colname.as_str()) {
-            let name = PlSmallStr::from(&colname[..colname.len() - suffix.len()]);
-            expr.set_columnlhs(name);
+        if !schema.contains(colname.as_str()) {
+            if let Some(name) = _join_strip_suffix(colname, suffix) {
+                expr.set_columnlhs(PlSmallStr::from(name));
+            }
         }
     }
 
@@ -142,7 +142,9 @@ pub(super) fn remove_suffix<'a>(
                 return Ok(RewriteRecursion::NoMutateAndContinue);
             };
 
-            if !colname.ends_with(self.suffix) || self.schema.contains(colname.as_str()) {
+            if _join_strip_suffix(colname, self.suffix).is_none()
+                || self.schema.contains(colname.as_str())
+            {
                 return Ok(RewriteRecursion::NoMutateAndContinue);
             }
 
@@ -158,15 +160,11 @@ pub(super) fn remove_suffix<'a>(
                 unreachable!();
             };
 
-            // Safety: Checked in pre_visit()
-            Ok(AexprNode::new(arena.add(AExpr::Column(PlSmallStr::from(
-                &colname[..colname.len() - self.suffix.len()],
-            )))))
+            // Checked in pre_visit()
+            let name = _join_strip_suffix(colname, self.suffix).unwrap();
+            Ok(AexprNode::new(
+                arena.add(AExpr::Column(PlSmallStr::from(name))),
+            ))
         }
     }
 }
-
-pub(super) fn split_suffix<'a>(name: &'a str, suffix: &str) -> &'a str {
-    let (original, _) = name.split_at(name.len() - suffix.len());
-    original
-}
@@ -3,6 +3,7 @@ use polars_core::prelude::*;
 use crate::prelude::*;
 
 mod cache_states;
+mod cardinality;
 mod delay_rechunk;
 
 mod cluster_with_columns;
@@ -22,12 +23,18 @@ mod expand_datasets;
 pub use expand_datasets::ExpandedPythonScan;
 mod predicate_pushdown;
 mod projection_pushdown;
+mod prune_empty;
+mod push_cast_to_scan;
+mod push_distinct;
+mod reorder_predicates;
+mod report;
 mod set_order;
 mod simplify_expr;
 mod slice_pushdown_expr;
 mod slice_pushdown_lp;
 mod stack_opt;
 
+pub use cardinality::{CardinalityEstimates, ScanRowCountOracle, estimate_cardinalities};
 use collapse_and_project::SimpleProjectionAndCollapse;
 #[cfg(feature = "cse")]
 pub use cse::NaiveExprMerger;
@@ -37,6 +44,12 @@ use polars_core::config::verbose;
 use polars_io::predicates::PhysicalIoExpr;
 pub use predicate_pushdown::PredicatePushDown;
 pub use projection_pushdown::ProjectionPushDown;
+use prune_empty::PruneEmptyPlans;
+use push_cast_to_scan::PushCastToScan;
+use push_distinct::PushDistinctBelowWithColumns;
+use reorder_predicates::ReorderPredicates;
+use report::PlanSnapshot;
+pub use report::{OptimizationReport, PassReport, ScanPredicateLineage, annotation_note};
 pub use simplify_expr::{SimplifyBooleanRule, SimplifyExprRule};
 use slice_pushdown_lp::SlicePushDown;
 pub use stack_opt::{OptimizationRule, OptimizeExprContext, StackOptimizer};
@@ -70,6 +83,7 @@ pub(crate) fn pushdown_maintain_errors() -> bool {
     std::env::var("POLARS_PUSHDOWN_OPT_MAINTAIN_ERRORS").as_deref() == Ok("1")
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn optimize(
     logical_plan: DslPlan,
     mut opt_flags: OptFlags,
@@ -77,6 +91,8 @@ pub fn optimize(
     expr_arena: &mut Arena<AExpr>,
     scratch: &mut Vec<Node>,
     expr_eval: ExprEval<'_>,
+    mut report: Option<&mut OptimizationReport>,
+    row_counts: Option<ScanRowCountOracle<'_>>,
 ) -> PolarsResult<Node> {
     #[allow(dead_code)]
     let verbose = verbose();
@@ -107,9 +123,11 @@ pub fn optimize(
     // Note: This can be in opt_flags in the future if needed.
     let pushdown_maintain_errors = pushdown_maintain_errors();
 
-    // During debug we check if the optimizations have not modified the final schema.
-    #[cfg(debug_assertions)]
-    let prev_schema = lp_arena.get(lp_top).schema(lp_arena).into_owned();
+    // During debug, or if requested, we check if the optimizations have not modified the final
+    // schema.
+    let verify_schema = opt_flags.contains(OptFlags::VERIFY_SCHEMA);
+    let prev_schema = (cfg!(debug_assertions) || verify_schema)
+        .then(|| lp_arena.get(lp_top).schema(lp_arena).into_owned());
 
     let mut _opt_members = &mut None;
 
@@ -125,6 +143,35 @@ pub fn optimize(
         };
     }
 
+    // Only snapshot the plan around the passes if a report is requested.
+    macro_rules! snapshot {
+        () => {
+            report
+                .is_some()
+                .then(|| PlanSnapshot::new(lp_top, lp_arena, expr_arena))
+        };
+    }
+
+    macro_rules! record_pass {
+        ($name:literal, $before:expr) => {
+            if let (Some(report), Some(before)) = (report.as_deref_mut(), $before) {
+                let after = PlanSnapshot::new(lp_top, lp_arena, expr_arena);
+                report.record_pass($name, before, after);
+            }
+        };
+    }
+
+    // Make sure it is before common subplan elimination and the pushdowns, those change the
+    // duplicated join inputs.
+    #[cfg(all(feature = "semi_anti_join", feature = "cse"))]
+    if opt_flags.collapse_joins()
+        && get_or_init_members!().has_joins_or_unions
+        && collapse_joins::collapse_reconcile_unions(lp_top, lp_arena, expr_arena)
+    {
+        // The members may have changed.
+        *_opt_members = None;
+    }
+
     // Run before slice pushdown
     if opt_flags.contains(OptFlags::CHECK_ORDER_OBSERVE) {
         let members = get_or_init_members!();
@@ -138,13 +185,16 @@ pub fn optimize(
         rules.push(Box::new(fused::FusedArithmetic {}));
     }
 
+    let before = snapshot!();
     #[cfg(feature = "cse")]
     let _cse_plan_changed = if comm_subplan_elim {
         let members = get_or_init_members!();
-        if (members.has_sink_multiple || members.has_joins_or_unions)
-            && members.has_duplicate_scans()
-            && !members.has_cache
-        {
+        let may_have_duplicates =
+            (members.has_sink_multiple || members.has_joins_or_unions) && !members.has_cache;
+        // Scans that only differ in their projection are made equal first, so they get cached.
+        let merged_scans =
+            may_have_duplicates && cse::merge_scan_projections(lp_top, lp_arena, expr_arena);
+        if may_have_duplicates && (merged_scans || members.has_duplicate_scans()) {
             if verbose {
                 eprintln!("found multiple sources; run comm_subplan_elim")
             }
@@ -164,9 +214,11 @@ pub fn optimize(
     };
     #[cfg(not(feature = "cse"))]
     let _cse_plan_changed = false;
+    record_pass!("comm_subplan_elim", before);
 
     // Should be run before predicate pushdown.
     if opt_flags.projection_pushdown() {
+        let before = snapshot!();
         let mut projection_pushdown_opt = ProjectionPushDown::new();
         let alp = lp_arena.take(lp_top);
         let alp = projection_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
@@ -176,22 +228,38 @@ pub fn optimize(
             let mut count_star_opt = CountStar::new();
             count_star_opt.optimize_plan(lp_arena, expr_arena, lp_top)?;
         }
+        record_pass!("projection_pushdown", before);
     }
 
     if opt_flags.predicate_pushdown() {
+        let before = snapshot!();
         let mut predicate_pushdown_opt = PredicatePushDown::new(
             expr_eval,
             pushdown_maintain_errors,
             opt_flags.new_streaming(),
-        );
+        )
+        .record_lineage(report.is_some() && opt_flags.predicate_lineage());
         let alp = lp_arena.take(lp_top);
         let alp = predicate_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
         lp_arena.replace(lp_top, alp);
+        record_pass!("predicate_pushdown", before);
+
+        if let Some(report) = report.as_deref_mut() {
+            report.predicate_lineage = predicate_pushdown_opt.take_lineage(lp_top, lp_arena);
+        }
     }
 
     // Make sure it is after predicate pushdown
     if opt_flags.collapse_joins() && get_or_init_members!().has_filter_with_join_input {
-        collapse_joins::optimize(lp_top, lp_arena, expr_arena, opt_flags.new_streaming());
+        let before = snapshot!();
+        collapse_joins::optimize(
+            lp_top,
+            lp_arena,
+            expr_arena,
+            opt_flags.new_streaming(),
+            pushdown_maintain_errors,
+        );
+        record_pass!("collapse_joins", before);
     }
 
     // Make sure its before slice pushdown.
@@ -206,6 +274,7 @@ pub fn optimize(
     }
 
     if opt_flags.slice_pushdown() {
+        let before = snapshot!();
         let mut slice_pushdown_opt = SlicePushDown::new(
             // We don't maintain errors on slice as the behavior is much more predictable that way.
             //
@@ -218,6 +287,7 @@ pub fn optimize(
         let alp = slice_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
 
         lp_arena.replace(lp_top, alp);
+        record_pass!("slice_pushdown", before);
 
         // Expressions use the stack optimizer.
         rules.push(Box::new(slice_pushdown_opt));
@@ -227,19 +297,49 @@ pub fn optimize(
     // is completed.
     if opt_flags.simplify_expr() {
         rules.push(Box::new(SimplifyBooleanRule {}));
+
+        // Reordering the terms of a predicate changes which error is raised first, and pruning an
+        // empty subplan skips the errors of its inputs.
+        if !pushdown_maintain_errors {
+            rules.push(Box::new(ReorderPredicates {}));
+            // Not when only the expressions are optimized, that is done on a projection of an
+            // empty frame which has to be kept.
+            if opt_flags.predicate_pushdown() {
+                rules.push(Box::new(PruneEmptyPlans {}));
+            }
+        }
     }
 
     if !opt_flags.eager() {
         rules.push(Box::new(FlattenUnionRule {}));
+
+        // Rows dropped by the `unique` are no longer evaluated, which can hide errors.
+        if !pushdown_maintain_errors {
+            rules.push(Box::new(PushDistinctBelowWithColumns {}));
+        }
+    }
+
+    // Casts are pushed into the projection of the scan.
+    if opt_flags.projection_pushdown() {
+        rules.push(Box::new(PushCastToScan {}));
     }
 
     // Note: ExpandDatasets must run after slice and predicate pushdown.
     rules.push(Box::new(expand_datasets::ExpandDatasets {}) as Box<dyn OptimizationRule>);
 
-    lp_top = opt.optimize_loop(&mut rules, expr_arena, lp_arena, lp_top)?;
+    lp_top = opt.optimize_loop(
+        &mut rules,
+        expr_arena,
+        lp_arena,
+        lp_top,
+        opt_flags.contains(OptFlags::VERIFY_FIXED_POINT),
+        report.as_deref_mut(),
+    )?;
 
     if opt_flags.cluster_with_columns() {
-        cluster_with_columns::optimize(lp_top, lp_arena, expr_arena)
+        let before = snapshot!();
+        cluster_with_columns::optimize(lp_top, lp_arena, expr_arena);
+        record_pass!("cluster_with_columns", before);
     }
 
     if _cse_plan_changed
@@ -263,6 +363,7 @@ pub fn optimize(
     // This one should run (nearly) last as this modifies the projections
     #[cfg(feature = "cse")]
     if comm_subexpr_elim && !get_or_init_members!().has_ext_context {
+        let before = snapshot!();
         let mut optimizer = CommonSubExprOptimizer::new();
         let alp_node = IRNode::new_mutate(lp_top);
 
@@ -270,21 +371,31 @@ pub fn optimize(
             let rewritten = alp_node.rewrite(&mut optimizer, arena)?;
             Ok(rewritten.node())
         })?;
+        record_pass!("comm_subexpr_elim", before);
     }
 
-    // During debug we check if the optimizations have not modified the final schema.
-    #[cfg(debug_assertions)]
-    {
+    // Run last, so that it also sees the scans that were added by the passes.
+    if let Some(row_counts) = row_counts {
+        cardinality::apply_row_count_oracle(lp_top, lp_arena, row_counts);
+    }
+
+    if let Some(prev_schema) = prev_schema {
         // only check by names because we may supercast types.
-        assert_eq!(
-            prev_schema.iter_names().collect::<Vec<_>>(),
-            lp_arena
-                .get(lp_top)
-                .schema(lp_arena)
-                .iter_names()
-                .collect::<Vec<_>>()
-        );
-    };
+        let prev_names = prev_schema.iter_names().collect::<Vec<_>>();
+        let schema = lp_arena.get(lp_top).schema(lp_arena);
+        let names = schema.iter_names().collect::<Vec<_>>();
+
+        if verify_schema {
+            polars_ensure!(
+                prev_names == names,
+                ComputeError: "optimizer changed the output columns from {:?} to {:?}; \
+                this is a bug, please open an issue",
+                prev_names, names
+            );
+        } else {
+            assert_eq!(prev_names, names);
+        }
+    }
 
     Ok(lp_top)
 }
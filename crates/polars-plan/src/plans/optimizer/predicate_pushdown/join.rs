@@ -1,5 +1,3 @@
-use polars_utils::format_pl_smallstr;
-
 use super::*;
 use crate::plans::optimizer::join_utils::remove_suffix;
 
@@ -28,6 +26,7 @@ pub(super) fn process_join(
         &right_on,
         &mut acc_predicates,
         expr_arena,
+        opt.lineage.as_mut(),
     );
 
     if match &options.args.how {
@@ -213,7 +212,7 @@ pub(super) fn process_join(
             let rhs_output_key: PlSmallStr = if schema_left.contains(rhs_input_key.as_str())
                 && !coalesced_to_right.contains(rhs_input_key.as_str())
             {
-                format_pl_smallstr!("{}{}", rhs_input_key, options.args.suffix())
+                _join_suffix_name(rhs_input_key.as_str(), options.args.suffix())
             } else {
                 rhs_input_key.clone()
             };
@@ -234,6 +233,16 @@ pub(super) fn process_join(
         let mut push_right = true;
 
         for col_name in aexpr_to_leaf_names_iter(predicate.node(), expr_arena) {
+            // The asof index column is added by the join, a predicate on it stays on top.
+            #[cfg(feature = "asof_join")]
+            if let JoinType::AsOf(asof_options) = &options.args.how
+                && asof_options.index_column.as_ref() == Some(&col_name)
+            {
+                push_left = false;
+                push_right = false;
+                continue;
+            }
+
             let origin: ExprOrigin = ExprOrigin::get_column_origin(
                 col_name.as_str(),
                 &schema_left,
@@ -318,9 +327,14 @@ pub(super) fn process_join(
             local_predicates.push(predicate.clone())
         }
 
+        let before = predicate.node();
+
         if push_left {
             let mut predicate = predicate.clone();
             map_column_references(&mut predicate, expr_arena, &output_key_to_left_input_map);
+            if let Some(lineage) = &mut opt.lineage {
+                lineage.record_rewrite(before, predicate.node(), expr_arena);
+            }
             pushdown_left.insert(predicate_key.clone(), predicate);
         }
 
@@ -333,6 +347,9 @@ pub(super) fn process_join(
                 &schema_right,
                 options.args.suffix(),
             );
+            if let Some(lineage) = &mut opt.lineage {
+                lineage.record_rewrite(before, predicate.node(), expr_arena);
+            }
             pushdown_right.insert(predicate_key, predicate);
         }
     }
@@ -383,6 +400,7 @@ fn try_rewrite_join_type(
     right_on: &[ExprIR],
     acc_predicates: &mut PlHashMap<PlSmallStr, ExprIR>,
     expr_arena: &mut Arena<AExpr>,
+    mut lineage: Option<&mut PredicateLineage>,
 ) -> Option<(Vec<ExprIR>, SchemaRef)> {
     if acc_predicates.is_empty()
         || !matches!(
@@ -555,7 +573,7 @@ fn try_rewrite_join_type(
                         .contains(rhs_input_key.as_str())
                         && !coalesced_to_right.contains(rhs_input_key.as_str())
                     {
-                        format_pl_smallstr!("{}{}", rhs_input_key, options.args.suffix())
+                        _join_suffix_name(rhs_input_key.as_str(), options.args.suffix())
                     } else {
                         rhs_input_key.clone()
                     };
@@ -595,7 +613,7 @@ fn try_rewrite_join_type(
                         expr.clone()
                     } else if schema_left.contains(rhs_input_col) {
                         let new_join_output_name =
-                            format_pl_smallstr!("{}{}", rhs_input_col, options.args.suffix());
+                            _join_suffix_name(rhs_input_col.as_str(), options.args.suffix());
 
                         let node = expr_arena.add(AExpr::Column(new_join_output_name.clone()));
                         let mut expr = ExprIR::from_node(node, expr_arena);
@@ -669,7 +687,7 @@ fn try_rewrite_join_type(
                         .contains(rhs_input_key.as_str())
                         && !coalesced_to_right.contains(rhs_input_key.as_str())
                     {
-                        format_pl_smallstr!("{}{}", rhs_input_key, options.args.suffix())
+                        _join_suffix_name(rhs_input_key.as_str(), options.args.suffix())
                     } else {
                         rhs_input_key.clone()
                     };
@@ -719,7 +737,7 @@ fn try_rewrite_join_type(
 
                     let new_join_output_name = if schema_left.contains(rhs_input_col) {
                         let suffixed =
-                            format_pl_smallstr!("{}{}", rhs_input_col, options.args.suffix());
+                            _join_suffix_name(rhs_input_col.as_str(), options.args.suffix());
 
                         if coalesced_to_right.contains(rhs_input_col) {
                             original_output_name = Some(suffixed);
@@ -769,7 +787,11 @@ fn try_rewrite_join_type(
         assert!(project_to_original.is_some());
 
         for (_, predicate_expr) in acc_predicates.iter_mut() {
+            let before = predicate_expr.node();
             map_column_references(predicate_expr, expr_arena, &original_to_new_names_map);
+            if let Some(lineage) = lineage.as_deref_mut() {
+                lineage.record_rewrite(before, predicate_expr.node(), expr_arena);
+            }
         }
     }
 
@@ -0,0 +1,114 @@
+use polars_core::prelude::*;
+use polars_utils::unitvec;
+use recursive::recursive;
+
+use super::*;
+
+/// Keeps track of the filter predicates that end up in the scans.
+///
+/// The accumulated predicates are combined with `&` and have their columns renamed on the way
+/// down, so every conjunct of an accumulated predicate is mapped back to the conjunct of the filter
+/// it was written in.
+#[derive(Default)]
+pub(super) struct PredicateLineage {
+    /// Maps a rewritten conjunct to the conjunct of the filter. Conjuncts that aren't in here were
+    /// never rewritten.
+    origins: PlHashMap<Node, Node>,
+    /// The copied filter predicates, by the node of the predicate of the scan they were pushed
+    /// into.
+    pushed: PlHashMap<Node, Vec<ExprIR>>,
+}
+
+impl PredicateLineage {
+    fn origin(&self, node: Node) -> Node {
+        self.origins.get(&node).copied().unwrap_or(node)
+    }
+
+    /// Record that a predicate was rewritten without changing its shape, e.g. by renaming columns.
+    pub(super) fn record_rewrite(&mut self, before: Node, after: Node, expr_arena: &Arena<AExpr>) {
+        if before == after {
+            return;
+        }
+        let before = conjuncts(before, expr_arena);
+        let after = conjuncts(after, expr_arena);
+        debug_assert_eq!(before.len(), after.len());
+
+        for (before, after) in before.into_iter().zip(after) {
+            let origin = self.origin(before);
+            self.origins.insert(after, origin);
+        }
+    }
+
+    /// Record the predicates that were pushed into a scan.
+    pub(super) fn record_scan(
+        &mut self,
+        scan_predicate: Node,
+        pushed: &[Node],
+        expr_arena: &mut Arena<AExpr>,
+    ) {
+        let conjuncts = pushed
+            .iter()
+            .flat_map(|node| conjuncts(*node, expr_arena))
+            .collect::<Vec<_>>();
+        let predicates = conjuncts
+            .into_iter()
+            .map(|node| {
+                // Later passes can rewrite the nodes of the plan in place, so take a copy.
+                let node = copy_expr(self.origin(node), expr_arena);
+                ExprIR::from_node(node, expr_arena)
+            })
+            .collect();
+        self.pushed.insert(scan_predicate, predicates);
+    }
+
+    /// Get the predicates pushed into the scans of the plan, in the order of the plan.
+    pub(super) fn finish(
+        mut self,
+        lp_top: Node,
+        lp_arena: &Arena<IR>,
+    ) -> Vec<ScanPredicateLineage> {
+        lp_arena
+            .iter(lp_top)
+            .filter_map(|(node, ir)| {
+                let IR::Scan {
+                    predicate: Some(predicate),
+                    ..
+                } = ir
+                else {
+                    return None;
+                };
+                let predicates = self.pushed.remove(&predicate.node())?;
+                Some(ScanPredicateLineage { node, predicates })
+            })
+            .collect()
+    }
+}
+
+/// Split a predicate on its top-level `&`.
+fn conjuncts(node: Node, expr_arena: &Arena<AExpr>) -> Vec<Node> {
+    let mut out = vec![];
+    let mut stack = unitvec![node];
+    while let Some(node) = stack.pop() {
+        match expr_arena.get(node) {
+            AExpr::BinaryExpr {
+                left,
+                op: Operator::And,
+                right,
+            } => stack.extend([*right, *left]),
+            _ => out.push(node),
+        }
+    }
+    out
+}
+
+#[recursive]
+fn copy_expr(node: Node, expr_arena: &mut Arena<AExpr>) -> Node {
+    let ae = expr_arena.get(node).clone();
+    let mut inputs = unitvec![];
+    ae.inputs_rev(&mut inputs);
+    for input in inputs.as_mut_slice() {
+        *input = copy_expr(*input, expr_arena);
+    }
+    inputs.as_mut_slice().reverse();
+    expr_arena.add(ae.replace_inputs(&inputs))
+}
@@ -1,8 +1,10 @@
 mod group_by;
 mod join;
 mod keys;
+mod lineage;
 mod utils;
 
+use lineage::PredicateLineage;
 use polars_core::datatypes::PlHashMap;
 use polars_core::prelude::*;
 use polars_utils::idx_vec::UnitVec;
@@ -24,7 +26,7 @@ mod inner {
     use polars_utils::idx_vec::UnitVec;
     use polars_utils::unitvec;
 
-    use super::ExprEval;
+    use super::{ExprEval, PredicateLineage};
 
     pub struct PredicatePushDown<'a> {
         // TODO: Remove unused
@@ -38,6 +40,7 @@ mod inner {
         pub(super) new_streaming: bool,
         // Controls pushing filters past fallible projections
         pub(super) maintain_errors: bool,
+        pub(super) lineage: Option<PredicateLineage>,
     }
 
     impl<'a> PredicatePushDown<'a> {
@@ -49,6 +52,7 @@ mod inner {
                 nodes_scratch: unitvec![],
                 new_streaming,
                 maintain_errors,
+                lineage: None,
             }
         }
 
@@ -68,6 +72,25 @@ impl PredicatePushDown<'_> {
         self
     }
 
+    /// Keep a copy of the filter predicates that are pushed into the scans, see
+    /// [`PredicatePushDown::take_lineage`].
+    pub(crate) fn record_lineage(mut self, toggle: bool) -> Self {
+        self.lineage = toggle.then(PredicateLineage::default);
+        self
+    }
+
+    /// Get the filter predicates that were pushed into the scans of the optimized plan.
+    pub(crate) fn take_lineage(
+        &mut self,
+        lp_top: Node,
+        lp_arena: &Arena<IR>,
+    ) -> Vec<ScanPredicateLineage> {
+        self.lineage
+            .take()
+            .map(|lineage| lineage.finish(lp_top, lp_arena))
+            .unwrap_or_default()
+    }
+
     fn optional_apply_predicate(
         &mut self,
         lp: IR,
@@ -146,7 +169,11 @@ impl PredicatePushDown<'_> {
 
             if !alias_rename_map.is_empty() {
                 for (_, expr_ir) in acc_predicates.iter_mut() {
+                    let before = expr_ir.node();
                     map_column_references(expr_ir, expr_arena, &alias_rename_map);
+                    if let Some(lineage) = &mut self.lineage {
+                        lineage.record_rewrite(before, expr_ir.node(), expr_arena);
+                    }
                 }
             }
 
@@ -388,6 +415,12 @@ impl PredicatePushDown<'_> {
                         blocked_names.contains(&name.as_ref())
                     })
                 };
+                let pushed = self.lineage.is_some().then(|| {
+                    acc_predicates
+                        .values()
+                        .map(|e| e.node())
+                        .collect::<Vec<_>>()
+                });
                 let predicate = predicate_at_scan(acc_predicates, predicate.clone(), expr_arena);
 
                 let mut do_optimization = match &*scan_type {
@@ -401,6 +434,13 @@ impl PredicatePushDown<'_> {
                 };
                 do_optimization &= predicate.is_some();
 
+                if do_optimization {
+                    if let (Some(lineage), Some(pushed)) = (&mut self.lineage, pushed) {
+                        let scan_predicate = predicate.as_ref().unwrap().node();
+                        lineage.record_scan(scan_predicate, &pushed, expr_arena);
+                    }
+                }
+
                 let hive_parts = scan_hive_parts;
 
                 let lp = if do_optimization {
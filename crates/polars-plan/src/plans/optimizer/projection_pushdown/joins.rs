@@ -2,7 +2,6 @@
 use std::borrow::Cow;
 
 use super::*;
-use crate::prelude::optimizer::join_utils::split_suffix;
 
 fn add_keys_to_accumulated_state(
     expr: Node,
@@ -135,6 +134,12 @@ pub(super) fn process_asof_join(
         }
 
         for proj in ctx.acc_projections {
+            // The index column is added by the join.
+            if asof_options.index_column.as_ref() == Some(column_node_to_name(proj, expr_arena)) {
+                local_projection.push(proj);
+                continue;
+            }
+
             let add_local = if local_projected_names.is_empty() {
                 true
             } else {
@@ -418,9 +423,10 @@ fn process_projection(
         let suffix = options.args.suffix().as_str();
         // If _right suffix exists we need to push a projection down without this
         // suffix.
-        if leaf_column_name.ends_with(suffix) && join_schema.contains(leaf_column_name.as_ref()) {
+        let downwards_name = _join_strip_suffix(leaf_column_name.as_ref(), suffix)
+            .filter(|_| join_schema.contains(leaf_column_name.as_ref()));
+        if let Some(downwards_name) = downwards_name {
             // downwards name is the name without the _right i.e. "foo".
-            let downwards_name = split_suffix(leaf_column_name.as_ref(), suffix);
             let downwards_name = PlSmallStr::from_str(downwards_name);
 
             let downwards_name_column = expr_arena.add(AExpr::Column(downwards_name.clone()));
@@ -481,8 +487,9 @@ fn resolve_join_suffixes(
         .iter()
         .map(|proj| {
             let name = column_node_to_name(*proj, expr_arena).clone();
-            if name.ends_with(suffix) && schema_after_join.get(&name).is_none() {
-                let downstream_name = &name.as_str()[..name.len() - suffix.len()];
+            let downstream_name = _join_strip_suffix(name.as_str(), suffix)
+                .filter(|_| schema_after_join.get(&name).is_none());
+            if let Some(downstream_name) = downstream_name {
                 let col = AExpr::Column(downstream_name.into());
                 let node = expr_arena.add(col);
                 all_columns = false;
@@ -757,6 +757,8 @@ impl ProjectionPushDown {
                 input_left,
                 input_right,
                 key,
+                descending,
+                nulls_last,
             } => {
                 if ctx.has_pushed_down() {
                     // make sure that the filter column is projected
@@ -770,6 +772,8 @@ impl ProjectionPushDown {
                     input_left,
                     input_right,
                     key,
+                    descending,
+                    nulls_last,
                 })
             },
             Invalid => unreachable!(),
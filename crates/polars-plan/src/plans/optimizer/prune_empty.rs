@@ -0,0 +1,94 @@
+//! Replace subplans that provably produce no rows with an empty `DataFrame`.
+//!
+//! A filter with a `false` (or null) literal in its conjunction, e.g. after simplifying
+//! contradictory predicates, never keeps a row. Such a filter is replaced with an empty scan of the same schema, and the empty
+//! scan is propagated upwards through the plans that can't produce rows from an empty input. The
+//! input of a pruned plan is never evaluated, so an error in it no longer surfaces, this rule must
+//! not run if errors need to be maintained.
+
+use polars_ops::frame::JoinType;
+
+use super::*;
+
+pub(super) struct PruneEmptyPlans {}
+
+fn is_empty_scan(node: Node, lp_arena: &Arena<IR>) -> bool {
+    matches!(lp_arena.get(node), IR::DataFrameScan { df, .. } if df.height() == 0)
+}
+
+/// Whether a projection of `exprs` on an empty input produces no rows. This holds for elementwise
+/// expressions, scalars are broadcast to the length of the other columns.
+fn projection_stays_empty(exprs: &[ExprIR], expr_arena: &Arena<AExpr>, is_select: bool) -> bool {
+    exprs
+        .iter()
+        .all(|e| e.is_scalar(expr_arena) || is_elementwise_rec(e.node(), expr_arena))
+        // A select of only scalars produces a single row.
+        && (!is_select || exprs.iter().any(|e| !e.is_scalar(expr_arena)))
+}
+
+impl OptimizationRule for PruneEmptyPlans {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<IR>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> PolarsResult<Option<IR>> {
+        let lp = lp_arena.get(node);
+        let is_empty = match lp {
+            IR::Filter { input, predicate } => {
+                is_empty_scan(*input, lp_arena)
+                    || MintermIter::new(predicate.node(), expr_arena).any(|term| {
+                        matches!(
+                            expr_arena.get(term),
+                            AExpr::Literal(lv) if lv.bool() == Some(false) || lv.is_null()
+                        )
+                    })
+            },
+            IR::Slice { input, len, .. } => *len == 0 || is_empty_scan(*input, lp_arena),
+            IR::Sort { input, .. }
+            | IR::Distinct { input, .. }
+            | IR::SimpleProjection { input, .. } => is_empty_scan(*input, lp_arena),
+            IR::Select { input, expr, .. } => {
+                is_empty_scan(*input, lp_arena) && projection_stays_empty(expr, expr_arena, true)
+            },
+            IR::HStack { input, exprs, .. } => {
+                is_empty_scan(*input, lp_arena) && projection_stays_empty(exprs, expr_arena, false)
+            },
+            IR::Union { inputs, .. } => inputs.iter().all(|i| is_empty_scan(*i, lp_arena)),
+            IR::Join {
+                input_left,
+                input_right,
+                options,
+                ..
+            } => {
+                let left = is_empty_scan(*input_left, lp_arena);
+                let right = is_empty_scan(*input_right, lp_arena);
+                match &options.args.how {
+                    JoinType::Inner | JoinType::Cross => left || right,
+                    #[cfg(feature = "iejoin")]
+                    JoinType::IEJoin => left || right,
+                    #[cfg(feature = "semi_anti_join")]
+                    JoinType::Semi => left || right,
+                    #[cfg(feature = "semi_anti_join")]
+                    JoinType::Anti => left,
+                    #[cfg(feature = "asof_join")]
+                    JoinType::AsOf(_) => left,
+                    JoinType::Left => left,
+                    JoinType::Right => right,
+                    JoinType::Full => left && right,
+                }
+            },
+            _ => false,
+        };
+        if !is_empty {
+            return Ok(None);
+        }
+
+        let schema = lp.schema(lp_arena).into_owned();
+        Ok(Some(IR::DataFrameScan {
+            df: Arc::new(DataFrame::empty_with_schema(&schema)),
+            schema,
+            output_schema: None,
+        }))
+    }
+}
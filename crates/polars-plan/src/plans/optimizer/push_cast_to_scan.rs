@@ -0,0 +1,174 @@
+//! Push casts of scanned columns into the scan.
+//!
+//! A `with_columns(col("x").cast(dtype))` directly on top of a Parquet or IPC scan reads the column
+//! with its file dtype and then casts it. The readers of these formats can cast a column to the
+//! dtype of the scan schema while reading, so the target dtype is set in the scan schema instead
+//! and the cast is dropped from the `with_columns`. Only lossless upcasts the reader's
+//! `CastColumnsPolicy` can express are pushed, any other cast stays where it is.
+
+use polars_core::utils::get_numeric_upcast_supertype_lossless;
+
+use super::*;
+
+pub(super) struct PushCastToScan {}
+
+/// Whether the reader can cast `from` to `to` under `policy` while reading, and whether the cast
+/// is lossless. If so, returns the policy with the required cast allowed.
+fn reader_cast_policy(
+    from: &DataType,
+    to: &DataType,
+    policy: &CastColumnsPolicy,
+) -> Option<CastColumnsPolicy> {
+    let mut policy = policy.clone();
+    if from.is_integer() && to.is_integer() {
+        if get_numeric_upcast_supertype_lossless(from, to).as_ref() != Some(to) {
+            return None;
+        }
+        policy.integer_upcast = true;
+    } else if matches!((from, to), (DataType::Float32, DataType::Float64)) {
+        policy.float_upcast = true;
+    } else {
+        return None;
+    }
+    Some(policy)
+}
+
+fn is_typed_read_scan(scan_type: &FileScanIR) -> bool {
+    match scan_type {
+        #[cfg(feature = "parquet")]
+        FileScanIR::Parquet { .. } => true,
+        #[cfg(feature = "ipc")]
+        FileScanIR::Ipc { .. } => true,
+        _ => false,
+    }
+}
+
+impl OptimizationRule for PushCastToScan {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<IR>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> PolarsResult<Option<IR>> {
+        let IR::HStack {
+            input,
+            exprs,
+            schema,
+            options,
+        } = lp_arena.get(node)
+        else {
+            return Ok(None);
+        };
+        let IR::Scan {
+            sources,
+            file_info,
+            hive_parts,
+            predicate,
+            output_schema,
+            scan_type,
+            unified_scan_args,
+        } = lp_arena.get(*input)
+        else {
+            return Ok(None);
+        };
+        if !is_typed_read_scan(scan_type)
+            || unified_scan_args.column_mapping.is_some()
+            || unified_scan_args.default_values.is_some()
+        {
+            return Ok(None);
+        }
+        // Allowing a cast in the policy applies to every column. That is only safe if the policy
+        // already allows it, or if no other column can mismatch: a single file without a
+        // user-provided schema is scanned with its own schema.
+        let other_columns_match = sources.len() == 1 && unified_scan_args.schema.is_none();
+
+        let mut policy = unified_scan_args.cast_columns_policy.clone();
+        let mut file_schema = file_info.schema.clone();
+        let mut pushed = vec![false; exprs.len()];
+        for (i, e) in exprs.iter().enumerate() {
+            let AExpr::Cast { expr, dtype, .. } = expr_arena.get(e.node()) else {
+                continue;
+            };
+            let AExpr::Column(name) = expr_arena.get(*expr) else {
+                continue;
+            };
+            // The cast must overwrite the column and be the only use of it.
+            if e.output_name() != name
+                || hive_parts
+                    .as_ref()
+                    .is_some_and(|hive| hive.schema().contains(name))
+                || unified_scan_args
+                    .row_index
+                    .as_ref()
+                    .is_some_and(|ri| ri.name == name)
+                || unified_scan_args.include_file_paths.as_ref() == Some(name)
+                || predicate.as_ref().is_some_and(|p| {
+                    aexpr_to_leaf_names_iter(p.node(), expr_arena).any(|n| n == name)
+                })
+                || exprs.iter().enumerate().any(|(j, other)| {
+                    j != i && aexpr_to_leaf_names_iter(other.node(), expr_arena).any(|n| n == name)
+                })
+            {
+                continue;
+            }
+            let Some(from) = file_schema.get(name) else {
+                continue;
+            };
+            let Some(new_policy) = reader_cast_policy(from, dtype, &policy) else {
+                continue;
+            };
+            if new_policy != policy && !other_columns_match {
+                continue;
+            }
+
+            policy = new_policy;
+            Arc::make_mut(&mut file_schema).with_column(name.clone(), dtype.clone());
+            pushed[i] = true;
+        }
+        if !pushed.contains(&true) {
+            return Ok(None);
+        }
+
+        let output_schema = output_schema.as_ref().map(|output_schema| {
+            let mut output_schema = output_schema.as_ref().clone();
+            for (e, _) in exprs.iter().zip(&pushed).filter(|(_, p)| **p) {
+                let dtype = file_schema.get(e.output_name()).unwrap();
+                output_schema.with_column(e.output_name().clone(), dtype.clone());
+            }
+            Arc::new(output_schema)
+        });
+        let mut unified_scan_args = unified_scan_args.clone();
+        unified_scan_args.cast_columns_policy = policy;
+        let scan = IR::Scan {
+            sources: sources.clone(),
+            file_info: FileInfo {
+                schema: file_schema,
+                ..file_info.clone()
+            },
+            hive_parts: hive_parts.clone(),
+            predicate: predicate.clone(),
+            output_schema,
+            scan_type: scan_type.clone(),
+            unified_scan_args,
+        };
+
+        let exprs: Vec<ExprIR> = exprs
+            .iter()
+            .zip(pushed)
+            .filter(|(_, p)| !p)
+            .map(|(e, _)| e.clone())
+            .collect();
+        if exprs.is_empty() {
+            return Ok(Some(scan));
+        }
+        let schema = schema.clone();
+        let options = *options;
+        let scan = lp_arena.add(scan);
+        Ok(Some(IR::HStack {
+            input: scan,
+            exprs,
+            schema,
+            options,
+        }))
+    }
+}
@@ -0,0 +1,50 @@
+//! Push a `unique` below a `with_columns` that doesn't touch the columns it deduplicates on.
+//!
+//! The rows a `unique` keeps only depend on the values of its subset and the order of the rows.
+//! If the `with_columns` is elementwise and doesn't add or overwrite any of the subset columns, it
+//! can run after the `unique`, on fewer rows. Rows that are dropped are never evaluated, so an
+//! error on such a row no longer surfaces, this rule must not run if errors need to be maintained.
+
+use super::*;
+
+pub(super) struct PushDistinctBelowWithColumns {}
+
+impl OptimizationRule for PushDistinctBelowWithColumns {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<IR>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> PolarsResult<Option<IR>> {
+        let IR::Distinct { input, options } = lp_arena.get(node) else {
+            return Ok(None);
+        };
+        // Without a subset every column is taken into account, including the added ones.
+        let Some(subset) = &options.subset else {
+            return Ok(None);
+        };
+        let IR::HStack {
+            input: hstack_input,
+            exprs,
+            ..
+        } = lp_arena.get(*input)
+        else {
+            return Ok(None);
+        };
+
+        if exprs
+            .iter()
+            .any(|e| subset.contains(e.output_name()) || !is_elementwise_rec(e.node(), expr_arena))
+        {
+            return Ok(None);
+        }
+
+        let hstack = lp_arena.get(*input).clone();
+        let distinct = IR::Distinct {
+            input: *hstack_input,
+            options: options.clone(),
+        };
+        let distinct = lp_arena.add(distinct);
+        Ok(Some(hstack.with_inputs([distinct])))
+    }
+}
@@ -0,0 +1,115 @@
+//! Reorder the conjunctions of a filter predicate so that cheap and selective terms come first.
+//!
+//! Both sides of an `&` are always evaluated, so the order of the terms doesn't change which
+//! expressions run or the result. It does change which error surfaces first if several terms
+//! fail, so this rule must not run if errors need to be maintained.
+
+use super::cardinality::predicate_selectivity;
+use super::*;
+
+/// Static evaluation cost of a predicate term, cheapest first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum PredicateCost {
+    Equality,
+    Range,
+    StringMatch,
+    Expensive,
+}
+
+pub(super) struct ReorderPredicates {}
+
+impl OptimizationRule for ReorderPredicates {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<IR>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> PolarsResult<Option<IR>> {
+        let IR::Filter { input, predicate } = lp_arena.get(node) else {
+            return Ok(None);
+        };
+
+        let terms = MintermIter::new(predicate.node(), expr_arena).collect::<Vec<_>>();
+        if terms.len() < 2 {
+            return Ok(None);
+        }
+
+        let keys = terms
+            .iter()
+            .map(|&term| {
+                (
+                    predicate_cost(term, expr_arena),
+                    predicate_selectivity(term, expr_arena),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut order = (0..terms.len()).collect::<Vec<_>>();
+        order.sort_by(|&l, &r| {
+            let (l_cost, l_sel) = keys[l];
+            let (r_cost, r_sel) = keys[r];
+            l_cost.cmp(&r_cost).then(l_sel.total_cmp(&r_sel))
+        });
+
+        // Already in order, this also ensures the rule reaches a fixed point.
+        if order.iter().enumerate().all(|(i, &j)| i == j) {
+            return Ok(None);
+        }
+
+        let new_node = order
+            .iter()
+            .map(|&i| terms[i])
+            .reduce(|left, right| {
+                expr_arena.add(AExpr::BinaryExpr {
+                    left,
+                    op: Operator::And,
+                    right,
+                })
+            })
+            .unwrap();
+
+        let input = *input;
+        let mut predicate = predicate.clone();
+        predicate.set_node(new_node);
+        Ok(Some(IR::Filter { input, predicate }))
+    }
+}
+
+fn predicate_cost(node: Node, expr_arena: &Arena<AExpr>) -> PredicateCost {
+    expr_arena
+        .iter(node)
+        .map(|(_, ae)| match ae {
+            AExpr::BinaryExpr {
+                op: Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq,
+                ..
+            } => PredicateCost::Range,
+            AExpr::BinaryExpr { .. } => PredicateCost::Equality,
+            AExpr::Function { function, .. } => match function {
+                #[cfg(feature = "is_between")]
+                IRFunctionExpr::Boolean(IRBooleanFunction::IsBetween { .. }) => {
+                    PredicateCost::Range
+                },
+                IRFunctionExpr::Boolean(_) => PredicateCost::Equality,
+                #[cfg(feature = "strings")]
+                IRFunctionExpr::StringExpr(f) => string_function_cost(f),
+                _ => PredicateCost::Range,
+            },
+            AExpr::AnonymousFunction { .. } => PredicateCost::Expensive,
+            _ => PredicateCost::Equality,
+        })
+        .max()
+        .unwrap_or(PredicateCost::Equality)
+}
+
+#[cfg(feature = "strings")]
+fn string_function_cost(function: &IRStringFunction) -> PredicateCost {
+    use IRStringFunction as S;
+    match function {
+        #[cfg(feature = "regex")]
+        S::Contains { literal: false, .. }
+        | S::Find { literal: false, .. }
+        | S::Replace { literal: false, .. } => PredicateCost::Expensive,
+        S::CountMatches(false) | S::Extract(_) | S::ExtractAll => PredicateCost::Expensive,
+        _ => PredicateCost::StringMatch,
+    }
+}
@@ -0,0 +1,137 @@
+use std::borrow::Cow;
+
+use super::*;
+use crate::plans::write_ir_non_recursive;
+
+/// What an optimization pass changed, see [`OptimizationReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PassReport {
+    /// The name of the pass, e.g. `predicate_pushdown`, or of the rule for the rules that are
+    /// applied until the plan reaches a fixed point.
+    pub name: &'static str,
+    /// The number of plan nodes the pass added, removed or rewrote. For a rule this is the number
+    /// of times it rewrote a plan or expression node.
+    pub nodes_changed: usize,
+}
+
+/// The filter predicates that predicate pushdown pushed into a scan, see [`OptimizationReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScanPredicateLineage {
+    /// The scan in the plan.
+    pub node: Node,
+    /// The predicates as they were written in the filters, before their columns were renamed to
+    /// the columns of the scan. These are copies in the expression arena that aren't used by the
+    /// plan, so later passes don't change them.
+    pub predicates: Vec<ExprIR>,
+}
+
+/// The optimization passes that changed a plan, in the order they ran.
+///
+/// Passes that didn't change anything are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OptimizationReport {
+    pub passes: Vec<PassReport>,
+    /// The predicates pushed into each scan, in the order of the plan. Only recorded if
+    /// [`OptFlags::PREDICATE_LINEAGE`] is set.
+    pub predicate_lineage: Vec<ScanPredicateLineage>,
+    /// The passes that added or rewrote a plan node, in the order they ran. Nodes that were
+    /// removed from the plan later can still have annotations. See [`annotation_note`] for how
+    /// these are rendered.
+    pub annotations: PlHashMap<Node, Vec<&'static str>>,
+}
+
+/// The note rendered next to a plan node that was added or rewritten by the pass `name`.
+pub fn annotation_note(name: &'static str) -> Cow<'static, str> {
+    match name {
+        "predicate_pushdown" => Cow::Borrowed("predicate pushed down to here"),
+        "projection_pushdown" => Cow::Borrowed("projection pushed down to here"),
+        "slice_pushdown" => Cow::Borrowed("slice pushed down to here"),
+        "comm_subplan_elim" => Cow::Borrowed("CSE cache inserted"),
+        "comm_subexpr_elim" => Cow::Borrowed("common subexpressions extracted"),
+        "cluster_with_columns" => Cow::Borrowed("with_columns clustered"),
+        "collapse_joins" => Cow::Borrowed("filter collapsed into join"),
+        name => Cow::Owned(format!("rewritten by {name}")),
+    }
+}
+
+impl OptimizationReport {
+    pub(super) fn record(&mut self, name: &'static str, nodes_changed: usize) {
+        if nodes_changed > 0 {
+            self.passes.push(PassReport {
+                name,
+                nodes_changed,
+            });
+        }
+    }
+
+    /// Annotate `node` as added or rewritten by the pass `name`.
+    pub(super) fn annotate(&mut self, node: Node, name: &'static str) {
+        let names = self.annotations.entry(node).or_default();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    /// Record the difference between the plan before and after a pass.
+    pub(super) fn record_pass(
+        &mut self,
+        name: &'static str,
+        before: PlanSnapshot,
+        after: PlanSnapshot,
+    ) {
+        let mut removed = before.with_inputs;
+        let mut added = 0;
+        for node in after.with_inputs {
+            match removed.iter().position(|n| *n == node) {
+                Some(i) => {
+                    removed.swap_remove(i);
+                },
+                None => added += 1,
+            }
+        }
+        self.record(name, added.max(removed.len()));
+
+        // Only the nodes that changed themselves are annotated, not the nodes whose inputs
+        // changed.
+        for node in after.nodes {
+            if !before.nodes.contains(&node) {
+                self.annotate(node.0, name);
+            }
+        }
+    }
+}
+
+/// The formatted nodes of a plan, including their expressions.
+pub(super) struct PlanSnapshot {
+    /// Every node formatted together with its inputs, so moving a node in the plan changes it.
+    with_inputs: Vec<String>,
+    /// Every node formatted on its own.
+    nodes: Vec<(Node, String)>,
+}
+
+impl PlanSnapshot {
+    pub(super) fn new(lp_top: Node, lp_arena: &Arena<IR>, expr_arena: &Arena<AExpr>) -> Self {
+        let fmt_node = |ir: &IR| {
+            let schema = ir.schema(lp_arena);
+            let mut s = String::new();
+            write_ir_non_recursive(&mut s, ir, expr_arena, &schema, 0).unwrap();
+            s
+        };
+        let nodes: Vec<_> = lp_arena
+            .iter(lp_top)
+            .map(|(node, ir)| (node, fmt_node(ir)))
+            .collect();
+        let with_inputs = nodes
+            .iter()
+            .map(|(node, s)| {
+                let mut s = s.clone();
+                for input in lp_arena.get(*node).inputs() {
+                    s.push_str(" <- ");
+                    s.push_str(&fmt_node(lp_arena.get(input)));
+                }
+                s
+            })
+            .collect();
+        Self { with_inputs, nodes }
+    }
+}
@@ -1,4 +1,5 @@
 use polars_core::prelude::*;
+use polars_ops::frame::{JoinType, MaintainOrderJoin};
 use polars_utils::idx_vec::UnitVec;
 use polars_utils::slice_enum::Slice;
 use recursive::recursive;
@@ -115,6 +116,71 @@ fn can_pushdown_slice_past_projections(
     (true, can_pushdown_and_any_expr_has_column)
 }
 
+/// Push a top-k on the output of a left join into the left input of the join, if the top-k only
+/// sorts by left columns.
+///
+/// Every left row produces at least one output row and these rows share the values of the left
+/// columns, so the top `k` output rows come from the top `k` left rows. The top-k above the join
+/// stays, as a left row can produce several output rows.
+fn push_top_k_into_left_join(
+    join: Node,
+    by_column: &[ExprIR],
+    sort_options: &SortMultipleOptions,
+    k: usize,
+    lp_arena: &mut Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) {
+    let IR::Join {
+        input_left,
+        options,
+        ..
+    } = lp_arena.get(join)
+    else {
+        return;
+    };
+    let args = &options.args;
+    // A stable sort breaks ties by the order of the join output, which has to be the order of the
+    // left rows.
+    let left_order_kept = !sort_options.maintain_order
+        || matches!(
+            args.maintain_order,
+            MaintainOrderJoin::None | MaintainOrderJoin::Left | MaintainOrderJoin::LeftRight
+        );
+    if !matches!(args.how, JoinType::Left)
+        || options.options.is_some()
+        || args.slice.is_some()
+        || args.validation.needs_checks()
+        || !left_order_kept
+    {
+        return;
+    }
+    let input_left = *input_left;
+    // Already limited by an earlier pass.
+    if matches!(lp_arena.get(input_left), IR::Sort { slice: Some(_), .. }) {
+        return;
+    }
+    let left_schema = lp_arena.get(input_left).schema(lp_arena);
+    let by_left_columns = by_column.iter().all(|e| {
+        is_elementwise_rec(e.node(), expr_arena)
+            && aexpr_to_leaf_names_iter(e.node(), expr_arena)
+                .all(|name| left_schema.contains(&name))
+    });
+    if !by_left_columns {
+        return;
+    }
+
+    // The left input may be shared, so the top-k gets a new node.
+    let top_k = lp_arena.add(IR::Sort {
+        input: input_left,
+        by_column: by_column.to_vec(),
+        slice: Some((0, k)),
+        sort_options: sort_options.clone(),
+    });
+    if let IR::Join { input_left, .. } = lp_arena.get_mut(join) {
+        *input_left = top_k;
+    }
+}
+
 impl SlicePushDown {
     // slice will be done at this node if we found any
     // we also stop optimization
@@ -187,6 +253,94 @@ impl SlicePushDown {
         Ok(lp.with_inputs(new_inputs))
     }
 
+    /// Split a `with_columns` that can't be sliced as a whole, so that the elementwise
+    /// expressions are only computed on the sliced rows. The expressions that need the full
+    /// columns (e.g. `over`, `cum_sum`, `rank`) stay below the slice.
+    fn split_hstack_at_slice(
+        &mut self,
+        lp: IR,
+        state: Option<State>,
+        lp_arena: &mut Arena<IR>,
+        expr_arena: &mut Arena<AExpr>,
+    ) -> PolarsResult<IR> {
+        let IR::HStack {
+            input,
+            exprs,
+            schema,
+            options,
+        } = &lp
+        else {
+            unreachable!()
+        };
+        let input_schema = lp_arena.get(*input).schema(lp_arena).into_owned();
+        if state.is_none() || input_schema.is_empty() {
+            return self.no_pushdown_restart_opt(lp, state, lp_arena, expr_arena);
+        }
+
+        let maintain_errors = self.maintain_errors;
+        let (elementwise, full): (Vec<_>, Vec<_>) = exprs.iter().cloned().partition(|e| {
+            can_pushdown_slice_past_projections(
+                std::slice::from_ref(e),
+                expr_arena,
+                self.empty_nodes_scratch_mut(),
+                maintain_errors,
+            )
+            .0
+        });
+
+        // The elementwise expressions are evaluated on top of the full ones, so they may not
+        // read columns that the full expressions overwrite.
+        let reads_full_output = || {
+            elementwise.iter().any(|e| {
+                aexpr_to_leaf_names_iter(e.node(), expr_arena)
+                    .any(|name| full.iter().any(|f| f.output_name() == &name))
+            })
+        };
+        if elementwise.is_empty() || full.is_empty() || reads_full_output() {
+            return self.no_pushdown_restart_opt(lp, state, lp_arena, expr_arena);
+        }
+
+        let extend_schema = |base: &Schema, exprs: &[ExprIR]| {
+            let mut out = base.clone();
+            for e in exprs {
+                let name = e.output_name();
+                out.with_column(name.clone(), schema.get(name).unwrap().clone());
+            }
+            Arc::new(out)
+        };
+        let full_schema = extend_schema(&input_schema, &full);
+        let elementwise_schema = extend_schema(&full_schema, &elementwise);
+        let output_schema = schema.clone();
+        let options = *options;
+
+        let lp = IR::HStack {
+            input: *input,
+            exprs: full,
+            schema: full_schema,
+            options,
+        };
+        let lp = self.no_pushdown_restart_opt(lp, state, lp_arena, expr_arena)?;
+        let lp = IR::HStack {
+            input: lp_arena.add(lp),
+            exprs: elementwise,
+            schema: elementwise_schema.clone(),
+            options,
+        };
+
+        // New columns are appended per `with_columns`, restore the original column order.
+        if elementwise_schema
+            .iter_names()
+            .eq(output_schema.iter_names())
+        {
+            Ok(lp)
+        } else {
+            Ok(IR::SimpleProjection {
+                input: lp_arena.add(lp),
+                columns: output_schema,
+            })
+        }
+    }
+
     #[recursive]
     fn pushdown(
         &mut self,
@@ -344,6 +498,10 @@ impl SlicePushDown {
                 let input= lp_arena.add(input_lp);
 
                 slice = Some((state.offset, state.len as usize));
+                if let Ok(offset) = usize::try_from(state.offset) {
+                    let k = offset + state.len as usize;
+                    push_top_k_into_left_join(input, &by_column, &sort_options, k, lp_arena, expr_arena);
+                }
                 Ok(Sort {
                     input,
                     by_column,
@@ -459,7 +617,7 @@ impl SlicePushDown {
                 // don't push down slice, but restart optimization
                 else {
                     let lp = HStack {input, exprs, schema, options};
-                    self.no_pushdown_restart_opt(lp, state, lp_arena, expr_arena)
+                    self.split_hstack_at_slice(lp, state, lp_arena, expr_arena)
                 }
             }
             (HConcat {inputs, schema, options}, _) => {
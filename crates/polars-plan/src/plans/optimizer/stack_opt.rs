@@ -1,6 +1,7 @@
-use polars_core::prelude::PolarsResult;
+use polars_core::prelude::{PolarsResult, polars_ensure};
 use polars_core::schema::Schema;
 
+use super::OptimizationReport;
 use crate::plans::aexpr::AExpr;
 use crate::plans::ir::IR;
 use crate::plans::{get_input, get_input_schema};
@@ -16,80 +17,139 @@ impl StackOptimizer {
         expr_arena: &mut Arena<AExpr>,
         lp_arena: &mut Arena<IR>,
         lp_top: Node,
+        verify_fixed_point: bool,
+        report: Option<&mut OptimizationReport>,
     ) -> PolarsResult<Node> {
-        let mut changed = true;
+        let mut rewrites = vec![0; rules.len()];
+        let mut rewritten = vec![];
+
+        // Run loop until reaching fixed point.
+        while self.optimize_pass(
+            rules,
+            expr_arena,
+            lp_arena,
+            lp_top,
+            &mut rewrites,
+            &mut rewritten,
+        )? {}
+
+        // A pass that changes the plan again means a rule doesn't reach a fixed point on its own
+        // output, e.g. because it keeps state between passes.
+        if verify_fixed_point {
+            polars_ensure!(
+                !self.optimize_pass(
+                    rules,
+                    expr_arena,
+                    lp_arena,
+                    lp_top,
+                    &mut rewrites,
+                    &mut rewritten
+                )?,
+                ComputeError: "optimizer rules did not reach a fixed point; \
+                this is a bug, please open an issue"
+            );
+        }
+
+        if let Some(report) = report {
+            for (rule, rewrites) in rules.iter().zip(rewrites) {
+                report.record(rule.name(), rewrites);
+            }
+            for (node, rule_idx) in rewritten {
+                report.annotate(node, rules[rule_idx].name());
+            }
+        }
+        Ok(lp_top)
+    }
+
+    /// Apply the rules once to every plan and expression, returns whether any rule made a change.
+    ///
+    /// The number of rewrites of every rule is added to `rewrites`, and the plan nodes a rule
+    /// rewrote, or whose expressions it rewrote, are added to `rewritten` with the index of the rule.
+    fn optimize_pass(
+        &self,
+        rules: &mut [Box<dyn OptimizationRule>],
+        expr_arena: &mut Arena<AExpr>,
+        lp_arena: &mut Arena<IR>,
+        lp_top: Node,
+        rewrites: &mut [usize],
+        rewritten: &mut Vec<(Node, usize)>,
+    ) -> PolarsResult<bool> {
+        let mut changed = false;
 
         // Nodes of expressions and lp node from which the expressions are a member of.
-        let mut plans = vec![];
+        let mut plans = vec![lp_top];
         let mut exprs = vec![];
         let mut scratch = vec![];
 
-        // Run loop until reaching fixed point.
+        // Recurse into sub plans and expressions and apply rules.
         #[allow(clippy::field_reassign_with_default)]
-        while changed {
-            // Recurse into sub plans and expressions and apply rules.
-            changed = false;
-            plans.push(lp_top);
-            while let Some(current_node) = plans.pop() {
-                // Apply rules
-                for rule in rules.iter_mut() {
-                    // keep iterating over same rule
-                    while let Some(x) = rule.optimize_plan(lp_arena, expr_arena, current_node)? {
-                        lp_arena.replace(current_node, x);
-                        changed = true;
-                    }
+        while let Some(current_node) = plans.pop() {
+            // Apply rules
+            for (rule_idx, (rule, rewrites)) in
+                rules.iter_mut().zip(rewrites.iter_mut()).enumerate()
+            {
+                // keep iterating over same rule
+                while let Some(x) = rule.optimize_plan(lp_arena, expr_arena, current_node)? {
+                    lp_arena.replace(current_node, x);
+                    changed = true;
+                    *rewrites += 1;
+                    rewritten.push((current_node, rule_idx));
                 }
+            }
 
-                let plan = lp_arena.get(current_node);
+            let plan = lp_arena.get(current_node);
 
-                // traverse subplans and expressions and add to the stack
-                plan.copy_exprs(&mut scratch);
-                plan.copy_inputs(&mut plans);
+            // traverse subplans and expressions and add to the stack
+            plan.copy_exprs(&mut scratch);
+            plan.copy_inputs(&mut plans);
 
-                if scratch.is_empty() {
-                    continue;
-                }
+            if scratch.is_empty() {
+                continue;
+            }
 
-                while let Some(expr_ir) = scratch.pop() {
-                    exprs.push(expr_ir.node());
-                }
+            while let Some(expr_ir) = scratch.pop() {
+                exprs.push(expr_ir.node());
+            }
+
+            let input_schema = get_input_schema(lp_arena, current_node);
+            let mut ctx = OptimizeExprContext::default();
+            #[cfg(feature = "python")]
+            {
+                use crate::dsl::python_dsl::PythonScanSource;
+                ctx.in_pyarrow_scan = matches!(plan, IR::PythonScan { options } if options.python_source == PythonScanSource::Pyarrow);
+                ctx.in_io_plugin = matches!(plan, IR::PythonScan { options } if options.python_source == PythonScanSource::IOPlugin);
+            };
+            ctx.in_filter = matches!(plan, IR::Filter { .. });
+            ctx.has_inputs = !get_input(lp_arena, current_node).is_empty();
 
-                let input_schema = get_input_schema(lp_arena, current_node);
-                let mut ctx = OptimizeExprContext::default();
-                #[cfg(feature = "python")]
+            // process the expressions on the stack and apply optimizations.
+            while let Some(current_expr_node) = exprs.pop() {
                 {
-                    use crate::dsl::python_dsl::PythonScanSource;
-                    ctx.in_pyarrow_scan = matches!(plan, IR::PythonScan { options } if options.python_source == PythonScanSource::Pyarrow);
-                    ctx.in_io_plugin = matches!(plan, IR::PythonScan { options } if options.python_source == PythonScanSource::IOPlugin);
-                };
-                ctx.in_filter = matches!(plan, IR::Filter { .. });
-                ctx.has_inputs = !get_input(lp_arena, current_node).is_empty();
-
-                // process the expressions on the stack and apply optimizations.
-                while let Some(current_expr_node) = exprs.pop() {
-                    {
-                        let expr = unsafe { expr_arena.get_unchecked(current_expr_node) };
-                        if expr.is_leaf() {
-                            continue;
-                        }
+                    let expr = unsafe { expr_arena.get_unchecked(current_expr_node) };
+                    if expr.is_leaf() {
+                        continue;
                     }
-                    for rule in rules.iter_mut() {
-                        // keep iterating over same rule
-                        while let Some(x) =
-                            rule.optimize_expr(expr_arena, current_expr_node, &input_schema, ctx)?
-                        {
-                            expr_arena.replace(current_expr_node, x);
-                            changed = true;
-                        }
+                }
+                for (rule_idx, (rule, rewrites)) in
+                    rules.iter_mut().zip(rewrites.iter_mut()).enumerate()
+                {
+                    // keep iterating over same rule
+                    while let Some(x) =
+                        rule.optimize_expr(expr_arena, current_expr_node, &input_schema, ctx)?
+                    {
+                        expr_arena.replace(current_expr_node, x);
+                        changed = true;
+                        *rewrites += 1;
+                        rewritten.push((current_node, rule_idx));
                     }
-
-                    let expr = unsafe { expr_arena.get_unchecked(current_expr_node) };
-                    // traverse subexpressions and add to the stack
-                    expr.inputs_rev(&mut exprs)
                 }
+
+                let expr = unsafe { expr_arena.get_unchecked(current_expr_node) };
+                // traverse subexpressions and add to the stack
+                expr.inputs_rev(&mut exprs)
             }
         }
-        Ok(lp_top)
+        Ok(changed)
     }
 }
 
@@ -102,6 +162,12 @@ pub struct OptimizeExprContext {
 }
 
 pub trait OptimizationRule {
+    /// The name of the rule in an [`OptimizationReport`], the name of the type by default.
+    fn name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     ///  Optimize (subplan) in LogicalPlan
     ///
     /// * `lp_arena` - LogicalPlan memory arena
@@ -348,6 +348,7 @@ mod tests {
                 input: filter,
                 id: UniqueId::new(),
                 cache_hits: 1,
+                memory_budget: None,
             });
 
             let left_sink = ir_arena.add(IR::Sink {
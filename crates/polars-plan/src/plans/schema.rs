@@ -6,7 +6,7 @@ use arrow::datatypes::ArrowSchemaRef;
 use either::Either;
 use polars_core::prelude::*;
 use polars_utils::idx_vec::UnitVec;
-use polars_utils::{format_pl_smallstr, unitvec};
+use polars_utils::unitvec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -102,7 +102,24 @@ pub(crate) fn det_join_schema(
         // semi and anti joins are just filtering operations
         // the schema will never change.
         #[cfg(feature = "semi_anti_join")]
-        JoinType::Semi | JoinType::Anti => Ok(schema_left.clone()),
+        JoinType::Semi | JoinType::Anti => {
+            let Some(names) = &options.args.semi_anti_key_names else {
+                return Ok(schema_left.clone());
+            };
+            let mut schema = schema_left.as_ref().clone();
+            for (e, name) in left_on.iter().zip(names) {
+                let key_name = e.output_name();
+                if key_name == name || !schema.contains(key_name) {
+                    continue;
+                }
+                polars_ensure!(
+                    !schema.contains(name),
+                    Duplicate: "column rename attempted with already existing name \"{name}\""
+                );
+                schema.rename(key_name, name.clone());
+            }
+            Ok(Arc::new(schema))
+        },
         // Right-join with coalesce enabled will coalesce LHS columns into RHS columns (i.e. LHS columns
         // are removed). This is the opposite of what a left join does so it has its own codepath.
         //
@@ -145,7 +162,7 @@ pub(crate) fn det_join_schema(
                     let is_coalesced = join_on_left.contains(name.as_str());
 
                     if in_left_schema && !is_coalesced {
-                        suffixed = Some(format_pl_smallstr!("{}{}", name, options.args.suffix()));
+                        suffixed = Some(_join_suffix_name(name.as_str(), options.args.suffix()));
                         (suffixed.clone().unwrap(), dtype.clone())
                     } else {
                         (name.clone(), dtype.clone())
@@ -237,7 +254,7 @@ pub(crate) fn det_join_schema(
                 // For the error message.
                 let mut suffixed = None;
                 let (name, dtype) = if schema_left.contains(name) {
-                    suffixed = Some(format_pl_smallstr!("{}{}", name, options.args.suffix()));
+                    suffixed = Some(_join_suffix_name(name.as_str(), options.args.suffix()));
                     (suffixed.clone().unwrap(), dtype.clone())
                 } else {
                     (name.clone(), dtype.clone())
@@ -252,6 +269,13 @@ pub(crate) fn det_join_schema(
                 })?;
             }
 
+            #[cfg(feature = "asof_join")]
+            if let JoinType::AsOf(asof_options) = &options.args.how
+                && let Some(name) = &asof_options.index_column
+            {
+                new_schema.try_insert(name.clone(), IDX_DTYPE)?;
+            }
+
             Ok(Arc::new(new_schema))
         },
     }
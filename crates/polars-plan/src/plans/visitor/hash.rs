@@ -186,17 +186,23 @@ impl Hash for HashableEqLP<'_> {
                 input: _,
                 id,
                 cache_hits,
+                memory_budget,
             } => {
                 id.hash(state);
                 cache_hits.hash(state);
+                memory_budget.hash(state);
             },
             #[cfg(feature = "merge_sorted")]
             IR::MergeSorted {
                 input_left: _,
                 input_right: _,
                 key,
+                descending,
+                nulls_last,
             } => {
                 key.hash(state);
+                descending.hash(state);
+                nulls_last.hash(state);
             },
             IR::Invalid => unreachable!(),
         }
@@ -215,7 +221,7 @@ fn expr_ir_eq(l: &ExprIR, r: &ExprIR, expr_arena: &Arena<AExpr>) -> bool {
     }
 }
 
-fn opt_expr_ir_eq(l: &Option<ExprIR>, r: &Option<ExprIR>, expr_arena: &Arena<AExpr>) -> bool {
+pub(crate) fn opt_expr_ir_eq(l: &Option<ExprIR>, r: &Option<ExprIR>, expr_arena: &Arena<AExpr>) -> bool {
     match (l, r) {
         (None, None) => true,
         (Some(l), Some(r)) => expr_ir_eq(l, r, expr_arena),
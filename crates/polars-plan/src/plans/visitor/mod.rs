@@ -8,6 +8,8 @@ mod lp;
 mod visitors;
 
 pub use expr::*;
+#[cfg(feature = "cse")]
+pub(crate) use hash::opt_expr_ir_eq;
 pub use lp::*;
 pub use visitors::*;
 
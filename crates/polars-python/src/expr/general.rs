@@ -878,6 +878,10 @@ impl PyExpr {
         self.inner.clone().set_sorted_flag(is_sorted).into()
     }
 
+    fn no_pushdown(&self) -> Self {
+        self.inner.clone().no_pushdown().into()
+    }
+
     fn replace(&self, old: PyExpr, new: PyExpr) -> Self {
         self.inner.clone().replace(old.inner, new.inner).into()
     }
@@ -764,6 +764,8 @@ impl PyLazyFrame {
                     sink_options.0,
                     partition.per_partition_sort_by,
                     partition.finish_callback,
+                    None,
+                    partition.expected_keys,
                 ),
             }
             .into()
@@ -828,6 +830,8 @@ impl PyLazyFrame {
                     sink_options.0,
                     partition.per_partition_sort_by,
                     partition.finish_callback,
+                    None,
+                    partition.expected_keys,
                 ),
             }
         })
@@ -922,6 +926,8 @@ impl PyLazyFrame {
                     sink_options.0,
                     partition.per_partition_sort_by,
                     partition.finish_callback,
+                    None,
+                    partition.expected_keys,
                 ),
             }
         })
@@ -973,6 +979,8 @@ impl PyLazyFrame {
                     sink_options.0,
                     partition.per_partition_sort_by,
                     partition.finish_callback,
+                    None,
+                    partition.expected_keys,
                 ),
             }
         })
@@ -1129,8 +1137,12 @@ impl PyLazyFrame {
                     Scalar::new(dtype, av)
                 }),
                 tolerance_str: tolerance_str.map(|s| s.into()),
+                tolerance_relative: false,
                 allow_eq,
                 check_sortedness,
+                auto_sort: false,
+                index_column: None,
+                string_collation: Default::default(),
             })))
             .suffix(suffix)
             .finish()
@@ -1541,11 +1553,24 @@ impl PyLazyFrame {
     }
 
     #[cfg(feature = "merge_sorted")]
-    fn merge_sorted(&self, other: Self, key: &str) -> PyResult<Self> {
+    #[pyo3(signature = (other, key, descending=false, nulls_last=false))]
+    fn merge_sorted(
+        &self,
+        other: Self,
+        key: &str,
+        descending: bool,
+        nulls_last: bool,
+    ) -> PyResult<Self> {
         let out = self
             .ldf
             .clone()
-            .merge_sorted(other.ldf, key)
+            .merge_sorted_with_options(
+                other.ldf,
+                key,
+                MergeSortedOptions::default()
+                    .with_order_descending(descending)
+                    .with_nulls_last(nulls_last),
+            )
             .map_err(PyPolarsErr::from)?;
         Ok(out.into())
     }
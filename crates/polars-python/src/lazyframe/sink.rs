@@ -2,8 +2,8 @@ use std::sync::{Arc, Mutex};
 
 use polars::prelude::sync_on_close::SyncOnCloseType;
 use polars::prelude::{
-    PartitionTargetCallbackResult, PartitionVariant, PlPath, SinkFinishCallback, SinkOptions,
-    SortColumn, SpecialEq,
+    DataFrame, PartitionTargetCallbackResult, PartitionVariant, PlPath, SinkFinishCallback,
+    SinkOptions, SortColumn, SpecialEq,
 };
 use polars_utils::IdxSize;
 use polars_utils::plpath::PlPathRef;
@@ -13,6 +13,7 @@ use pyo3::pybacked::PyBackedStr;
 use pyo3::types::{PyAnyMethods, PyDict, PyDictMethods};
 use pyo3::{Bound, FromPyObject, PyAny, PyObject, PyResult, Python, pyclass, pymethods};
 
+use crate::PyDataFrame;
 use crate::expr::PyExpr;
 use crate::prelude::Wrap;
 
@@ -31,6 +32,7 @@ pub struct PyPartitioning {
     pub variant: PartitionVariant,
     pub per_partition_sort_by: Option<Vec<SortColumn>>,
     pub finish_callback: Option<SinkFinishCallback>,
+    pub expected_keys: Option<Arc<DataFrame>>,
 }
 
 fn parse_per_partition_sort_by(sort_by: Option<Vec<PyExpr>>) -> Option<Vec<SortColumn>> {
@@ -68,11 +70,12 @@ impl PyPartitioning {
             variant: PartitionVariant::MaxSize(max_size),
             per_partition_sort_by: parse_per_partition_sort_by(per_partition_sort_by),
             finish_callback,
+            expected_keys: None,
         }
     }
 
     #[staticmethod]
-    #[pyo3(signature = (base_path, file_path_cb, by, include_key, per_partition_sort_by, finish_callback))]
+    #[pyo3(signature = (base_path, file_path_cb, by, include_key, per_partition_sort_by, finish_callback, expected_keys, max_rows_per_file, dry_run, separate_null_keys, flush_morsel_rows, strict_order))]
     pub fn new_by_key(
         base_path: Wrap<PlPath>,
         file_path_cb: Option<PyObject>,
@@ -80,6 +83,12 @@ impl PyPartitioning {
         include_key: bool,
         per_partition_sort_by: Option<Vec<PyExpr>>,
         finish_callback: Option<PyObject>,
+        expected_keys: Option<PyDataFrame>,
+        max_rows_per_file: Option<IdxSize>,
+        dry_run: bool,
+        separate_null_keys: bool,
+        flush_morsel_rows: Option<IdxSize>,
+        strict_order: bool,
     ) -> PyPartitioning {
         let file_path_cb = file_path_cb.map(|f| PythonObject(f.into_any()));
         let finish_callback =
@@ -91,9 +100,15 @@ impl PyPartitioning {
             variant: PartitionVariant::ByKey {
                 key_exprs: by.into_iter().map(|e| e.inner).collect(),
                 include_key,
+                max_rows_per_file,
+                dry_run,
+                separate_null_keys,
+                flush_morsel_rows,
+                strict_order,
             },
             per_partition_sort_by: parse_per_partition_sort_by(per_partition_sort_by),
             finish_callback,
+            expected_keys: expected_keys.map(|df| Arc::new(df.df)),
         }
     }
 
@@ -120,6 +135,7 @@ impl PyPartitioning {
             },
             per_partition_sort_by: parse_per_partition_sort_by(per_partition_sort_by),
             finish_callback,
+            expected_keys: None,
         }
     }
 }
@@ -147,7 +163,9 @@ impl<'py> FromPyObject<'py> for Wrap<polars_plan::dsl::SinkTarget> {
 
 impl<'py> FromPyObject<'py> for Wrap<PartitionTargetCallbackResult> {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        if let Ok(v) = ob.extract::<PyBackedStr>() {
+        if ob.is_none() {
+            Ok(Wrap(polars::prelude::PartitionTargetCallbackResult::Skip))
+        } else if let Ok(v) = ob.extract::<PyBackedStr>() {
             Ok(Wrap(polars::prelude::PartitionTargetCallbackResult::Str(
                 v.to_string(),
             )))
@@ -1304,6 +1304,7 @@ pub(crate) fn into_py(py: Python<'_>, expr: &AExpr) -> PyResult<PyObject> {
                     },
                 )
                     .into_py_any(py),
+                IRFunctionExpr::NoPushdown => ("no_pushdown",).into_py_any(py),
                 #[cfg(feature = "ffi_plugin")]
                 IRFunctionExpr::FfiPlugin { .. } => {
                     return Err(PyNotImplementedError::new_err("ffi plugin"));
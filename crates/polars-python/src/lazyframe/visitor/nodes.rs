@@ -232,6 +232,8 @@ pub struct Cache {
     id_: u128,
     #[pyo3(get)]
     cache_hits: u32,
+    #[pyo3(get)]
+    memory_budget: Option<usize>,
 }
 
 #[pyclass]
@@ -275,6 +277,10 @@ pub struct MergeSorted {
     input_right: usize,
     #[pyo3(get)]
     key: String,
+    #[pyo3(get)]
+    descending: bool,
+    #[pyo3(get)]
+    nulls_last: bool,
 }
 
 #[pyclass]
@@ -487,10 +493,12 @@ pub(crate) fn into_py(py: Python<'_>, plan: &IR) -> PyResult<PyObject> {
             input,
             id,
             cache_hits,
+            memory_budget,
         } => Cache {
             input: input.0,
             id_: id.as_u128(),
             cache_hits: *cache_hits,
+            memory_budget: *memory_budget,
         }
         .into_py_any(py),
         IR::GroupBy {
@@ -711,10 +719,14 @@ pub(crate) fn into_py(py: Python<'_>, plan: &IR) -> PyResult<PyObject> {
             input_left,
             input_right,
             key,
+            descending,
+            nulls_last,
         } => MergeSorted {
             input_left: input_left.0,
             input_right: input_right.0,
             key: key.to_string(),
+            descending: *descending,
+            nulls_last: *nulls_last,
         }
         .into_py_any(py),
         IR::Invalid => Err(PyNotImplementedError::new_err("Invalid")),
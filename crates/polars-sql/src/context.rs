@@ -853,6 +853,14 @@ impl SQLContext {
                                 suffix: None,
                                 slice: None,
                                 nulls_equal: false,
+                                nans_equal: true,
+                                case_insensitive: false,
+                                semi_anti_key_names: None,
+                                semi_anti_from_right: false,
+                                output_columns: None,
+                                key_struct: None,
+                                dedup_right: Default::default(),
+                                rechunk: Default::default(),
                                 coalesce: Default::default(),
                                 maintain_order: polars_ops::frame::MaintainOrderJoin::Left,
                             },
@@ -0,0 +1,182 @@
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use parking_lot::{Condvar, Mutex};
+
+use super::ReadyTask;
+
+/// How long an idle blocking thread waits for new work before exiting.
+const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+static MAX_BLOCKING_THREADS: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("POLARS_MAX_BLOCKING_THREADS")
+        .map(|x| x.parse().unwrap())
+        .unwrap_or(512)
+});
+
+/// A growable pool of threads for running tasks that block, such as synchronous
+/// IO. These threads are separate from the executor's compute threads, so
+/// blocking on them never starves the compute pipelines.
+///
+/// Threads are spawned on demand when no idle thread is available and exit
+/// again after having been idle for a while.
+pub struct BlockingPool {
+    state: Mutex<BlockingPoolState>,
+    task_available: Condvar,
+    max_threads: usize,
+}
+
+#[derive(Default)]
+struct BlockingPoolState {
+    queue: VecDeque<ReadyTask>,
+    num_threads: usize,
+    num_idle: usize,
+}
+
+impl BlockingPool {
+    fn new(max_threads: usize) -> Self {
+        assert!(max_threads > 0);
+        Self {
+            state: Mutex::default(),
+            task_available: Condvar::new(),
+            max_threads,
+        }
+    }
+
+    pub fn global() -> &'static BlockingPool {
+        static GLOBAL_BLOCKING_POOL: LazyLock<BlockingPool> =
+            LazyLock::new(|| BlockingPool::new(*MAX_BLOCKING_THREADS));
+        &GLOBAL_BLOCKING_POOL
+    }
+
+    pub fn schedule_task(&'static self, task: ReadyTask) {
+        if super::is_shut_down() {
+            // Refuse the task, running it fails it without calling the closure.
+            task.run();
+            return;
+        }
+
+        let mut state = self.state.lock();
+        state.queue.push_back(task);
+
+        // Idle threads which were already notified still count as idle until
+        // they wake up, so compare against the queue length to decide whether
+        // another thread is needed.
+        if state.queue.len() > state.num_idle && state.num_threads < self.max_threads {
+            state.num_threads += 1;
+            let thread_idx = state.num_threads;
+            drop(state);
+            std::thread::Builder::new()
+                .name(format!("async-executor-blocking-{thread_idx}"))
+                .spawn(move || self.runner())
+                .unwrap();
+        } else {
+            drop(state);
+            self.task_available.notify_one();
+        }
+    }
+
+    fn runner(&self) {
+        let mut state = self.state.lock();
+        loop {
+            if let Some(task) = state.queue.pop_front() {
+                drop(state);
+                task.run();
+                state = self.state.lock();
+                continue;
+            }
+
+            state.num_idle += 1;
+            let wait_result = self.task_available.wait_for(&mut state, KEEP_ALIVE);
+            state.num_idle -= 1;
+
+            if wait_result.timed_out() && state.queue.is_empty() {
+                state.num_threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Barrier};
+
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::async_executor::{TEST_LOCK, spawn_blocking_on};
+
+    fn leaked_pool(max_threads: usize) -> &'static BlockingPool {
+        Box::leak(Box::new(BlockingPool::new(max_threads)))
+    }
+
+    #[test]
+    fn test_blocking_pool_grows_on_demand() {
+        let _guard = TEST_LOCK.lock();
+        let pool = leaked_pool(8);
+        assert_eq!(pool.state.lock().num_threads, 0);
+
+        // Every job blocks until all of them run at the same time, which only
+        // completes if the pool spawned a thread for each.
+        let barrier = Arc::new(Barrier::new(5));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let barrier = barrier.clone();
+                spawn_blocking_on(pool, move || {
+                    barrier.wait();
+                })
+            })
+            .collect();
+        barrier.wait();
+
+        for handle in handles {
+            block_on(handle);
+        }
+        assert_eq!(pool.state.lock().num_threads, 4);
+    }
+
+    #[test]
+    fn test_blocking_pool_respects_max_threads() {
+        let _guard = TEST_LOCK.lock();
+        let pool = leaked_pool(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let running = running.clone();
+                let max_running = max_running.clone();
+                spawn_blocking_on(pool, move || {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(10));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    i
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = handles.into_iter().map(block_on).collect();
+        assert_eq!(results, (0..8).collect::<Vec<_>>());
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+        assert!(pool.state.lock().num_threads <= 2);
+    }
+
+    #[test]
+    fn test_blocking_pool_propagates_panic() {
+        let _guard = TEST_LOCK.lock();
+        let pool = leaked_pool(1);
+        let handle = spawn_blocking_on(pool, || -> usize { panic!("blocking job failed") });
+
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| block_on(handle)))
+            .unwrap_err();
+        let msg = payload.downcast_ref::<String>().unwrap();
+        assert!(msg.starts_with("blocking job failed\n    in task spawned at"));
+
+        // The thread survives the panic and keeps serving jobs.
+        assert_eq!(block_on(spawn_blocking_on(pool, || 42)), 42);
+    }
+}
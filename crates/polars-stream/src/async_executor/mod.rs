@@ -1,5 +1,6 @@
 #![allow(clippy::disallowed_types)]
 
+mod blocking;
 mod park_group;
 mod task;
 
@@ -8,20 +9,27 @@ use std::collections::HashMap;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::panic::{AssertUnwindSafe, Location};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, LazyLock, OnceLock, Weak};
-use std::time::Duration;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
+use blocking::BlockingPool;
 use crossbeam_deque::{Injector, Steal, Stealer, Worker as WorkQueue};
 use crossbeam_utils::CachePadded;
+use futures::Stream;
 use park_group::ParkGroup;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
+use polars_error::{PolarsResult, polars_ensure, polars_err};
 use polars_utils::relaxed_cell::RelaxedCell;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
 use slotmap::SlotMap;
 pub use task::{AbortOnDropHandle, JoinHandle};
-use task::{CancelHandle, Runnable};
+use task::{CancelHandle, Runnable, SpawnLocation};
+
+use crate::async_primitives::connector;
 
 static NUM_EXECUTOR_THREADS: RelaxedCell<usize> = RelaxedCell::new_usize(0);
 pub fn set_num_threads(t: usize) {
@@ -30,11 +38,93 @@ pub fn set_num_threads(t: usize) {
 
 static GLOBAL_SCHEDULER: OnceLock<Executor> = OnceLock::new();
 
+/// The executor's configuration is process-global, so tests that touch it
+/// must not run concurrently.
+#[cfg(test)]
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// Start the executor threads now, instead of when the first task is spawned.
+///
+/// The number of threads is the one given to [`set_num_threads`] before the first call. Calling
+/// this again, or after tasks were spawned, has no effect.
+pub fn init_eager() {
+    Executor::global();
+}
+
+static EXECUTOR_RNG_SEED: OnceLock<u64> = OnceLock::new();
+
+/// Seed the random number generators that decide which worker to steal work from.
+///
+/// Worker thread `i` is seeded with `seed + i`, making the steal attempts reproducible when
+/// debugging the scheduler. By default every thread is seeded randomly.
+///
+/// Note that this does not make the order in which tasks run deterministic, that still depends on
+/// the timing of the threads.
+///
+/// # Errors
+/// Errors if the executor threads were already started, or if the seed was already set.
+pub fn set_executor_rng_seed(seed: u64) -> PolarsResult<()> {
+    polars_ensure!(
+        GLOBAL_SCHEDULER.get().is_none(),
+        InvalidOperation: "the executor RNG seed must be set before the executor threads are started"
+    );
+    EXECUTOR_RNG_SEED.set(seed).map_err(|_| {
+        polars_err!(InvalidOperation: "the executor RNG seed was already set to {}", EXECUTOR_RNG_SEED.get().unwrap())
+    })
+}
+
+static EXECUTOR_THREAD_PREFIX: OnceLock<String> = OnceLock::new();
+
+/// Set the prefix of the names of the executor threads, which are named `{prefix}-{i}`.
+///
+/// This only has an effect if called before the executor threads are started, and only the first
+/// call counts. By default the threads are named `async-executor-{i}`.
+pub fn set_executor_thread_prefix(prefix: &str) {
+    let _ = EXECUTOR_THREAD_PREFIX.set(prefix.to_owned());
+}
+
 thread_local!(
     /// Used to store which executor thread this is.
     static TLS_THREAD_ID: Cell<usize> = const { Cell::new(usize::MAX) };
 );
 
+/// Whether the current thread is an executor worker thread.
+///
+/// Blocking on a future that needs the executor from within a worker thread
+/// can deadlock, callers that may run on either kind of thread can use this
+/// to decide between spawning onto the executor and running inline.
+pub fn is_on_executor_thread() -> bool {
+    TLS_THREAD_ID.get() != usize::MAX
+}
+
+/// The index of the executor worker thread we're running on, if any.
+pub fn current_worker_id() -> Option<usize> {
+    let thread = TLS_THREAD_ID.get();
+    (thread != usize::MAX).then_some(thread)
+}
+
+/// Run the tasks in the global queues on the calling thread until both queues are empty.
+///
+/// Meant for shutdown, so that side-effecting finalization tasks complete before the runner
+/// threads stop. A high priority task is always run before a low priority one. Tasks scheduled
+/// by the drained tasks are added to the global queues as well, so they are also run. The tasks of
+/// a [`task_scope_on_threads`] are never in the global queues. Must not be called from an executor
+/// thread. Returns the number of tasks that were run.
+pub fn drain_global_queues() -> usize {
+    debug_assert!(!is_on_executor_thread());
+    let Some(executor) = GLOBAL_SCHEDULER.get() else {
+        return 0;
+    };
+
+    let mut num_tasks = 0;
+    while let Some(task) = executor.try_pop_global_task() {
+        // Not counted as an active runner, the scheduler metrics only cover the executor threads.
+        task.run();
+        num_tasks += 1;
+    }
+    num_tasks
+}
+
 static NS_SPENT_BLOCKED: LazyLock<Mutex<HashMap<&'static Location<'static>, u64>>> =
     LazyLock::new(Mutex::default);
 
@@ -44,16 +134,155 @@ pub fn track_task_wait_statistics(should_track: bool) {
     TRACK_WAIT_STATISTICS.store(should_track);
 }
 
-pub fn get_task_wait_statistics() -> Vec<(&'static Location<'static>, Duration)> {
-    NS_SPENT_BLOCKED
-        .lock()
-        .iter()
-        .map(|(l, ns)| (*l, Duration::from_nanos(*ns)))
+pub fn clear_task_wait_statistics() {
+    NS_SPENT_BLOCKED.lock().clear()
+}
+
+/// Return the task wait statistics and clear them under the same lock, so no waits that end in
+/// between are lost.
+pub fn take_task_wait_statistics() -> Vec<(&'static Location<'static>, Duration)> {
+    let stats = std::mem::take(&mut *NS_SPENT_BLOCKED.lock());
+    stats
+        .into_iter()
+        .map(|(l, ns)| (l, Duration::from_nanos(ns)))
         .collect()
 }
 
-pub fn clear_task_wait_statistics() {
-    NS_SPENT_BLOCKED.lock().clear()
+/// Zero if priority boosting is disabled.
+static PRIORITY_BOOST_THRESHOLD_NS: LazyLock<RelaxedCell<u64>> = LazyLock::new(|| {
+    let threshold = std::env::var("POLARS_TASK_PRIORITY_BOOST_MS")
+        .ok()
+        .map(|ms| Duration::from_millis(ms.parse().unwrap()));
+    RelaxedCell::new_u64(boost_threshold_ns(threshold))
+});
+
+fn boost_threshold_ns(threshold: Option<Duration>) -> u64 {
+    threshold.map_or(0, |t| t.as_nanos().try_into().unwrap_or(u64::MAX).max(1))
+}
+
+/// Promote low priority tasks to high priority once they waited longer than `threshold` to be
+/// run after being woken up.
+///
+/// A low priority task can hold up high priority tasks, e.g. by holding a resource they need. As
+/// a cheap proxy for that we boost tasks that sat in the queues for a long time, the next time
+/// they are scheduled they are routed like a high priority task, and stay so until they finish.
+/// `None` disables boosting, tasks that were already boosted keep their priority. Defaults to
+/// the `POLARS_TASK_PRIORITY_BOOST_MS` environment variable.
+pub fn set_priority_boost_threshold(threshold: Option<Duration>) {
+    PRIORITY_BOOST_THRESHOLD_NS.store(boost_threshold_ns(threshold));
+}
+
+/// Nanoseconds since some fixed point in time, never zero.
+fn monotonic_ns() -> u64 {
+    static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+    EPOCH
+        .elapsed()
+        .as_nanos()
+        .try_into()
+        .unwrap_or(u64::MAX)
+        .max(1)
+}
+
+/// Zero if the executor never shuts down because of panics.
+static PANIC_THRESHOLD: LazyLock<RelaxedCell<u64>> = LazyLock::new(|| {
+    let threshold = std::env::var("POLARS_TASK_PANIC_THRESHOLD")
+        .ok()
+        .map(|n| n.parse().unwrap());
+    RelaxedCell::new_u64(panic_threshold_plus_one(threshold))
+});
+
+/// The threshold is stored plus one, so that zero means disabled.
+fn panic_threshold_plus_one(threshold: Option<u64>) -> u64 {
+    threshold.map_or(0, |t| t.saturating_add(1))
+}
+
+static NUM_TASK_PANICS: RelaxedCell<u64> = RelaxedCell::new_u64(0);
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Shut the executor down once more than `threshold` tasks have panicked.
+///
+/// Afterwards no task is run anymore: tasks that are spawned or woken up fail immediately, so
+/// awaiting their [`JoinHandle`] panics with a message explaining the shutdown instead of
+/// returning. This lets batch jobs fail fast rather than continue after many failures. `None`
+/// disables the shutdown, but doesn't revive an executor that is already shut down, see
+/// [`reset_panic_shutdown`]. Defaults to the `POLARS_TASK_PANIC_THRESHOLD` environment variable.
+///
+/// A task that panics because it joined a panicked task is not counted again.
+pub fn set_panic_threshold(threshold: Option<u64>) {
+    PANIC_THRESHOLD.store(panic_threshold_plus_one(threshold));
+    check_panic_threshold();
+}
+
+/// The number of tasks that have panicked since the last call to [`reset_panic_shutdown`].
+pub fn num_task_panics() -> u64 {
+    NUM_TASK_PANICS.load()
+}
+
+/// Whether the executor was shut down because too many tasks panicked.
+pub fn is_shut_down() -> bool {
+    SHUT_DOWN.load(Ordering::Acquire)
+}
+
+/// Reset the panic counter and run tasks again after a shutdown.
+///
+/// The counter is never reset implicitly, so a shutdown also fails the queries that run after
+/// the one that caused it, until this is called.
+pub fn reset_panic_shutdown() {
+    NUM_TASK_PANICS.store(0);
+    SHUT_DOWN.store(false, Ordering::Release);
+}
+
+/// Called for every task that panics.
+fn record_task_panic() {
+    NUM_TASK_PANICS.fetch_add(1);
+    check_panic_threshold();
+}
+
+fn check_panic_threshold() {
+    let threshold = PANIC_THRESHOLD.load();
+    if threshold > 0 && NUM_TASK_PANICS.load() >= threshold {
+        SHUT_DOWN.store(true, Ordering::Release);
+    }
+}
+
+/// The panic payload of the tasks that fail because the executor is shut down.
+fn shut_down_panic_payload() -> Box<dyn std::any::Any + Send + 'static> {
+    Box::new(format!(
+        "the async executor was shut down after {} task panics, which exceeds the threshold of {}",
+        NUM_TASK_PANICS.load(),
+        PANIC_THRESHOLD.load().saturating_sub(1)
+    ))
+}
+
+static NUM_ACTIVE_RUNNERS: AtomicUsize = AtomicUsize::new(0);
+static MAX_ACTIVE_RUNNERS: AtomicUsize = AtomicUsize::new(0);
+
+/// A snapshot of the executor's utilization.
+#[derive(Clone, Copy, Debug)]
+pub struct SchedulerMetrics {
+    /// The number of executor threads, zero if the executor hasn't started yet.
+    pub num_threads: usize,
+    /// The highest number of executor threads simultaneously running a task
+    /// since the last call to [`reset_scheduler_metrics`].
+    pub max_active_runners: usize,
+}
+
+/// Returns a snapshot of the executor's utilization.
+pub fn get_scheduler_metrics() -> SchedulerMetrics {
+    SchedulerMetrics {
+        num_threads: GLOBAL_SCHEDULER
+            .get()
+            .map_or(0, |e| e.thread_task_lists.len()),
+        max_active_runners: MAX_ACTIVE_RUNNERS.load(Ordering::Relaxed),
+    }
+}
+
+/// Starts a new window for the high-water mark of [`SchedulerMetrics`].
+pub fn reset_scheduler_metrics() {
+    MAX_ACTIVE_RUNNERS.store(
+        NUM_ACTIVE_RUNNERS.load(Ordering::Relaxed),
+        Ordering::Relaxed,
+    );
 }
 
 slotmap::new_key_type! {
@@ -77,8 +306,45 @@ struct TaskMetadata {
     spawn_location: &'static Location<'static>,
     ns_spent_blocked: RelaxedCell<u64>,
     priority: TaskPriority,
+    boosted: AtomicBool,
+    /// When the task was last scheduled, see [`monotonic_ns`]. Zero if not tracked.
+    scheduled_at_ns: RelaxedCell<u64>,
+    /// How long the task waited between being scheduled and being run the last time.
+    ns_last_queued: RelaxedCell<u64>,
     freshly_spawned: AtomicBool,
     scoped: Option<ScopedTaskMetadata>,
+    /// The threads the task may run on, `None` if it may run on any thread.
+    thread_set: Option<Arc<ThreadSet>>,
+}
+
+impl TaskMetadata {
+    /// The priority the task is scheduled with, boosting it if it waited too long to be run.
+    fn scheduling_priority(&self) -> TaskPriority {
+        let threshold = PRIORITY_BOOST_THRESHOLD_NS.load();
+        if threshold > 0 {
+            self.scheduled_at_ns.store(monotonic_ns());
+        }
+
+        if self.priority == TaskPriority::High || self.boosted.load(Ordering::Relaxed) {
+            return TaskPriority::High;
+        }
+
+        if threshold > 0 && self.ns_last_queued.load() > threshold {
+            self.boosted.store(true, Ordering::Relaxed);
+            return TaskPriority::High;
+        }
+        TaskPriority::Low
+    }
+
+    /// Record how long the task waited since it was scheduled, call right before running it.
+    fn record_queued_time(&self) {
+        let scheduled_at = self.scheduled_at_ns.load();
+        if scheduled_at > 0 {
+            self.scheduled_at_ns.store(0);
+            self.ns_last_queued
+                .store(monotonic_ns().saturating_sub(scheduled_at));
+        }
+    }
 }
 
 impl Drop for TaskMetadata {
@@ -95,6 +361,12 @@ impl Drop for TaskMetadata {
     }
 }
 
+impl SpawnLocation for TaskMetadata {
+    fn spawn_location(&self) -> &'static Location<'static> {
+        self.spawn_location
+    }
+}
+
 /// A task ready to run.
 type ReadyTask = Runnable<TaskMetadata>;
 
@@ -110,17 +382,82 @@ struct ThreadLocalTaskList {
 
 unsafe impl Sync for ThreadLocalTaskList {}
 
+/// A subset of the executor threads that the tasks of a scope are restricted to.
+///
+/// The ready tasks of the scope are queued here instead of in the global or thread-local queues,
+/// and only the threads in the subset take tasks from these queues, so the tasks never migrate
+/// to another thread.
+struct ThreadSet {
+    /// Bit `i` is set if thread `i` is in the set.
+    mask: Vec<u64>,
+    high_prio_tasks: Injector<ReadyTask>,
+    low_prio_tasks: Injector<ReadyTask>,
+}
+
+impl ThreadSet {
+    fn new(threads: &[usize], num_threads: usize) -> PolarsResult<Self> {
+        let mut mask = vec![0u64; num_threads.div_ceil(64)];
+        for &thread in threads.iter().filter(|t| **t < num_threads) {
+            mask[thread / 64] |= 1 << (thread % 64);
+        }
+        polars_ensure!(
+            mask.iter().any(|bits| *bits != 0),
+            InvalidOperation: "none of the threads {:?} is one of the {} executor threads",
+            threads, num_threads
+        );
+        Ok(Self {
+            mask,
+            high_prio_tasks: Injector::new(),
+            low_prio_tasks: Injector::new(),
+        })
+    }
+
+    fn contains(&self, thread: usize) -> bool {
+        self.mask
+            .get(thread / 64)
+            .is_some_and(|bits| (bits >> (thread % 64)) & 1 == 1)
+    }
+
+    /// Take a task from the queues of the set, high priority tasks first.
+    fn try_pop_task(&self) -> Option<ReadyTask> {
+        steal_from(&self.high_prio_tasks).or_else(|| steal_from(&self.low_prio_tasks))
+    }
+}
+
+fn steal_from(queue: &Injector<ReadyTask>) -> Option<ReadyTask> {
+    loop {
+        match queue.steal() {
+            Steal::Empty => return None,
+            Steal::Success(task) => return Some(task),
+            Steal::Retry => std::hint::spin_loop(),
+        }
+    }
+}
+
 struct Executor {
     park_group: ParkGroup,
     thread_task_lists: Vec<CachePadded<ThreadLocalTaskList>>,
     global_high_prio_task_queue: Injector<ReadyTask>,
     global_low_prio_task_queue: Injector<ReadyTask>,
+    /// The thread sets of the scopes that are restricted to a subset of the threads.
+    thread_sets: RwLock<Vec<Weak<ThreadSet>>>,
 }
 
 impl Executor {
     fn schedule_task(&self, task: ReadyTask) {
+        if is_shut_down() {
+            // Refuse the task, running it fails it without polling its future.
+            task.run();
+            return;
+        }
+
         let thread = TLS_THREAD_ID.get();
         let meta = task.metadata();
+        let priority = meta.scheduling_priority();
+        if let Some(thread_set) = meta.thread_set.clone() {
+            self.schedule_thread_set_task(task, priority, &thread_set, thread);
+            return;
+        }
         let opt_ttl = self.thread_task_lists.get(thread);
 
         let mut use_global_queue = opt_ttl.is_none();
@@ -131,7 +468,7 @@ impl Executor {
 
         if use_global_queue {
             // Scheduled from an unknown thread, add to global queue.
-            if meta.priority == TaskPriority::High {
+            if priority == TaskPriority::High {
                 self.global_high_prio_task_queue.push(task);
             } else {
                 self.global_low_prio_task_queue.push(task);
@@ -142,7 +479,7 @@ impl Executor {
             // SAFETY: this slot may only be accessed from the local thread, which we are.
             let slot = unsafe { &mut *ttl.local_slot.get() };
 
-            if meta.priority == TaskPriority::High {
+            if priority == TaskPriority::High {
                 // Insert new task into thread local slot, taking out the old task.
                 let Some(task) = slot.replace(task) else {
                     // We pushed a task into our local slot which was empty. Since
@@ -165,22 +502,56 @@ impl Executor {
         }
     }
 
+    /// Schedule a task that may only run on the threads in `thread_set`.
+    fn schedule_thread_set_task(
+        &self,
+        task: ReadyTask,
+        priority: TaskPriority,
+        thread_set: &ThreadSet,
+        thread: usize,
+    ) {
+        // The thread-local queues can be stolen from by any thread, so the task always goes into
+        // the queues of the set.
+        if priority == TaskPriority::High {
+            thread_set.high_prio_tasks.push(task);
+        } else {
+            thread_set.low_prio_tasks.push(task);
+        }
+
+        if thread_set.contains(thread) {
+            // We are awake and may take the task ourselves.
+            self.park_group.unpark_one();
+        } else {
+            // Unparking a single worker might wake one that may not take the task.
+            self.park_group.unpark_all();
+        }
+    }
+
+    /// Take a task from the global queues, high priority tasks first.
+    fn try_pop_global_task(&self) -> Option<ReadyTask> {
+        steal_from(&self.global_high_prio_task_queue)
+            .or_else(|| steal_from(&self.global_low_prio_task_queue))
+    }
+
+    /// Take a task from the thread sets that `thread` is in.
+    fn try_pop_thread_set_task(&self, thread: usize) -> Option<ReadyTask> {
+        let thread_sets = self.thread_sets.read();
+        thread_sets
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|thread_set| thread_set.contains(thread))
+            .find_map(|thread_set| thread_set.try_pop_task())
+    }
+
     fn try_steal_task<R: Rng>(&self, thread: usize, rng: &mut R) -> Option<ReadyTask> {
         // Try to get a global task.
-        loop {
-            match self.global_high_prio_task_queue.steal() {
-                Steal::Empty => break,
-                Steal::Success(task) => return Some(task),
-                Steal::Retry => std::hint::spin_loop(),
-            }
+        if let Some(task) = self.try_pop_global_task() {
+            return Some(task);
         }
 
-        loop {
-            match self.global_low_prio_task_queue.steal() {
-                Steal::Empty => break,
-                Steal::Success(task) => return Some(task),
-                Steal::Retry => std::hint::spin_loop(),
-            }
+        // Try to get a task of a scope restricted to a subset of threads including this one.
+        if let Some(task) = self.try_pop_thread_set_task(thread) {
+            return Some(task);
         }
 
         // Try to steal tasks.
@@ -212,7 +583,7 @@ impl Executor {
     fn runner(&self, thread: usize) {
         TLS_THREAD_ID.set(thread);
 
-        let mut rng = SmallRng::from_rng(&mut rand::rng());
+        let mut rng = worker_rng(EXECUTOR_RNG_SEED.get().copied(), thread);
         let mut worker = self.park_group.new_worker();
         let mut last_block_start = None;
 
@@ -255,7 +626,12 @@ impl Executor {
                     }
                 }
                 worker.recruit_next();
+                task.metadata().record_queued_time();
+
+                let active = NUM_ACTIVE_RUNNERS.fetch_add(1, Ordering::Relaxed) + 1;
+                MAX_ACTIVE_RUNNERS.fetch_max(active, Ordering::Relaxed);
                 task.run();
+                NUM_ACTIVE_RUNNERS.fetch_sub(1, Ordering::Relaxed);
             }
         }
     }
@@ -269,10 +645,13 @@ impl Executor {
                     .unwrap_or(4);
             }
 
+            let prefix = EXECUTOR_THREAD_PREFIX
+                .get()
+                .map_or("async-executor", |p| p.as_str());
             let thread_task_lists = (0..n_threads)
                 .map(|t| {
                     std::thread::Builder::new()
-                        .name(format!("async-executor-{t}"))
+                        .name(format!("{prefix}-{t}"))
                         .spawn(move || Self::global().runner(t))
                         .unwrap();
 
@@ -289,6 +668,7 @@ impl Executor {
                 thread_task_lists,
                 global_high_prio_task_queue: Injector::new(),
                 global_low_prio_task_queue: Injector::new(),
+                thread_sets: RwLock::default(),
             }
         })
     }
@@ -301,6 +681,8 @@ pub struct TaskScope<'scope, 'env: 'scope> {
     // reclaim the memory used by the cancel_handles.
     cancel_handles: Mutex<SlotMap<TaskKey, CancelHandle>>,
     completed_tasks: Arc<Mutex<Vec<TaskKey>>>,
+    // The threads the tasks may run on, `None` if they may run on any thread.
+    thread_set: Option<Arc<ThreadSet>>,
 
     // Copied from std::thread::scope. Necessary to prevent unsoundness.
     scope: PhantomData<&'scope mut &'scope ()>,
@@ -333,6 +715,25 @@ impl<'scope> TaskScope<'scope, '_> {
         <F as Future>::Output: Send + 'static,
     {
         let spawn_location = Location::caller();
+        let executor = Executor::global();
+        let on_wake = move |task| executor.schedule_task(task);
+        let thread_set = self.thread_set.clone();
+        self.spawn_with_schedule(priority, fut, on_wake, spawn_location, thread_set)
+    }
+
+    fn spawn_with_schedule<F, S>(
+        &self,
+        priority: TaskPriority,
+        fut: F,
+        on_wake: S,
+        spawn_location: &'static Location<'static>,
+        thread_set: Option<Arc<ThreadSet>>,
+    ) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'scope,
+        <F as Future>::Output: Send + 'static,
+        S: Fn(ReadyTask) + Send + Sync + Copy + 'static,
+    {
         self.clear_completed_tasks();
 
         let mut runnable = None;
@@ -340,8 +741,6 @@ impl<'scope> TaskScope<'scope, '_> {
         self.cancel_handles.lock().insert_with_key(|task_key| {
             let (run, jh) = unsafe {
                 // SAFETY: we make sure to cancel this task before 'scope ends.
-                let executor = Executor::global();
-                let on_wake = move |task| executor.schedule_task(task);
                 task::spawn_with_lifetime(
                     fut,
                     on_wake,
@@ -349,11 +748,15 @@ impl<'scope> TaskScope<'scope, '_> {
                         spawn_location,
                         ns_spent_blocked: RelaxedCell::new_u64(0),
                         priority,
+                        boosted: AtomicBool::new(false),
+                        scheduled_at_ns: RelaxedCell::new_u64(0),
+                        ns_last_queued: RelaxedCell::new_u64(0),
                         freshly_spawned: AtomicBool::new(true),
                         scoped: Some(ScopedTaskMetadata {
                             task_key,
                             completed_tasks: Arc::downgrade(&self.completed_tasks),
                         }),
+                        thread_set,
                     },
                 )
             };
@@ -368,6 +771,40 @@ impl<'scope> TaskScope<'scope, '_> {
 }
 
 pub fn task_scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope TaskScope<'scope, 'env>) -> T,
+{
+    task_scope_with_thread_set(None, f)
+}
+
+/// Like [`task_scope`], but the tasks spawned in the scope only run on the executor threads with
+/// the given indices, or on any thread if `threads` is `None`.
+///
+/// This isolates the tasks from the other work in the process, e.g. to keep some threads free for
+/// a latency-critical pipeline while a bulk job runs on the others. The tasks are never run on or
+/// stolen by the other threads, but the threads in the subset still run other tasks too. Indices
+/// that aren't executor threads are ignored.
+///
+/// # Errors
+/// Errors if none of `threads` is an executor thread, without calling `f`.
+pub fn task_scope_on_threads<'env, F, T>(threads: Option<&[usize]>, f: F) -> PolarsResult<T>
+where
+    F: for<'scope> FnOnce(&'scope TaskScope<'scope, 'env>) -> T,
+{
+    let Some(threads) = threads else {
+        return Ok(task_scope(f));
+    };
+    let executor = Executor::global();
+    let thread_set = Arc::new(ThreadSet::new(threads, executor.thread_task_lists.len())?);
+    {
+        let mut thread_sets = executor.thread_sets.write();
+        thread_sets.retain(|s| s.strong_count() > 0);
+        thread_sets.push(Arc::downgrade(&thread_set));
+    }
+    Ok(task_scope_with_thread_set(Some(thread_set), f))
+}
+
+fn task_scope_with_thread_set<'env, F, T>(thread_set: Option<Arc<ThreadSet>>, f: F) -> T
 where
     F: for<'scope> FnOnce(&'scope TaskScope<'scope, 'env>) -> T,
 {
@@ -377,6 +814,7 @@ where
     let scope = TaskScope {
         cancel_handles: Mutex::default(),
         completed_tasks: Arc::new(Mutex::default()),
+        thread_set,
         scope: PhantomData,
         env: PhantomData,
     };
@@ -407,14 +845,116 @@ where
             spawn_location,
             ns_spent_blocked: RelaxedCell::new_u64(0),
             priority,
+            boosted: AtomicBool::new(false),
+            scheduled_at_ns: RelaxedCell::new_u64(0),
+            ns_last_queued: RelaxedCell::new_u64(0),
             freshly_spawned: AtomicBool::new(true),
             scoped: None,
+            thread_set: None,
         },
     );
     runnable.schedule();
     join_handle
 }
 
+/// Spawns a task that produces a stream of values, such as the morsels of a source.
+///
+/// The task is created by calling `f` with the sending end of a channel, every value it sends is
+/// yielded by the returned [`StreamHandle`]. The stream ends once the task has completed, and
+/// dropping the handle cancels the task.
+#[allow(unused)]
+#[track_caller]
+pub fn spawn_stream<T, F, Fut>(priority: TaskPriority, f: F) -> StreamHandle<T>
+where
+    T: Send + 'static,
+    F: FnOnce(connector::Sender<T>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (send, recv) = connector::connector();
+    let producer = spawn(priority, f(send));
+    StreamHandle {
+        recv,
+        producer: Some(AbortOnDropHandle::new(producer)),
+    }
+}
+
+/// The values produced by a task spawned with [`spawn_stream`].
+pub struct StreamHandle<T> {
+    recv: connector::Receiver<T>,
+    /// `None` once the task has completed.
+    producer: Option<AbortOnDropHandle<()>>,
+}
+
+impl<T: Send> Stream for StreamHandle<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if let Poll::Ready(Ok(value)) = self.recv.poll_recv(cx) {
+            return Poll::Ready(Some(value));
+        }
+        // The channel is either empty or closed. Only end the stream once the task has completed,
+        // so that a panic in the task reaches the consumer.
+        let Some(producer) = self.producer.as_mut() else {
+            return Poll::Ready(None);
+        };
+        if Pin::new(producer).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        self.producer = None;
+        // The task may have sent a last value before it completed.
+        Poll::Ready(self.recv.try_recv().ok())
+    }
+}
+
+/// Runs a blocking closure, such as synchronous IO, on a separate pool of threads
+/// so it does not occupy one of the executor's compute threads.
+///
+/// The returned handle can be awaited from an executor task, which is then free
+/// to yield until the closure has completed.
+#[track_caller]
+pub fn spawn_blocking<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    spawn_blocking_on(BlockingPool::global(), f)
+}
+
+#[track_caller]
+fn spawn_blocking_on<F, T>(pool: &'static BlockingPool, f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let spawn_location = Location::caller();
+    let on_wake = move |task| pool.schedule_task(task);
+    let (runnable, join_handle) = task::spawn(
+        async move { f() },
+        on_wake,
+        TaskMetadata {
+            spawn_location,
+            ns_spent_blocked: RelaxedCell::new_u64(0),
+            priority: TaskPriority::Low,
+            boosted: AtomicBool::new(false),
+            scheduled_at_ns: RelaxedCell::new_u64(0),
+            ns_last_queued: RelaxedCell::new_u64(0),
+            freshly_spawned: AtomicBool::new(true),
+            scoped: None,
+            thread_set: None,
+        },
+    );
+    runnable.schedule();
+    join_handle
+}
+
+/// The random number generator a worker thread uses to pick whom to steal from.
+fn worker_rng(seed: Option<u64>, thread: usize) -> SmallRng {
+    match seed {
+        Some(seed) => SmallRng::seed_from_u64(seed.wrapping_add(thread as u64)),
+        None => SmallRng::from_rng(&mut rand::rng()),
+    }
+}
+
 fn random_permutation<R: Rng>(len: u32, rng: &mut R) -> impl Iterator<Item = u32> {
     let modulus = len.next_power_of_two();
     let halfwidth = modulus.trailing_zeros() / 2;
@@ -442,3 +982,331 @@ fn random_permutation<R: Rng>(len: u32, rng: &mut R) -> impl Iterator<Item = u32
             i
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn test_current_worker_id() {
+        let _guard = TEST_LOCK.lock();
+        assert!(!is_on_executor_thread());
+        assert_eq!(current_worker_id(), None);
+
+        let (on_executor, worker_id) = block_on(spawn(TaskPriority::High, async {
+            (is_on_executor_thread(), current_worker_id())
+        }));
+        assert!(on_executor);
+        assert!(worker_id.unwrap() < Executor::global().thread_task_lists.len());
+    }
+
+    #[test]
+    fn test_executor_rng_seed() {
+        let _guard = TEST_LOCK.lock();
+
+        // Every worker visits the other workers in the same order when seeded the same.
+        let steal_order = |thread| {
+            let mut rng = worker_rng(Some(1234), thread);
+            (0..4)
+                .flat_map(|_| random_permutation(16, &mut rng).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        for thread in 0..4 {
+            assert_eq!(steal_order(thread), steal_order(thread));
+        }
+        assert_ne!(steal_order(0), steal_order(1));
+
+        // Seeding a running executor has no effect, so it is refused.
+        init_eager();
+        assert!(set_executor_rng_seed(1234).is_err());
+        assert!(EXECUTOR_RNG_SEED.get().is_none());
+    }
+
+    #[test]
+    fn test_drain_global_queues() {
+        let _guard = TEST_LOCK.lock();
+        init_eager();
+
+        let handles: Vec<_> = (0..64)
+            .map(|i| {
+                let priority = if i % 2 == 0 {
+                    TaskPriority::High
+                } else {
+                    TaskPriority::Low
+                };
+                spawn(priority, async { current_worker_id() })
+            })
+            .collect();
+        let num_drained = drain_global_queues();
+
+        // The drained tasks ran on this thread, the others on the executor threads.
+        let worker_ids: Vec<_> = handles.into_iter().map(block_on).collect();
+        assert_eq!(
+            worker_ids.iter().filter(|id| id.is_none()).count(),
+            num_drained
+        );
+        assert!(Executor::global().try_pop_global_task().is_none());
+    }
+
+    #[test]
+    fn test_take_task_wait_statistics() {
+        let _guard = TEST_LOCK.lock();
+        let location = Location::caller();
+        *NS_SPENT_BLOCKED.lock().entry(location).or_default() += 5_000;
+
+        let stats = take_task_wait_statistics();
+        assert!(stats.contains(&(location, Duration::from_micros(5))));
+
+        // The statistics were cleared while taking them.
+        assert!(
+            !take_task_wait_statistics()
+                .iter()
+                .any(|(l, _)| *l == location)
+        );
+    }
+
+    #[test]
+    fn test_priority_boost() {
+        let _guard = TEST_LOCK.lock();
+        set_priority_boost_threshold(Some(Duration::from_millis(10)));
+
+        let meta = TaskMetadata {
+            spawn_location: Location::caller(),
+            ns_spent_blocked: RelaxedCell::new_u64(0),
+            priority: TaskPriority::Low,
+            boosted: AtomicBool::new(false),
+            scheduled_at_ns: RelaxedCell::new_u64(0),
+            ns_last_queued: RelaxedCell::new_u64(0),
+            freshly_spawned: AtomicBool::new(true),
+            scoped: None,
+            thread_set: None,
+        };
+
+        // A short wait in the queue keeps the priority.
+        assert_eq!(meta.scheduling_priority(), TaskPriority::Low);
+        meta.record_queued_time();
+        assert_eq!(meta.scheduling_priority(), TaskPriority::Low);
+
+        // Time spent running or waiting on other tasks doesn't count.
+        meta.record_queued_time();
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(meta.scheduling_priority(), TaskPriority::Low);
+
+        // A long wait boosts the next time the task is scheduled, for the rest of its life.
+        std::thread::sleep(Duration::from_millis(20));
+        meta.record_queued_time();
+        assert_eq!(meta.scheduling_priority(), TaskPriority::High);
+        set_priority_boost_threshold(None);
+        meta.record_queued_time();
+        assert_eq!(meta.scheduling_priority(), TaskPriority::High);
+    }
+
+    #[test]
+    fn test_scheduler_metrics() {
+        let _guard = TEST_LOCK.lock();
+        init_eager();
+        reset_scheduler_metrics();
+
+        // Keep several tasks running at the same time, each waits for the others to have started.
+        let num_tasks = Executor::global().thread_task_lists.len().min(2);
+        let started = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..num_tasks)
+            .map(|_| {
+                let started = started.clone();
+                spawn(TaskPriority::High, async move {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    while started.load(Ordering::SeqCst) < num_tasks {
+                        std::hint::spin_loop();
+                    }
+                })
+            })
+            .collect();
+        handles.into_iter().for_each(block_on);
+
+        let metrics = get_scheduler_metrics();
+        assert_eq!(
+            metrics.num_threads,
+            Executor::global().thread_task_lists.len()
+        );
+        assert!(metrics.max_active_runners >= num_tasks);
+        assert!(metrics.max_active_runners <= metrics.num_threads);
+
+        // Nothing runs anymore, so a new window starts from zero.
+        while NUM_ACTIVE_RUNNERS.load(Ordering::Relaxed) > 0 {
+            std::hint::spin_loop();
+        }
+        reset_scheduler_metrics();
+        assert_eq!(get_scheduler_metrics().max_active_runners, 0);
+    }
+
+    #[test]
+    fn test_panic_threshold() {
+        let _guard = TEST_LOCK.lock();
+        reset_panic_shutdown();
+
+        // The panic of the inner task is re-raised in the task joining it, but counts once.
+        let inner: JoinHandle<()> =
+            spawn(TaskPriority::High, async { panic!("inner task failed") });
+        let outer = spawn(TaskPriority::High, inner);
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(|| block_on(outer))).unwrap_err();
+        assert!(
+            payload
+                .downcast_ref::<String>()
+                .unwrap()
+                .starts_with("inner task failed")
+        );
+        assert_eq!(num_task_panics(), 1);
+
+        set_panic_threshold(Some(1));
+        assert!(!is_shut_down());
+        let failing: JoinHandle<()> =
+            spawn(TaskPriority::High, async { panic!("second task failed") });
+        assert!(std::panic::catch_unwind(AssertUnwindSafe(|| block_on(failing))).is_err());
+        assert!(is_shut_down());
+
+        // New tasks are refused until the executor is reset.
+        let refused = spawn(TaskPriority::High, async { 1 });
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(|| block_on(refused))).unwrap_err();
+        assert!(
+            payload
+                .downcast_ref::<String>()
+                .unwrap()
+                .contains("shut down after 2 task panics")
+        );
+
+        set_panic_threshold(None);
+        reset_panic_shutdown();
+        assert_eq!(block_on(spawn(TaskPriority::High, async { 1 })), 1);
+    }
+
+    #[test]
+    fn test_panic_shutdown_across_queries() {
+        let _guard = TEST_LOCK.lock();
+        reset_panic_shutdown();
+        set_panic_threshold(Some(0));
+
+        // The first query shuts the executor down.
+        let first = task_scope(|scope| {
+            let failing = scope.spawn_task(TaskPriority::High, async { panic!("query failed") });
+            std::panic::catch_unwind(AssertUnwindSafe(|| block_on(failing)))
+        });
+        assert!(first.is_err());
+        assert!(is_shut_down());
+
+        // The next query fails too, the shutdown isn't reset between queries.
+        let second = task_scope(|scope| {
+            let refused = scope.spawn_task(TaskPriority::High, async { 1 });
+            std::panic::catch_unwind(AssertUnwindSafe(|| block_on(refused)))
+        });
+        assert!(second.is_err());
+        assert!(is_shut_down());
+        assert_eq!(num_task_panics(), 1);
+
+        set_panic_threshold(None);
+        reset_panic_shutdown();
+        let third = task_scope(|scope| block_on(scope.spawn_task(TaskPriority::High, async { 1 })));
+        assert_eq!(third, 1);
+    }
+
+    #[test]
+    fn test_spawn_stream() {
+        use futures::StreamExt;
+
+        let _guard = TEST_LOCK.lock();
+        let stream = spawn_stream(TaskPriority::High, |mut send| async move {
+            for i in 0..10 {
+                if send.send(i).await.is_err() {
+                    break;
+                }
+            }
+        });
+        assert_eq!(
+            block_on(stream.collect::<Vec<_>>()),
+            (0..10).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_spawn_stream_cancel_on_drop() {
+        use futures::StreamExt;
+
+        struct SetOnDrop(Arc<AtomicBool>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let _guard = TEST_LOCK.lock();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+        let mut stream = spawn_stream(TaskPriority::High, |mut send| async move {
+            let _guard = guard;
+            // Never ends on its own, only cancelling the task drops its future.
+            for i in 0.. {
+                if send.send(i).await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            }
+        });
+        assert_eq!(block_on(stream.next()), Some(0));
+
+        // Dropping the handle cancels the task, which drops its future.
+        drop(stream);
+        let start = Instant::now();
+        while !dropped.load(Ordering::SeqCst) {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn test_task_scope_on_threads() {
+        let _guard = TEST_LOCK.lock();
+
+        let yield_now = || {
+            let mut yielded = false;
+            std::future::poll_fn(move |cx| {
+                if yielded {
+                    return Poll::Ready(());
+                }
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            })
+        };
+
+        // The tasks stay on the thread, also after being woken up again.
+        let worker_ids = task_scope_on_threads(Some(&[0]), |scope| {
+            let handles: Vec<_> = (0..32)
+                .map(|i| {
+                    let priority = if i % 2 == 0 {
+                        TaskPriority::High
+                    } else {
+                        TaskPriority::Low
+                    };
+                    scope.spawn_task(priority, async move {
+                        let mut worker_ids = vec![current_worker_id()];
+                        for _ in 0..4 {
+                            yield_now().await;
+                            worker_ids.push(current_worker_id());
+                        }
+                        worker_ids
+                    })
+                })
+                .collect();
+            handles.into_iter().flat_map(block_on).collect::<Vec<_>>()
+        })
+        .unwrap();
+        assert!(worker_ids.iter().all(|id| *id == Some(0)));
+
+        // A set without any executor thread is refused.
+        let num_threads = Executor::global().thread_task_lists.len();
+        assert!(task_scope_on_threads(Some(&[num_threads]), |_| ()).is_err());
+    }
+}
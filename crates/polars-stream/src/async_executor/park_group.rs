@@ -80,6 +80,13 @@ impl ParkGroup {
     pub fn unpark_one(&self) {
         self.inner.unpark_one();
     }
+
+    /// Unparks all idle workers, for work that only some of the workers may take.
+    ///
+    /// Also cancels in-progress park attempts.
+    pub fn unpark_all(&self) {
+        self.inner.unpark_all();
+    }
 }
 
 impl ParkGroupWorker {
@@ -206,6 +213,23 @@ impl ParkGroupInner {
         }
     }
 
+    fn unpark_all(&self) {
+        let mut num_unparked = 0;
+        let _ = self
+            .state
+            .fetch_update(Ordering::Release, Ordering::SeqCst, |state| {
+                num_unparked = state_num_idle(state);
+                let state = state - num_unparked as u64 * IDLE_UNIT;
+                Some(state.wrapping_add(VERSION_UNIT) & !PREPARING_TO_PARK_BIT)
+            });
+
+        if num_unparked > 0 {
+            let mut notifications = self.notifications.lock();
+            notifications.0 += num_unparked;
+            self.condvar.notify_all();
+        }
+    }
+
     #[cold]
     fn unpark_one_slow_as_recruiter(&self) {
         let mut notifications = self.notifications.lock();
@@ -1,7 +1,8 @@
 #![allow(unsafe_op_in_unsafe_fn)]
 use std::any::Any;
+use std::cell::Cell;
 use std::future::Future;
-use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::panic::{AssertUnwindSafe, Location, catch_unwind, resume_unwind};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::{Arc, Weak};
@@ -85,7 +86,7 @@ where
     F: Future + Send + 'a,
     F::Output: Send + 'static,
     S: Fn(Runnable<M>) + Send + Sync + Copy + 'static,
-    M: Send + Sync + 'static,
+    M: SpawnLocation + Send + Sync + 'static,
 {
     /// # Safety
     /// It is the responsibility of the caller that before lifetime 'a ends the
@@ -128,7 +129,7 @@ where
     F: Future + Send,
     F::Output: Send + 'static,
     S: Fn(Runnable<M>) + Send + Sync + Copy + 'static,
-    M: Send + Sync + 'static,
+    M: SpawnLocation + Send + Sync + 'static,
 {
     fn wake(self: Arc<Self>) {
         if self.state.wake() {
@@ -142,6 +143,38 @@ where
     }
 }
 
+/// Task metadata which knows where its task was spawned.
+pub trait SpawnLocation {
+    fn spawn_location(&self) -> &'static Location<'static>;
+}
+
+/// Adds the spawn location of the task to a panic message. Panics that
+/// propagate through joined tasks collect the location of every task on the
+/// way, innermost first. Payloads that aren't messages are left as-is.
+fn annotate_panic(
+    payload: Box<dyn Any + Send + 'static>,
+    location: &Location<'_>,
+) -> Box<dyn Any + Send + 'static> {
+    let msg = if let Some(s) = payload.downcast_ref::<&str>() {
+        *s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        return payload;
+    };
+    Box::new(format!("{msg}\n    in task spawned at {location}"))
+}
+
+thread_local!(
+    /// The address of the panic payload this thread last re-raised from a joined task, zero if
+    /// none. A task that panics because it joined a panicked task is not a new panic.
+    static RETHROWN_PANIC: Cell<usize> = const { Cell::new(0) };
+);
+
+fn payload_addr(payload: &(dyn Any + Send + 'static)) -> usize {
+    payload as *const (dyn Any + Send) as *const () as usize
+}
+
 pub trait DynTask<M>: Send + Sync {
     fn metadata(&self) -> &M;
     fn run(self: Arc<Self>) -> bool;
@@ -153,7 +186,7 @@ where
     F: Future + Send,
     F::Output: Send + 'static,
     S: Fn(Runnable<M>) + Send + Sync + Copy + 'static,
-    M: Send + Sync + 'static,
+    M: SpawnLocation + Send + Sync + 'static,
 {
     fn metadata(&self) -> &M {
         &self.metadata
@@ -163,11 +196,19 @@ where
         let mut data = self.data.lock();
 
         let poll_result = match &mut *data {
+            TaskData::Polling(..) if super::is_shut_down() => {
+                self.state.start_running();
+                *data = TaskData::Panic(super::shut_down_panic_payload());
+                drop(data);
+                self.join_waker.wake();
+                return true;
+            },
             TaskData::Polling(future, waker) => {
                 self.state.start_running();
                 // SAFETY: we always store a Task in an Arc and never move it.
                 let fut = unsafe { Pin::new_unchecked(future) };
                 let mut ctx = Context::from_waker(waker);
+                RETHROWN_PANIC.set(0);
                 catch_unwind(AssertUnwindSafe(|| {
                     try_raise_keyboard_interrupt();
                     fut.poll(&mut ctx)
@@ -178,7 +219,12 @@ where
         };
 
         *data = match poll_result {
-            Err(error) => TaskData::Panic(error),
+            Err(error) => {
+                if RETHROWN_PANIC.replace(0) != payload_addr(&*error) {
+                    super::record_task_panic();
+                }
+                TaskData::Panic(annotate_panic(error, self.metadata.spawn_location()))
+            },
             Ok(Poll::Ready(output)) => TaskData::Ready(output),
             Ok(Poll::Pending) => {
                 drop(data);
@@ -212,7 +258,7 @@ where
     F: Future + Send,
     F::Output: Send + 'static,
     S: Fn(Runnable<M>) + Send + Sync + Copy + 'static,
-    M: Send + Sync + 'static,
+    M: SpawnLocation + Send + Sync + 'static,
 {
     fn cancel_handle(self: Arc<Self>) -> CancelHandle {
         self.into_cancel_handle()
@@ -227,7 +273,10 @@ where
 
             match core::mem::replace(&mut *data, TaskData::Joined) {
                 TaskData::Ready(output) => Poll::Ready(output),
-                TaskData::Panic(error) => resume_unwind(error),
+                TaskData::Panic(error) => {
+                    RETHROWN_PANIC.set(payload_addr(&*error));
+                    resume_unwind(error)
+                },
                 TaskData::Cancelled => panic!("joined on cancelled task"),
                 _ => unreachable!("invalid TaskData when joining"),
             }
@@ -353,7 +402,7 @@ where
     F: Future + Send + 'static,
     F::Output: Send + 'static,
     S: Fn(Runnable<M>) + Send + Sync + Copy + 'static,
-    M: Send + Sync + 'static,
+    M: SpawnLocation + Send + Sync + 'static,
 {
     let task = unsafe { Task::spawn(future, schedule, metadata) };
     (task.clone().into_runnable(), task.into_join_handle())
@@ -372,7 +421,7 @@ where
     F: Future + Send + 'a,
     F::Output: Send + 'static,
     S: Fn(Runnable<M>) + Send + Sync + Copy + 'static,
-    M: Send + Sync + 'static,
+    M: SpawnLocation + Send + Sync + 'static,
 {
     let task = Task::spawn(future, schedule, metadata);
     (task.clone().into_runnable(), task.into_join_handle())
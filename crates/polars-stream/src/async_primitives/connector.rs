@@ -275,6 +275,11 @@ impl<T: Send> Receiver<T> {
     pub fn try_recv(&mut self) -> Result<T, RecvError> {
         unsafe { self.connector.try_recv() }
     }
+
+    /// Polls for a value, like polling the future returned by [`Receiver::recv`].
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, ()>> {
+        unsafe { self.connector.poll_recv(cx.waker()) }
+    }
 }
 
 impl<T> std::future::Future for RecvFuture<'_, T> {
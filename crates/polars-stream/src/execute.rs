@@ -12,6 +12,7 @@ use tokio::task::JoinHandle;
 
 use crate::async_executor;
 use crate::graph::{Graph, GraphNode, GraphNodeKey, LogicalPipeKey, PortState};
+use crate::morsel::MorselMemoryBudget;
 use crate::pipe::PhysicalPipe;
 
 #[derive(Clone)]
@@ -22,6 +23,9 @@ pub struct StreamingExecutionState {
     /// The ExecutionState passed to any non-streaming operations.
     pub in_memory_exec_state: ExecutionState,
 
+    /// The memory of the morsels in flight in this graph, sources wait on it.
+    pub morsel_memory_budget: Arc<MorselMemoryBudget>,
+
     query_tasks_send: Sender<JoinHandle<PolarsResult<()>>>,
     subphase_tasks_send: Sender<JoinHandle<PolarsResult<()>>>,
 }
@@ -146,6 +150,7 @@ fn run_subgraph(
     pipes: &[LogicalPipeKey],
     pipe_seq_offsets: &mut SecondaryMap<LogicalPipeKey, Arc<RelaxedCell<u64>>>,
     state: &StreamingExecutionState,
+    executor_threads: Option<&[usize]>,
 ) -> PolarsResult<()> {
     // Construct physical pipes for the logical pipes we'll use.
     let mut physical_pipes = SecondaryMap::new();
@@ -177,7 +182,7 @@ fn run_subgraph(
         }
     }
 
-    async_executor::task_scope(|scope| {
+    async_executor::task_scope_on_threads(executor_threads, |scope| {
         // Using SlotMap::iter_mut we can get simultaneous mutable references. By storing them and
         // removing the references from the secondary map as we do our topological sort we ensure
         // they are unique.
@@ -280,17 +285,33 @@ fn run_subgraph(
             async_executor::track_task_wait_statistics(false);
         }
         ret
-    })?;
+    })??;
 
     Ok(())
 }
 
+/// Spawn the threads of the streaming engine, so that the first query doesn't have to.
+///
+/// Latency-sensitive applications can call this at startup. Calling it more than once is fine.
+pub fn init_executor() {
+    set_executor_num_threads();
+    async_executor::init_eager();
+}
+
+fn set_executor_num_threads() -> usize {
+    // Get the number of threads from the rayon thread-pool as that respects our config.
+    let num_threads = POOL.current_num_threads();
+    async_executor::set_num_threads(num_threads);
+    num_threads
+}
+
+/// Executes the graph, with the node tasks only running on the executor threads with the given
+/// indices if `executor_threads` is set.
 pub fn execute_graph(
     graph: &mut Graph,
+    executor_threads: Option<&[usize]>,
 ) -> PolarsResult<SparseSecondaryMap<GraphNodeKey, DataFrame>> {
-    // Get the number of threads from the rayon thread-pool as that respects our config.
-    let num_pipelines = POOL.current_num_threads();
-    async_executor::set_num_threads(num_pipelines);
+    let num_pipelines = set_executor_num_threads();
 
     let (query_tasks_send, query_tasks_recv) = crossbeam_channel::unbounded();
     let (subphase_tasks_send, subphase_tasks_recv) = crossbeam_channel::unbounded();
@@ -298,6 +319,7 @@ pub fn execute_graph(
     let state = StreamingExecutionState {
         num_pipelines,
         in_memory_exec_state: ExecutionState::default(),
+        morsel_memory_budget: Arc::new(MorselMemoryBudget::new()),
         query_tasks_send,
         subphase_tasks_send,
     };
@@ -344,7 +366,14 @@ pub fn execute_graph(
         }
 
         // Run the subgraph until phase completion.
-        run_subgraph(graph, &nodes, &pipes, &mut pipe_seq_offsets, &state)?;
+        run_subgraph(
+            graph,
+            &nodes,
+            &pipes,
+            &mut pipe_seq_offsets,
+            &state,
+            executor_threads,
+        )?;
         polars_io::pl_async::get_runtime().block_on(async {
             while let Ok(handle) = subphase_tasks_recv.try_recv() {
                 handle.await.unwrap()?;
@@ -1,4 +1,10 @@
 mod async_executor;
+pub use async_executor::{
+    SchedulerMetrics, current_worker_id, drain_global_queues, get_scheduler_metrics,
+    is_on_executor_thread, is_shut_down, num_task_panics, reset_panic_shutdown,
+    reset_scheduler_metrics, set_executor_rng_seed, set_executor_thread_prefix,
+    set_panic_threshold, set_priority_boost_threshold,
+};
 mod async_primitives;
 mod skeleton;
 
@@ -7,11 +13,14 @@ use std::sync::LazyLock;
 pub use skeleton::{run_query, visualize_physical_plan};
 
 mod execute;
+pub use execute::init_executor;
 pub(crate) mod expression;
 mod graph;
 pub use skeleton::{QueryResult, StreamingQuery};
 mod morsel;
+pub use morsel::set_morsel_memory_budget;
 mod nodes;
+pub use nodes::io_sinks::partition::PartiallyWrittenFiles;
 mod physical_plan;
 mod pipe;
 mod utils;
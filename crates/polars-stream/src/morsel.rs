@@ -1,6 +1,9 @@
-use std::future::Future;
+use std::future::{Future, poll_fn};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, OnceLock};
+use std::task::{Poll, Waker};
 
+use parking_lot::Mutex;
 use polars_core::frame::DataFrame;
 use polars_utils::relaxed_cell::RelaxedCell;
 
@@ -16,6 +19,103 @@ pub fn get_ideal_morsel_size() -> usize {
     })
 }
 
+/// The number of bytes the morsels in flight in a streaming query may take up, 0 for no limit.
+static MORSEL_MEMORY_BUDGET: AtomicUsize = AtomicUsize::new(0);
+
+/// Cap the memory of the morsels in flight between the nodes of each streaming query at `bytes`,
+/// or remove the cap with `None`. Applies to the queries started after this call.
+///
+/// Sources pause before producing their next morsel while the cap is exceeded, until enough
+/// morsels are consumed. The size of a morsel is the estimated size of its `DataFrame`. A morsel
+/// is accounted for from the moment it is sent by a source until a node takes its `DataFrame`
+/// out or buffers it, so the data nodes accumulate, e.g. for a sort, the build side of a join or
+/// the inputs of a zip, doesn't count towards the cap.
+pub fn set_morsel_memory_budget(bytes: Option<usize>) {
+    MORSEL_MEMORY_BUDGET.store(bytes.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// The memory of the morsels in flight between the nodes of a single graph, see
+/// [`set_morsel_memory_budget`].
+#[derive(Debug)]
+pub struct MorselMemoryBudget {
+    /// 0 for no limit.
+    limit: usize,
+    in_use: AtomicUsize,
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl MorselMemoryBudget {
+    /// A budget with the limit set by [`set_morsel_memory_budget`].
+    pub fn new() -> Self {
+        Self {
+            limit: MORSEL_MEMORY_BUDGET.load(Ordering::Relaxed),
+            in_use: AtomicUsize::new(0),
+            waiters: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn is_limited(&self) -> bool {
+        self.limit > 0
+    }
+
+    fn has_memory(&self) -> bool {
+        !self.is_limited() || self.in_use.load(Ordering::Acquire) < self.limit
+    }
+
+    fn wake_waiters(&self) {
+        let waiters = std::mem::take(&mut *self.waiters.lock());
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+
+    /// Waits until the memory of the morsels in flight is below the limit.
+    ///
+    /// Only sources may wait on this. Any other node could hold up the morsels whose memory it
+    /// waits for.
+    pub async fn wait(&self) {
+        poll_fn(|cx| {
+            if self.has_memory() {
+                return Poll::Ready(());
+            }
+
+            // Check again while holding the lock to prevent missed notifications.
+            let mut waiters = self.waiters.lock();
+            if self.has_memory() {
+                return Poll::Ready(());
+            }
+            if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+/// The memory of a morsel, counted towards a [`MorselMemoryBudget`] until this is dropped.
+#[derive(Debug)]
+pub struct MemoryReservation {
+    budget: Arc<MorselMemoryBudget>,
+    bytes: usize,
+}
+
+impl MemoryReservation {
+    fn new(budget: Arc<MorselMemoryBudget>, bytes: usize) -> Self {
+        budget.in_use.fetch_add(bytes, Ordering::AcqRel);
+        Self { budget, bytes }
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.budget.in_use.fetch_sub(self.bytes, Ordering::AcqRel);
+        if self.budget.has_memory() {
+            self.budget.wake_waiters();
+        }
+    }
+}
+
 /// A token indicating the order of morsels in a stream.
 ///
 /// The sequence tokens going through a pipe are monotonely non-decreasing and are allowed to be
@@ -90,6 +190,9 @@ pub struct Morsel {
 
     /// Used to notify someone when this morsel is consumed, to provide backpressure.
     consume_token: Option<WaitToken>,
+
+    /// Counts the memory of this morsel towards a [`MorselMemoryBudget`], shared by its clones.
+    memory_reservation: Option<Arc<MemoryReservation>>,
 }
 
 impl Morsel {
@@ -99,6 +202,7 @@ impl Morsel {
             seq,
             source_token,
             consume_token: None,
+            memory_reservation: None,
         }
     }
 
@@ -158,6 +262,23 @@ impl Morsel {
         self.consume_token.take()
     }
 
+    /// Count the memory of this morsel towards `budget`, until the morsel and all its clones are
+    /// dropped, their `DataFrame` is taken out or [`Morsel::release_memory`] is called. Does
+    /// nothing if the budget has no limit.
+    pub fn reserve_memory(&mut self, budget: &Arc<MorselMemoryBudget>) {
+        if budget.is_limited() {
+            let bytes = self.df.estimated_size();
+            self.memory_reservation = Some(Arc::new(MemoryReservation::new(budget.clone(), bytes)));
+        }
+    }
+
+    /// Stop counting the memory of this morsel towards its [`MorselMemoryBudget`]. Nodes that
+    /// buffer morsels call this, as the sources could otherwise wait for the buffered morsels to
+    /// be consumed.
+    pub fn release_memory(&mut self) {
+        self.memory_reservation = None;
+    }
+
     pub fn source_token(&self) -> &SourceToken {
         &self.source_token
     }
@@ -0,0 +1,141 @@
+use polars_core::prelude::{Column, DataType, IntoColumn};
+use polars_core::scalar::Scalar;
+use polars_core::series::Series;
+use polars_core::utils::last_non_null;
+use polars_error::polars_bail;
+use polars_utils::IdxSize;
+
+use super::compute_node_prelude::*;
+
+/// The cumulative aggregations supported by [`CumAggNode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CumAggKind {
+    Sum,
+    Min,
+    Max,
+    Count,
+}
+
+/// Computes a running aggregation over a single column, carrying the state from one morsel to
+/// the next. The morsels must be received in order.
+pub struct CumAggNode {
+    kind: CumAggKind,
+    dtype: DataType,
+
+    /// The aggregate up to and including the last non-null value seen so far.
+    carry: Option<Scalar>,
+    /// The number of non-null values seen so far.
+    count: IdxSize,
+    last_seq: MorselSeq,
+}
+
+impl CumAggNode {
+    pub fn new(kind: CumAggKind, dtype: DataType) -> Self {
+        Self {
+            kind,
+            dtype,
+            carry: None,
+            count: 0,
+            last_seq: MorselSeq::default(),
+        }
+    }
+
+    fn aggregate(&mut self, column: &Column) -> PolarsResult<Column> {
+        let s = column.as_materialized_series();
+
+        if self.kind == CumAggKind::Count {
+            let out = polars_ops::series::cum_count(s, false)? + self.count;
+            self.count += (s.len() - s.null_count()) as IdxSize;
+            return Ok(out.into_column());
+        }
+
+        // Prepend the carried aggregate so the aggregation continues where the previous morsel
+        // left off, and slice it off again afterwards.
+        let s = s.cast(&self.dtype)?;
+        let (mut input, offset) = match &self.carry {
+            Some(carry) => (carry.clone().into_series(s.name().clone()), 1),
+            None => (Series::new_empty(s.name().clone(), &self.dtype), 0),
+        };
+        input.append(&s)?;
+
+        let out = match self.kind {
+            CumAggKind::Sum => polars_ops::series::cum_sum(&input, false)?,
+            CumAggKind::Min => polars_ops::series::cum_min(&input, false)?,
+            CumAggKind::Max => polars_ops::series::cum_max(&input, false)?,
+            CumAggKind::Count => unreachable!(),
+        };
+        let out = out.slice(offset, s.len());
+
+        let validities = out.chunks().iter().map(|arr| arr.validity());
+        if let Some(idx) = last_non_null(validities, out.len()) {
+            let value = out.get(idx)?.into_static();
+            self.carry = Some(Scalar::new(self.dtype.clone(), value));
+        }
+
+        Ok(out.into_column())
+    }
+}
+
+impl ComputeNode for CumAggNode {
+    fn name(&self) -> &str {
+        match self.kind {
+            CumAggKind::Sum => "cum_sum",
+            CumAggKind::Min => "cum_min",
+            CumAggKind::Max => "cum_max",
+            CumAggKind::Count => "cum_count",
+        }
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        _state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 1 && send.len() == 1);
+        recv.swap_with_slice(send);
+        Ok(())
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        _state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert_eq!(recv_ports.len(), 1);
+        assert_eq!(send_ports.len(), 1);
+
+        let mut recv = recv_ports[0].take().unwrap().serial();
+        let mut send = send_ports[0].take().unwrap().serial();
+
+        join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+            while let Ok(mut m) = recv.recv().await {
+                if m.seq() < self.last_seq {
+                    polars_bail!(
+                        ComputeError: "{} received its input out of order",
+                        self.name()
+                    );
+                }
+                self.last_seq = m.seq();
+
+                let df = m.df_mut();
+                if df.height() == 0 {
+                    continue;
+                }
+
+                assert_eq!(df.width(), 1);
+                let column = self.aggregate(&df[0])?;
+                *df = unsafe { DataFrame::new_no_checks(column.len(), vec![column]) };
+
+                if send.send(m).await.is_err() {
+                    break;
+                }
+            }
+
+            Ok(())
+        }));
+    }
+}
@@ -1,19 +1,22 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 use polars_core::POOL;
 use polars_core::prelude::{IntoColumn, PlHashSet, PlRandomState};
 use polars_core::schema::Schema;
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_expr::EvictIdx;
 use polars_expr::groups::Grouper;
 use polars_expr::hash_keys::HashKeys;
 use polars_expr::hot_groups::{HotGrouper, new_hash_hot_grouper};
 use polars_expr::reduce::GroupedReduction;
-use polars_utils::IdxSize;
 use polars_utils::cardinality_sketch::CardinalitySketch;
 use polars_utils::hashing::HashPartitioner;
 use polars_utils::itertools::Itertools;
 use polars_utils::pl_str::PlSmallStr;
 use polars_utils::sparse_init_vec::SparseInitVec;
+use polars_utils::{IdxSize, format_pl_smallstr};
 use rayon::prelude::*;
 
 use super::compute_node_prelude::*;
@@ -28,7 +31,99 @@ const DEFAULT_HOT_TABLE_SIZE: usize = 4;
 #[cfg(not(debug_assertions))]
 const DEFAULT_HOT_TABLE_SIZE: usize = 4096;
 
-struct LocalGroupBySinkState {
+static NEXT_SPILL_DIR_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Where and when the cold rows of a group-by are spilled to disk.
+///
+/// Every pipeline buffers the rows whose keys aren't in its hot table until the input is done.
+/// With a high cardinality these can exceed the available memory, so once the rows buffered by a
+/// pipeline exceed `max_cold_bytes` they are split by partition and written to an IPC file per
+/// partition. When combining, every partition reads its files back and inserts them like the
+/// buffered rows. The evicted pre-aggregates of the hot tables stay in memory.
+struct GroupBySpill {
+    dir: PathBuf,
+    max_cold_bytes: usize,
+    #[cfg_attr(not(feature = "ipc"), allow(unused))]
+    num_files: AtomicUsize,
+}
+
+impl GroupBySpill {
+    /// Spill if `POLARS_GROUP_BY_SPILL_BYTES` is set, or spill all cold rows if
+    /// `POLARS_FORCE_OOC` is set.
+    fn from_env() -> Option<Self> {
+        if !cfg!(feature = "ipc") {
+            return None;
+        }
+        let max_cold_bytes = if std::env::var("POLARS_FORCE_OOC").as_deref() == Ok("1") {
+            0
+        } else {
+            std::env::var("POLARS_GROUP_BY_SPILL_BYTES")
+                .ok()?
+                .parse::<usize>()
+                .unwrap()
+        };
+        let dir = polars_io::path_utils::POLARS_TEMP_DIR_BASE_PATH.join(format!(
+            "group-by-spill-{}-{}",
+            std::process::id(),
+            NEXT_SPILL_DIR_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        Some(Self {
+            dir,
+            max_cold_bytes,
+            num_files: AtomicUsize::new(0),
+        })
+    }
+
+    #[cfg(feature = "ipc")]
+    fn write(&self, mut df: DataFrame) -> PolarsResult<PathBuf> {
+        use polars_io::SerWriter;
+        use polars_io::ipc::IpcWriter;
+
+        std::fs::create_dir_all(&self.dir)?;
+        let i = self.num_files.fetch_add(1, Ordering::Relaxed);
+        let path = self.dir.join(format!("{i}.ipc"));
+        let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        IpcWriter::new(file).finish(&mut df)?;
+        Ok(path)
+    }
+
+    #[cfg(not(feature = "ipc"))]
+    fn write(&self, _df: DataFrame) -> PolarsResult<PathBuf> {
+        unreachable!()
+    }
+
+    #[cfg(feature = "ipc")]
+    fn read(path: &Path) -> PolarsResult<DataFrame> {
+        use polars_io::SerReader;
+        use polars_io::ipc::IpcReader;
+
+        let df = IpcReader::new(std::fs::File::open(path)?).finish()?;
+        std::fs::remove_file(path)?;
+        Ok(df)
+    }
+
+    #[cfg(not(feature = "ipc"))]
+    fn read(_path: &Path) -> PolarsResult<DataFrame> {
+        unreachable!()
+    }
+}
+
+impl Drop for GroupBySpill {
+    fn drop(&mut self) {
+        // The files are removed once they're read, this only cleans up after errors.
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// The cold rows of one partition spilled by a pipeline.
+struct SpillFile {
+    path: PathBuf,
+    // The rows in the file are the cold rows of consecutive morsels, these are the sequence ids
+    // and number of rows of those morsels.
+    runs: Vec<(u64, IdxSize)>,
+}
+
+pub(crate) struct LocalGroupBySinkState {
     hot_grouper: Box<dyn HotGrouper>,
     hot_grouped_reductions: Vec<Box<dyn GroupedReduction>>,
 
@@ -43,10 +138,32 @@ struct LocalGroupBySinkState {
     morsel_idxs_values_per_p: Vec<Vec<IdxSize>>,
     morsel_idxs_offsets_per_p: Vec<usize>,
 
+    // If we spill, the key columns of cold_morsels[i], the size of the cold morsels and the files
+    // spilled per partition.
+    cold_keys: Vec<DataFrame>,
+    cold_bytes: usize,
+    spill_files_per_p: Vec<Vec<SpillFile>>,
+
     // Similar to the above, but for (evicted) pre-aggregates.
     pre_aggs: Vec<(HashKeys, Vec<Box<dyn GroupedReduction>>)>,
     pre_agg_idxs_values_per_p: Vec<Vec<IdxSize>>,
     pre_agg_idxs_offsets_per_p: Vec<usize>,
+
+    // Scratch space for inserting a morsel.
+    hot_idxs: Vec<IdxSize>,
+    hot_group_idxs: Vec<EvictIdx>,
+    cold_idxs: Vec<IdxSize>,
+}
+
+/// The state shared by the pipelines of a group-by sink which is needed to insert a morsel.
+#[derive(Clone, Copy)]
+pub(crate) struct GroupByInsertContext<'a> {
+    key_selectors: &'a [StreamExpr],
+    uniq_grouped_reduction_cols: &'a [PlSmallStr],
+    grouped_reduction_cols: &'a [PlSmallStr],
+    random_state: PlRandomState,
+    partitioner: &'a HashPartitioner,
+    spill: Option<&'a GroupBySpill>,
 }
 
 impl LocalGroupBySinkState {
@@ -67,12 +184,108 @@ impl LocalGroupBySinkState {
             morsel_idxs_values_per_p: vec![Vec::new(); num_partitions],
             morsel_idxs_offsets_per_p: vec![0; num_partitions],
 
+            cold_keys: Vec::new(),
+            cold_bytes: 0,
+            spill_files_per_p: (0..num_partitions).map(|_| Vec::new()).collect(),
+
             pre_aggs: Vec::new(),
             pre_agg_idxs_values_per_p: vec![Vec::new(); num_partitions],
             pre_agg_idxs_offsets_per_p: vec![0; num_partitions],
+
+            hot_idxs: Vec::new(),
+            hot_group_idxs: Vec::new(),
+            cold_idxs: Vec::new(),
         }
     }
 
+    /// Insert the rows of a morsel into the hot table, buffering the rows with cold keys.
+    pub(crate) async fn insert_morsel(
+        &mut self,
+        mut df: DataFrame,
+        seq: u64,
+        ctx: &GroupByInsertContext<'_>,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        // Compute hot group indices from key.
+        let mut key_columns = Vec::new();
+        for selector in ctx.key_selectors {
+            let s = selector.evaluate(&df, &state.in_memory_exec_state).await?;
+            key_columns.push(s.into_column());
+        }
+        let keys = DataFrame::new_with_broadcast_len(key_columns, df.height())?;
+        let hash_keys = HashKeys::from_df(&keys, ctx.random_state, true, false);
+
+        self.hot_idxs.clear();
+        self.hot_group_idxs.clear();
+        self.cold_idxs.clear();
+        self.hot_grouper.insert_keys(
+            &hash_keys,
+            &mut self.hot_idxs,
+            &mut self.hot_group_idxs,
+            &mut self.cold_idxs,
+        );
+
+        // Drop columns not used for reductions (key-only columns).
+        if ctx.uniq_grouped_reduction_cols.len() < ctx.grouped_reduction_cols.len() {
+            df = df._select_impl(ctx.uniq_grouped_reduction_cols).unwrap();
+        }
+        df.rechunk_mut(); // For gathers.
+
+        // Update hot reductions.
+        for (col, reduction) in ctx
+            .grouped_reduction_cols
+            .iter()
+            .zip(&mut self.hot_grouped_reductions)
+        {
+            unsafe {
+                // SAFETY: we resize the reduction to the number of groups beforehand.
+                reduction.resize(self.hot_grouper.num_groups());
+                reduction.update_groups_while_evicting(
+                    df.column(col).unwrap(),
+                    &self.hot_idxs,
+                    &self.hot_group_idxs,
+                    seq,
+                )?;
+            }
+        }
+
+        // Store cold keys.
+        // TODO: don't always gather, if majority cold simply store all and remember offsets into it.
+        if !self.cold_idxs.is_empty() {
+            unsafe {
+                let cold_keys = hash_keys.gather_unchecked(&self.cold_idxs);
+                let cold_df = df.take_slice_unchecked_impl(&self.cold_idxs, false);
+
+                cold_keys.gen_idxs_per_partition(
+                    ctx.partitioner,
+                    &mut self.morsel_idxs_values_per_p,
+                    &mut self.sketch_per_p,
+                    true,
+                );
+                self.morsel_idxs_offsets_per_p
+                    .extend(self.morsel_idxs_values_per_p.iter().map(|vp| vp.len()));
+                if ctx.spill.is_some() {
+                    let cold_key_df = keys.take_slice_unchecked_impl(&self.cold_idxs, false);
+                    self.cold_bytes += cold_key_df.estimated_size() + cold_df.estimated_size();
+                    self.cold_keys.push(cold_key_df);
+                }
+                self.cold_morsels.push((seq, cold_keys, cold_df));
+            }
+
+            if let Some(spill) = ctx.spill {
+                if self.cold_bytes > spill.max_cold_bytes {
+                    self.spill_cold_morsels(spill, ctx.uniq_grouped_reduction_cols)?;
+                }
+            }
+        }
+
+        // If we have too many evicted rows, flush them.
+        if self.hot_grouper.num_evictions() >= get_ideal_morsel_size() {
+            self.flush_evictions(ctx.partitioner);
+        }
+        Ok(())
+    }
+
     fn flush_evictions(&mut self, partitioner: &HashPartitioner) {
         let hash_keys = self.hot_grouper.take_evicted_keys();
         let reductions = self
@@ -83,6 +296,57 @@ impl LocalGroupBySinkState {
         self.add_pre_agg(hash_keys, reductions, partitioner);
     }
 
+    /// Write the cold morsels to a file per partition and drop them.
+    fn spill_cold_morsels(
+        &mut self,
+        spill: &GroupBySpill,
+        uniq_grouped_reduction_cols: &[PlSmallStr],
+    ) -> PolarsResult<()> {
+        let num_partitions = self.spill_files_per_p.len();
+        for p in 0..num_partitions {
+            let mut dfs = Vec::new();
+            let mut runs = Vec::new();
+            for (i, ((seq, _, cols), keys)) in
+                self.cold_morsels.iter().zip(&self.cold_keys).enumerate()
+            {
+                let start = self.morsel_idxs_offsets_per_p[i * num_partitions + p];
+                let stop = self.morsel_idxs_offsets_per_p[(i + 1) * num_partitions + p];
+                if start == stop {
+                    continue;
+                }
+                let idxs = &self.morsel_idxs_values_per_p[p][start..stop];
+
+                // Keys and reduction columns can share names, so we name the columns by position.
+                let mut columns =
+                    unsafe { keys.take_slice_unchecked_impl(idxs, false) }.take_columns();
+                for c in uniq_grouped_reduction_cols {
+                    let values = cols.column(c).unwrap();
+                    columns.push(unsafe { values.take_slice_unchecked(idxs) });
+                }
+                for (j, c) in columns.iter_mut().enumerate() {
+                    c.rename(format_pl_smallstr!("{j}"));
+                }
+                dfs.push(unsafe { DataFrame::new_no_checks(idxs.len(), columns) });
+                runs.push((*seq, idxs.len() as IdxSize));
+            }
+
+            if !dfs.is_empty() {
+                let path = spill.write(accumulate_dataframes_vertical_unchecked(dfs))?;
+                self.spill_files_per_p[p].push(SpillFile { path, runs });
+            }
+        }
+
+        self.cold_morsels.clear();
+        self.cold_keys.clear();
+        self.cold_bytes = 0;
+        for values in &mut self.morsel_idxs_values_per_p {
+            values.clear();
+        }
+        self.morsel_idxs_offsets_per_p.clear();
+        self.morsel_idxs_offsets_per_p.resize(num_partitions, 0);
+        Ok(())
+    }
+
     fn add_pre_agg(
         &mut self,
         hash_keys: HashKeys,
@@ -110,9 +374,23 @@ struct GroupBySinkState {
     locals: Vec<LocalGroupBySinkState>,
     random_state: PlRandomState,
     partitioner: HashPartitioner,
+    spill: Option<GroupBySpill>,
 }
 
 impl GroupBySinkState {
+    /// Split into the context shared by the pipelines and the state local to every pipeline.
+    fn split_locals(&mut self) -> (GroupByInsertContext<'_>, &mut [LocalGroupBySinkState]) {
+        let ctx = GroupByInsertContext {
+            key_selectors: &self.key_selectors,
+            uniq_grouped_reduction_cols: &self.uniq_grouped_reduction_cols,
+            grouped_reduction_cols: &self.grouped_reduction_cols,
+            random_state: self.random_state,
+            partitioner: &self.partitioner,
+            spill: self.spill.as_ref(),
+        };
+        (ctx, &mut self.locals)
+    }
+
     fn spawn<'env, 's>(
         &'env mut self,
         scope: &'s TaskScope<'s, 'env>,
@@ -120,85 +398,14 @@ impl GroupBySinkState {
         state: &'s StreamingExecutionState,
         join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
     ) {
-        for (mut recv, local) in receivers.into_iter().zip(&mut self.locals) {
-            let key_selectors = &self.key_selectors;
-            let uniq_grouped_reduction_cols = &self.uniq_grouped_reduction_cols;
-            let grouped_reduction_cols = &self.grouped_reduction_cols;
-            let random_state = &self.random_state;
-            let partitioner = self.partitioner.clone();
+        let (ctx, locals) = self.split_locals();
+        for (mut recv, local) in receivers.into_iter().zip(locals) {
             join_handles.push(scope.spawn_task(TaskPriority::High, async move {
-                let mut hot_idxs = Vec::new();
-                let mut hot_group_idxs = Vec::new();
-                let mut cold_idxs = Vec::new();
                 while let Ok(morsel) = recv.recv().await {
-                    // Compute hot group indices from key.
                     let seq = morsel.seq().to_u64();
-                    let mut df = morsel.into_df();
-                    let mut key_columns = Vec::new();
-                    for selector in key_selectors {
-                        let s = selector.evaluate(&df, &state.in_memory_exec_state).await?;
-                        key_columns.push(s.into_column());
-                    }
-                    let keys = DataFrame::new_with_broadcast_len(key_columns, df.height())?;
-                    let hash_keys = HashKeys::from_df(&keys, *random_state, true, false);
-
-                    hot_idxs.clear();
-                    hot_group_idxs.clear();
-                    cold_idxs.clear();
-                    local.hot_grouper.insert_keys(
-                        &hash_keys,
-                        &mut hot_idxs,
-                        &mut hot_group_idxs,
-                        &mut cold_idxs,
-                    );
-
-                    // Drop columns not used for reductions (key-only columns).
-                    if uniq_grouped_reduction_cols.len() < grouped_reduction_cols.len() {
-                        df = df._select_impl(uniq_grouped_reduction_cols).unwrap();
-                    }
-                    df.rechunk_mut(); // For gathers.
-
-                    // Update hot reductions.
-                    for (col, reduction) in grouped_reduction_cols
-                        .iter()
-                        .zip(&mut local.hot_grouped_reductions)
-                    {
-                        unsafe {
-                            // SAFETY: we resize the reduction to the number of groups beforehand.
-                            reduction.resize(local.hot_grouper.num_groups());
-                            reduction.update_groups_while_evicting(
-                                df.column(col).unwrap(),
-                                &hot_idxs,
-                                &hot_group_idxs,
-                                seq,
-                            )?;
-                        }
-                    }
-
-                    // Store cold keys.
-                    // TODO: don't always gather, if majority cold simply store all and remember offsets into it.
-                    if !cold_idxs.is_empty() {
-                        unsafe {
-                            let cold_keys = hash_keys.gather_unchecked(&cold_idxs);
-                            let cold_df = df.take_slice_unchecked_impl(&cold_idxs, false);
-
-                            cold_keys.gen_idxs_per_partition(
-                                &partitioner,
-                                &mut local.morsel_idxs_values_per_p,
-                                &mut local.sketch_per_p,
-                                true,
-                            );
-                            local
-                                .morsel_idxs_offsets_per_p
-                                .extend(local.morsel_idxs_values_per_p.iter().map(|vp| vp.len()));
-                            local.cold_morsels.push((seq, cold_keys, cold_df));
-                        }
-                    }
-
-                    // If we have too many evicted rows, flush them.
-                    if local.hot_grouper.num_evictions() >= get_ideal_morsel_size() {
-                        local.flush_evictions(&partitioner);
-                    }
+                    local
+                        .insert_morsel(morsel.into_df(), seq, &ctx, state)
+                        .await?;
                 }
                 Ok(())
             }));
@@ -248,6 +455,9 @@ impl GroupBySinkState {
         let grouper_template = &self.grouper;
         let grouped_reductions_template = &self.grouped_reductions;
         let grouped_reduction_cols = &self.grouped_reduction_cols;
+        let uniq_grouped_reduction_cols = &self.uniq_grouped_reduction_cols;
+        let num_keys = self.key_selectors.len();
+        let random_state = self.random_state;
 
         async_executor::task_scope(|s| {
             // Wrap in outer Arc to move to each thread, performing the
@@ -375,6 +585,48 @@ impl GroupBySinkState {
                         }
                     }
 
+                    // Insert spilled morsels.
+                    for l in locals {
+                        for file in &l.spill_files_per_p[p] {
+                            let df = GroupBySpill::read(&file.path)?;
+                            let height = df.height();
+                            let mut columns = df.take_columns();
+                            let mut values = columns.split_off(num_keys);
+                            for (c, name) in values.iter_mut().zip(uniq_grouped_reduction_cols) {
+                                c.rename(name.clone());
+                            }
+                            let keys = unsafe { DataFrame::new_no_checks(height, columns) };
+                            let cols = unsafe { DataFrame::new_no_checks(height, values) };
+                            let keys = HashKeys::from_df(&keys, random_state, true, false);
+
+                            let mut offset = 0;
+                            for (seq_id, len) in &file.runs {
+                                let idxs = (offset..offset + len).collect_vec();
+                                offset += len;
+                                unsafe {
+                                    group_idxs.clear();
+                                    p_grouper.insert_keys_subset(
+                                        &keys,
+                                        &idxs,
+                                        Some(&mut group_idxs),
+                                    );
+                                    for (c, r) in
+                                        grouped_reduction_cols.iter().zip(&mut p_reductions)
+                                    {
+                                        let values = cols.column(c.as_str()).unwrap();
+                                        r.resize(p_grouper.num_groups());
+                                        r.update_groups_subset(
+                                            values,
+                                            &idxs,
+                                            &group_idxs,
+                                            *seq_id,
+                                        )?;
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // We're done, help others out by doing drops.
                     drop(drop_q_send); // So we don't deadlock trying to receive from ourselves.
                     while let Ok(to_drop) = drop_q_recv.recv().await {
@@ -499,6 +751,7 @@ impl GroupByNode {
                 grouped_reduction_cols,
                 locals,
                 partitioner,
+                spill: GroupBySpill::from_env(),
             }),
             key_schema,
             output_schema,
@@ -506,6 +759,19 @@ impl GroupByNode {
     }
 }
 
+impl GroupByNode {
+    /// The context and the per-pipeline state to insert morsels into the sink without receiving
+    /// them from the input port, or `None` if the sink is finished.
+    pub(crate) fn sink_pipelines(
+        &mut self,
+    ) -> Option<(GroupByInsertContext<'_>, &mut [LocalGroupBySinkState])> {
+        match &mut self.state {
+            GroupByState::Sink(sink) => Some(sink.split_locals()),
+            GroupByState::Source(_) | GroupByState::Done => None,
+        }
+    }
+}
+
 impl ComputeNode for GroupByNode {
     fn name(&self) -> &str {
         "group-by"
@@ -69,12 +69,13 @@ impl ComputeNode for InMemorySourceNode {
         scope: &'s TaskScope<'s, 'env>,
         recv_ports: &mut [Option<RecvPort<'_>>],
         send_ports: &mut [Option<SendPort<'_>>],
-        _state: &'s StreamingExecutionState,
+        state: &'s StreamingExecutionState,
         join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
     ) {
         assert!(recv_ports.is_empty() && send_ports.len() == 1);
         let senders = send_ports[0].take().unwrap().parallel();
         let source = self.source.as_ref().unwrap();
+        let budget = &state.morsel_memory_budget;
 
         // TODO: can this just be serial, using the work distributor?
         let source_token = SourceToken::new();
@@ -84,6 +85,7 @@ impl ComputeNode for InMemorySourceNode {
             join_handles.push(scope.spawn_task(TaskPriority::Low, async move {
                 let wait_group = WaitGroup::default();
                 loop {
+                    budget.wait().await;
                     let seq = slf.seq.fetch_add(1, Ordering::Relaxed);
                     let offset = (seq as usize * slf.morsel_size) as i64;
                     let df = source.slice(offset, slf.morsel_size);
@@ -97,6 +99,7 @@ impl ComputeNode for InMemorySourceNode {
                     let morsel_seq = MorselSeq::new(seq).offset_by(slf.seq_offset);
                     let mut morsel = Morsel::new(df, morsel_seq, source_token.clone());
                     morsel.set_consume_token(wait_group.token());
+                    morsel.reserve_memory(budget);
                     if send.send(morsel).await.is_err() {
                         break;
                     }
@@ -1,8 +1,11 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use arrow::array::builder::ShareStrategy;
 use polars_core::frame::DataFrame;
 use polars_core::prelude::{
-    AnyValue, ChunkedBuilder, DataType, IntoColumn, PrimitiveChunkedBuilder, StringChunkedBuilder,
-    StructChunked, UInt64Type,
+    AnyValue, BooleanChunkedBuilder, ChunkedBuilder, DataType, IntoColumn, PrimitiveChunkedBuilder,
+    StringChunkedBuilder, StructChunked, UInt64Type,
 };
 use polars_core::schema::Schema;
 use polars_core::series::builder::SeriesBuilder;
@@ -22,6 +25,8 @@ pub struct WriteMetrics {
     pub file_size: u64,
     /// Keys of the partition.
     pub keys: Option<Vec<AnyValue<'static>>>,
+    /// The partition target callback skipped the file, so its rows weren't written.
+    pub skipped: bool,
     /// Metrics for each column.
     pub columns: Vec<WriteMetricsColumn>,
 }
@@ -49,6 +54,7 @@ impl WriteMetrics {
             file_size: 0,
             num_rows: 0,
             keys: None,
+            skipped: false,
             columns: schema
                 .iter_values()
                 .cloned()
@@ -90,7 +96,7 @@ impl WriteMetrics {
         metrics: Vec<Self>,
         input_schema: &Schema,
         key_schema: Option<&Schema>,
-    ) -> DataFrame {
+    ) -> PolarsResult<DataFrame> {
         let num_metrics = metrics.len();
 
         let mut path = StringChunkedBuilder::new(PlSmallStr::from_static("path"), num_metrics);
@@ -102,6 +108,8 @@ impl WriteMetrics {
             PlSmallStr::from_static("file_size"),
             num_metrics,
         );
+        let mut skipped =
+            BooleanChunkedBuilder::new(PlSmallStr::from_static("skipped"), num_metrics);
         let mut keys = key_schema.map(|s| {
             (0..s.len())
                 .map(|_| Vec::with_capacity(metrics.len()))
@@ -131,6 +139,7 @@ impl WriteMetrics {
             path.append_value(m.path);
             num_rows.append_value(m.num_rows);
             file_size.append_value(m.file_size);
+            skipped.append_value(m.skipped);
             match (&mut keys, m.keys) {
                 (None, None) => {},
                 (Some(keys), Some(m_keys)) => {
@@ -155,41 +164,28 @@ impl WriteMetrics {
             }
         }
 
-        let mut df_columns = Vec::with_capacity(4 + input_schema.len());
+        let mut df_columns = Vec::with_capacity(5 + input_schema.len());
         df_columns.push(path.finish().into_column());
         df_columns.push(num_rows.finish().into_column());
         df_columns.push(file_size.finish().into_column());
-        match (keys, key_schema) {
-            (None, None) => df_columns.push(
-                StructChunked::from_series(
-                    PlSmallStr::from_static("keys"),
-                    num_metrics,
-                    [].into_iter(),
-                )
-                .unwrap()
-                .into_column(),
-            ),
-            (Some(keys), Some(key_schema)) => {
-                let keys = keys
-                    .into_iter()
-                    .zip(key_schema.iter())
-                    .map(|(key, (name, dtype))| {
-                        Series::from_any_values_and_dtype(name.clone(), key.as_slice(), dtype, true)
-                            .unwrap()
-                    })
-                    .collect::<Vec<Series>>();
-                df_columns.push(
-                    StructChunked::from_series(
-                        PlSmallStr::from_static("keys"),
-                        num_metrics,
-                        keys.iter(),
-                    )
-                    .unwrap()
-                    .into_column(),
-                );
-            },
+        df_columns.push(skipped.finish().into_column());
+        // Every key is a field of the `keys` column with the dtype of the key, also if the key is
+        // a struct itself, so the written partitions can be filtered by key with `struct.field`.
+        let keys = match (keys, key_schema) {
+            (None, None) => Vec::new(),
+            (Some(keys), Some(key_schema)) => keys
+                .into_iter()
+                .zip(key_schema.iter())
+                .map(|(key, (name, dtype))| {
+                    Series::from_any_values_and_dtype(name.clone(), key.as_slice(), dtype, true)
+                })
+                .collect::<PolarsResult<Vec<_>>>()?,
             _ => unreachable!(),
-        }
+        };
+        df_columns.push(
+            StructChunked::from_series(PlSmallStr::from_static("keys"), num_metrics, keys.iter())?
+                .into_column(),
+        );
         for (name, column) in input_schema.iter_names().zip(columns) {
             let struct_ca = StructChunked::from_series(
                 format_pl_smallstr!("{name}_stats"),
@@ -206,7 +202,7 @@ impl WriteMetrics {
             df_columns.push(struct_ca.into_column());
         }
 
-        DataFrame::new_with_height(num_metrics, df_columns).unwrap()
+        DataFrame::new_with_height(num_metrics, df_columns)
     }
 }
 
@@ -232,3 +228,77 @@ impl WriteMetricsColumn {
         }
     }
 }
+
+/// Samples of the morsels a sink sends to its files, to follow the write throughput over time.
+pub struct MorselMetrics {
+    start: Instant,
+    samples: Mutex<Vec<MorselSample>>,
+}
+
+struct MorselSample {
+    path: PlSmallStr,
+    elapsed: Duration,
+    num_rows: u64,
+    num_bytes: u64,
+}
+
+impl Default for MorselMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MorselMetrics {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            samples: Mutex::default(),
+        }
+    }
+
+    /// Record that `df` was sent to the file at `path`.
+    pub fn record(&self, path: &PlSmallStr, df: &DataFrame) {
+        let sample = MorselSample {
+            path: path.clone(),
+            elapsed: self.start.elapsed(),
+            num_rows: df.height() as u64,
+            num_bytes: df.estimated_size() as u64,
+        };
+        self.samples.lock().unwrap().push(sample);
+    }
+
+    /// Collect the samples in order of recording. The time of a sample is relative to the creation
+    /// of the metrics.
+    pub fn to_df(&self) -> DataFrame {
+        let samples = self.samples.lock().unwrap();
+        let num_samples = samples.len();
+
+        let mut path = StringChunkedBuilder::new(PlSmallStr::from_static("path"), num_samples);
+        let mut elapsed_ns = PrimitiveChunkedBuilder::<UInt64Type>::new(
+            PlSmallStr::from_static("elapsed_ns"),
+            num_samples,
+        );
+        let mut num_rows = PrimitiveChunkedBuilder::<UInt64Type>::new(
+            PlSmallStr::from_static("num_rows"),
+            num_samples,
+        );
+        let mut num_bytes = PrimitiveChunkedBuilder::<UInt64Type>::new(
+            PlSmallStr::from_static("num_bytes"),
+            num_samples,
+        );
+        for sample in samples.iter() {
+            path.append_value(sample.path.as_str());
+            elapsed_ns.append_value(sample.elapsed.as_nanos() as u64);
+            num_rows.append_value(sample.num_rows);
+            num_bytes.append_value(sample.num_bytes);
+        }
+
+        let df_columns = vec![
+            path.finish().into_column(),
+            elapsed_ns.finish().into_column(),
+            num_rows.finish().into_column(),
+            num_bytes.finish().into_column(),
+        ];
+        DataFrame::new_with_height(num_samples, df_columns).unwrap()
+    }
+}
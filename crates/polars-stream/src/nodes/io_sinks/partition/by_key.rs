@@ -1,13 +1,16 @@
 use std::cmp::Reverse;
-use std::pin::Pin;
+use std::num::NonZeroUsize;
+use std::pin::{Pin, pin};
 use std::sync::{Arc, OnceLock};
 
 use futures::StreamExt;
+use futures::future::{Either, select};
 use futures::stream::FuturesUnordered;
 use polars_core::config;
 use polars_core::frame::DataFrame;
-use polars_core::prelude::{Column, PlHashSet, PlIndexMap, row_encode};
+use polars_core::prelude::{Column, PlHashSet, PlIndexMap, PlIndexSet, row_encode};
 use polars_core::schema::SchemaRef;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 use polars_core::utils::arrow::buffer::Buffer;
 use polars_error::PolarsResult;
 use polars_plan::dsl::{PartitionTargetCallback, SinkFinishCallback, SinkOptions};
@@ -15,19 +18,24 @@ use polars_utils::pl_str::PlSmallStr;
 use polars_utils::plpath::PlPath;
 use polars_utils::priority::Priority;
 
-use super::{CreateNewSinkFn, PerPartitionSortBy};
+use super::{CreateNewSinkFn, PartiallyWrittenFiles, PerPartitionSortBy};
 use crate::async_executor::{AbortOnDropHandle, spawn};
 use crate::async_primitives::connector::connector;
 use crate::execute::StreamingExecutionState;
 use crate::morsel::SourceToken;
-use crate::nodes::io_sinks::metrics::WriteMetrics;
-use crate::nodes::io_sinks::partition::{SinkSender, open_new_sink};
+use crate::nodes::io_sinks::metrics::{MorselMetrics, WriteMetrics};
+use crate::nodes::io_sinks::partition::{SinkSender, open_new_sink, resolve_sink_target};
 use crate::nodes::io_sinks::phase::PhaseOutcome;
 use crate::nodes::io_sinks::{SinkInputPort, SinkNode, parallelize_receive_task};
 use crate::nodes::{JoinHandle, Morsel, MorselSeq, TaskPriority};
 
 type Linearized =
     Priority<Reverse<MorselSeq>, (SourceToken, Vec<(Buffer<u8>, Vec<Column>, DataFrame)>)>;
+
+/// Writes every key to its own files.
+///
+/// With `maintain_order` the rows of every file are in input order. The `strict_order` option of
+/// the by-key partitioning turns it on regardless of the `maintain_order` of the sink.
 pub struct PartitionByKeySinkNode {
     input_schema: SchemaRef,
     // This is not be the same as the input_schema, e.g. when include_key=false then this will not
@@ -37,7 +45,15 @@ pub struct PartitionByKeySinkNode {
     key_cols: Arc<[PlSmallStr]>,
 
     max_open_partitions: usize,
+    /// Number of rows of the morsels a buffered partition is sent to its sink in. Otherwise the
+    /// buffered frames are sent as they are.
+    flush_morsel_rows: Option<NonZeroUsize>,
     include_key: bool,
+    /// Only resolve the partitions and their paths, without writing any files.
+    dry_run: bool,
+    /// Route rows with only null keys to their own partition, and write null keys as `__NULL__`
+    /// in the default paths.
+    separate_null_keys: bool,
 
     base_path: Arc<PlPath>,
     file_path_cb: Option<PartitionTargetCallback>,
@@ -49,6 +65,11 @@ pub struct PartitionByKeySinkNode {
     per_partition_sort_by: Option<PerPartitionSortBy>,
     written_partitions: Arc<OnceLock<DataFrame>>,
     finish_callback: Option<SinkFinishCallback>,
+    morsel_metrics: Option<(SinkFinishCallback, Arc<MorselMetrics>)>,
+    /// Keys that get a file even if they receive no rows.
+    expected_keys: Option<Arc<DataFrame>>,
+    /// The rows of a key are split over several files of at most this many rows.
+    max_rows_per_file: Option<NonZeroUsize>,
 }
 
 impl PartitionByKeySinkNode {
@@ -64,6 +85,12 @@ impl PartitionByKeySinkNode {
         include_key: bool,
         per_partition_sort_by: Option<PerPartitionSortBy>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
+        expected_keys: Option<Arc<DataFrame>>,
+        max_rows_per_file: Option<NonZeroUsize>,
+        dry_run: bool,
+        separate_null_keys: bool,
+        flush_morsel_rows: Option<NonZeroUsize>,
     ) -> Self {
         assert!(!key_cols.is_empty());
 
@@ -94,7 +121,10 @@ impl PartitionByKeySinkNode {
             sink_input_schema,
             key_cols,
             max_open_partitions,
+            flush_morsel_rows,
             include_key,
+            dry_run,
+            separate_null_keys,
             base_path,
             file_path_cb,
             create_new,
@@ -103,6 +133,9 @@ impl PartitionByKeySinkNode {
             per_partition_sort_by,
             written_partitions: Arc::new(OnceLock::new()),
             finish_callback,
+            morsel_metrics: morsel_metrics_callback.map(|f| (f, Arc::new(MorselMetrics::new()))),
+            expected_keys,
+            max_rows_per_file,
         }
     }
 }
@@ -143,6 +176,7 @@ impl SinkNode for PartitionByKeySinkNode {
             let key_cols = self.key_cols.clone();
             let stable = self.sink_options.maintain_order;
             let include_key = self.include_key;
+            let separate_null_keys = self.separate_null_keys;
 
             spawn(TaskPriority::High, async move {
                 while let Ok((mut rx, mut lin_tx)) = pass_rx.recv().await {
@@ -169,11 +203,7 @@ impl SinkNode for PartitionByKeySinkNode {
                                     .map(|c| c.head(Some(1)))
                                     .collect::<Vec<_>>();
 
-                                let row_encoded = row_encode::encode_rows_unordered(&keys)?
-                                    .downcast_into_iter()
-                                    .next()
-                                    .unwrap();
-                                let row_encoded = row_encoded.into_inner().2;
+                                let row_encoded = encode_partition_keys(&keys, separate_null_keys)?;
 
                                 if !include_key {
                                     df = df.drop_many(key_cols.iter().cloned());
@@ -206,12 +236,23 @@ impl SinkNode for PartitionByKeySinkNode {
         let key_cols = self.key_cols.clone();
         let sink_input_schema = self.sink_input_schema.clone();
         let max_open_partitions = self.max_open_partitions;
+        let flush_morsel_rows = self.flush_morsel_rows;
+        let dry_run = self.dry_run;
         let base_path = self.base_path.clone();
         let file_path_cb = self.file_path_cb.clone();
         let create_new_sink = self.create_new.clone();
         let ext = self.ext.clone();
         let per_partition_sort_by = self.per_partition_sort_by.clone();
+        let morsel_metrics = self.morsel_metrics.as_ref().map(|(_, m)| m.clone());
         let output_written_partitions = self.written_partitions.clone();
+        let expected_keys = self.expected_keys.clone();
+        let separate_null_keys = self.separate_null_keys;
+        let default_file_path_cb: super::FilePathCallback = if separate_null_keys {
+            super::separate_null_keys_file_path_cb
+        } else {
+            super::default_by_key_file_path_cb
+        };
+        let max_rows_per_file = self.max_rows_per_file;
         join_handles.push(spawn(TaskPriority::High, async move {
             enum OpenPartition {
                 Sink {
@@ -219,27 +260,66 @@ impl SinkNode for PartitionByKeySinkNode {
                     join_handles: FuturesUnordered<AbortOnDropHandle<PolarsResult<()>>>,
                     node: Box<dyn SinkNode + Send>,
                     keys: Vec<Column>,
+                    /// The rows sent to the current file of the partition.
+                    rows: usize,
+                    /// The sequence number of the next morsel sent to the current file.
+                    seq: MorselSeq,
+                    in_part_idx: usize,
                 },
                 Buffer {
                     buffered: Vec<DataFrame>,
                     keys: Vec<Column>,
                 },
+                DryRun {
+                    metrics: WriteMetrics,
+                    keys: Vec<Column>,
+                    in_part_idx: usize,
+                },
             }
 
             let verbose = config::verbose();
             let mut file_idx = 0;
             let mut open_partitions: PlIndexMap<Buffer<u8>, OpenPartition> = PlIndexMap::default();
+            // The metrics of the files that are done, files of a partition are done before the
+            // partition is if it is split over several files.
+            let mut partition_metrics = Vec::new();
 
             // Wrap this in a closure so that a failure to send (which signifies a failure) can be
             // caught while waiting for tasks.
             let mut receive_and_pass = async || {
                 while let Ok(mut lin_rx) = io_rx.recv().await {
-                    while let Some(Priority(Reverse(seq), (source_token, partitions))) =
+                    while let Some(Priority(_, (source_token, partitions))) =
                         lin_rx.get().await
                     {
                         for (row_encoded, keys, partition) in partitions {
                             let num_open_partitions = open_partitions.len();
                             let open_partition = match open_partitions.get_mut(&row_encoded) {
+                                None if dry_run => {
+                                    let target = resolve_sink_target(
+                                        base_path.as_ref().as_ref(),
+                                        file_path_cb.as_ref(),
+                                        default_file_path_cb,
+                                        file_idx,
+                                        file_idx,
+                                        0,
+                                        Some(keys.as_slice()),
+                                        ext.as_str(),
+                                    ).await?;
+                                    file_idx += 1;
+
+                                    let path = target.to_display_string();
+                                    if verbose {
+                                        eprintln!("[partition[by-key]]: Dry run, skipping file '{path}'");
+                                    }
+
+                                    let metrics = target.new_metrics(&sink_input_schema);
+                                    let (idx, previous) = open_partitions.insert_full(
+                                        row_encoded,
+                                        OpenPartition::DryRun { metrics, keys, in_part_idx: 0 },
+                                    );
+                                    debug_assert!(previous.is_none());
+                                    open_partitions.get_index_mut(idx).unwrap().1
+                                },
                                 None if num_open_partitions >= max_open_partitions => {
                                     if num_open_partitions == max_open_partitions && verbose {
                                         eprintln!(
@@ -258,7 +338,7 @@ impl SinkNode for PartitionByKeySinkNode {
                                     let result = open_new_sink(
                                         base_path.as_ref().as_ref(),
                                         file_path_cb.as_ref(),
-                                        super::default_by_key_file_path_cb,
+                                        default_file_path_cb,
                                         file_idx,
                                         file_idx,
                                         0,
@@ -270,6 +350,7 @@ impl SinkNode for PartitionByKeySinkNode {
                                         verbose,
                                         &state,
                                         per_partition_sort_by.as_ref(),
+                                        morsel_metrics.as_ref(),
                                     ).await?;
                                     file_idx += 1;
 
@@ -279,7 +360,15 @@ impl SinkNode for PartitionByKeySinkNode {
 
                                     let (idx, previous) = open_partitions.insert_full(
                                         row_encoded,
-                                        OpenPartition::Sink { sender, join_handles, node, keys },
+                                        OpenPartition::Sink {
+                                            sender,
+                                            join_handles,
+                                            node,
+                                            keys,
+                                            rows: 0,
+                                            seq: MorselSeq::default(),
+                                            in_part_idx: 0,
+                                        },
                                     );
                                     debug_assert!(previous.is_none());
                                     open_partitions.get_index_mut(idx).unwrap().1
@@ -288,13 +377,106 @@ impl SinkNode for PartitionByKeySinkNode {
                             };
 
                             match open_partition {
-                                OpenPartition::Sink { sender, .. } => {
-                                    let morsel = Morsel::new(partition, seq, source_token.clone());
-                                    if sender.send(morsel).await.is_err() {
-                                        return Ok(());
+                                OpenPartition::Sink { sender, join_handles, node, keys, rows, seq: file_seq, in_part_idx } => {
+                                    let mut partition = partition;
+                                    loop {
+                                        let (head, rest) =
+                                            split_at_file_limit(partition, *rows, max_rows_per_file);
+                                        if head.height() > 0 {
+                                            *rows += head.height();
+                                            // The morsels arrive in the order they are written in,
+                                            // number them so the sink of the file keeps that order.
+                                            let mut morsel = Morsel::new(head, *file_seq, source_token.clone());
+                                            morsel.reserve_memory(&state.morsel_memory_budget);
+                                            *file_seq = file_seq.successor();
+                                            if sender.send(morsel).await.is_err() {
+                                                return Ok(());
+                                            }
+                                        }
+                                        let Some(rest) = rest else {
+                                            break;
+                                        };
+
+                                        // The current file is full, continue in the next file of
+                                        // this partition.
+                                        *in_part_idx += 1;
+                                        let result = open_new_sink(
+                                            base_path.as_ref().as_ref(),
+                                            file_path_cb.as_ref(),
+                                            default_file_path_cb,
+                                            file_idx,
+                                            file_idx,
+                                            *in_part_idx,
+                                            Some(keys.as_slice()),
+                                            &create_new_sink,
+                                            sink_input_schema.clone(),
+                                            "by-key",
+                                            ext.as_str(),
+                                            verbose,
+                                            &state,
+                                            per_partition_sort_by.as_ref(),
+                                            morsel_metrics.as_ref(),
+                                        ).await?;
+                                        file_idx += 1;
+                                        let Some((new_join_handles, new_sender, new_node)) = result else {
+                                            return Ok(());
+                                        };
+
+                                        let full_sender = std::mem::replace(sender, new_sender);
+                                        let full_join_handles = std::mem::replace(join_handles, new_join_handles);
+                                        let full_node = std::mem::replace(node, new_node);
+                                        *rows = 0;
+                                        *file_seq = MorselSeq::default();
+                                        close_sink(
+                                            full_sender,
+                                            full_join_handles,
+                                            full_node,
+                                            keys,
+                                            &state,
+                                            &mut partition_metrics,
+                                        ).await?;
+                                        partition = rest;
                                     }
                                 },
                                 OpenPartition::Buffer { buffered, .. } => buffered.push(partition),
+                                OpenPartition::DryRun { metrics, keys, in_part_idx } => {
+                                    let mut partition = partition;
+                                    loop {
+                                        let (head, rest) = split_at_file_limit(
+                                            partition,
+                                            metrics.num_rows as usize,
+                                            max_rows_per_file,
+                                        );
+                                        metrics.append(&head)?;
+                                        let Some(rest) = rest else {
+                                            break;
+                                        };
+
+                                        *in_part_idx += 1;
+                                        let target = resolve_sink_target(
+                                            base_path.as_ref().as_ref(),
+                                            file_path_cb.as_ref(),
+                                            default_file_path_cb,
+                                            file_idx,
+                                            file_idx,
+                                            *in_part_idx,
+                                            Some(keys.as_slice()),
+                                            ext.as_str(),
+                                        ).await?;
+                                        file_idx += 1;
+                                        let path = target.to_display_string();
+                                        if verbose {
+                                            eprintln!("[partition[by-key]]: Dry run, skipping file '{path}'");
+                                        }
+
+                                        let full = std::mem::replace(
+                                            metrics,
+                                            target.new_metrics(&sink_input_schema),
+                                        );
+                                        partition_metrics.push(with_keys(full, keys));
+                                        partition = rest;
+                                    }
+                                },
                             }
                         }
                     }
@@ -304,64 +486,142 @@ impl SinkNode for PartitionByKeySinkNode {
             };
             receive_and_pass().await?;
 
-            let mut partition_metrics = Vec::with_capacity(file_idx);
+            // Expected partitions that didn't receive any rows still get a file, without rows.
+            if let Some(expected_keys) = &expected_keys {
+                let expected_keys = expected_keys
+                    .select_columns(key_cols.iter().cloned())?
+                    .into_iter()
+                    .map(|c| c.strict_cast(input_schema.get(c.name()).unwrap()))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let height = expected_keys.first().map_or(0, |c| c.len());
+                for i in 0..height {
+                    let keys = expected_keys
+                        .iter()
+                        .map(|c| c.slice(i as i64, 1))
+                        .collect::<Vec<_>>();
+                    let row_encoded = encode_partition_keys(&keys, separate_null_keys)?;
+                    if open_partitions.contains_key(&row_encoded) {
+                        continue;
+                    }
 
-            // At this point, we need to wait for all sinks to finish writing and close them. Also,
-            // sinks that ended up buffering need to output their data.
-            for open_partition in open_partitions.into_values() {
-                let (sender, mut join_handles, mut node, keys) = match open_partition {
-                    OpenPartition::Sink { sender, join_handles, node, keys } => (sender, join_handles, node, keys),
-                    OpenPartition::Buffer { buffered, keys } => {
-                        let result = open_new_sink(
+                    let open_partition = if dry_run {
+                        let target = resolve_sink_target(
                             base_path.as_ref().as_ref(),
                             file_path_cb.as_ref(),
-                            super::default_by_key_file_path_cb,
+                            default_file_path_cb,
                             file_idx,
                             file_idx,
                             0,
                             Some(keys.as_slice()),
-                            &create_new_sink,
-                            sink_input_schema.clone(),
-                            "by-key",
                             ext.as_str(),
-                            verbose,
-                            &state,
-                            per_partition_sort_by.as_ref(),
                         ).await?;
                         file_idx += 1;
-                        let Some((join_handles, mut sender, node)) = result else {
-                            return Ok(());
-                        };
-
-                        let source_token = SourceToken::new();
-                        let mut seq = MorselSeq::default();
-                        for df in buffered {
-                            let morsel = Morsel::new(df, seq, source_token.clone());
-                            if sender.send(morsel).await.is_err() {
-                                return Ok(());
-                            }
-                            seq = seq.successor();
-                        }
+                        let metrics = target.new_metrics(&sink_input_schema);
+                        OpenPartition::DryRun { metrics, keys, in_part_idx: 0 }
+                    } else {
+                        OpenPartition::Buffer { buffered: Vec::new(), keys }
+                    };
+                    open_partitions.insert(row_encoded, open_partition);
+                }
+            }
 
-                        (sender, join_handles, node, keys)
-                    },
-                };
+            // At this point, we need to wait for all sinks to finish writing and close them. Also,
+            // sinks that ended up buffering need to output their data.
+            //
+            // With slow IO this can take a while, so stop as soon as the query is cancelled. The
+            // pending writes are aborted and the files that weren't closed yet are reported.
+            let mut unfinished_files = open_partitions
+                .values()
+                .filter_map(|p| match p {
+                    OpenPartition::Sink { sender, .. } => Some(sender.path.clone()),
+                    _ => None,
+                })
+                .collect::<PlIndexSet<_>>();
+            let finished = {
+                let finalize = async {
+                    for open_partition in open_partitions.into_values() {
+                        match open_partition {
+                            OpenPartition::Sink { sender, join_handles, node, keys, .. } => {
+                                let path = sender.path.clone();
+                                close_sink(sender, join_handles, node, &keys, &state, &mut partition_metrics).await?;
+                                unfinished_files.shift_remove(&path);
+                            },
+                            OpenPartition::DryRun { metrics, keys, .. } => {
+                                partition_metrics.push(with_keys(metrics, &keys));
+                            },
+                            OpenPartition::Buffer { buffered, keys } => {
+                                // A partition without any rows still gets a single, empty file.
+                                let files = match max_rows_per_file {
+                                    Some(max_rows) if !buffered.is_empty() => {
+                                        split_into_morsels(buffered, max_rows)
+                                            .into_iter()
+                                            .map(|df| vec![df])
+                                            .collect()
+                                    },
+                                    _ => vec![buffered],
+                                };
+
+                                for (in_part_idx, buffered) in files.into_iter().enumerate() {
+                                    let result = open_new_sink(
+                                        base_path.as_ref().as_ref(),
+                                        file_path_cb.as_ref(),
+                                        default_file_path_cb,
+                                        file_idx,
+                                        file_idx,
+                                        in_part_idx,
+                                        Some(keys.as_slice()),
+                                        &create_new_sink,
+                                        sink_input_schema.clone(),
+                                        "by-key",
+                                        ext.as_str(),
+                                        verbose,
+                                        &state,
+                                        per_partition_sort_by.as_ref(),
+                                        morsel_metrics.as_ref(),
+                                    ).await?;
+                                    file_idx += 1;
+                                    let Some((join_handles, mut sender, node)) = result else {
+                                        return Ok(());
+                                    };
+                                    let path = sender.path.clone();
+                                    unfinished_files.insert(path.clone());
 
-                drop(sender); // Signal to the sink that nothing more is coming.
-                while let Some(res) = join_handles.next().await {
-                    res?;
-                }
+                                    let buffered = match flush_morsel_rows {
+                                        None => buffered,
+                                        Some(morsel_rows) => split_into_morsels(buffered, morsel_rows),
+                                    };
+                                    let source_token = SourceToken::new();
+                                    let mut seq = MorselSeq::default();
+                                    for df in buffered {
+                                        let morsel = Morsel::new(df, seq, source_token.clone());
+                                        if sender.send(morsel).await.is_err() {
+                                            return Ok(());
+                                        }
+                                        seq = seq.successor();
+                                    }
 
-                if let Some(mut metrics) = node.get_metrics()? {
-                    metrics.keys = Some(keys.into_iter().map(|c| c.get(0).unwrap().into_static()).collect());
-                    partition_metrics.push(metrics);
-                }
-                if let Some(finalize) = node.finalize(&state) {
-                    finalize.await?;
+                                    close_sink(sender, join_handles, node, &keys, &state, &mut partition_metrics).await?;
+                                    unfinished_files.shift_remove(&path);
+                                }
+                            },
+                        }
+                    }
+                    PolarsResult::Ok(())
+                };
+                let cancel_token = state.in_memory_exec_state.cancel_token();
+                match select(pin!(finalize), pin!(cancel_token.cancelled())).await {
+                    Either::Left((result, _)) => Some(result),
+                    Either::Right(_) => None,
                 }
+            };
+            match finished {
+                Some(result) => result?,
+                None => {
+                    return Err(PartiallyWrittenFiles(unfinished_files.into_iter().collect()).into());
+                },
             }
 
-            let df = WriteMetrics::collapse_to_df(partition_metrics, &sink_input_schema, Some(&input_schema.try_project(key_cols.iter()).unwrap()));
+            let df = WriteMetrics::collapse_to_df(partition_metrics, &sink_input_schema, Some(&input_schema.try_project(key_cols.iter()).unwrap()))?;
             output_written_partitions.set(df).unwrap();
             Ok(())
         }));
@@ -374,7 +634,12 @@ impl SinkNode for PartitionByKeySinkNode {
         let finish_callback = self.finish_callback.clone();
         let written_partitions = self.written_partitions.clone();
 
+        let morsel_metrics = self.morsel_metrics.clone();
+
         Some(Box::pin(async move {
+            if let Some((morsel_metrics_callback, morsel_metrics)) = &morsel_metrics {
+                morsel_metrics_callback.call(morsel_metrics.to_df())?;
+            }
             if let Some(finish_callback) = &finish_callback {
                 let df = written_partitions.get().unwrap();
                 finish_callback.call(df.clone())?;
@@ -383,3 +648,83 @@ impl SinkNode for PartitionByKeySinkNode {
         }))
     }
 }
+
+/// Wait for the sink of a file to finish writing and close it.
+async fn close_sink(
+    sender: SinkSender,
+    mut join_handles: FuturesUnordered<AbortOnDropHandle<PolarsResult<()>>>,
+    mut node: Box<dyn SinkNode + Send>,
+    keys: &[Column],
+    state: &StreamingExecutionState,
+    partition_metrics: &mut Vec<WriteMetrics>,
+) -> PolarsResult<()> {
+    drop(sender); // Signal to the sink that nothing more is coming.
+    while let Some(res) = join_handles.next().await {
+        res?;
+    }
+
+    if let Some(metrics) = node.get_metrics()? {
+        partition_metrics.push(with_keys(metrics, keys));
+    }
+    if let Some(finalize) = node.finalize(state) {
+        finalize.await?;
+    }
+    Ok(())
+}
+
+fn with_keys(mut metrics: WriteMetrics, keys: &[Column]) -> WriteMetrics {
+    metrics.keys = Some(
+        keys.iter()
+            .map(|c| c.get(0).unwrap().into_static())
+            .collect(),
+    );
+    metrics
+}
+
+/// Split `df` into the rows that still fit into a file that already has `rows` rows, and the rows
+/// that have to go to the next file.
+fn split_at_file_limit(
+    df: DataFrame,
+    rows: usize,
+    max_rows_per_file: Option<NonZeroUsize>,
+) -> (DataFrame, Option<DataFrame>) {
+    match max_rows_per_file {
+        Some(max_rows) if rows + df.height() > max_rows.get() => {
+            let (head, rest) = df.split_at((max_rows.get() - rows) as i64);
+            (head, Some(rest))
+        },
+        _ => (df, None),
+    }
+}
+
+/// Encode the keys of a partition into the key it is looked up by.
+fn encode_partition_keys(keys: &[Column], separate_null_keys: bool) -> PolarsResult<Buffer<u8>> {
+    // Every encoded row is non-empty, so the empty buffer is free to use for the null keys
+    // partition.
+    if separate_null_keys && keys.iter().all(|c| c.has_nulls()) {
+        return Ok(Buffer::default());
+    }
+    let row_encoded = row_encode::encode_rows_unordered(keys)?
+        .downcast_into_iter()
+        .next()
+        .unwrap();
+    Ok(row_encoded.into_inner().2)
+}
+
+/// Concatenate `dfs` and split them again into frames of `morsel_rows` rows, only the last frame
+/// can be shorter.
+fn split_into_morsels(dfs: Vec<DataFrame>, morsel_rows: NonZeroUsize) -> Vec<DataFrame> {
+    if dfs.is_empty() {
+        return dfs;
+    }
+    let df = accumulate_dataframes_vertical_unchecked(dfs);
+    let morsel_rows = morsel_rows.get();
+    (0..df.height())
+        .step_by(morsel_rows)
+        .map(|offset| {
+            let mut morsel = df.slice(offset as i64, morsel_rows);
+            morsel.as_single_chunk();
+            morsel
+        })
+        .collect()
+}
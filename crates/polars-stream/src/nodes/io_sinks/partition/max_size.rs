@@ -18,7 +18,7 @@ use crate::async_executor::{AbortOnDropHandle, spawn};
 use crate::async_primitives::connector::Receiver;
 use crate::async_primitives::distributor_channel::distributor_channel;
 use crate::execute::StreamingExecutionState;
-use crate::nodes::io_sinks::metrics::WriteMetrics;
+use crate::nodes::io_sinks::metrics::{MorselMetrics, WriteMetrics};
 use crate::nodes::io_sinks::partition::{SinkSender, open_new_sink};
 use crate::nodes::io_sinks::phase::PhaseOutcome;
 use crate::nodes::io_sinks::{SinkInputPort, SinkNode};
@@ -47,6 +47,7 @@ pub struct MaxSizePartitionSinkNode {
     per_partition_sort_by: Option<PerPartitionSortBy>,
     partition_metrics: Arc<Mutex<Vec<Vec<WriteMetrics>>>>,
     finish_callback: Option<SinkFinishCallback>,
+    morsel_metrics: Option<(SinkFinishCallback, Arc<MorselMetrics>)>,
 }
 
 const DEFAULT_RETIRE_TASKS: usize = 1;
@@ -63,6 +64,7 @@ impl MaxSizePartitionSinkNode {
 
         per_partition_sort_by: Option<PerPartitionSortBy>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
     ) -> Self {
         assert!(max_size > 0);
         let num_retire_tasks =
@@ -84,6 +86,7 @@ impl MaxSizePartitionSinkNode {
             per_partition_sort_by,
             partition_metrics: Arc::new(Mutex::new(Vec::with_capacity(num_retire_tasks))),
             finish_callback,
+            morsel_metrics: morsel_metrics_callback.map(|f| (f, Arc::new(MorselMetrics::new()))),
         }
     }
 }
@@ -140,6 +143,7 @@ impl SinkNode for MaxSizePartitionSinkNode {
         let create_new = self.create_new.clone();
         let ext = self.ext.clone();
         let per_partition_sort_by = self.per_partition_sort_by.clone();
+        let morsel_metrics = self.morsel_metrics.as_ref().map(|(_, m)| m.clone());
         let retire_error = has_error_occurred.clone();
         join_handles.push(spawn(TaskPriority::High, async move {
             struct CurrentSink {
@@ -179,6 +183,7 @@ impl SinkNode for MaxSizePartitionSinkNode {
                                     verbose,
                                     &task_state,
                                     per_partition_sort_by.as_ref(),
+                                    morsel_metrics.as_ref(),
                                 )
                                 .await?;
                                 file_idx += 1;
@@ -202,10 +207,7 @@ impl SinkNode for MaxSizePartitionSinkNode {
                             // This sends the consume token along so that we don't start buffering here
                             // too much. The sinks are very specific about how they handle consume
                             // tokens and we want to keep that behavior.
-                            let result = match &mut current_sink.sender {
-                                SinkSender::Connector(s) => s.send(morsel).await.ok(),
-                                SinkSender::Distributor(s) => s.send(morsel).await.ok(),
-                            };
+                            let result = current_sink.sender.send(morsel).await.ok();
 
                             if result.is_none() {
                                 break 'morsel_loop;
@@ -307,7 +309,12 @@ impl SinkNode for MaxSizePartitionSinkNode {
         let partition_metrics = self.partition_metrics.clone();
         let input_schema = self.input_schema.clone();
 
+        let morsel_metrics = self.morsel_metrics.clone();
+
         Some(Box::pin(async move {
+            if let Some((morsel_metrics_callback, morsel_metrics)) = &morsel_metrics {
+                morsel_metrics_callback.call(morsel_metrics.to_df())?;
+            }
             if let Some(finish_callback) = &finish_callback {
                 let mut partition_metrics = partition_metrics.lock().unwrap();
                 let partition_metrics =
@@ -315,7 +322,7 @@ impl SinkNode for MaxSizePartitionSinkNode {
                         .into_iter()
                         .flatten()
                         .collect();
-                let df = WriteMetrics::collapse_to_df(partition_metrics, &input_schema, None);
+                let df = WriteMetrics::collapse_to_df(partition_metrics, &input_schema, None)?;
                 finish_callback.call(df)?;
             }
             Ok(())
@@ -1,28 +1,30 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use futures::StreamExt;
 use futures::stream::FuturesUnordered;
 use polars_core::prelude::{Column, DataType, SortMultipleOptions};
 use polars_core::scalar::Scalar;
-use polars_core::schema::SchemaRef;
-use polars_error::PolarsResult;
+use polars_core::schema::{Schema, SchemaRef};
+use polars_error::{PolarsError, PolarsResult};
 use polars_io::cloud::CloudOptions;
 use polars_plan::dsl::{
     FileType, PartitionTargetCallback, PartitionTargetCallbackResult, PartitionTargetContext,
     SinkOptions, SinkTarget,
 };
 use polars_utils::format_pl_smallstr;
+use polars_utils::pl_str::PlSmallStr;
 use polars_utils::plpath::PlPathRef;
 
 use super::{DEFAULT_SINK_DISTRIBUTOR_BUFFER_SIZE, SinkInputPort, SinkNode};
-use crate::async_executor::{AbortOnDropHandle, spawn};
+use crate::async_executor::{AbortOnDropHandle, spawn, spawn_blocking};
 use crate::async_primitives::wait_group::WaitGroup;
 use crate::async_primitives::{connector, distributor_channel};
 use crate::execute::StreamingExecutionState;
 use crate::expression::StreamExpr;
 use crate::morsel::{MorselSeq, SourceToken};
+use crate::nodes::io_sinks::metrics::{MorselMetrics, WriteMetrics};
 use crate::nodes::io_sinks::phase::PhaseOutcome;
-use crate::nodes::{Morsel, TaskPriority};
+use crate::nodes::{JoinHandle, Morsel, TaskPriority};
 
 pub mod by_key;
 pub mod max_size;
@@ -37,6 +39,37 @@ pub struct PerPartitionSortBy {
     pub maintain_order: bool,
 }
 
+/// The files a partitioned sink was still writing when the query was cancelled. These may be
+/// partially written and should be cleaned up.
+///
+/// A cancelled query returns this as the source of an [`std::io::ErrorKind::Interrupted`] error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartiallyWrittenFiles(pub Vec<PlSmallStr>);
+
+impl std::fmt::Display for PartiallyWrittenFiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "query interrupted, these files may be partially written: "
+        )?;
+        for (i, path) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{path}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PartiallyWrittenFiles {}
+
+impl From<PartiallyWrittenFiles> for PolarsError {
+    fn from(files: PartiallyWrittenFiles) -> Self {
+        std::io::Error::new(std::io::ErrorKind::Interrupted, files).into()
+    }
+}
+
 pub type CreateNewSinkFn =
     Arc<dyn Send + Sync + Fn(SchemaRef, SinkTarget) -> PolarsResult<Box<dyn SinkNode + Send>>>;
 
@@ -104,16 +137,27 @@ pub fn get_create_new_fn(
     }
 }
 
-enum SinkSender {
+enum SinkChannel {
     Connector(connector::Sender<Morsel>),
     Distributor(distributor_channel::Sender<Morsel>),
 }
 
+struct SinkSender {
+    channel: SinkChannel,
+    /// The file the morsels are sent to.
+    path: PlSmallStr,
+    /// Where to record the sent morsels.
+    morsel_metrics: Option<Arc<MorselMetrics>>,
+}
+
 impl SinkSender {
     pub async fn send(&mut self, morsel: Morsel) -> Result<(), Morsel> {
-        match self {
-            SinkSender::Connector(sender) => sender.send(morsel).await,
-            SinkSender::Distributor(sender) => sender.send(morsel).await,
+        if let Some(metrics) = &self.morsel_metrics {
+            metrics.record(&self.path, morsel.df());
+        }
+        match &mut self.channel {
+            SinkChannel::Connector(sender) => sender.send(morsel).await,
+            SinkChannel::Distributor(sender) => sender.send(morsel).await,
         }
     }
 }
@@ -125,6 +169,38 @@ fn default_by_key_file_path_cb(
     in_part_idx: usize,
     columns: Option<&[Column]>,
     separator: char,
+) -> PolarsResult<String> {
+    by_key_file_path(
+        ext,
+        in_part_idx,
+        columns,
+        separator,
+        "__HIVE_DEFAULT_PARTITION__",
+    )
+}
+
+/// The default path of a partition if null keys are separated. Every null key is written as
+/// `{name}=__NULL__`, also if other keys of the partition aren't null, so the null keys are never
+/// mixed up with a string key and the hive layout is kept. Hive scans read these values back as
+/// the string `__NULL__` instead of as null.
+fn separate_null_keys_file_path_cb(
+    ext: &str,
+    _file_idx: usize,
+    _part_idx: usize,
+    in_part_idx: usize,
+    columns: Option<&[Column]>,
+    separator: char,
+) -> PolarsResult<String> {
+    by_key_file_path(ext, in_part_idx, columns, separator, "__NULL__")
+}
+
+/// The hive path of a partition, with `null_value` as the value of the null keys.
+fn by_key_file_path(
+    ext: &str,
+    in_part_idx: usize,
+    columns: Option<&[Column]>,
+    separator: char,
+    null_value: &str,
 ) -> PolarsResult<String> {
     use std::fmt::Write;
 
@@ -136,10 +212,7 @@ fn default_by_key_file_path_cb(
         let name = c.name();
         let value = c.head(Some(1)).strict_cast(&DataType::String)?;
         let value = value.str().unwrap();
-        let value = value
-            .get(0)
-            .unwrap_or("__HIVE_DEFAULT_PARTITION__")
-            .as_bytes();
+        let value = value.get(0).unwrap_or(null_value).as_bytes();
         let value = percent_encoding::percent_encode(value, polars_io::utils::URL_ENCODE_CHAR_SET);
         write!(&mut file_path, "{name}={value}").unwrap();
         file_path.push(separator);
@@ -152,8 +225,32 @@ fn default_by_key_file_path_cb(
 type FilePathCallback =
     fn(&str, usize, usize, usize, Option<&[Column]>, char) -> PolarsResult<String>;
 
+/// Where a file of a partition goes, see [`resolve_sink_target`].
+enum PartitionTarget {
+    Write(SinkTarget),
+    /// The target callback skipped the file. This is the default target it would have been
+    /// written to.
+    Skip(SinkTarget),
+}
+
+impl PartitionTarget {
+    fn to_display_string(&self) -> String {
+        match self {
+            Self::Write(target) | Self::Skip(target) => target.to_display_string(),
+        }
+    }
+
+    /// The metrics of the file, before any rows are added.
+    fn new_metrics(&self, schema: &Schema) -> WriteMetrics {
+        let mut metrics = WriteMetrics::new(self.to_display_string(), schema);
+        metrics.skipped = matches!(self, Self::Skip(_));
+        metrics
+    }
+}
+
+/// Resolve where the partition with the given indices and keys should be written to.
 #[allow(clippy::too_many_arguments)]
-async fn open_new_sink(
+async fn resolve_sink_target(
     base_path: PlPathRef<'_>,
     file_path_cb: Option<&PartitionTargetCallback>,
     default_file_path_cb: FilePathCallback,
@@ -161,20 +258,8 @@ async fn open_new_sink(
     part_idx: usize,
     in_part_idx: usize,
     keys: Option<&[Column]>,
-    create_new_sink: &CreateNewSinkFn,
-    sink_input_schema: SchemaRef,
-    partition_name: &'static str,
     ext: &str,
-    verbose: bool,
-    state: &StreamingExecutionState,
-    per_partition_sort_by: Option<&PerPartitionSortBy>,
-) -> PolarsResult<
-    Option<(
-        FuturesUnordered<AbortOnDropHandle<PolarsResult<()>>>,
-        SinkSender,
-        Box<dyn SinkNode + Send>,
-    )>,
-> {
+) -> PolarsResult<PartitionTarget> {
     let separator = '/'; // note: accepted by both Windows and Linux
     let file_path = default_file_path_cb(ext, file_idx, part_idx, in_part_idx, keys, separator)?;
     let path = base_path.join(file_path.as_str());
@@ -190,36 +275,96 @@ async fn open_new_sink(
                 .collect()
         });
 
-        let target = file_path_cb.call(PartitionTargetContext {
+        let context = PartitionTargetContext {
             file_idx,
             part_idx,
             in_part_idx,
             keys,
             file_path,
-            full_path: path,
-        })?;
+            full_path: path.clone(),
+        };
+
+        // The callback is user code that might do synchronous IO, so keep it off the compute
+        // threads.
+        let file_path_cb = file_path_cb.clone();
+        let target = spawn_blocking(move || file_path_cb.call(context)).await?;
         match target {
             // Offset the given path by the base_path.
-            PartitionTargetCallbackResult::Str(p) => SinkTarget::Path(base_path.join(p)),
-            PartitionTargetCallbackResult::Dyn(t) => SinkTarget::Dyn(t),
+            PartitionTargetCallbackResult::Str(p) => {
+                PartitionTarget::Write(SinkTarget::Path(base_path.join(p)))
+            },
+            PartitionTargetCallbackResult::Dyn(t) => PartitionTarget::Write(SinkTarget::Dyn(t)),
+            PartitionTargetCallbackResult::Skip => PartitionTarget::Skip(SinkTarget::Path(path)),
         }
     } else {
-        SinkTarget::Path(path)
+        PartitionTarget::Write(SinkTarget::Path(path))
     };
 
+    Ok(target)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn open_new_sink(
+    base_path: PlPathRef<'_>,
+    file_path_cb: Option<&PartitionTargetCallback>,
+    default_file_path_cb: FilePathCallback,
+    file_idx: usize,
+    part_idx: usize,
+    in_part_idx: usize,
+    keys: Option<&[Column]>,
+    create_new_sink: &CreateNewSinkFn,
+    sink_input_schema: SchemaRef,
+    partition_name: &'static str,
+    ext: &str,
+    verbose: bool,
+    state: &StreamingExecutionState,
+    per_partition_sort_by: Option<&PerPartitionSortBy>,
+    morsel_metrics: Option<&Arc<MorselMetrics>>,
+) -> PolarsResult<
+    Option<(
+        FuturesUnordered<AbortOnDropHandle<PolarsResult<()>>>,
+        SinkSender,
+        Box<dyn SinkNode + Send>,
+    )>,
+> {
+    let target = resolve_sink_target(
+        base_path,
+        file_path_cb,
+        default_file_path_cb,
+        file_idx,
+        part_idx,
+        in_part_idx,
+        keys,
+        ext,
+    )
+    .await?;
+
     if verbose {
         match &target {
-            SinkTarget::Path(p) => eprintln!(
+            PartitionTarget::Write(SinkTarget::Path(p)) => eprintln!(
                 "[partition[{partition_name}]]: Start on new file '{}'",
                 p.display(),
             ),
-            SinkTarget::Dyn(_) => eprintln!("[partition[{partition_name}]]: Start on new file",),
+            PartitionTarget::Write(SinkTarget::Dyn(_)) => {
+                eprintln!("[partition[{partition_name}]]: Start on new file",)
+            },
+            PartitionTarget::Skip(target) => eprintln!(
+                "[partition[{partition_name}]]: Skipping file '{}'",
+                target.to_display_string(),
+            ),
         }
     }
 
-    let mut node = (create_new_sink)(sink_input_schema.clone(), target)?;
+    let path = PlSmallStr::from(target.to_display_string());
+    let skipped = matches!(target, PartitionTarget::Skip(_));
+    let mut node = match target {
+        PartitionTarget::Write(target) => (create_new_sink)(sink_input_schema.clone(), target)?,
+        target @ PartitionTarget::Skip(_) => Box::new(SkippedPartitionSinkNode {
+            metrics: Arc::new(Mutex::new(Some(target.new_metrics(&sink_input_schema)))),
+        }) as Box<dyn SinkNode + Send>,
+    };
     let mut join_handles = Vec::new();
-    let (sink_input, mut sender) = if node.is_sink_input_parallel() {
+    let (sink_input, channel) = if node.is_sink_input_parallel() {
         let (tx, dist_rxs) = distributor_channel::distributor_channel(
             state.num_pipelines,
             *DEFAULT_SINK_DISTRIBUTOR_BUFFER_SIZE,
@@ -238,10 +383,16 @@ async fn open_new_sink(
             })
         }));
 
-        (SinkInputPort::Parallel(rxs), SinkSender::Distributor(tx))
+        (SinkInputPort::Parallel(rxs), SinkChannel::Distributor(tx))
     } else {
         let (tx, rx) = connector::connector();
-        (SinkInputPort::Serial(rx), SinkSender::Connector(tx))
+        (SinkInputPort::Serial(rx), SinkChannel::Connector(tx))
+    };
+    let mut sender = SinkSender {
+        channel,
+        path,
+        // Nothing is written for a skipped file.
+        morsel_metrics: morsel_metrics.filter(|_| !skipped).cloned(),
     };
 
     // Handle sorting per partition.
@@ -255,8 +406,13 @@ async fn open_new_sink(
         let nulls_last = per_partition_sort_by.nulls_last.clone();
         let maintain_order = per_partition_sort_by.maintain_order;
 
-        // Tell the partitioning sink to send stuff here instead.
-        let mut old_sender = std::mem::replace(&mut sender, SinkSender::Connector(tx));
+        // Tell the partitioning sink to send stuff here instead. The morsels are recorded when they
+        // are sent here, not again when sending the sorted partition.
+        let mut old_sender = SinkSender {
+            channel: std::mem::replace(&mut sender.channel, SinkChannel::Connector(tx)),
+            path: sender.path.clone(),
+            morsel_metrics: None,
+        };
 
         // This all happens in a single thread per partition. Acceptable for now as the main
         // usecase here is writing many partitions, not the best idea for the future.
@@ -317,3 +473,44 @@ async fn open_new_sink(
 
     Ok(Some((join_handles, sender, node)))
 }
+
+/// Takes the place of the sink of a file that the target callback skipped. The rows are
+/// discarded, only their metrics are kept.
+struct SkippedPartitionSinkNode {
+    metrics: Arc<Mutex<Option<WriteMetrics>>>,
+}
+
+impl SinkNode for SkippedPartitionSinkNode {
+    fn name(&self) -> &str {
+        "skipped-partition-sink"
+    }
+
+    fn is_sink_input_parallel(&self) -> bool {
+        false
+    }
+
+    fn spawn_sink(
+        &mut self,
+        mut recv_port_rx: connector::Receiver<(PhaseOutcome, SinkInputPort)>,
+        _state: &StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        let metrics = self.metrics.clone();
+        join_handles.push(spawn(TaskPriority::High, async move {
+            while let Ok((outcome, recv_port)) = recv_port_rx.recv().await {
+                let mut recv_port = recv_port.serial();
+                while let Ok(morsel) = recv_port.recv().await {
+                    if let Some(metrics) = metrics.lock().unwrap().as_mut() {
+                        metrics.append(morsel.df())?;
+                    }
+                }
+                outcome.stopped();
+            }
+            Ok(())
+        }));
+    }
+
+    fn get_metrics(&self) -> PolarsResult<Option<WriteMetrics>> {
+        Ok(self.metrics.lock().unwrap().take())
+    }
+}
@@ -18,7 +18,7 @@ use crate::async_executor::{AbortOnDropHandle, spawn};
 use crate::async_primitives::connector::Receiver;
 use crate::async_primitives::distributor_channel::distributor_channel;
 use crate::execute::StreamingExecutionState;
-use crate::nodes::io_sinks::metrics::WriteMetrics;
+use crate::nodes::io_sinks::metrics::{MorselMetrics, WriteMetrics};
 use crate::nodes::io_sinks::partition::{SinkSender, open_new_sink};
 use crate::nodes::io_sinks::phase::PhaseOutcome;
 use crate::nodes::io_sinks::{SinkInputPort, SinkNode};
@@ -51,6 +51,7 @@ pub struct PartedPartitionSinkNode {
     per_partition_sort_by: Option<PerPartitionSortBy>,
     partition_metrics: Arc<Mutex<Vec<Vec<WriteMetrics>>>>,
     finish_callback: Option<SinkFinishCallback>,
+    morsel_metrics: Option<(SinkFinishCallback, Arc<MorselMetrics>)>,
 }
 
 const DEFAULT_RETIRE_TASKS: usize = 1;
@@ -67,6 +68,7 @@ impl PartedPartitionSinkNode {
         include_key: bool,
         per_partition_sort_by: Option<PerPartitionSortBy>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
     ) -> Self {
         assert!(!key_cols.is_empty());
 
@@ -106,6 +108,7 @@ impl PartedPartitionSinkNode {
             per_partition_sort_by,
             partition_metrics: Arc::new(Mutex::new(Vec::with_capacity(num_retire_tasks))),
             finish_callback,
+            morsel_metrics: morsel_metrics_callback.map(|f| (f, Arc::new(MorselMetrics::new()))),
         }
     }
 }
@@ -151,6 +154,7 @@ impl SinkNode for PartedPartitionSinkNode {
         let include_key = self.include_key;
         let retire_error = has_error_occurred.clone();
         let per_partition_sort_by = self.per_partition_sort_by.clone();
+        let morsel_metrics = self.morsel_metrics.as_ref().map(|(_, m)| m.clone());
         join_handles.push(spawn(TaskPriority::High, async move {
             struct CurrentSink {
                 sender: SinkSender,
@@ -235,6 +239,7 @@ impl SinkNode for PartedPartitionSinkNode {
                                     verbose,
                                     &task_state,
                                     per_partition_sort_by.as_ref(),
+                                    morsel_metrics.as_ref(),
                                 )
                                 .await?;
                                 file_idx += 1;
@@ -343,7 +348,12 @@ impl SinkNode for PartedPartitionSinkNode {
         let input_schema = self.input_schema.clone();
         let key_cols = self.key_cols.clone();
 
+        let morsel_metrics = self.morsel_metrics.clone();
+
         Some(Box::pin(async move {
+            if let Some((morsel_metrics_callback, morsel_metrics)) = &morsel_metrics {
+                morsel_metrics_callback.call(morsel_metrics.to_df())?;
+            }
             if let Some(finish_callback) = &finish_callback {
                 let mut written_partitions = partition_metrics.lock().unwrap();
                 let written_partitions =
@@ -355,7 +365,7 @@ impl SinkNode for PartedPartitionSinkNode {
                     written_partitions,
                     &sink_input_schema,
                     Some(&input_schema.try_project(key_cols.iter()).unwrap()),
-                );
+                )?;
                 finish_callback.call(df)?;
             }
             Ok(())
@@ -196,7 +196,7 @@ impl MultiScanState {
             task_handle,
             phase_channel_tx,
             bridge_state,
-        } = initialize_multi_scan_pipeline(config);
+        } = initialize_multi_scan_pipeline(config, execution_state.morsel_memory_budget.clone());
 
         let wait_group = WaitGroup::default();
 
@@ -4,7 +4,7 @@ use crate::async_executor;
 use crate::async_executor::{JoinHandle, TaskPriority};
 use crate::async_primitives::connector;
 use crate::async_primitives::wait_group::WaitToken;
-use crate::morsel::{Morsel, MorselSeq, SourceToken};
+use crate::morsel::{Morsel, MorselMemoryBudget, MorselSeq, SourceToken};
 use crate::nodes::io_sources::multi_scan::components::bridge::{
     BridgeRecvPort, BridgeState, StopReason,
 };
@@ -12,6 +12,7 @@ use crate::nodes::io_sources::multi_scan::components::bridge::{
 #[expect(clippy::type_complexity)]
 pub fn spawn_bridge(
     bridge_state: Arc<Mutex<BridgeState>>,
+    morsel_memory_budget: Arc<MorselMemoryBudget>,
 ) -> (
     JoinHandle<()>,
     // For attaching file reader output port
@@ -29,6 +30,7 @@ pub fn spawn_bridge(
             outgoing,
             bridge_state,
             source_token: SourceToken::new(),
+            morsel_memory_budget,
         }
         .run(),
     );
@@ -43,6 +45,7 @@ struct Bridge {
     outgoing: connector::Receiver<(connector::Sender<Morsel>, WaitToken)>,
     bridge_state: Arc<Mutex<BridgeState>>,
     source_token: SourceToken,
+    morsel_memory_budget: Arc<MorselMemoryBudget>,
 }
 
 impl Bridge {
@@ -104,6 +107,10 @@ impl Bridge {
 
             morsel_seq = morsel_seq.saturating_add(1);
 
+            // Pausing here pauses the readers once their output channels are full.
+            self.morsel_memory_budget.wait().await;
+            morsel.reserve_memory(&self.morsel_memory_budget);
+
             while let Err(v) = tx.send(morsel).await {
                 drop(tx);
                 drop(current_phase_wait_token);
@@ -0,0 +1,459 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use polars_core::prelude::row_encode::_get_rows_encoded_ca_unordered;
+use polars_core::prelude::*;
+use polars_core::schema::Schema;
+use polars_error::polars_warn;
+use polars_ops::frame::{AsOfOptions, AsofStrategy, DataFrameJoinOps, JoinArgs, JoinType};
+use polars_ops::series::{SearchSortedSide, SeriesMethods, search_sorted};
+use polars_utils::pl_str::PlSmallStr;
+
+use crate::async_primitives::connector::Receiver;
+use crate::morsel::SourceToken;
+use crate::nodes::compute_node_prelude::*;
+
+/// An asof join which streams both sides in lockstep.
+///
+/// Both sides are sorted by their key, so every left morsel can be joined as soon as the right
+/// rows up to its largest key have arrived. Afterwards only the right rows that the next, larger,
+/// left keys can still match are kept: with `by` groups that is the last row before the key of
+/// every group.
+pub struct AsOfJoinNode {
+    left_on: PlSmallStr,
+    right_on: PlSmallStr,
+    args: JoinArgs,
+    /// The right rows that can still be matched, sorted by their key, without null keys.
+    right: DataFrame,
+    right_done: bool,
+    /// The left morsels that are waiting for their right rows to arrive.
+    pending_left: VecDeque<DataFrame>,
+    /// The largest left key so far, to check that the left side is sorted across morsels.
+    left_max: Option<Scalar>,
+    seq: MorselSeq,
+}
+
+impl AsOfJoinNode {
+    pub fn new(
+        right_input_schema: Arc<Schema>,
+        left_on: PlSmallStr,
+        right_on: PlSmallStr,
+        mut args: JoinArgs,
+    ) -> Self {
+        let JoinType::AsOf(options) = &mut args.how else {
+            unreachable!()
+        };
+        assert!(args.slice.is_none());
+        if options.left_by.is_some() && options.check_sortedness {
+            // Warn once here instead of on every morsel.
+            polars_warn!("Sortedness of columns cannot be checked when 'by' groups provided");
+            options.check_sortedness = false;
+        }
+
+        Self {
+            left_on,
+            right_on,
+            args,
+            right: DataFrame::empty_with_schema(&right_input_schema),
+            right_done: false,
+            pending_left: VecDeque::new(),
+            left_max: None,
+            seq: MorselSeq::default(),
+        }
+    }
+}
+
+/// The state the join task borrows from the node.
+struct AsOfJoinState<'a> {
+    left_on: &'a PlSmallStr,
+    right_on: &'a PlSmallStr,
+    args: &'a JoinArgs,
+    right: &'a mut DataFrame,
+    right_done: bool,
+    pending_left: &'a mut VecDeque<DataFrame>,
+    left_max: &'a mut Option<Scalar>,
+    seq: &'a mut MorselSeq,
+}
+
+impl AsOfJoinState<'_> {
+    fn options(&self) -> &AsOfOptions {
+        let JoinType::AsOf(options) = &self.args.how else {
+            unreachable!()
+        };
+        options
+    }
+
+    fn check_sortedness(&self, key: &Series) -> bool {
+        self.options().check_sortedness && key.dtype().to_physical().is_primitive()
+    }
+
+    fn push_left(&mut self, left: DataFrame) -> PolarsResult<()> {
+        let key = left.column(self.left_on)?.as_materialized_series();
+        if self.check_sortedness(key) {
+            let min = key.min_reduce()?;
+            if let (Some(prev_max), false) = (self.left_max.as_ref(), min.is_null()) {
+                polars_ensure!(
+                    min.value() >= prev_max.value(),
+                    InvalidOperation: "argument in operation 'asof_join' is not sorted, please sort the 'expr/series/column' first"
+                );
+            }
+            let max = key.max_reduce()?;
+            if !max.is_null() {
+                *self.left_max = Some(max);
+            }
+        }
+        self.pending_left.push_back(left);
+        Ok(())
+    }
+
+    fn push_right(&mut self, mut morsel: DataFrame) -> PolarsResult<()> {
+        let key = morsel.column(self.right_on)?.as_materialized_series();
+        // Rows with a null key never match.
+        if key.null_count() > 0 {
+            morsel = morsel.filter(&key.is_not_null())?;
+        }
+        if morsel.height() == 0 {
+            return Ok(());
+        }
+
+        let key = morsel.column(self.right_on)?.as_materialized_series();
+        if self.check_sortedness(key) {
+            key.ensure_sorted_arg("asof_join")?;
+            if self.right.height() > 0 {
+                let last = self
+                    .right
+                    .column(self.right_on)?
+                    .get(self.right.height() - 1)?;
+                polars_ensure!(
+                    key.get(0)? >= last,
+                    InvalidOperation: "argument in operation 'asof_join' is not sorted, please sort the 'expr/series/column' first"
+                );
+            }
+        }
+        self.right.vstack_mut_owned(morsel)?;
+        Ok(())
+    }
+
+    /// Whether all the right rows the keys of `left`, up to `max`, can match have arrived.
+    fn has_right_rows_for(&self, left: &DataFrame, max: &Scalar) -> PolarsResult<bool> {
+        if self.right_done || max.is_null() {
+            return Ok(true);
+        }
+        let right_key = self.right.column(self.right_on)?.as_materialized_series();
+        let len = right_key.len();
+        let n_le_max = search_key(
+            right_key,
+            &max.clone().into_series(PlSmallStr::EMPTY),
+            SearchSortedSide::Right,
+        )?;
+
+        let options = self.options();
+        let (left_by, right_by) = match (&options.left_by, &options.right_by) {
+            (Some(left_by), Some(right_by)) if options.strategy != AsofStrategy::Backward => {
+                (left_by, right_by)
+            },
+            // The right side is sorted, so once a row past the maximum arrived no more rows of
+            // any group can arrive that a backward search would pick.
+            _ if options.strategy != AsofStrategy::Nearest || n_le_max == len => {
+                return Ok(n_le_max < len);
+            },
+            // Ties are broken towards the last equal element, so the nearest strategy needs the
+            // whole run of the first key past the maximum.
+            _ => {
+                let next = right_key.slice(n_le_max as i64, 1);
+                return Ok(search_key(right_key, &next, SearchSortedSide::Right)? < len);
+            },
+        };
+
+        let left_by_dtypes = left_by
+            .iter()
+            .map(|name| Ok(left.column(name)?.dtype()))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let right_by_dtypes = right_by
+            .iter()
+            .map(|name| Ok(self.right.column(name)?.dtype()))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        if left_by_dtypes != right_by_dtypes {
+            // The groups can't be compared by their encoding, wait for the whole right side.
+            return Ok(false);
+        }
+
+        // A group is complete once a row past the maximum arrived, or for the nearest strategy
+        // once the run of the first key past the maximum in the group ended.
+        let after = self.right.slice(n_le_max as i64, len - n_le_max);
+        let after_key = after.column(self.right_on)?.as_materialized_series();
+        let after_groups = _get_rows_encoded_ca_unordered(
+            PlSmallStr::EMPTY,
+            &after.select_columns(right_by.clone())?,
+        )?;
+        let mut complete = PlHashMap::<&[u8], (AnyValue, bool)>::new();
+        for (i, group) in after_groups.iter().enumerate() {
+            let key = after_key.get(i)?;
+            let (first, complete) = complete
+                .entry(group.unwrap())
+                .or_insert_with(|| (key.clone(), options.strategy == AsofStrategy::Forward));
+            *complete |= *first != key;
+        }
+
+        // Left rows with a null key never match.
+        let left_key = left.column(self.left_on)?.as_materialized_series();
+        let left = left.filter(&left_key.is_not_null())?;
+        let left_groups = _get_rows_encoded_ca_unordered(
+            PlSmallStr::EMPTY,
+            &left.select_columns(left_by.clone())?,
+        )?;
+        Ok(left_groups
+            .iter()
+            .all(|group| complete.get(group.unwrap()).is_some_and(|(_, c)| *c)))
+    }
+
+    fn join(&mut self, left: DataFrame) -> PolarsResult<DataFrame> {
+        self.right.as_single_chunk_par();
+        let right = if self.options().left_by.is_none() {
+            let left_key = left.column(self.left_on)?.as_materialized_series();
+            let right_key = self.right.column(self.right_on)?.as_materialized_series();
+            match right_window(left_key, right_key, self.options().strategy)? {
+                Some((start, end)) => self.right.slice(start as i64, end - start),
+                None => self.right.clone(),
+            }
+        } else {
+            self.right.clone()
+        };
+        left.join(
+            &right,
+            [self.left_on.clone()],
+            [self.right_on.clone()],
+            self.args.clone(),
+            None,
+        )
+    }
+
+    /// Drop the right rows that the left keys from `min` on can't match anymore.
+    fn prune_right(&mut self, min: &Scalar) -> PolarsResult<()> {
+        if min.is_null() {
+            return Ok(());
+        }
+        let right_key = self.right.column(self.right_on)?.as_materialized_series();
+        let n_lt_min = search_key(
+            right_key,
+            &min.clone().into_series(PlSmallStr::EMPTY),
+            SearchSortedSide::Left,
+        )?;
+        let len = self.right.height();
+
+        let options = self.options();
+        let keep_last_before = options.strategy != AsofStrategy::Forward;
+        *self.right = match &options.right_by {
+            None => {
+                let start = if keep_last_before {
+                    n_lt_min.saturating_sub(1)
+                } else {
+                    n_lt_min
+                };
+                self.right.slice(start as i64, len - start)
+            },
+            Some(right_by) => {
+                let after = self.right.slice(n_lt_min as i64, len - n_lt_min);
+                if keep_last_before && n_lt_min > 0 {
+                    let mut before = self.right.slice(0, n_lt_min).unique_impl(
+                        true,
+                        Some(right_by.clone()),
+                        UniqueKeepStrategy::Last,
+                        None,
+                    )?;
+                    before.vstack_mut_owned(after)?;
+                    before
+                } else {
+                    after
+                }
+            },
+        };
+        Ok(())
+    }
+
+    async fn run(
+        &mut self,
+        mut left: Option<Receiver<Morsel>>,
+        mut right: Option<Receiver<Morsel>>,
+        mut send: crate::async_primitives::connector::Sender<Morsel>,
+    ) -> PolarsResult<()> {
+        let source_token = SourceToken::new();
+        loop {
+            // Join the left morsels whose right rows have all arrived.
+            while let Some(pending) = self.pending_left.front() {
+                let key = pending.column(self.left_on)?.as_materialized_series();
+                let max = key.max_reduce()?;
+                if !self.has_right_rows_for(pending, &max)? {
+                    let Some(recv) = &mut right else {
+                        break;
+                    };
+                    match recv.recv().await {
+                        Ok(morsel) => self.push_right(morsel.into_df())?,
+                        Err(()) => {
+                            self.right_done = true;
+                            right = None;
+                        },
+                    }
+                    continue;
+                }
+
+                let pending = self.pending_left.pop_front().unwrap();
+                let out = self.join(pending)?;
+                // The next left keys are at least as large as this maximum.
+                self.prune_right(&max)?;
+                let morsel = Morsel::new(out, *self.seq, source_token.clone());
+                *self.seq = self.seq.successor();
+                if send.send(morsel).await.is_err() {
+                    return Ok(());
+                }
+            }
+
+            let Some(recv) = &mut left else {
+                return Ok(());
+            };
+            if !self.pending_left.is_empty() || source_token.stop_requested() {
+                // We can't continue in this phase, stop the left side and buffer the morsels it
+                // already produced.
+                if let Ok(morsel) = recv.recv().await {
+                    morsel.source_token().stop();
+                    self.push_left(morsel.into_df())?;
+                    while let Ok(morsel) = recv.recv().await {
+                        self.push_left(morsel.into_df())?;
+                    }
+                }
+                return Ok(());
+            }
+            match recv.recv().await {
+                Ok(morsel) => self.push_left(morsel.into_df())?,
+                Err(()) => left = None,
+            }
+        }
+    }
+}
+
+/// The number of rows of the sorted `key` before the first `value`, or up to and including it.
+fn search_key(key: &Series, value: &Series, side: SearchSortedSide) -> PolarsResult<usize> {
+    let value = value.cast(key.dtype())?;
+    let idx = search_sorted(key, &value, side, false)?;
+    Ok(idx.get(0).unwrap() as usize)
+}
+
+/// Returns the range of right rows that the left keys can match, or `None` if
+/// the whole right side must be used.
+fn right_window(
+    left_key: &Series,
+    right_key: &Series,
+    strategy: AsofStrategy,
+) -> PolarsResult<Option<(usize, usize)>> {
+    if left_key.dtype() != right_key.dtype() || !right_key.dtype().to_physical().is_primitive() {
+        // Let the join itself raise the appropriate error.
+        return Ok(None);
+    }
+
+    let lo = left_key.min_reduce()?;
+    let hi = left_key.max_reduce()?;
+    if lo.is_null() || hi.is_null() {
+        return Ok(Some((0, 0)));
+    }
+
+    let search = |value: &Series, side: SearchSortedSide| -> PolarsResult<usize> {
+        let idx = search_sorted(right_key, value, side, false)?;
+        Ok(idx.get(0).unwrap() as usize)
+    };
+    let n_lt_lo = search(&lo.into_series(PlSmallStr::EMPTY), SearchSortedSide::Left)?;
+    let n_le_hi = search(&hi.into_series(PlSmallStr::EMPTY), SearchSortedSide::Right)?;
+    let len = right_key.len();
+
+    let window = match strategy {
+        AsofStrategy::Backward => (n_lt_lo.saturating_sub(1), n_le_hi),
+        AsofStrategy::Forward => (n_lt_lo, (n_le_hi + 1).min(len)),
+        AsofStrategy::Nearest => {
+            // Ties are broken towards the last equal element, so include all
+            // duplicates of the first key past the window.
+            let end = if n_le_hi < len {
+                search(&right_key.slice(n_le_hi as i64, 1), SearchSortedSide::Right)?
+            } else {
+                len
+            };
+            (n_lt_lo.saturating_sub(1), end)
+        },
+    };
+    Ok(Some(window))
+}
+
+impl ComputeNode for AsOfJoinNode {
+    fn name(&self) -> &str {
+        "asof-join"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        _state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 2 && send.len() == 1);
+
+        self.right_done |= recv[1] == PortState::Done;
+        let left_done = recv[0] == PortState::Done && self.pending_left.is_empty();
+        if send[0] == PortState::Done || left_done {
+            recv[0] = PortState::Done;
+            recv[1] = PortState::Done;
+            send[0] = PortState::Done;
+            return Ok(());
+        }
+
+        // Like a merge of both sides, we can only make progress if both sides can.
+        let send_blocked = send[0] == PortState::Blocked;
+        let left_blocked = recv[0] == PortState::Blocked && self.pending_left.is_empty();
+        let right_blocked = recv[1] == PortState::Blocked;
+        send[0] = if left_blocked || right_blocked {
+            PortState::Blocked
+        } else {
+            PortState::Ready
+        };
+        if recv[0] != PortState::Done {
+            recv[0] = if send_blocked || right_blocked {
+                PortState::Blocked
+            } else {
+                PortState::Ready
+            };
+        }
+        if !self.right_done {
+            recv[1] = if send_blocked || left_blocked {
+                PortState::Blocked
+            } else {
+                PortState::Ready
+            };
+        }
+        Ok(())
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        _state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 2 && send_ports.len() == 1);
+        let left = recv_ports[0].take().map(|p| p.serial());
+        let right = recv_ports[1].take().map(|p| p.serial());
+        let send = send_ports[0].take().unwrap().serial();
+
+        let mut state = AsOfJoinState {
+            left_on: &self.left_on,
+            right_on: &self.right_on,
+            args: &self.args,
+            right: &mut self.right,
+            right_done: self.right_done,
+            pending_left: &mut self.pending_left,
+            left_max: &mut self.left_max,
+            seq: &mut self.seq,
+        };
+        join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+            state.run(left, right, send).await
+        }));
+    }
+}
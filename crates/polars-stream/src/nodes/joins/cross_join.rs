@@ -3,8 +3,7 @@ use std::sync::Arc;
 use arrow::array::builder::ShareStrategy;
 use polars_core::frame::builder::DataFrameBuilder;
 use polars_core::schema::Schema;
-use polars_ops::frame::{JoinArgs, MaintainOrderJoin};
-use polars_utils::format_pl_smallstr;
+use polars_ops::frame::{_join_suffix_name, JoinArgs, MaintainOrderJoin};
 use polars_utils::pl_str::PlSmallStr;
 
 use crate::morsel::get_ideal_morsel_size;
@@ -40,7 +39,7 @@ impl CrossJoinNode {
             .iter_names()
             .map(|rname| {
                 if left_input_schema.contains(rname) {
-                    Some(format_pl_smallstr!("{}{}", rname, args.suffix()))
+                    Some(_join_suffix_name(rname.as_str(), args.suffix()))
                 } else {
                     None
                 }
@@ -57,6 +56,116 @@ impl CrossJoinNode {
     }
 }
 
+/// How far the output of a cross join of a build frame with a probe morsel has been generated.
+#[derive(Default)]
+pub(crate) struct CrossJoinOffsets {
+    probe_offset: usize,
+    build_offset: usize,
+}
+
+/// Generates the output of the cross join of the build frame with a probe morsel in chunks of
+/// about the ideal morsel size.
+pub(crate) struct CrossJoinChunker<'a> {
+    left_is_build: bool,
+    right_rename: &'a [Option<PlSmallStr>],
+    build_repeater: DataFrameBuilder,
+    probe_repeater: DataFrameBuilder,
+    cached_build_df_repeated: DataFrame,
+    ideal_morsel_size: usize,
+}
+
+impl<'a> CrossJoinChunker<'a> {
+    pub(crate) fn new(
+        left_is_build: bool,
+        left_input_schema: Arc<Schema>,
+        right_input_schema: Arc<Schema>,
+        right_rename: &'a [Option<PlSmallStr>],
+    ) -> Self {
+        let mut build_repeater = DataFrameBuilder::new(left_input_schema);
+        let mut probe_repeater = DataFrameBuilder::new(right_input_schema);
+        if !left_is_build {
+            core::mem::swap(&mut build_repeater, &mut probe_repeater);
+        }
+        Self {
+            left_is_build,
+            right_rename,
+            build_repeater,
+            probe_repeater,
+            cached_build_df_repeated: DataFrame::empty(),
+            ideal_morsel_size: get_ideal_morsel_size(),
+        }
+    }
+
+    /// The next chunk of the cross join of `build_df` with `probe_df`, or `None` if all rows of
+    /// `probe_df` have been joined.
+    pub(crate) fn next_chunk(
+        &mut self,
+        build_df: &DataFrame,
+        probe_df: &DataFrame,
+        offsets: &mut CrossJoinOffsets,
+    ) -> Option<DataFrame> {
+        if offsets.probe_offset >= probe_df.height() {
+            return None;
+        }
+
+        let (build_join_df, probe_join_df);
+        if build_df.height() >= self.ideal_morsel_size {
+            let height = (build_df.height() - offsets.build_offset).min(self.ideal_morsel_size);
+            build_join_df = build_df.slice(offsets.build_offset as i64, height);
+            probe_join_df = probe_df.new_from_index(offsets.probe_offset, height);
+            offsets.build_offset += height;
+            if offsets.build_offset == build_df.height() {
+                offsets.build_offset = 0;
+                offsets.probe_offset += 1;
+            }
+        } else {
+            let max_build_repeats = self.ideal_morsel_size / build_df.height();
+            let build_repeats = (probe_df.height() - offsets.probe_offset).min(max_build_repeats);
+            let build_height = build_repeats * build_df.height();
+            if build_height > self.cached_build_df_repeated.height() {
+                self.build_repeater.subslice_extend_repeated(
+                    build_df,
+                    0,
+                    build_df.height(),
+                    build_repeats,
+                    ShareStrategy::Never,
+                );
+                self.cached_build_df_repeated = self.build_repeater.freeze_reset();
+            }
+            build_join_df = self.cached_build_df_repeated.slice(0, build_height);
+
+            self.probe_repeater.subslice_extend_each_repeated(
+                probe_df,
+                offsets.probe_offset,
+                build_repeats,
+                build_df.height(),
+                ShareStrategy::Always,
+            );
+            probe_join_df = self.probe_repeater.freeze_reset();
+            offsets.probe_offset += build_repeats;
+        }
+
+        let (mut left_join_df, mut right_join_df) = (build_join_df, probe_join_df);
+        if !self.left_is_build {
+            core::mem::swap(&mut left_join_df, &mut right_join_df);
+        }
+        // SAFETY: renaming doesn't change the lengths of the columns.
+        unsafe {
+            for (col, opt_rename) in right_join_df
+                .get_columns_mut()
+                .iter_mut()
+                .zip(self.right_rename)
+            {
+                if let Some(rename) = opt_rename {
+                    col.rename(rename.clone());
+                }
+            }
+        }
+        unsafe { left_join_df.hstack_mut_unchecked(right_join_df.get_columns()) };
+        Some(left_join_df)
+    }
+}
+
 enum CrossJoinState {
     Build(InMemorySinkNode),
     Probe(DataFrame),
@@ -146,112 +255,30 @@ impl ComputeNode for CrossJoinNode {
                 assert!(recv_ports[build_idx].is_none());
                 let receivers = recv_ports[probe_idx].take().unwrap().parallel();
                 let senders = send_ports[0].take().unwrap().parallel();
-                let ideal_morsel_size = get_ideal_morsel_size();
 
                 for (mut recv, mut send) in receivers.into_iter().zip(senders) {
-                    let left_is_build = self.left_is_build;
-                    let left_input_schema = self.left_input_schema.clone();
-                    let right_input_schema = self.right_input_schema.clone();
-                    let right_rename = &self.right_rename;
+                    let mut chunker = CrossJoinChunker::new(
+                        self.left_is_build,
+                        self.left_input_schema.clone(),
+                        self.right_input_schema.clone(),
+                        &self.right_rename,
+                    );
                     let build_df = &*build_df;
-                    join_handles.push(
-                        scope.spawn_task(TaskPriority::High, async move {
-                            let mut build_repeater = DataFrameBuilder::new(left_input_schema);
-                            let mut probe_repeater = DataFrameBuilder::new(right_input_schema);
-                            if !left_is_build {
-                                core::mem::swap(&mut build_repeater, &mut probe_repeater);
-                            }
-                            let mut cached_build_df_repeated = DataFrame::empty();
-
-                            while let Ok(morsel) = recv.recv().await {
-                                let combine =
-                                    |build_join_df: DataFrame, probe_join_df: DataFrame| unsafe {
-                                        let (mut left_join_df, mut right_join_df);
-                                        left_join_df = build_join_df;
-                                        right_join_df = probe_join_df;
-                                        if !left_is_build {
-                                            core::mem::swap(&mut left_join_df, &mut right_join_df);
-                                        }
-
-                                        for (col, opt_rename) in right_join_df
-                                            .get_columns_mut()
-                                            .iter_mut()
-                                            .zip(right_rename)
-                                        {
-                                            if let Some(rename) = opt_rename {
-                                                col.rename(rename.clone());
-                                            }
-                                        }
-
-                                        left_join_df
-                                            .hstack_mut_unchecked(right_join_df.get_columns());
-                                        Morsel::new(
-                                            left_join_df,
-                                            morsel.seq(),
-                                            morsel.source_token().clone(),
-                                        )
-                                    };
-
-                                let probe_df = morsel.df();
-                                if build_df.height() >= ideal_morsel_size {
-                                    for probe_offset in 0..probe_df.height() {
-                                        let mut build_offset = 0;
-                                        while build_offset < build_df.height() {
-                                            let height = (build_df.height() - build_offset)
-                                                .min(ideal_morsel_size);
-                                            let build_join_df =
-                                                build_df.slice(build_offset as i64, height);
-                                            let probe_join_df =
-                                                probe_df.new_from_index(probe_offset, height);
-                                            let combined = combine(build_join_df, probe_join_df);
-                                            if send.send(combined).await.is_err() {
-                                                return Ok(());
-                                            }
-                                            build_offset += height;
-                                        }
-                                    }
-                                } else {
-                                    let max_build_repeats = ideal_morsel_size / build_df.height();
-                                    let mut probe_offset = 0;
-                                    while probe_offset < probe_df.height() {
-                                        let build_repeats = (probe_df.height() - probe_offset)
-                                            .min(max_build_repeats);
-                                        let build_height = build_repeats * build_df.height();
-                                        if build_height > cached_build_df_repeated.height() {
-                                            build_repeater.subslice_extend_repeated(
-                                                build_df,
-                                                0,
-                                                build_df.height(),
-                                                build_repeats,
-                                                ShareStrategy::Never,
-                                            );
-                                            cached_build_df_repeated =
-                                                build_repeater.freeze_reset();
-                                        }
-                                        let build_join_df =
-                                            cached_build_df_repeated.slice(0, build_height);
-
-                                        probe_repeater.subslice_extend_each_repeated(
-                                            probe_df,
-                                            probe_offset,
-                                            build_repeats,
-                                            build_df.height(),
-                                            ShareStrategy::Always,
-                                        );
-                                        let probe_join_df = probe_repeater.freeze_reset();
-
-                                        let combined = combine(build_join_df, probe_join_df);
-                                        if send.send(combined).await.is_err() {
-                                            return Ok(());
-                                        }
-
-                                        probe_offset += build_repeats;
-                                    }
+                    join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+                        while let Ok(morsel) = recv.recv().await {
+                            let mut offsets = CrossJoinOffsets::default();
+                            while let Some(df) =
+                                chunker.next_chunk(build_df, morsel.df(), &mut offsets)
+                            {
+                                let combined =
+                                    Morsel::new(df, morsel.seq(), morsel.source_token().clone());
+                                if send.send(combined).await.is_err() {
+                                    return Ok(());
                                 }
                             }
-                            Ok(())
-                        }),
-                    );
+                        }
+                        Ok(())
+                    }));
                 }
             },
             CrossJoinState::Done => unreachable!(),
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use polars_core::schema::Schema;
+use polars_ops::frame::{_join_suffix_name, JoinArgs, MaintainOrderJoin};
+use polars_utils::pl_str::PlSmallStr;
+
+use super::cross_join::{CrossJoinChunker, CrossJoinOffsets};
+use crate::nodes::compute_node_prelude::*;
+use crate::nodes::group_by::GroupByNode;
+use crate::nodes::in_memory_sink::InMemorySinkNode;
+use crate::nodes::select::SelectNode;
+
+/// A cross join whose output is only consumed by a group-by.
+///
+/// Every pipeline generates the cross join of the build side with its probe morsels chunk by chunk
+/// and inserts the chunks directly into the group-by, so the product is never sent between nodes
+/// and only the running aggregates per group are kept.
+pub struct CrossJoinGroupByNode {
+    left_is_build: bool,
+    left_input_schema: Arc<Schema>,
+    right_input_schema: Arc<Schema>,
+    right_rename: Vec<Option<PlSmallStr>>,
+    // Evaluated in order on the cross join output to get the input of the group-by.
+    selects: Vec<SelectNode>,
+    group_by: GroupByNode,
+    state: CrossJoinGroupByState,
+}
+
+impl CrossJoinGroupByNode {
+    pub fn new(
+        left_input_schema: Arc<Schema>,
+        right_input_schema: Arc<Schema>,
+        args: &JoinArgs,
+        selects: Vec<SelectNode>,
+        group_by: GroupByNode,
+    ) -> Self {
+        let left_is_build = match args.maintain_order {
+            MaintainOrderJoin::None => true, // TODO: size estimation.
+            MaintainOrderJoin::Left | MaintainOrderJoin::LeftRight => false,
+            MaintainOrderJoin::Right | MaintainOrderJoin::RightLeft => true,
+        };
+        let build_input_schema = if left_is_build {
+            &left_input_schema
+        } else {
+            &right_input_schema
+        };
+        let sink_node = InMemorySinkNode::new(build_input_schema.clone());
+        let right_rename = right_input_schema
+            .iter_names()
+            .map(|rname| {
+                if left_input_schema.contains(rname) {
+                    Some(_join_suffix_name(rname.as_str(), args.suffix()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            left_is_build,
+            left_input_schema,
+            right_input_schema,
+            right_rename,
+            selects,
+            group_by,
+            state: CrossJoinGroupByState::Build(sink_node),
+        }
+    }
+}
+
+enum CrossJoinGroupByState {
+    Build(InMemorySinkNode),
+    Probe(DataFrame),
+    // The cross join is done, the group-by produces the output.
+    Aggregate,
+}
+
+impl ComputeNode for CrossJoinGroupByNode {
+    fn name(&self) -> &str {
+        "cross-join-group-by"
+    }
+
+    fn is_memory_intensive_pipeline_blocker(&self) -> bool {
+        true
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 2 && send.len() == 1);
+
+        let build_idx = if self.left_is_build { 0 } else { 1 };
+        let probe_idx = 1 - build_idx;
+
+        // If the output doesn't want any more data, the group-by transitions to being done.
+        if send[0] == PortState::Done {
+            self.state = CrossJoinGroupByState::Aggregate;
+        }
+
+        // Transition to probe?
+        if recv[build_idx] == PortState::Done {
+            if let CrossJoinGroupByState::Build(sink_node) = &mut self.state {
+                let df = sink_node.get_output()?.unwrap();
+                if df.height() > 0 {
+                    self.state = CrossJoinGroupByState::Probe(df);
+                } else {
+                    // An empty build side means the group-by gets no input.
+                    self.state = CrossJoinGroupByState::Aggregate;
+                }
+            }
+        }
+
+        // Transition to aggregate?
+        if recv[probe_idx] == PortState::Done
+            && matches!(self.state, CrossJoinGroupByState::Probe(_))
+        {
+            self.state = CrossJoinGroupByState::Aggregate;
+        }
+
+        match &self.state {
+            CrossJoinGroupByState::Build(_) => {
+                recv[build_idx] = PortState::Ready;
+                recv[probe_idx] = PortState::Blocked;
+                send[0] = PortState::Blocked;
+            },
+            CrossJoinGroupByState::Probe(_) => {
+                recv[build_idx] = PortState::Done;
+                recv[probe_idx] = PortState::Ready;
+                send[0] = PortState::Blocked;
+            },
+            CrossJoinGroupByState::Aggregate => {
+                recv[0] = PortState::Done;
+                recv[1] = PortState::Done;
+                self.group_by
+                    .update_state(&mut [PortState::Done], send, state)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 2 && send_ports.len() == 1);
+        let build_idx = if self.left_is_build { 0 } else { 1 };
+        let probe_idx = 1 - build_idx;
+        match &mut self.state {
+            CrossJoinGroupByState::Build(sink_node) => {
+                assert!(send_ports[0].is_none());
+                assert!(recv_ports[probe_idx].is_none());
+                sink_node.spawn(
+                    scope,
+                    &mut recv_ports[build_idx..build_idx + 1],
+                    &mut [],
+                    state,
+                    join_handles,
+                );
+            },
+            CrossJoinGroupByState::Probe(build_df) => {
+                assert!(send_ports[0].is_none());
+                assert!(recv_ports[build_idx].is_none());
+                let receivers = recv_ports[probe_idx].take().unwrap().parallel();
+                let (ctx, locals) = self.group_by.sink_pipelines().unwrap();
+
+                for (mut recv, local) in receivers.into_iter().zip(locals) {
+                    let mut chunker = CrossJoinChunker::new(
+                        self.left_is_build,
+                        self.left_input_schema.clone(),
+                        self.right_input_schema.clone(),
+                        &self.right_rename,
+                    );
+                    let selects = &self.selects;
+                    let build_df = &*build_df;
+                    join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+                        while let Ok(morsel) = recv.recv().await {
+                            let seq = morsel.seq().to_u64();
+                            let mut offsets = CrossJoinOffsets::default();
+                            while let Some(mut df) =
+                                chunker.next_chunk(build_df, morsel.df(), &mut offsets)
+                            {
+                                for select in selects {
+                                    df = select.select(df, state).await?;
+                                }
+                                local.insert_morsel(df, seq, &ctx, state).await?;
+                            }
+                        }
+                        Ok(())
+                    }));
+                }
+            },
+            CrossJoinGroupByState::Aggregate => {
+                self.group_by
+                    .spawn(scope, &mut [None], send_ports, state, join_handles);
+            },
+        }
+    }
+}
@@ -11,7 +11,7 @@ use polars_core::{POOL, config};
 use polars_expr::hash_keys::HashKeys;
 use polars_expr::idx_table::{IdxTable, new_idx_table};
 use polars_io::pl_async::get_runtime;
-use polars_ops::frame::{JoinArgs, JoinType, MaintainOrderJoin};
+use polars_ops::frame::{_join_suffix_name, JoinArgs, JoinType, MaintainOrderJoin};
 use polars_ops::series::coalesce_columns;
 use polars_utils::cardinality_sketch::CardinalitySketch;
 use polars_utils::hashing::HashPartitioner;
@@ -120,7 +120,7 @@ fn compute_payload_selector(
                 return Ok(selector);
             }
 
-            let suffixed = format_pl_smallstr!("{}{}", c, args.suffix());
+            let suffixed = _join_suffix_name(c.as_str(), args.suffix());
             if other.contains(&suffixed) {
                 polars_bail!(Duplicate: "column with name '{suffixed}' already exists\n\n\
                 You may want to try:\n\
@@ -279,7 +279,10 @@ impl SampleState {
                 morsel.source_token().stop();
             }
 
+            // The samples are only consumed once both sides are sampled, so they can't hold up
+            // the sources.
             drop(morsel.take_consume_token());
+            morsel.release_memory();
             morsels.push(morsel);
         }
         this_final_len.store(*len);
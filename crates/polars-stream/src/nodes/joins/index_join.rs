@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use polars_core::prelude::*;
+use polars_core::schema::Schema;
+use polars_ops::frame::{_join_suffix_name, JoinArgs, JoinIndex, JoinType};
+use polars_utils::pl_str::PlSmallStr;
+
+use crate::nodes::compute_node_prelude::*;
+use crate::nodes::in_memory_sink::InMemorySinkNode;
+
+/// An inner join which collects the right side into a [`JoinIndex`] and streams the left side.
+///
+/// Every left morsel is probed against the index on its own, so the output of a morsel follows
+/// the order of its left rows.
+pub struct IndexJoinNode {
+    left_input_schema: Arc<Schema>,
+    left_on: Vec<PlSmallStr>,
+    right_on: Vec<PlSmallStr>,
+    args: JoinArgs,
+    state: IndexJoinState,
+}
+
+impl IndexJoinNode {
+    pub fn new(
+        left_input_schema: Arc<Schema>,
+        right_input_schema: Arc<Schema>,
+        left_on: Vec<PlSmallStr>,
+        right_on: Vec<PlSmallStr>,
+        args: JoinArgs,
+    ) -> Self {
+        assert!(args.how == JoinType::Inner && args.should_coalesce());
+        assert!(args.slice.is_none());
+        Self {
+            left_input_schema,
+            left_on,
+            right_on,
+            args,
+            state: IndexJoinState::Build(InMemorySinkNode::new(right_input_schema)),
+        }
+    }
+
+    fn finish_build(&self, mut right: DataFrame) -> PolarsResult<JoinIndex> {
+        // The index doesn't know about the left side, so give the right columns that clash with
+        // a left column their suffixed name up front.
+        let clashing = right
+            .get_column_names_owned()
+            .into_iter()
+            .filter(|name| self.left_input_schema.contains(name) && !self.right_on.contains(name))
+            .collect::<Vec<_>>();
+        for name in clashing {
+            right.rename(&name, _join_suffix_name(&name, self.args.suffix()))?;
+        }
+        right.as_single_chunk_par();
+        JoinIndex::build(&right, self.right_on.iter().cloned(), self.args.nulls_equal)
+    }
+}
+
+enum IndexJoinState {
+    Build(InMemorySinkNode),
+    Probe(JoinIndex),
+    Done,
+}
+
+impl ComputeNode for IndexJoinNode {
+    fn name(&self) -> &str {
+        "index-join"
+    }
+
+    fn is_memory_intensive_pipeline_blocker(&self) -> bool {
+        true
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        _state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 2 && send.len() == 1);
+
+        // Are we done?
+        if send[0] == PortState::Done || recv[0] == PortState::Done {
+            self.state = IndexJoinState::Done;
+        }
+
+        // Transition to probe?
+        if recv[1] == PortState::Done {
+            if let IndexJoinState::Build(sink_node) = &mut self.state {
+                let df = sink_node.get_output()?.unwrap();
+                self.state = if df.height() > 0 {
+                    IndexJoinState::Probe(self.finish_build(df)?)
+                } else {
+                    IndexJoinState::Done
+                };
+            }
+        }
+
+        match &self.state {
+            IndexJoinState::Build(_) => {
+                recv[0] = PortState::Blocked;
+                recv[1] = PortState::Ready;
+                send[0] = PortState::Blocked;
+            },
+            IndexJoinState::Probe(_) => {
+                recv[1] = PortState::Done;
+                core::mem::swap(&mut recv[0], &mut send[0]);
+            },
+            IndexJoinState::Done => {
+                recv[0] = PortState::Done;
+                recv[1] = PortState::Done;
+                send[0] = PortState::Done;
+            },
+        }
+        Ok(())
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 2 && send_ports.len() == 1);
+        match &mut self.state {
+            IndexJoinState::Build(sink_node) => {
+                assert!(send_ports[0].is_none());
+                assert!(recv_ports[0].is_none());
+                sink_node.spawn(scope, &mut recv_ports[1..2], &mut [], state, join_handles);
+            },
+            IndexJoinState::Probe(index) => {
+                assert!(recv_ports[1].is_none());
+                let receivers = recv_ports[0].take().unwrap().parallel();
+                let senders = send_ports[0].take().unwrap().parallel();
+
+                for (mut recv, mut send) in receivers.into_iter().zip(senders) {
+                    let index = &*index;
+                    let left_on = &self.left_on;
+                    join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+                        while let Ok(morsel) = recv.recv().await {
+                            let out = morsel
+                                .try_map(|left| index.inner_join(&left, left_on.iter().cloned()))?;
+                            if out.df().height() == 0 {
+                                continue;
+                            }
+                            if send.send(out).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(())
+                    }));
+                }
+            },
+            IndexJoinState::Done => unreachable!(),
+        }
+    }
+}
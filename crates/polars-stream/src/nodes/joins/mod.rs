@@ -12,9 +12,13 @@ use crate::async_primitives::wait_group::WaitGroup;
 use crate::morsel::{Morsel, MorselSeq, SourceToken};
 use crate::pipe::RecvPort;
 
+#[cfg(feature = "asof_join")]
+pub mod asof_join;
 pub mod cross_join;
+pub mod cross_join_group_by;
 pub mod equi_join;
 pub mod in_memory;
+pub mod index_join;
 #[cfg(feature = "semi_anti_join")]
 pub mod semi_anti_join;
 
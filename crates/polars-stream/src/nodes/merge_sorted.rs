@@ -1,8 +1,9 @@
 use std::collections::VecDeque;
 use std::sync::Arc;
 
-use polars_core::prelude::ChunkCompareIneq;
+use polars_core::prelude::{ChunkCompareIneq, Column};
 use polars_core::schema::Schema;
+use polars_error::polars_ensure;
 use polars_ops::frame::_merge_sorted_dfs;
 use polars_utils::pl_str::PlSmallStr;
 
@@ -14,10 +15,13 @@ use crate::nodes::compute_node_prelude::*;
 
 pub struct MergeSortedNode {
     key_column_idx: usize,
+    descending: bool,
+    nulls_last: bool,
 
     seq: MorselSeq,
 
-    starting_nulls: bool,
+    left_null_check: NullPlacementCheck,
+    right_null_check: NullPlacementCheck,
 
     // Not yet merged buffers.
     left_unmerged: VecDeque<DataFrame>,
@@ -25,16 +29,19 @@ pub struct MergeSortedNode {
 }
 
 impl MergeSortedNode {
-    pub fn new(schema: Arc<Schema>, key: PlSmallStr) -> Self {
+    pub fn new(schema: Arc<Schema>, key: PlSmallStr, descending: bool, nulls_last: bool) -> Self {
         assert!(schema.contains(key.as_str()));
         let key_column_idx = schema.index_of(key.as_str()).unwrap();
 
         Self {
             key_column_idx,
+            descending,
+            nulls_last,
 
             seq: MorselSeq::default(),
 
-            starting_nulls: false,
+            left_null_check: NullPlacementCheck::new(key_column_idx, nulls_last),
+            right_null_check: NullPlacementCheck::new(key_column_idx, nulls_last),
 
             left_unmerged: VecDeque::new(),
             right_unmerged: VecDeque::new(),
@@ -42,6 +49,58 @@ impl MergeSortedNode {
     }
 }
 
+/// Checks that the nulls of the key are placed at the expected end of an input.
+///
+/// The morsels of an input are checked as they are buffered for merging, so every row is checked
+/// once instead of for every merged part. Once one of the inputs is exhausted the other one is
+/// passed through as is, without being checked.
+struct NullPlacementCheck {
+    key_column_idx: usize,
+    nulls_last: bool,
+
+    /// Whether the input has passed the point where it switches between nulls and non-nulls.
+    passed_switch: bool,
+}
+
+impl NullPlacementCheck {
+    fn new(key_column_idx: usize, nulls_last: bool) -> Self {
+        Self {
+            key_column_idx,
+            nulls_last,
+            passed_switch: false,
+        }
+    }
+
+    fn check(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        let key = &df[self.key_column_idx];
+        let null_count = key.null_count();
+        let has_non_nulls = null_count < key.len();
+
+        // The nulls need to be at the expected end of this morsel, and this morsel may not go
+        // back to the other side of the switch.
+        let (placed_nulls, is_misplaced) = if self.nulls_last {
+            let placed_nulls = key.tail(Some(null_count));
+            (placed_nulls, self.passed_switch && has_non_nulls)
+        } else {
+            let placed_nulls = key.head(Some(null_count));
+            (placed_nulls, self.passed_switch && null_count > 0)
+        };
+        polars_ensure!(
+            !is_misplaced && placed_nulls.null_count() == null_count,
+            InvalidOperation: "merge-sort expected the nulls of key '{}' to be placed {}",
+            key.name(),
+            if self.nulls_last { "last" } else { "first" },
+        );
+
+        self.passed_switch |= if self.nulls_last {
+            null_count > 0
+        } else {
+            has_non_nulls
+        };
+        Ok(())
+    }
+}
+
 /// Find a part amongst both unmerged buffers which is mergeable.
 ///
 /// This returns `None` if there is nothing mergeable at this point.
@@ -50,9 +109,8 @@ fn find_mergeable(
     right_unmerged: &mut VecDeque<DataFrame>,
 
     key_column_idx: usize,
-
-    is_first: bool,
-    starting_nulls: &mut bool,
+    descending: bool,
+    nulls_last: bool,
 ) -> PolarsResult<Option<(DataFrame, DataFrame)>> {
     fn first_non_empty(vd: &mut VecDeque<DataFrame>) -> Option<DataFrame> {
         let mut df = vd.pop_front()?;
@@ -87,18 +145,13 @@ fn find_mergeable(
 
         let has_nulls = left_null_count > 0 || right_null_count > 0;
 
-        // If we are on the first morsel we need to decide whether we have
-        // nulls first or not.
-        if is_first
-            && has_nulls
-            && (left_key.head(Some(1)).has_nulls() || right_key.head(Some(1)).has_nulls())
-        {
-            *starting_nulls = true;
-        }
+        // Whether a key value comes before or after another one in the sort order.
+        let is_before = |a: &Column, b: &Column| if descending { a.gt(b) } else { a.lt(b) };
+        let is_after = |a: &Column, b: &Column| if descending { a.lt(b) } else { a.gt(b) };
 
-        // For both left and right, find row index of the minimum of the maxima
-        // of the left and right key columns. We can safely merge until this
-        // point.
+        // For both left and right, find row index of the earliest of the last
+        // keys of the left and right key columns. We can safely merge until
+        // this point.
         let mut left_cutoff = left.height();
         let mut right_cutoff = right.height();
 
@@ -110,7 +163,7 @@ fn find_mergeable(
         assert!(!right_key_last.is_empty());
 
         if has_nulls {
-            if *starting_nulls {
+            if !nulls_last {
                 // If there are starting nulls do those first, then repeat
                 // without the nulls.
                 left_cutoff = left_null_count;
@@ -141,16 +194,16 @@ fn find_mergeable(
                     (true, true) => {},
                 }
             }
-        } else if left_key_last.lt(&right_key_last)?.all() {
+        } else if is_before(&left_key_last, &right_key_last)?.all() {
             // @TODO: This is essentially search sorted, but that does not
             // support categoricals at moment.
-            let gt_mask = right_key.gt(&left_key_last)?;
-            right_cutoff = gt_mask.downcast_as_array().values().leading_zeros();
-        } else if left_key_last.gt(&right_key_last)?.all() {
+            let after_mask = is_after(right_key, &left_key_last)?;
+            right_cutoff = after_mask.downcast_as_array().values().leading_zeros();
+        } else if is_after(&left_key_last, &right_key_last)?.all() {
             // @TODO: This is essentially search sorted, but that does not
             // support categoricals at moment.
-            let gt_mask = left_key.gt(&right_key_last)?;
-            left_cutoff = gt_mask.downcast_as_array().values().leading_zeros();
+            let after_mask = is_after(left_key, &right_key_last)?;
+            left_cutoff = after_mask.downcast_as_array().values().leading_zeros();
         }
 
         let left_mergeable: DataFrame;
@@ -237,8 +290,11 @@ impl ComputeNode for MergeSortedNode {
         let send = send_ports[0].take().unwrap().parallel();
 
         let seq = &mut self.seq;
-        let starting_nulls = &mut self.starting_nulls;
         let key_column_idx = self.key_column_idx;
+        let descending = self.descending;
+        let nulls_last = self.nulls_last;
+        let left_null_check = &mut self.left_null_check;
+        let right_null_check = &mut self.right_null_check;
         let left_unmerged = &mut self.left_unmerged;
         let right_unmerged = &mut self.right_unmerged;
 
@@ -288,21 +344,25 @@ impl ComputeNode for MergeSortedNode {
                 async fn buffer_unmerged(
                     port: &mut Receiver<Morsel>,
                     unmerged: &mut VecDeque<DataFrame>,
-                ) {
+                    null_check: &mut NullPlacementCheck,
+                ) -> PolarsResult<()> {
                     // If a stop was requested, we need to buffer the remaining
                     // morsels and trigger a phase transition.
                     let Ok(morsel) = port.recv().await else {
-                        return;
+                        return Ok(());
                     };
 
                     // Request the port stop producing morsels.
                     morsel.source_token().stop();
 
                     // Buffer all the morsels that were already produced.
+                    null_check.check(morsel.df())?;
                     unmerged.push_back(morsel.into_df());
                     while let Ok(morsel) = port.recv().await {
+                        null_check.check(morsel.df())?;
                         unmerged.push_back(morsel.into_df());
                     }
+                    Ok(())
                 }
 
                 let (mut distributor, dist_recv) =
@@ -325,8 +385,8 @@ impl ComputeNode for MergeSortedNode {
                             left_unmerged,
                             right_unmerged,
                             key_column_idx,
-                            seq.to_u64() == 0,
-                            starting_nulls,
+                            descending,
+                            nulls_last,
                         )? {
                             let left_mergeable =
                                 Morsel::new(left_mergeable, *seq, source_token.clone());
@@ -345,23 +405,27 @@ impl ComputeNode for MergeSortedNode {
                             // Request that a port stops producing morsels and buffers all the
                             // remaining morsels.
                             if let Some(p) = &mut left {
-                                buffer_unmerged(p, left_unmerged).await;
+                                buffer_unmerged(p, left_unmerged, left_null_check).await?;
                             }
                             if let Some(p) = &mut right {
-                                buffer_unmerged(p, right_unmerged).await;
+                                buffer_unmerged(p, right_unmerged, right_null_check).await?;
                             }
                             break;
                         }
 
                         assert!(left_unmerged.is_empty() || right_unmerged.is_empty());
-                        let (empty_port, empty_unmerged) = match (
+                        let (empty_port, empty_unmerged, empty_null_check) = match (
                             left_unmerged.is_empty(),
                             right_unmerged.is_empty(),
                             left.as_mut(),
                             right.as_mut(),
                         ) {
-                            (true, _, Some(left), _) => (left, &mut *left_unmerged),
-                            (_, true, _, Some(right)) => (right, &mut *right_unmerged),
+                            (true, _, Some(left), _) => {
+                                (left, &mut *left_unmerged, &mut *left_null_check)
+                            },
+                            (_, true, _, Some(right)) => {
+                                (right, &mut *right_unmerged, &mut *right_null_check)
+                            },
 
                             // If the port that is empty is closed, we don't need to merge anymore.
                             _ => break,
@@ -370,13 +434,14 @@ impl ComputeNode for MergeSortedNode {
                         // Try to get a new morsel from the empty side.
                         let Ok(m) = empty_port.recv().await else {
                             if let Some(p) = &mut left {
-                                buffer_unmerged(p, left_unmerged).await;
+                                buffer_unmerged(p, left_unmerged, left_null_check).await?;
                             }
                             if let Some(p) = &mut right {
-                                buffer_unmerged(p, right_unmerged).await;
+                                buffer_unmerged(p, right_unmerged, right_null_check).await?;
                             }
                             break;
                         };
+                        empty_null_check.check(m.df())?;
                         empty_unmerged.push_back(m.into_df());
                     }
 
@@ -386,8 +451,8 @@ impl ComputeNode for MergeSortedNode {
                         left_unmerged,
                         right_unmerged,
                         key_column_idx,
-                        seq.to_u64() == 0,
-                        starting_nulls,
+                        descending,
+                        nulls_last,
                     )? {
                         let left_mergeable =
                             Morsel::new(left_mergeable, *seq, source_token.clone());
@@ -470,7 +535,9 @@ impl ComputeNode for MergeSortedNode {
                             let left_s = left[key_column_idx].as_materialized_series();
                             let right_s = right[key_column_idx].as_materialized_series();
 
-                            let merged = _merge_sorted_dfs(&left, &right, left_s, right_s, false)?;
+                            let merged = _merge_sorted_dfs(
+                                &left, &right, left_s, right_s, None, false, descending, nulls_last,
+                            )?;
 
                             if ideal_morsel_size > 1 && merged.height() > ideal_morsel_size {
                                 // The merged dataframe will have at most doubled in size from the
@@ -1,3 +1,5 @@
+#[cfg(feature = "cum_agg")]
+pub mod cum_agg;
 pub mod dynamic_slice;
 pub mod filter;
 pub mod group_by;
@@ -142,7 +142,12 @@ impl ComputeNode for MultiplexerNode {
                     let Ok(mut morsel) = receiver.recv().await else {
                         break;
                     };
+                    // The buffered morsels are only consumed once their listeners are
+                    // ready, so they can't hold up the source. This also holds for their
+                    // memory: the clones sent to the listener channels and pushed into the
+                    // buffers share the reservation, so release it before cloning.
                     drop(morsel.take_consume_token());
+                    morsel.release_memory();
 
                     let mut anyone_interested = false;
                     let mut active_listener_interested = false;
@@ -22,6 +22,29 @@ impl SelectNode {
     }
 }
 
+impl SelectNode {
+    /// Evaluate the selectors on `df`.
+    pub(crate) async fn select(
+        &self,
+        df: DataFrame,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<DataFrame> {
+        let mut selected = Vec::new();
+        for selector in self.selectors.iter() {
+            let s = selector.evaluate(&df, &state.in_memory_exec_state).await?;
+            selected.push(s.into_column());
+        }
+
+        if self.extend_original {
+            let mut out = df;
+            out._add_columns(selected, &self.schema)?;
+            Ok(out)
+        } else {
+            DataFrame::new_with_broadcast(selected)
+        }
+    }
+}
+
 impl ComputeNode for SelectNode {
     fn name(&self) -> &str {
         if self.extend_original {
@@ -59,19 +82,7 @@ impl ComputeNode for SelectNode {
             join_handles.push(scope.spawn_task(TaskPriority::High, async move {
                 while let Ok(morsel) = recv.recv().await {
                     let (df, seq, source_token, consume_token) = morsel.into_inner();
-                    let mut selected = Vec::new();
-                    for selector in slf.selectors.iter() {
-                        let s = selector.evaluate(&df, &state.in_memory_exec_state).await?;
-                        selected.push(s.into_column());
-                    }
-
-                    let ret = if slf.extend_original {
-                        let mut out = df;
-                        out._add_columns(selected, &slf.schema)?;
-                        out
-                    } else {
-                        DataFrame::new_with_broadcast(selected)?
-                    };
+                    let ret = slf.select(df, state).await?;
 
                     let mut morsel = Morsel::new(ret, seq, source_token);
                     if let Some(token) = consume_token {
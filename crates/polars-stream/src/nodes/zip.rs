@@ -43,6 +43,8 @@ impl InputHead {
 
     fn add_morsel(&mut self, mut morsel: Morsel) {
         self.total_len += morsel.df().height();
+        // The head is only consumed once all inputs have data, so it can't hold up the sources.
+        morsel.release_memory();
 
         if self.is_broadcast.is_none() {
             if self.total_len > 1 {
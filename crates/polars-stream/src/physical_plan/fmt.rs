@@ -32,12 +32,16 @@ impl NodeStyle {
             | K::InMemorySink { .. }
             | K::Sort { .. }
             | K::GroupBy { .. }
+            | K::CrossJoinGroupBy { .. }
             | K::EquiJoin { .. }
             | K::SemiAntiJoin { .. }
             | K::InMemoryJoin { .. }
+            | K::IndexJoin { .. }
             | K::Multiplexer { .. } => Self::MemoryIntensive,
             #[cfg(feature = "merge_sorted")]
             K::MergeSorted { .. } => Self::MemoryIntensive,
+            #[cfg(feature = "asof_join")]
+            K::AsOfJoin { .. } => Self::MemoryIntensive,
             _ => Self::Generic,
         }
     }
@@ -340,6 +344,17 @@ fn visualize_plan_rec(
         PhysNodeKind::Repeat { value, repeats } => ("repeat".to_owned(), &[*value, *repeats][..]),
         PhysNodeKind::Rle(input) => ("rle".to_owned(), &[*input][..]),
         PhysNodeKind::RleId(input) => ("rle_id".to_owned(), &[*input][..]),
+        #[cfg(feature = "cum_agg")]
+        PhysNodeKind::CumAgg { input, kind } => {
+            use crate::nodes::cum_agg::CumAggKind;
+            let label = match kind {
+                CumAggKind::Sum => "cum_sum",
+                CumAggKind::Min => "cum_min",
+                CumAggKind::Max => "cum_max",
+                CumAggKind::Count => "cum_count",
+            };
+            (label.to_owned(), &[*input][..])
+        },
         PhysNodeKind::OrderedUnion { inputs } => ("ordered-union".to_string(), inputs.as_slice()),
         PhysNodeKind::Zip {
             inputs,
@@ -508,16 +523,73 @@ fn visualize_plan_rec(
             input_right,
             args: _,
         } => ("cross-join".to_string(), &[*input_left, *input_right][..]),
+        PhysNodeKind::CrossJoinGroupBy {
+            input_left,
+            input_right,
+            key,
+            aggs,
+            ..
+        } => (
+            format!(
+                "cross-join-group-by\\nkey:\\n{}\\naggs:\\n{}",
+                fmt_exprs_to_label(key, expr_arena, FormatExprStyle::Select),
+                fmt_exprs_to_label(aggs, expr_arena, FormatExprStyle::Select)
+            ),
+            &[*input_left, *input_right][..],
+        ),
+        PhysNodeKind::IndexJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args: _,
+        } => {
+            let mut out = "index-join".to_string();
+            let mut f = EscapeLabel(&mut out);
+
+            write!(
+                f,
+                "\nleft_on: {}\nright_on: {}",
+                left_on.join(", "),
+                right_on.join(", ")
+            )
+            .unwrap();
+
+            (out, &[*input_left, *input_right][..])
+        },
+        #[cfg(feature = "asof_join")]
+        PhysNodeKind::AsOfJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args: _,
+        } => {
+            let mut out = "asof-join".to_string();
+            let mut f = EscapeLabel(&mut out);
+
+            write!(f, "\nleft_on: {left_on}\nright_on: {right_on}").unwrap();
+
+            (out, &[*input_left, *input_right][..])
+        },
         #[cfg(feature = "merge_sorted")]
         PhysNodeKind::MergeSorted {
             input_left,
             input_right,
             key,
+            descending,
+            nulls_last,
         } => {
             let mut out = "merge-sorted".to_string();
             let mut f = EscapeLabel(&mut out);
 
             write!(f, "\nkey: {key}").unwrap();
+            if *descending {
+                write!(f, "\ndescending: true").unwrap();
+            }
+            if *nulls_last {
+                write!(f, "\nnulls_last: true").unwrap();
+            }
 
             (out, &[*input_left, *input_right][..])
         },
@@ -918,6 +918,14 @@ fn lower_exprs_with_ctx(
                         suffix: None,
                         slice: None,
                         nulls_equal,
+                        nans_equal: true,
+                        case_insensitive: false,
+                        semi_anti_key_names: None,
+                        semi_anti_from_right: false,
+                        output_columns: None,
+                        key_struct: None,
+                        dedup_right: Default::default(),
+                        rechunk: Default::default(),
                         coalesce: Default::default(),
                         maintain_order: Default::default(),
                     },
@@ -994,6 +1002,49 @@ fn lower_exprs_with_ctx(
                 transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(value_key.clone())));
             },
 
+            #[cfg(feature = "cum_agg")]
+            AExpr::Function {
+                input: ref inner_exprs,
+                function:
+                    ref function @ (IRFunctionExpr::CumSum { reverse: false }
+                    | IRFunctionExpr::CumMin { reverse: false }
+                    | IRFunctionExpr::CumMax { reverse: false }
+                    | IRFunctionExpr::CumCount { reverse: false }),
+                options: _,
+            } => {
+                use crate::nodes::cum_agg::CumAggKind;
+
+                assert_eq!(inner_exprs.len(), 1);
+
+                let kind = match function {
+                    IRFunctionExpr::CumSum { .. } => CumAggKind::Sum,
+                    IRFunctionExpr::CumMin { .. } => CumAggKind::Min,
+                    IRFunctionExpr::CumMax { .. } => CumAggKind::Max,
+                    IRFunctionExpr::CumCount { .. } => CumAggKind::Count,
+                    _ => unreachable!(),
+                };
+
+                let input_schema = &ctx.phys_sm[input.node].output_schema;
+                let value_key = unique_column_name();
+                let output_dtype = ExprIR::from_node(expr, ctx.expr_arena)
+                    .dtype(input_schema, ctx.expr_arena)?
+                    .clone();
+
+                let input = build_select_stream_with_ctx(
+                    input,
+                    &[inner_exprs[0].with_alias(value_key.clone())],
+                    ctx,
+                )?;
+                let node_kind = PhysNodeKind::CumAgg { input, kind };
+
+                let output_schema = Schema::from_iter([(value_key.clone(), output_dtype)]);
+                let node_key = ctx
+                    .phys_sm
+                    .insert(PhysNode::new(Arc::new(output_schema), node_kind));
+                input_streams.insert(PhysStream::first(node_key));
+                transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(value_key)));
+            },
+
             // Lower arbitrary elementwise functions.
             ref node @ AExpr::Function {
                 input: ref inner_exprs,
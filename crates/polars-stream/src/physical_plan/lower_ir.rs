@@ -121,6 +121,88 @@ pub struct StreamingLowerIRContext {
     pub prepare_visualization: bool,
 }
 
+/// Returns the key column names if this asof join can run on the streaming
+/// asof join node, which only supports a single plain column key.
+#[cfg(feature = "asof_join")]
+fn streamable_asof_keys(
+    args: &polars_ops::frame::JoinArgs,
+    options: &Option<polars_plan::dsl::JoinTypeOptionsIR>,
+    left_on: &[ExprIR],
+    right_on: &[ExprIR],
+    expr_arena: &Arena<AExpr>,
+) -> Option<(
+    polars_utils::pl_str::PlSmallStr,
+    polars_utils::pl_str::PlSmallStr,
+)> {
+    let polars_ops::frame::JoinType::AsOf(asof_options) = &args.how else {
+        return None;
+    };
+    // String tolerances are resolved against the key dtype by the in-memory engine, which also
+    // sorts the inputs if asked to and collates string keys.
+    if asof_options.tolerance_str.is_some()
+        || asof_options.auto_sort
+        || asof_options.index_column.is_some()
+        || asof_options.string_collation != Default::default()
+        || options.is_some()
+        || args.validation.needs_checks()
+        || left_on.len() != 1
+        || right_on.len() != 1
+    {
+        return None;
+    }
+    let AExpr::Column(left_key) = expr_arena.get(left_on[0].node()) else {
+        return None;
+    };
+    let AExpr::Column(right_key) = expr_arena.get(right_on[0].node()) else {
+        return None;
+    };
+    Some((left_key.clone(), right_key.clone()))
+}
+
+/// The key columns of an inner join that can use an index of the right side, see
+/// [`PhysNodeKind::IndexJoin`]. Only used if `POLARS_STREAMING_INDEX_JOIN=1`.
+fn index_join_keys(
+    args: &polars_ops::frame::JoinArgs,
+    options: &Option<polars_plan::dsl::JoinTypeOptionsIR>,
+    left_on: &[ExprIR],
+    right_on: &[ExprIR],
+    expr_arena: &Arena<AExpr>,
+) -> Option<(
+    Vec<polars_utils::pl_str::PlSmallStr>,
+    Vec<polars_utils::pl_str::PlSmallStr>,
+)> {
+    use polars_ops::frame::{JoinDedupRight, JoinType, MaintainOrderJoin};
+
+    if std::env::var("POLARS_STREAMING_INDEX_JOIN").as_deref() != Ok("1") {
+        return None;
+    }
+    // The left rows are probed in order, so the output can only follow the order of the left
+    // side.
+    if args.how != JoinType::Inner
+        || !args.should_coalesce()
+        || !matches!(
+            args.maintain_order,
+            MaintainOrderJoin::None | MaintainOrderJoin::Left | MaintainOrderJoin::LeftRight
+        )
+        || options.is_some()
+        || args.validation.needs_checks()
+        || !args.nans_equal
+        || args.case_insensitive
+        || args.dedup_right != JoinDedupRight::None
+    {
+        return None;
+    }
+    let key_columns = |keys: &[ExprIR]| {
+        keys.iter()
+            .map(|e| match expr_arena.get(e.node()) {
+                AExpr::Column(name) if name == e.output_name() => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+    Some((key_columns(left_on)?, key_columns(right_on)?))
+}
+
 #[recursive::recursive]
 #[allow(clippy::too_many_arguments)]
 pub fn lower_ir(
@@ -284,6 +366,8 @@ pub fn lower_ir(
                 cloud_options,
                 per_partition_sort_by,
                 finish_callback,
+                morsel_metrics_callback,
+                expected_keys,
             }) => {
                 let base_path = base_path.clone();
                 let file_path_cb = file_path_cb.clone();
@@ -293,6 +377,8 @@ pub fn lower_ir(
                 let cloud_options = cloud_options.clone();
                 let per_partition_sort_by = per_partition_sort_by.clone();
                 let finish_callback = finish_callback.clone();
+                let morsel_metrics_callback = morsel_metrics_callback.clone();
+                let expected_keys = expected_keys.clone();
 
                 let mut input = lower_ir!(*input)?;
                 match &variant {
@@ -301,10 +387,7 @@ pub fn lower_ir(
                         key_exprs,
                         include_key: _,
                     }
-                    | PartitionVariantIR::ByKey {
-                        key_exprs,
-                        include_key: _,
-                    } => {
+                    | PartitionVariantIR::ByKey { key_exprs, .. } => {
                         if key_exprs.is_empty() {
                             polars_bail!(InvalidOperation: "cannot partition by-key without key expressions");
                         }
@@ -341,6 +424,8 @@ pub fn lower_ir(
                     cloud_options,
                     per_partition_sort_by,
                     finish_callback,
+                    morsel_metrics_callback,
+                    expected_keys,
                 }
             },
         },
@@ -365,10 +450,14 @@ pub fn lower_ir(
             input_left,
             input_right,
             key,
+            descending,
+            nulls_last,
         } => {
             let input_left = *input_left;
             let input_right = *input_right;
             let key = key.clone();
+            let descending = *descending;
+            let nulls_last = *nulls_last;
 
             let phys_left = lower_ir!(input_left)?;
             let phys_right = lower_ir!(input_right)?;
@@ -377,6 +466,8 @@ pub fn lower_ir(
                 input_left: phys_left,
                 input_right: phys_right,
                 key,
+                descending,
+                nulls_last,
             }
         },
 
@@ -847,6 +938,7 @@ pub fn lower_ir(
             input,
             id,
             cache_hits: _,
+            memory_budget: _,
         } => {
             let id = *id;
             if let Some(cached) = cache_nodes.get(&id) {
@@ -906,7 +998,40 @@ pub fn lower_ir(
             let options = options.options.clone();
             let phys_left = lower_ir!(input_left)?;
             let phys_right = lower_ir!(input_right)?;
-            if (args.how.is_equi() || args.how.is_semi_anti()) && !args.validation.needs_checks() {
+
+            if let Some((left_keys, right_keys)) =
+                index_join_keys(&args, &options, &left_on, &right_on, expr_arena)
+            {
+                let slice = args.slice;
+                let mut args = args;
+                args.slice = None;
+                let node = phys_sm.insert(PhysNode::new(
+                    output_schema,
+                    PhysNodeKind::IndexJoin {
+                        input_left: phys_left,
+                        input_right: phys_right,
+                        left_on: left_keys,
+                        right_on: right_keys,
+                        args,
+                    },
+                ));
+                let mut stream = PhysStream::first(node);
+                if let Some((offset, len)) = slice {
+                    stream = build_slice_stream(stream, offset, len, phys_sm);
+                }
+                return Ok(stream);
+            }
+
+            // The streaming joins always match NaN keys, compare strings by case, keep the key
+            // names and join every right row, so joins where NaN should never match, the case is
+            // ignored, the keys are renamed or the right keys are deduplicated run in-memory.
+            if (args.how.is_equi() || args.how.is_semi_anti())
+                && !args.validation.needs_checks()
+                && args.nans_equal
+                && !args.case_insensitive
+                && args.semi_anti_key_names.is_none()
+                && args.dedup_right == polars_ops::frame::JoinDedupRight::None
+            {
                 // When lowering the expressions for the keys we need to ensure we keep around the
                 // payload columns, otherwise the input nodes can get replaced by input-independent
                 // nodes since the lowering code does not see we access any non-literal expressions.
@@ -983,15 +1108,39 @@ pub fn lower_ir(
                     stream = build_slice_stream(stream, offset, len, phys_sm);
                 }
                 return Ok(stream);
-            } else {
-                PhysNodeKind::InMemoryJoin {
-                    input_left: phys_left,
-                    input_right: phys_right,
-                    left_on,
-                    right_on,
-                    args,
-                    options,
+            }
+
+            #[cfg(feature = "asof_join")]
+            if let Some((left_key, right_key)) =
+                streamable_asof_keys(&args, &options, &left_on, &right_on, expr_arena)
+            {
+                let slice = args.slice;
+                let mut args = args;
+                args.slice = None;
+                let node = phys_sm.insert(PhysNode::new(
+                    output_schema,
+                    PhysNodeKind::AsOfJoin {
+                        input_left: phys_left,
+                        input_right: phys_right,
+                        left_on: left_key,
+                        right_on: right_key,
+                        args,
+                    },
+                ));
+                let mut stream = PhysStream::first(node);
+                if let Some((offset, len)) = slice {
+                    stream = build_slice_stream(stream, offset, len, phys_sm);
                 }
+                return Ok(stream);
+            }
+
+            PhysNodeKind::InMemoryJoin {
+                input_left: phys_left,
+                input_right: phys_right,
+                left_on,
+                right_on,
+                args,
+                options,
             }
         },
 
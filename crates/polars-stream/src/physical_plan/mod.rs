@@ -164,6 +164,8 @@ pub enum PhysNodeKind {
         cloud_options: Option<CloudOptions>,
         per_partition_sort_by: Option<Vec<SortColumnIR>>,
         finish_callback: Option<SinkFinishCallback>,
+        morsel_metrics_callback: Option<SinkFinishCallback>,
+        expected_keys: Option<Arc<DataFrame>>,
     },
 
     SinkMultiple {
@@ -210,6 +212,12 @@ pub enum PhysNodeKind {
     Rle(PhysStream),
     RleId(PhysStream),
 
+    #[cfg(feature = "cum_agg")]
+    CumAgg {
+        input: PhysStream,
+        kind: crate::nodes::cum_agg::CumAggKind,
+    },
+
     OrderedUnion {
         inputs: Vec<PhysStream>,
     },
@@ -289,6 +297,39 @@ pub enum PhysNodeKind {
         args: JoinArgs,
     },
 
+    /// A cross join directly feeding into a group-by, see [`fuse_cross_join_group_by`].
+    CrossJoinGroupBy {
+        input_left: PhysStream,
+        input_right: PhysStream,
+        args: JoinArgs,
+        /// The schema of the cross join output.
+        join_schema: SchemaRef,
+        /// The selects between the cross join and the group-by in order of evaluation, as the
+        /// selectors, whether they extend their input and the output schema.
+        selects: Vec<(Vec<ExprIR>, bool, SchemaRef)>,
+        key: Vec<ExprIR>,
+        aggs: Vec<ExprIR>,
+    },
+
+    #[cfg(feature = "asof_join")]
+    AsOfJoin {
+        input_left: PhysStream,
+        input_right: PhysStream,
+        left_on: PlSmallStr,
+        right_on: PlSmallStr,
+        args: JoinArgs,
+    },
+
+    /// An inner join on plain key columns which builds a hash index of the right side and probes
+    /// it with the left side.
+    IndexJoin {
+        input_left: PhysStream,
+        input_right: PhysStream,
+        left_on: Vec<PlSmallStr>,
+        right_on: Vec<PlSmallStr>,
+        args: JoinArgs,
+    },
+
     /// Generic fallback for (as-of-yet) unsupported streaming joins.
     /// Fully sinks all data to in-memory data frames and uses the in-memory
     /// engine to perform the join.
@@ -307,6 +348,8 @@ pub enum PhysNodeKind {
         input_right: PhysStream,
 
         key: PlSmallStr,
+        descending: bool,
+        nulls_last: bool,
     },
 }
 
@@ -354,6 +397,12 @@ fn visit_node_inputs_mut(
                 visit(input);
             },
 
+            #[cfg(feature = "cum_agg")]
+            PhysNodeKind::CumAgg { input, .. } => {
+                rec!(input.node);
+                visit(input);
+            },
+
             PhysNodeKind::InMemoryJoin {
                 input_left,
                 input_right,
@@ -373,6 +422,28 @@ fn visit_node_inputs_mut(
                 input_left,
                 input_right,
                 ..
+            }
+            | PhysNodeKind::CrossJoinGroupBy {
+                input_left,
+                input_right,
+                ..
+            }
+            | PhysNodeKind::IndexJoin {
+                input_left,
+                input_right,
+                ..
+            } => {
+                rec!(input_left.node);
+                rec!(input_right.node);
+                visit(input_left);
+                visit(input_right);
+            },
+
+            #[cfg(feature = "asof_join")]
+            PhysNodeKind::AsOfJoin {
+                input_left,
+                input_right,
+                ..
             } => {
                 rec!(input_left.node);
                 rec!(input_right.node);
@@ -463,6 +534,65 @@ fn insert_multiplexers(roots: Vec<PhysNodeKey>, phys_sm: &mut SlotMap<PhysNodeKe
     });
 }
 
+/// Fuse every group-by whose input is a cross join, optionally followed by selects, into a single
+/// node, so the cross join output is aggregated as it is generated.
+///
+/// This must run after the multiplexers are inserted, which guarantees that the cross join and
+/// selects feeding into a group-by have no other consumers.
+fn fuse_cross_join_group_by(root: PhysNodeKey, phys_sm: &mut SlotMap<PhysNodeKey, PhysNode>) {
+    let mut group_by_nodes = vec![root];
+    visit_node_inputs_mut(vec![root], phys_sm, |i| group_by_nodes.push(i.node));
+    group_by_nodes.retain(|n| matches!(phys_sm[*n].kind, PhysNodeKind::GroupBy { .. }));
+    group_by_nodes.sort_unstable();
+    group_by_nodes.dedup();
+
+    for node in group_by_nodes {
+        let PhysNodeKind::GroupBy { input, key, aggs } = &phys_sm[node].kind else {
+            unreachable!()
+        };
+        let (mut input, key, aggs) = (*input, key.clone(), aggs.clone());
+
+        let mut select_nodes = Vec::new();
+        let mut selects = Vec::new();
+        while let PhysNodeKind::Select {
+            input: select_input,
+            selectors,
+            extend_original,
+        } = &phys_sm[input.node].kind
+        {
+            let output_schema = phys_sm[input.node].output_schema.clone();
+            select_nodes.push(input.node);
+            selects.push((selectors.clone(), *extend_original, output_schema));
+            input = *select_input;
+        }
+        let PhysNodeKind::CrossJoin {
+            input_left,
+            input_right,
+            args,
+        } = &phys_sm[input.node].kind
+        else {
+            continue;
+        };
+        let (input_left, input_right, args) = (*input_left, *input_right, args.clone());
+        let join_schema = phys_sm[input.node].output_schema.clone();
+        selects.reverse();
+
+        phys_sm[node].kind = PhysNodeKind::CrossJoinGroupBy {
+            input_left,
+            input_right,
+            args,
+            join_schema,
+            selects,
+            key,
+            aggs,
+        };
+        for select_node in select_nodes {
+            phys_sm.remove(select_node);
+        }
+        phys_sm.remove(input.node);
+    }
+}
+
 pub fn build_physical_plan(
     root: Node,
     ir_arena: &mut Arena<IR>,
@@ -484,5 +614,6 @@ pub fn build_physical_plan(
         ctx,
     )?;
     insert_multiplexers(vec![phys_root.node], phys_sm);
+    fuse_cross_join_group_by(phys_root.node, phys_sm);
     Ok(phys_root.node)
 }
@@ -1,3 +1,4 @@
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
@@ -92,6 +93,44 @@ pub fn physical_plan_to_graph(
     Ok((ctx.graph, ctx.phys_to_graph))
 }
 
+fn build_group_by_node(
+    input_schema: &Arc<Schema>,
+    key: &[ExprIR],
+    aggs: &[ExprIR],
+    output_schema: Arc<Schema>,
+    ctx: &mut GraphConversionContext<'_>,
+) -> PolarsResult<nodes::group_by::GroupByNode> {
+    let key_schema = compute_output_schema(input_schema, key, ctx.expr_arena)?;
+    let grouper = new_hash_grouper(key_schema.clone());
+
+    let key_selectors = key
+        .iter()
+        .map(|e| create_stream_expr(e, ctx, input_schema))
+        .try_collect_vec()?;
+
+    let mut grouped_reductions = Vec::new();
+    let mut grouped_reduction_cols = Vec::new();
+    for agg in aggs {
+        let (reduction, input_node) = into_reduction(agg.node(), ctx.expr_arena, input_schema)?;
+        let AExpr::Column(col) = ctx.expr_arena.get(input_node) else {
+            unreachable!()
+        };
+        grouped_reductions.push(reduction);
+        grouped_reduction_cols.push(col.clone());
+    }
+
+    Ok(nodes::group_by::GroupByNode::new(
+        key_schema,
+        key_selectors,
+        grouper,
+        grouped_reduction_cols,
+        grouped_reductions,
+        output_schema,
+        PlRandomState::default(),
+        ctx.num_pipelines,
+    ))
+}
+
 #[recursive]
 fn to_graph_rec<'a>(
     phys_node_key: PhysNodeKey,
@@ -338,6 +377,8 @@ fn to_graph_rec<'a>(
             cloud_options,
             per_partition_sort_by,
             finish_callback,
+            morsel_metrics_callback,
+            expected_keys,
         } => {
             let input_schema = ctx.phys_sm[input.node].output_schema.clone();
             let input_key = to_graph_rec(input.node, ctx)?;
@@ -345,6 +386,15 @@ fn to_graph_rec<'a>(
             let base_path = base_path.clone();
             let file_path_cb = file_path_cb.clone();
             let ext = PlSmallStr::from_static(file_type.extension());
+            // The files of a partition can only be written in input order if their sinks maintain
+            // the order as well.
+            let mut sink_options = sink_options.clone();
+            if let PartitionVariantIR::ByKey {
+                strict_order: true, ..
+            } = variant
+            {
+                sink_options.maintain_order = true;
+            }
             let create_new = nodes::io_sinks::partition::get_create_new_fn(
                 file_type.clone(),
                 sink_options.clone(),
@@ -387,6 +437,7 @@ fn to_graph_rec<'a>(
                         sink_options.clone(),
                         per_partition_sort_by,
                         finish_callback.clone(),
+                        morsel_metrics_callback.clone(),
                     ),
                 ),
                 PartitionVariantIR::Parted {
@@ -404,11 +455,17 @@ fn to_graph_rec<'a>(
                         *include_key,
                         per_partition_sort_by,
                         finish_callback.clone(),
+                        morsel_metrics_callback.clone(),
                     ),
                 ),
                 PartitionVariantIR::ByKey {
                     key_exprs,
                     include_key,
+                    max_rows_per_file,
+                    dry_run,
+                    separate_null_keys,
+                    flush_morsel_rows,
+                    strict_order: _,
                 } => SinkComputeNode::from(
                     nodes::io_sinks::partition::by_key::PartitionByKeySinkNode::new(
                         input_schema,
@@ -421,6 +478,12 @@ fn to_graph_rec<'a>(
                         *include_key,
                         per_partition_sort_by,
                         finish_callback.clone(),
+                        morsel_metrics_callback.clone(),
+                        expected_keys.clone(),
+                        max_rows_per_file.map(|n| NonZeroUsize::new(n as usize).unwrap()),
+                        *dry_run,
+                        *separate_null_keys,
+                        flush_morsel_rows.map(|n| NonZeroUsize::new(n as usize).unwrap()),
                     ),
                 ),
             };
@@ -551,6 +614,17 @@ fn to_graph_rec<'a>(
             )
         },
 
+        #[cfg(feature = "cum_agg")]
+        CumAgg { input, kind } => {
+            let input_key = to_graph_rec(input.node, ctx)?;
+            assert_eq!(node.output_schema.len(), 1);
+            let (_, dtype) = node.output_schema.get_at_index(0).unwrap();
+            ctx.graph.add_node(
+                nodes::cum_agg::CumAggNode::new(*kind, dtype.clone()),
+                [(input_key, input.port)],
+            )
+        },
+
         OrderedUnion { inputs } => {
             let input_keys = inputs
                 .iter()
@@ -671,39 +745,9 @@ fn to_graph_rec<'a>(
             let input_key = to_graph_rec(input.node, ctx)?;
 
             let input_schema = &ctx.phys_sm[input.node].output_schema;
-            let key_schema = compute_output_schema(input_schema, key, ctx.expr_arena)?;
-            let grouper = new_hash_grouper(key_schema.clone());
-
-            let key_selectors = key
-                .iter()
-                .map(|e| create_stream_expr(e, ctx, input_schema))
-                .try_collect_vec()?;
-
-            let mut grouped_reductions = Vec::new();
-            let mut grouped_reduction_cols = Vec::new();
-            for agg in aggs {
-                let (reduction, input_node) =
-                    into_reduction(agg.node(), ctx.expr_arena, input_schema)?;
-                let AExpr::Column(col) = ctx.expr_arena.get(input_node) else {
-                    unreachable!()
-                };
-                grouped_reductions.push(reduction);
-                grouped_reduction_cols.push(col.clone());
-            }
-
-            ctx.graph.add_node(
-                nodes::group_by::GroupByNode::new(
-                    key_schema,
-                    key_selectors,
-                    grouper,
-                    grouped_reduction_cols,
-                    grouped_reductions,
-                    node.output_schema.clone(),
-                    PlRandomState::default(),
-                    ctx.num_pipelines,
-                ),
-                [(input_key, input.port)],
-            )
+            let group_by =
+                build_group_by_node(input_schema, key, aggs, node.output_schema.clone(), ctx)?;
+            ctx.graph.add_node(group_by, [(input_key, input.port)])
         },
 
         InMemoryJoin {
@@ -890,11 +934,112 @@ fn to_graph_rec<'a>(
             )
         },
 
+        CrossJoinGroupBy {
+            input_left,
+            input_right,
+            args,
+            join_schema,
+            selects,
+            key,
+            aggs,
+        } => {
+            let left_input_key = to_graph_rec(input_left.node, ctx)?;
+            let right_input_key = to_graph_rec(input_right.node, ctx)?;
+            let left_input_schema = ctx.phys_sm[input_left.node].output_schema.clone();
+            let right_input_schema = ctx.phys_sm[input_right.node].output_schema.clone();
+
+            let mut input_schema = join_schema;
+            let mut phys_selects = Vec::with_capacity(selects.len());
+            for (selectors, extend_original, output_schema) in selects {
+                let phys_selectors = selectors
+                    .iter()
+                    .map(|selector| create_stream_expr(selector, ctx, input_schema))
+                    .try_collect_vec()?;
+                phys_selects.push(nodes::select::SelectNode::new(
+                    phys_selectors,
+                    output_schema.clone(),
+                    *extend_original,
+                ));
+                input_schema = output_schema;
+            }
+            let group_by =
+                build_group_by_node(input_schema, key, aggs, node.output_schema.clone(), ctx)?;
+
+            ctx.graph.add_node(
+                nodes::joins::cross_join_group_by::CrossJoinGroupByNode::new(
+                    left_input_schema,
+                    right_input_schema,
+                    args,
+                    phys_selects,
+                    group_by,
+                ),
+                [
+                    (left_input_key, input_left.port),
+                    (right_input_key, input_right.port),
+                ],
+            )
+        },
+
+        IndexJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args,
+        } => {
+            let left_input_key = to_graph_rec(input_left.node, ctx)?;
+            let right_input_key = to_graph_rec(input_right.node, ctx)?;
+            let left_input_schema = ctx.phys_sm[input_left.node].output_schema.clone();
+            let right_input_schema = ctx.phys_sm[input_right.node].output_schema.clone();
+
+            ctx.graph.add_node(
+                nodes::joins::index_join::IndexJoinNode::new(
+                    left_input_schema,
+                    right_input_schema,
+                    left_on.clone(),
+                    right_on.clone(),
+                    args.clone(),
+                ),
+                [
+                    (left_input_key, input_left.port),
+                    (right_input_key, input_right.port),
+                ],
+            )
+        },
+
+        #[cfg(feature = "asof_join")]
+        AsOfJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args,
+        } => {
+            let left_input_key = to_graph_rec(input_left.node, ctx)?;
+            let right_input_key = to_graph_rec(input_right.node, ctx)?;
+            let right_input_schema = ctx.phys_sm[input_right.node].output_schema.clone();
+
+            ctx.graph.add_node(
+                nodes::joins::asof_join::AsOfJoinNode::new(
+                    right_input_schema,
+                    left_on.clone(),
+                    right_on.clone(),
+                    args.clone(),
+                ),
+                [
+                    (left_input_key, input_left.port),
+                    (right_input_key, input_right.port),
+                ],
+            )
+        },
+
         #[cfg(feature = "merge_sorted")]
         MergeSorted {
             input_left,
             input_right,
             key,
+            descending,
+            nulls_last,
         } => {
             let left_input_key = to_graph_rec(input_left.node, ctx)?;
             let right_input_key = to_graph_rec(input_right.node, ctx)?;
@@ -902,7 +1047,12 @@ fn to_graph_rec<'a>(
             let input_schema = ctx.phys_sm[input_left.node].output_schema.clone();
 
             ctx.graph.add_node(
-                nodes::merge_sorted::MergeSortedNode::new(input_schema, key.clone()),
+                nodes::merge_sorted::MergeSortedNode::new(
+                    input_schema,
+                    key.clone(),
+                    *descending,
+                    *nulls_last,
+                ),
                 [
                     (left_input_key, input_left.port),
                     (right_input_key, input_right.port),
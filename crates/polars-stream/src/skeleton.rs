@@ -57,6 +57,7 @@ pub struct StreamingQuery {
     root_phys_node: PhysNodeKey,
     phys_sm: SlotMap<PhysNodeKey, PhysNode>,
     phys_to_graph: SecondaryMap<PhysNodeKey, GraphNodeKey>,
+    executor_threads: Option<Vec<usize>>,
 }
 
 impl StreamingQuery {
@@ -102,11 +103,23 @@ impl StreamingQuery {
             root_phys_node,
             phys_sm,
             phys_to_graph,
+            executor_threads: None,
         };
 
         Ok(out)
     }
 
+    /// Only run the tasks of the query on the executor threads with the given indices, or on any
+    /// thread if `None`.
+    ///
+    /// This keeps the other threads free for the rest of the process, e.g. for a latency-critical
+    /// query while a bulk query runs. Indices that aren't executor threads are ignored, executing
+    /// the query errors if none of them is.
+    pub fn with_executor_threads(mut self, threads: Option<Vec<usize>>) -> Self {
+        self.executor_threads = threads;
+        self
+    }
+
     pub fn execute(self) -> PolarsResult<QueryResult> {
         let StreamingQuery {
             top_ir,
@@ -114,18 +127,26 @@ impl StreamingQuery {
             root_phys_node,
             phys_sm,
             phys_to_graph,
+            executor_threads,
         } = self;
 
         crate::async_executor::clear_task_wait_statistics();
-        let mut results = crate::execute::execute_graph(&mut graph)?;
+        crate::async_executor::reset_scheduler_metrics();
+        let mut results = crate::execute::execute_graph(&mut graph, executor_threads.as_deref())?;
 
         if std::env::var("POLARS_TRACK_WAIT_STATS").as_deref() == Ok("1") {
-            let mut stats = crate::async_executor::get_task_wait_statistics();
+            let mut stats = crate::async_executor::take_task_wait_statistics();
             stats.sort_by_key(|(_l, w)| Reverse(*w));
             eprintln!("Time spent waiting for async tasks:");
             for (loc, wait_time) in stats {
                 eprintln!("{}:{} - {:?}", loc.file(), loc.line(), wait_time);
             }
+
+            let metrics = crate::async_executor::get_scheduler_metrics();
+            eprintln!(
+                "Peak number of busy executor threads: {} / {}",
+                metrics.max_active_runners, metrics.num_threads
+            );
         }
 
         match top_ir {
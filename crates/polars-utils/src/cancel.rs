@@ -0,0 +1,93 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+use crate::relaxed_cell::RelaxedCell;
+
+/// A flag that cancels a query, which wakes the tasks waiting for the cancellation.
+#[derive(Default, Debug)]
+pub struct CancelToken {
+    cancelled: RelaxedCell<bool>,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel and wake everything waiting in [`CancelToken::cancelled`].
+    pub fn cancel(&self) {
+        self.cancelled.store(true);
+        for waker in std::mem::take(&mut *self.wakers.lock().unwrap()) {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load()
+    }
+
+    /// Resolves once the token is cancelled.
+    pub fn cancelled(&self) -> Cancelled<'_> {
+        Cancelled { token: self }
+    }
+}
+
+pub struct Cancelled<'a> {
+    token: &'a CancelToken,
+}
+
+impl Future for Cancelled<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        let mut wakers = self.token.wakers.lock().unwrap();
+        // Check again under the lock, `cancel` takes the wakers after setting the flag.
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+
+    use super::*;
+
+    struct CountWakes(AtomicUsize);
+
+    impl Wake for CountWakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_cancel_wakes_waiters() {
+        let token = CancelToken::new();
+        let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+        let waker = Waker::from(wakes.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut cancelled = std::pin::pin!(token.cancelled());
+        assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(wakes.0.load(Ordering::Relaxed), 0);
+
+        token.cancel();
+        assert_eq!(wakes.0.load(Ordering::Relaxed), 1);
+        assert!(cancelled.as_mut().poll(&mut cx).is_ready());
+        assert!(std::pin::pin!(token.cancelled()).poll(&mut cx).is_ready());
+    }
+}
@@ -10,6 +10,7 @@ pub mod algebraic_ops;
 pub mod arena;
 pub mod binary_search;
 pub mod cache;
+pub mod cancel;
 pub mod cardinality_sketch;
 pub mod cell;
 pub mod chunks;
@@ -1,7 +1,10 @@
 use chrono::NaiveDate;
 use polars::prelude::*;
-use polars_lazy::frame::pivot::PivotExpr;
-use polars_ops::pivot::{PivotAgg, pivot, pivot_stable};
+use polars_lazy::frame::pivot::{PivotExpr, pivot_group_by};
+use polars_ops::pivot::{
+    PivotAgg, PivotOptions, PivotReducer, PivotState, pivot, pivot_stable, pivot_stable_multi_agg,
+    pivot_stable_with_options,
+};
 
 #[test]
 #[cfg(feature = "dtype-date")]
@@ -271,3 +274,670 @@ fn test_pivot_datetime() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "concat_str")]
+fn test_pivot_string_concat() -> PolarsResult<()> {
+    let df = df![
+        "user" => ["ann", "bob", "ann", "ann", "bob", "cid"],
+        "kind" => ["tag", "tag", "tag", "label", "label", "label"],
+        "value" => ["red", "blue", "green", "a", "b", "c"],
+    ]?;
+    let agg = || {
+        Some(PivotAgg(Arc::new(PivotExpr::from_expr(
+            col("").str().join(", ", true),
+        ))))
+    };
+
+    // Values within a cell are joined in order of appearance, missing cells are empty strings.
+    let out = pivot_stable(
+        &df,
+        ["kind"],
+        Some(["user"]),
+        Some(["value"]),
+        false,
+        agg(),
+        None,
+    )?;
+    let expected = df![
+        "user" => ["ann", "bob", "cid"],
+        "tag" => ["red, green", "blue", ""],
+        "label" => ["a", "b", "c"],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    let options = PivotOptions {
+        missing_as_null: true,
+        ..Default::default()
+    };
+    let out = pivot_stable_with_options(
+        &df,
+        ["kind"],
+        Some(["user"]),
+        Some(["value"]),
+        false,
+        agg(),
+        None,
+        &options,
+    )?;
+    let expected = df![
+        "user" => ["ann", "bob", "cid"],
+        "tag" => [Some("red, green"), Some("blue"), None],
+        "label" => ["a", "b", "c"],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_drop_all_null_columns() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "b"],
+        "on" => ["x", "y", "x"],
+        "values" => [Some(1), None, Some(3)],
+    ]?;
+
+    let options = PivotOptions {
+        drop_all_null_columns: true,
+        ..Default::default()
+    };
+    let out = pivot_stable_with_options(
+        &df,
+        ["on"],
+        Some(["index"]),
+        Some(["values"]),
+        false,
+        None,
+        None,
+        &options,
+    )?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "x" => [1, 3],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_require_unique_cells() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "b", "b"],
+        "on" => ["x", "y", "x", "x"],
+        "values" => [1, 2, 3, 4],
+    ]?;
+
+    let options = PivotOptions {
+        require_unique_cells: true,
+        ..Default::default()
+    };
+    let pivot = |df: &DataFrame| {
+        pivot_stable_with_options(
+            df,
+            ["on"],
+            Some(["index"]),
+            Some(["values"]),
+            false,
+            Some(PivotAgg(Arc::new(PivotExpr::from_expr(col("").first())))),
+            None,
+            &options,
+        )
+    };
+
+    let err = pivot(&df).unwrap_err().to_string();
+    assert!(err.contains("index (\"b\") and column \"x\""), "{err}");
+
+    let out = pivot(&df.head(Some(3)))?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "x" => [Some(1), Some(3)],
+        "y" => [Some(2), None],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_group_by() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "b", "b", "c", "a"],
+        "on" => ["x", "y", "x", "x", "y", "x"],
+        "values" => [1, 2, 3, 4, 5, 6],
+    ]?;
+    let aggs = [
+        col("values").sum().alias("sum"),
+        col("values").max().alias("max"),
+    ];
+
+    let out = pivot_group_by(&df, ["on"], ["index"], aggs.clone(), false, None)?;
+
+    let grouped = df
+        .lazy()
+        .group_by_stable([col("index"), col("on")])
+        .agg(aggs)
+        .collect()?;
+    let expected = pivot_stable(
+        &grouped,
+        ["on"],
+        Some(["index"]),
+        Some(["sum", "max"]),
+        false,
+        Some(PivotAgg(Arc::new(PivotExpr::from_expr(col("").first())))),
+        None,
+    )?;
+    assert!(out.equals_missing(&expected));
+    assert_eq!(
+        out.get_column_names_str(),
+        ["index", "sum_x", "sum_y", "max_x", "max_y"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_input_is_grouped() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "b", "c"],
+        "on" => ["x", "y", "x", "y"],
+        "values" => [1, 2, 3, 4],
+    ]?;
+    let pivot = |df: &DataFrame, options: &PivotOptions| {
+        pivot_stable_with_options(
+            df,
+            ["on"],
+            Some(["index"]),
+            Some(["values"]),
+            false,
+            Some(PivotAgg(Arc::new(PivotExpr::from_expr(col("").first())))),
+            None,
+            options,
+        )
+    };
+
+    let grouped = PivotOptions {
+        input_is_grouped: true,
+        ..Default::default()
+    };
+    let out = pivot(&df, &grouped)?;
+    let expected = pivot(&df, &PivotOptions::default())?;
+    assert!(out.equals_missing(&expected));
+
+    // Without validation the last value of a repeated combination wins.
+    let ungrouped = df.vstack(&df![
+        "index" => ["a"],
+        "on" => ["x"],
+        "values" => [10],
+    ]?)?;
+    let out = pivot(&ungrouped, &grouped)?;
+    assert_eq!(out.column("x")?.i32()?.get(0), Some(10));
+
+    // An input that isn't grouped is rejected instead of silently keeping one of the values.
+    let options = PivotOptions {
+        require_unique_cells: true,
+        ..grouped
+    };
+    let err = pivot(&ungrouped, &options).unwrap_err().to_string();
+    assert!(err.contains("index (\"a\") and column \"x\""), "{err}");
+    assert!(err.contains("declared to be grouped"), "{err}");
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_sort_columns_by_value() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "b", "b"],
+        "on" => [10, 2, 1, 10],
+        "values" => [1, 2, 3, 4],
+    ]?;
+
+    let pivot = |options: &PivotOptions| {
+        pivot_stable_with_options(
+            &df,
+            ["on"],
+            Some(["index"]),
+            Some(["values"]),
+            true,
+            None,
+            None,
+            options,
+        )
+    };
+
+    let out = pivot(&PivotOptions::default())?;
+    assert_eq!(out.get_column_names_str(), ["index", "1", "10", "2"]);
+
+    let options = PivotOptions {
+        sort_columns_by_value: true,
+        ..Default::default()
+    };
+    let out = pivot(&options)?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "1" => [None, Some(3)],
+        "2" => [Some(2), None],
+        "10" => [Some(1), Some(4)],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_closure() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "a", "b"],
+        "on" => ["x", "x", "y", "x"],
+        "values" => [1, 2, 3, 4],
+    ]?;
+    let pivot = |agg_fn: PivotAgg| {
+        pivot_stable(
+            &df,
+            ["on"],
+            Some(["index"]),
+            Some(["values"]),
+            false,
+            Some(agg_fn),
+            None,
+        )
+    };
+
+    let sum_of_squares = PivotAgg::closure(|s| {
+        let ca = s.i32().unwrap();
+        AnyValue::Float64(
+            ca.into_no_null_iter()
+                .fold(0.0, |acc, v| acc + (v * v) as f64),
+        )
+    });
+    let out = pivot(sum_of_squares)?;
+    // Cells without values get the result of the closure on an empty `Series`.
+    let expected = df![
+        "index" => ["a", "b"],
+        "x" => [5.0, 16.0],
+        "y" => [9.0, 0.0],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out}");
+
+    // The dtype of the cells must be consistent.
+    let inconsistent = PivotAgg::closure(|s| match s.len() {
+        1 => AnyValue::Int64(1),
+        _ => AnyValue::StringOwned("many".into()),
+    });
+    assert!(pivot(inconsistent).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_list() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "a", "b"],
+        "on" => ["x", "x", "y", "x"],
+        "values" => [1, 2, 3, 4],
+    ]?;
+    let pivot = |agg_fn: PivotAgg, options: PivotOptions| {
+        pivot_stable_with_options(
+            &df,
+            ["on"],
+            Some(["index"]),
+            Some(["values"]),
+            false,
+            Some(agg_fn),
+            None,
+            &options,
+        )
+    };
+    let list = |values: &[&[i32]]| {
+        let values = values
+            .iter()
+            .map(|v| Series::new(PlSmallStr::EMPTY, *v))
+            .collect::<Vec<_>>();
+        Series::new(PlSmallStr::EMPTY, values)
+    };
+
+    // Cells without values get an empty list.
+    let expected = DataFrame::new(vec![
+        Column::new("index".into(), ["a", "b"]),
+        list(&[&[1, 2], &[4]]).with_name("x".into()).into(),
+        list(&[&[3], &[]]).with_name("y".into()).into(),
+    ])?;
+    let out = pivot(PivotAgg::list(), PivotOptions::default())?;
+    assert!(out.equals_missing(&expected), "{out}");
+    let implode = PivotAgg(Arc::new(PivotExpr::from_expr(col("").implode())));
+    let out = pivot(implode, PivotOptions::default())?;
+    assert!(out.equals_missing(&expected), "{out}");
+
+    let options = PivotOptions {
+        missing_as_null: true,
+        ..Default::default()
+    };
+    let out = pivot(PivotAgg::list(), options)?;
+    assert_eq!(
+        out.column("y")?.dtype(),
+        &DataType::List(Box::new(DataType::Int32))
+    );
+    assert_eq!(out.column("y")?.null_count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_state_update() -> PolarsResult<()> {
+    let base = df![
+        "index" => ["a", "a", "b"],
+        "on" => ["x", "y", "x"],
+        "v1" => [Some(1), None, Some(3)],
+        "v2" => [1.5, 2.5, 3.5],
+    ]?;
+    let batches = [
+        // Updates existing cells.
+        df![
+            "index" => ["a", "b"],
+            "on" => ["y", "x"],
+            "v1" => [Some(-4), None],
+            "v2" => [0.5, 8.5],
+        ]?,
+        // Adds a row and a column.
+        df![
+            "index" => ["c", "a"],
+            "on" => ["z", "z"],
+            "v1" => [Some(5), Some(6)],
+            "v2" => [1.0, -1.0],
+        ]?,
+    ];
+
+    for reducer in [
+        PivotReducer::Sum,
+        PivotReducer::Count,
+        PivotReducer::Min,
+        PivotReducer::Max,
+    ] {
+        let mut state = PivotState::new(
+            &base,
+            ["on"],
+            Some(["index"]),
+            None::<[&str; 0]>,
+            reducer,
+            None,
+        )?;
+        let mut all = base.clone();
+        for batch in &batches {
+            state.update(batch)?;
+            all.vstack_mut(batch)?;
+
+            let expected = pivot_stable(
+                &all,
+                ["on"],
+                Some(["index"]),
+                None::<[&str; 0]>,
+                false,
+                Some(reducer.into()),
+                None,
+            )?;
+            let out = state.emit()?;
+            assert!(
+                out.equals_missing(&expected),
+                "{reducer:?}: {out} != {expected}"
+            );
+        }
+        // Emitting again without updates gives the same frame.
+        let expected = state.emit()?;
+        assert!(state.emit()?.equals_missing(&expected));
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dtype-date")]
+fn test_pivot_column_name_format() -> PolarsResult<()> {
+    let mut df = df![
+        "index" => ["a", "a", "a", "b"],
+        "on" => [0, 31, 40, 100],
+        "values" => [1, 2, 3, 4],
+    ]?;
+    df.try_apply("on", |s| s.cast(&DataType::Date))?;
+
+    let pivot = |format: &str| {
+        let options = PivotOptions {
+            column_name_format: Some(format.into()),
+            ..Default::default()
+        };
+        pivot_stable_with_options(
+            &df,
+            ["on"],
+            Some(["index"]),
+            Some(["values"]),
+            false,
+            Some(PivotAgg(Arc::new(PivotExpr::from_expr(col("").sum())))),
+            None,
+            &options,
+        )
+    };
+
+    let out = pivot("%Y-%m")?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "1970-01" => [1, 0],
+        "1970-02" => [5, 0],
+        "1970-04" => [0, 4],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    let out = pivot("%Y-Q%q")?;
+    assert_eq!(out.get_column_names_str(), ["index", "1970-Q1", "1970-Q2"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_margins() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "b", "b", "c"],
+        "on" => ["x", "y", "x", "x", "y"],
+        "values" => [1, 2, 3, 4, 5],
+    ]?;
+    let options = PivotOptions {
+        margins: true,
+        ..Default::default()
+    };
+    let pivot = |agg_expr: Expr| {
+        pivot_stable_with_options(
+            &df,
+            ["on"],
+            Some(["index"]),
+            Some(["values"]),
+            false,
+            Some(PivotAgg(Arc::new(PivotExpr::from_expr(agg_expr)))),
+            None,
+            &options,
+        )
+    };
+
+    let out = pivot(col("").sum())?;
+    let expected = df![
+        "index" => ["a", "b", "c", "Total"],
+        "x" => [1, 7, 0, 8],
+        "y" => [2, 0, 5, 7],
+        "Total" => [3, 7, 5, 15],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    let out = pivot(col("").count())?;
+    let expected = df![
+        "index" => ["a", "b", "c", "Total"],
+        "x" => [1 as IdxSize, 2, 0, 3],
+        "y" => [1 as IdxSize, 0, 1, 2],
+        "Total" => [2 as IdxSize, 2, 1, 5],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    assert!(pivot(col("").first()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_struct_columns() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "b", "b"],
+        "size" => ["large", "small", "large", "large"],
+        "kind" => ["egg", "egg", "egg", "ham"],
+        "values" => [1, 2, 3, 4],
+    ]?;
+    let options = PivotOptions {
+        struct_columns: true,
+        missing_as_null: true,
+        ..Default::default()
+    };
+    let out = pivot_stable_with_options(
+        &df,
+        ["size", "kind"],
+        Some(["index"]),
+        Some(["values"]),
+        false,
+        Some(PivotAgg(Arc::new(PivotExpr::from_expr(col("").sum())))),
+        None,
+        &options,
+    )?;
+
+    assert_eq!(out.get_column_names_str(), ["index", "large", "small"]);
+    let large = out.column("large")?.struct_()?.fields_as_series();
+    assert_eq!(large.len(), 2);
+    assert!(large[0].equals_missing(&Series::new("egg".into(), [Some(1), Some(3)])));
+    assert!(large[1].equals_missing(&Series::new("ham".into(), [None, Some(4)])));
+    let small = out.column("small")?.struct_()?.fields_as_series();
+    assert_eq!(small.len(), 1);
+    assert!(small[0].equals_missing(&Series::new("egg".into(), [Some(2), None])));
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_weighted() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "a", "b", "b"],
+        "on" => ["x", "x", "y", "x", "x"],
+        "values" => [Some(1.0), Some(3.0), Some(2.0), None, Some(5.0)],
+        "weight" => [1, 3, 0, 2, 0],
+    ]?;
+    // The weight column is not inferred as a value column.
+    let pivot = |agg_fn: PivotAgg| {
+        pivot_stable(
+            &df,
+            ["on"],
+            Some(["index"]),
+            None::<[&str; 0]>,
+            false,
+            Some(agg_fn),
+            None,
+        )
+    };
+
+    let out = pivot(PivotAgg::weighted_mean("weight"))?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "x" => [Some(2.5), None],
+        "y" => [None::<f64>, None],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out}");
+
+    let out = pivot(PivotAgg::weighted_sum("weight"))?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "x" => [10.0, 0.0],
+        "y" => [0.0, 0.0],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out}");
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_multi_agg() -> PolarsResult<()> {
+    let df = df![
+        "index" => ["a", "a", "a", "b"],
+        "on" => ["large", "large", "small", "large"],
+        "values" => [1, 3, 2, 5],
+    ]?;
+    let agg = |e: Expr| PivotAgg(Arc::new(PivotExpr::from_expr(e)));
+
+    let out = pivot_stable_multi_agg(
+        &df,
+        ["on"],
+        Some(["index"]),
+        Some(["values"]),
+        false,
+        &[
+            ("sum".into(), agg(col("").sum())),
+            ("mean".into(), agg(col("").mean())),
+        ],
+        None,
+        &PivotOptions::default(),
+    )?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "large_sum" => [4, 5],
+        "large_mean" => [2.0, 5.0],
+        "small_sum" => [2, 0],
+        "small_mean" => [Some(2.0), None],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out}");
+
+    let out = pivot_stable_multi_agg(
+        &df,
+        ["on"],
+        Some(["index"]),
+        Some(["values"]),
+        false,
+        &[
+            ("sum".into(), agg(col("").sum())),
+            ("sum".into(), agg(col("").mean())),
+        ],
+        None,
+        &PivotOptions::default(),
+    );
+    assert!(out.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_pivot_state_emit_is_not_patched() -> PolarsResult<()> {
+    let base = df![
+        "index" => ["a", "b"],
+        "on" => ["x", "x"],
+        "v" => [1, 2],
+    ]?;
+    let mut state = PivotState::new(
+        &base,
+        ["on"],
+        Some(["index"]),
+        None::<[&str; 0]>,
+        PivotReducer::Sum,
+        None,
+    )?;
+    let first = state.emit()?;
+
+    // The update patches the cells of the state, the emitted frame keeps its values.
+    state.update(&df![
+        "index" => ["b"],
+        "on" => ["x"],
+        "v" => [10],
+    ]?)?;
+    let expected = df![
+        "index" => ["a", "b"],
+        "x" => [1, 2],
+    ]?;
+    assert!(first.equals_missing(&expected), "{first}");
+    let expected = df![
+        "index" => ["a", "b"],
+        "x" => [1, 12],
+    ]?;
+    let out = state.emit()?;
+    assert!(out.equals_missing(&expected), "{out}");
+    Ok(())
+}
@@ -1,4 +1,3 @@
-#[cfg(feature = "lazy")]
 use polars::prelude::*;
 
 #[test]
@@ -56,3 +55,1223 @@ fn join_empty_datasets() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(all(feature = "lazy", feature = "iejoin"))]
+fn iejoin_maintain_order() -> PolarsResult<()> {
+    let left = df![
+        "id" => [0, 1, 2, 3, 4],
+        "x" => [5, 1, 3, 2, 4],
+    ]?;
+    let right = df![
+        "y" => [2, 4, 3],
+    ]?;
+    let options = Some(JoinTypeOptions::IEJoin(IEJoinOptions {
+        operator1: InequalityOperator::Lt,
+        operator2: None,
+    }));
+    let join = |maintain_order| {
+        let args = JoinArgs {
+            maintain_order,
+            ..JoinArgs::new(JoinType::IEJoin)
+        };
+        left.join(&right, ["x"], ["y"], args, options.clone())
+    };
+
+    let out = join(MaintainOrderJoin::LeftRight)?;
+    let expected = df![
+        "id" => [1, 1, 1, 2, 3, 3],
+        "x" => [1, 1, 1, 3, 2, 2],
+        "y" => [2, 4, 3, 4, 4, 3],
+    ]?;
+    assert!(out.equals(&expected));
+
+    let out = join(MaintainOrderJoin::Left)?;
+    assert!(out.select(["id"])?.equals(&expected.select(["id"])?));
+
+    // Without an order requirement the same rows are returned in the native order.
+    let out = join(MaintainOrderJoin::None)?.sort(["id", "y"], Default::default())?;
+    let expected = expected.sort(["id", "y"], Default::default())?;
+    assert!(out.equals(&expected));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "lazy", feature = "iejoin"))]
+fn iejoin_row_indices_match_join() -> PolarsResult<()> {
+    let left = df![
+        "x" => [5, 1, 3, 2, 4],
+        "t" => [1, 5, 2, 4, 3],
+    ]?;
+    let right = df![
+        "y" => [2, 4, 3],
+        "u" => [3, 1, 4],
+    ]?;
+    let options = IEJoinOptions {
+        operator1: InequalityOperator::Lt,
+        operator2: Some(InequalityOperator::Gt),
+    };
+    let args = JoinArgs {
+        maintain_order: MaintainOrderJoin::LeftRight,
+        ..JoinArgs::new(JoinType::IEJoin)
+    };
+    let out = left.join(
+        &right,
+        ["x", "t"],
+        ["y", "u"],
+        args,
+        Some(JoinTypeOptions::IEJoin(options.clone())),
+    )?;
+
+    let series = |df: &DataFrame, names: [&str; 2]| {
+        names.map(|name| df.column(name).unwrap().as_materialized_series().clone())
+    };
+    let (left_idx, right_idx) = iejoin_row_indices(
+        series(&left, ["x", "t"]).to_vec(),
+        series(&right, ["y", "u"]).to_vec(),
+        &options,
+        None,
+        MaintainOrderJoin::LeftRight,
+    )?;
+    assert_eq!(left_idx.len(), out.height());
+    let gathered = left
+        .take(&left_idx)?
+        .hstack(right.take(&right_idx)?.get_columns())?;
+    assert!(gathered.equals(&out));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn join_on_expressions() -> PolarsResult<()> {
+    use polars::lazy::frame::join_on::join_on;
+
+    let left = df![
+        "a" => [1, 2, 3],
+        "x" => ["p", "q", "r"],
+    ]?;
+    let right = df![
+        "b" => [2, 4, 8],
+        "y" => ["s", "t", "u"],
+    ]?;
+    let args = JoinArgs {
+        maintain_order: MaintainOrderJoin::Left,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+
+    // Computed keys are not coalesced.
+    let out = join_on(
+        &left,
+        &right,
+        &[col("a") * lit(2)],
+        &[col("b")],
+        args.clone(),
+    )?;
+    let expected = df![
+        "a" => [1, 2],
+        "x" => ["p", "q"],
+        "b" => [2, 4],
+        "y" => ["s", "t"],
+    ]?;
+    assert!(out.equals(&expected));
+
+    // Column keys behave like a regular join.
+    let out = join_on(&left, &right, &[col("a")], &[col("b")], args.clone())?;
+    let expected = left.join(&right, ["a"], ["b"], args, None)?;
+    assert!(out.equals(&expected));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn join_suffix_prefix_and_template() -> PolarsResult<()> {
+    let left = df![
+        "id" => [1, 2],
+        "amount" => [10, 20],
+    ]?;
+    let right = df![
+        "id" => [1, 2],
+        "amount" => [30, 40],
+    ]?;
+    let args = JoinArgs {
+        maintain_order: MaintainOrderJoin::Left,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+
+    let out = left.join(
+        &right,
+        ["id"],
+        ["id"],
+        args.clone().with_prefix("right_"),
+        None,
+    )?;
+    assert_eq!(out.get_column_names(), &["id", "amount", "right_amount"]);
+
+    let args = args.with_suffix(Some("{}_r".into()));
+    let out = left.join(&right, ["id"], ["id"], args.clone(), None)?;
+    assert_eq!(out.get_column_names(), &["id", "amount", "amount_r"]);
+
+    // The optimizer has to resolve the renamed columns as well.
+    let out = left
+        .lazy()
+        .join(
+            right.lazy(),
+            [col("id")],
+            [col("id")],
+            args.with_suffix(Some("r_{}".into())),
+        )
+        .filter(col("r_amount").gt(lit(30)))
+        .select([col("r_amount")])
+        .collect()?;
+    assert!(out.equals(&df!["r_amount" => [40]]?));
+
+    Ok(())
+}
+
+#[test]
+fn left_join_slice_stops_early() -> PolarsResult<()> {
+    let left = df![
+        "id" => [1, 2, 3, 4, 2, 5],
+        "l" => [0, 1, 2, 3, 4, 5],
+    ]?;
+    let right = df![
+        "id" => [2, 2, 3, 2, 4],
+        "r" => [10, 11, 12, 13, 14],
+    ]?;
+
+    let full = left.join(&right, ["id"], ["id"], JoinType::Left.into(), None)?;
+    for (offset, len) in [(0, 2), (1, 3), (2, 4), (7, 10), (20, 1)] {
+        let args = JoinArgs {
+            slice: Some((offset, len)),
+            ..JoinArgs::new(JoinType::Left)
+        };
+        let out = left.join(&right, ["id"], ["id"], args, None)?;
+        assert!(out.equals_missing(&full.slice(offset, len)));
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn join_nans_equal() -> PolarsResult<()> {
+    let left = df![
+        "k" => [Some(f64::NAN), Some(-0.0), None, Some(1.0)],
+        "l" => [0, 1, 2, 3],
+    ]?;
+    let right = df![
+        "k" => [Some(-f64::NAN), Some(0.0), None],
+        "r" => [10, 11, 12],
+    ]?;
+    let args = JoinArgs {
+        maintain_order: MaintainOrderJoin::Left,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+    let join = |args: JoinArgs, on: &[&str]| {
+        let on = on.iter().copied();
+        left.join(&right, on.clone(), on, args, None)
+    };
+
+    // All NaN bit patterns match, as do -0.0 and +0.0.
+    let out = join(args.clone(), &["k"])?;
+    assert_eq!(out.column("l")?.i32()?.to_vec(), &[Some(0), Some(1)]);
+
+    // NaN never matches, not even if nulls match.
+    for nulls_equal in [false, true] {
+        let args = JoinArgs {
+            nans_equal: false,
+            nulls_equal,
+            ..args.clone()
+        };
+        let out = join(args.clone(), &["k"])?;
+        let expected = if nulls_equal {
+            vec![Some(1), Some(2)]
+        } else {
+            vec![Some(1)]
+        };
+        assert_eq!(out.column("l")?.i32()?.to_vec(), expected);
+
+        let args = JoinArgs {
+            how: JoinType::Left,
+            ..args
+        };
+        let out = join(args, &["k"])?;
+        assert_eq!(out.height(), 4);
+        assert_eq!(out.column("r")?.i32()?.get(0), None);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn join_case_insensitive() -> PolarsResult<()> {
+    let left = df![
+        "name" => ["Alice", "BOB", "carol"],
+        "l" => [0, 1, 2],
+    ]?;
+    let right = df![
+        "name" => ["alice", "Bob", "Dave"],
+        "r" => [10, 11, 12],
+    ]?;
+    let args = JoinArgs {
+        case_insensitive: true,
+        maintain_order: MaintainOrderJoin::Left,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+
+    let out = left.join(&right, ["name"], ["name"], args.clone(), None)?;
+    assert_eq!(
+        out.column("name")?
+            .str()?
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        &["Alice", "BOB"]
+    );
+    assert_eq!(out.column("r")?.i32()?.to_vec(), &[Some(10), Some(11)]);
+
+    // The keys of both sides are kept as they are.
+    let args = JoinArgs {
+        how: JoinType::Full,
+        coalesce: JoinCoalesce::KeepColumns,
+        ..args
+    };
+    let out = left.join(&right, ["name"], ["name"], args.clone(), None)?;
+    assert_eq!(out.column("name_right")?.str()?.get(1), Some("Bob"));
+
+    let left = left.lazy().with_column(col("l").alias("k")).collect()?;
+    let right = right.lazy().with_column(col("r").alias("k")).collect()?;
+    assert!(left.join(&right, ["k"], ["k"], args, None).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn full_join_coalesce_multiple_keys() -> PolarsResult<()> {
+    let left = df![
+        "a" => [Some(1), Some(2), None, Some(3)],
+        "b" => [Some("x"), Some("y"), Some("z"), None],
+        "l" => [0, 1, 2, 3],
+    ]?;
+    let right = df![
+        "a" => [Some(1), Some(2), None, Some(4)],
+        "b" => [Some("x"), Some("w"), Some("z"), Some("v")],
+        "r" => [10, 11, 12, 13],
+    ]?;
+
+    for nulls_equal in [false, true] {
+        let args = JoinArgs {
+            coalesce: JoinCoalesce::CoalesceColumns,
+            maintain_order: MaintainOrderJoin::LeftRight,
+            nulls_equal,
+            ..JoinArgs::new(JoinType::Full)
+        };
+        let out = left.join(&right, ["a", "b"], ["a", "b"], args, None)?;
+        assert_eq!(out.get_column_names(), &["a", "b", "l", "r"]);
+
+        // The keys of every row are those of the side it came from.
+        let a = out.column("a")?.i32()?;
+        let b = out.column("b")?.str()?;
+        let l = out.column("l")?.i32()?;
+        let r = out.column("r")?.i32()?;
+        for i in 0..out.height() {
+            let (keys, idx) = match l.get(i) {
+                Some(idx) => (&left, idx),
+                None => (&right, r.get(i).unwrap() - 10),
+            };
+            assert_eq!(a.get(i), keys.column("a")?.i32()?.get(idx as usize));
+            assert_eq!(b.get(i), keys.column("b")?.str()?.get(idx as usize));
+        }
+
+        let expected_height = if nulls_equal { 6 } else { 7 };
+        assert_eq!(out.height(), expected_height);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn join_index_reused_for_probes() -> PolarsResult<()> {
+    let dim = df![
+        "a" => [Some(1), Some(2), Some(2), None],
+        "b" => ["x", "y", "y", "z"],
+        "v" => [10, 20, 21, 30],
+    ]?;
+    let facts = [
+        df![
+            "a" => [Some(2), Some(1), None, Some(3)],
+            "b" => ["y", "x", "z", "x"],
+            "v" => [0, 1, 2, 3],
+        ]?,
+        df![
+            "a" => [Some(1), Some(1), Some(2)],
+            "b" => ["x", "y", "y"],
+            "v" => [4, 5, 6],
+        ]?,
+    ];
+
+    for nulls_equal in [false, true] {
+        let index = JoinIndex::build(&dim, ["a", "b"], nulls_equal)?;
+        let args = JoinArgs {
+            maintain_order: MaintainOrderJoin::Left,
+            nulls_equal,
+            ..JoinArgs::new(JoinType::Inner)
+        };
+        for fact in &facts {
+            let out = index.inner_join(fact, ["a", "b"])?;
+            let expected = fact.join(&dim, ["a", "b"], ["a", "b"], args.clone(), None)?;
+            assert!(out.equals_missing(&expected));
+        }
+    }
+
+    let index = JoinIndex::build(&dim, ["a"], false)?;
+    assert!(index.inner_join(&facts[0], ["b"]).is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lazy")]
+fn join_with_report_matches_output() -> PolarsResult<()> {
+    let left = df![
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"],
+        "v" => [1, 2, 3],
+    ]?;
+    let right = df![
+        "a" => [1, 2, 4],
+        "c" => ["x", "y", "z"],
+        "v" => [4, 5, 6],
+        "w" => [7, 8, 9],
+    ]?;
+
+    let (out, report) = left.join_with_report(
+        &right,
+        ["a", "b"],
+        ["a", "c"],
+        JoinArgs::new(JoinType::Inner),
+        None,
+    )?;
+    assert_eq!(report.dropped, ["a", "c"]);
+    assert_eq!(report.renamed, [("v".into(), "v_right".into())]);
+    assert_eq!(out.get_column_names(), ["a", "b", "v", "v_right", "w"]);
+
+    for how in [
+        JoinType::Inner,
+        JoinType::Left,
+        JoinType::Right,
+        JoinType::Full,
+        JoinType::Cross,
+    ] {
+        for coalesce in [JoinCoalesce::CoalesceColumns, JoinCoalesce::KeepColumns] {
+            let args = JoinArgs::new(how.clone()).with_coalesce(coalesce);
+            let (on_left, on_right) = if how == JoinType::Cross {
+                (vec![], vec![])
+            } else {
+                (vec!["a"], vec!["a"])
+            };
+            let (out, report) = left.join_with_report(&right, on_left, on_right, args, None)?;
+
+            let is_right = how == JoinType::Right;
+            let mut expected = left
+                .get_column_names()
+                .into_iter()
+                .filter(|name| !(is_right && report.dropped.contains(name)))
+                .cloned()
+                .collect::<Vec<_>>();
+            expected.extend(
+                right
+                    .get_column_names()
+                    .into_iter()
+                    .filter(|name| is_right || !report.dropped.contains(name))
+                    .map(|name| {
+                        report
+                            .renamed
+                            .iter()
+                            .find(|(old, _)| old == name)
+                            .map_or(name.clone(), |(_, new)| new.clone())
+                    }),
+            );
+            assert_eq!(
+                out.get_column_names_owned(),
+                expected,
+                "{how:?} {coalesce:?}"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "semi_anti_join")]
+fn semi_join_small_left() -> PolarsResult<()> {
+    let left = df![
+        "a" => [Some(3), None, Some(1), Some(7), Some(3)],
+        "v" => [0, 1, 2, 3, 4],
+    ]?;
+    let right = df![
+        "a" => (0..100).map(|i| (i % 10 != 5).then_some(i % 4)).collect::<Vec<_>>(),
+    ]?;
+
+    for (nulls_equal, expected) in [(false, [0, 2, 4].as_slice()), (true, &[0, 1, 2, 4])] {
+        let args = JoinArgs {
+            nulls_equal,
+            ..JoinArgs::new(JoinType::Semi)
+        };
+        let out = left.join(&right, ["a"], ["a"], args, None)?;
+        assert_eq!(
+            out.column("v")?
+                .i32()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "lazy", feature = "semi_anti_join"))]
+fn semi_anti_join_key_names() -> PolarsResult<()> {
+    let left = df![
+        "id" => [1, 2, 3],
+        "grp" => ["x", "y", "x"],
+    ]?;
+    let right = df![
+        "key" => [1, 3],
+        "g" => ["x", "x"],
+    ]?;
+
+    let args = JoinArgs {
+        semi_anti_key_names: Some(vec!["key".into()]),
+        ..JoinArgs::new(JoinType::Anti)
+    };
+    let out = left.join(&right, ["id"], ["key"], args, None)?;
+    assert_eq!(out.get_column_names_str(), ["key", "grp"]);
+    assert_eq!(out.column("key")?.i32()?.to_vec(), &[Some(2)]);
+
+    // Multiple keys, the schema of the lazy join matches its output.
+    let q = left
+        .lazy()
+        .join_builder()
+        .with(right.lazy())
+        .left_on([col("id"), col("grp")])
+        .right_on([col("key"), col("g")])
+        .how(JoinType::Semi)
+        .semi_anti_key_names(["key", "g"])
+        .finish();
+    let schema = q.clone().collect_schema()?;
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names_str(), ["key", "g"]);
+    assert_eq!(out.schema().as_ref(), schema.as_ref());
+    assert_eq!(out.height(), 2);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "lazy", feature = "semi_anti_join"))]
+fn semi_anti_join_from_right() -> PolarsResult<()> {
+    let left = df![
+        "id" => [1, 2, 3],
+        "a" => ["x", "y", "z"],
+    ]?;
+    let right = df![
+        "key" => [3, 4, 1, 5],
+        "b" => [30, 40, 10, 50],
+    ]?;
+
+    let args = JoinArgs {
+        semi_anti_from_right: true,
+        ..JoinArgs::new(JoinType::Anti)
+    };
+    let out = left.join(&right, ["id"], ["key"], args, None)?;
+    let expected = df![
+        "key" => [4, 5],
+        "b" => [40, 50],
+    ]?;
+    assert!(out.equals(&expected));
+
+    let args = JoinArgs {
+        semi_anti_from_right: true,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+    assert!(left.join(&right, ["id"], ["key"], args, None).is_err());
+
+    let q = left
+        .lazy()
+        .join_builder()
+        .with(right.lazy())
+        .left_on([col("id")])
+        .right_on([col("key")])
+        .how(JoinType::Semi)
+        .semi_anti_from_right(true)
+        .maintain_order(MaintainOrderJoin::Right)
+        .finish();
+    let schema = q.clone().collect_schema()?;
+    let out = q.collect()?;
+    let expected = df![
+        "key" => [3, 1],
+        "b" => [30, 10],
+    ]?;
+    assert!(out.equals(&expected));
+    assert_eq!(out.schema().as_ref(), schema.as_ref());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "cross_join")]
+fn cross_join_max_rows() -> PolarsResult<()> {
+    let left = df!["a" => [1, 2, 3]]?;
+    let right = df!["b" => [1, 2, 3, 4]]?;
+
+    let out = left.cross_join(&right, None, None, Some(12))?;
+    assert_eq!(out.height(), 12);
+
+    let err = left.cross_join(&right, None, None, Some(11)).unwrap_err();
+    assert!(err.to_string().contains("12 rows (3 x 4)"));
+
+    // Only the rows in the slice count.
+    let out = left.cross_join(&right, None, Some((0, 5)), Some(5))?;
+    assert_eq!(out.height(), 5);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "cross_join")]
+fn cross_join_pairs() -> PolarsResult<()> {
+    let left = df!["a" => [1, 2, 3]]?;
+    let right = df!["b" => [10, 20]]?;
+
+    let out = left.cross_join(&right, None, None, None)?;
+    let pairs: Vec<_> = left.cross_join_pairs(&right).collect();
+    assert_eq!(pairs.len(), out.height());
+    for (row, (l, r)) in pairs.into_iter().enumerate() {
+        assert_eq!(out.column("a")?.get(row)?, left.column("a")?.get(l)?);
+        assert_eq!(out.column("b")?.get(row)?, right.column("b")?.get(r)?);
+    }
+
+    // The pairs are produced lazily, so the caller can stop early.
+    let first = left.cross_join_pairs(&right).find(|&(_, r)| r == 1);
+    assert_eq!(first, Some((0, 1)));
+    assert_eq!(left.cross_join_pairs(&right.clear()).count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn inner_join_broadcast_small_input() -> PolarsResult<()> {
+    use polars_core::series::IsSorted;
+
+    // The large input is sorted, so without broadcasting the small input gets sorted for a merge
+    // join.
+    let mut large_key = Series::new("a".into(), (0..1000).map(|i| i / 2).collect::<Vec<i32>>());
+    large_key.set_sorted_flag(IsSorted::Ascending);
+    let large = DataFrame::new(vec![
+        large_key.into(),
+        Column::new("l".into(), (0..1000).collect::<Vec<i32>>()),
+    ])?;
+    let small = df![
+        "a" => [7, 3, 499, 3, 1000],
+        "s" => [0, 1, 2, 3, 4],
+    ]?;
+
+    for maintain_order in [MaintainOrderJoin::Left, MaintainOrderJoin::Right] {
+        let args = JoinArgs {
+            maintain_order,
+            ..JoinArgs::new(JoinType::Inner)
+        };
+        let expected = (
+            large.join(&small, ["a"], ["a"], args.clone(), None)?,
+            small.join(&large, ["a"], ["a"], args.clone(), None)?,
+        );
+
+        unsafe { std::env::set_var("POLARS_JOIN_BROADCAST_ROWS", "10") };
+        let out = (
+            large.join(&small, ["a"], ["a"], args.clone(), None),
+            small.join(&large, ["a"], ["a"], args.clone(), None),
+        );
+        unsafe { std::env::remove_var("POLARS_JOIN_BROADCAST_ROWS") };
+
+        assert!(out.0?.equals(&expected.0));
+        assert!(out.1?.equals(&expected.1));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn inner_join_sorted_keys_with_nulls() -> PolarsResult<()> {
+    use polars_core::series::IsSorted;
+
+    let sorted = |name: &str, values: &[Option<i32>]| {
+        let mut s = Series::new(name.into(), values);
+        s.set_sorted_flag(IsSorted::Ascending);
+        s
+    };
+    let left_key = sorted("a", &[None, None, Some(1), Some(2), Some(2), Some(4)]);
+    let right_key = sorted("a", &[Some(0), Some(2), Some(2), Some(4), None]);
+
+    let left = DataFrame::new(vec![
+        left_key.clone().into(),
+        Column::new("l".into(), [0, 1, 2, 3, 4, 5]),
+    ])?;
+    let right = DataFrame::new(vec![
+        right_key.clone().into(),
+        Column::new("r".into(), [0, 1, 2, 3, 4]),
+    ])?;
+    let out = left.join(&right, ["a"], ["a"], JoinType::Inner.into(), None)?;
+    let expected = df![
+        "a" => [2, 2, 2, 2, 4],
+        "l" => [3, 3, 4, 4, 5],
+        "r" => [1, 2, 1, 2, 3],
+    ]?;
+    assert!(out.equals(&expected));
+
+    // Matching nulls can't use the merge join on the non-null values.
+    let args = JoinArgs {
+        nulls_equal: true,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+    let out = left.join(&right, ["a"], ["a"], args, None)?;
+    assert_eq!(out.height(), 7);
+
+    Ok(())
+}
+
+#[test]
+fn join_validation_reports_duplicate_key() -> PolarsResult<()> {
+    let left = df![
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"],
+    ]?;
+    let right = df![
+        "a" => [Some(1), None, Some(3), None, Some(3), Some(3)],
+        "b" => ["x", "y", "z", "y", "z", "z"],
+    ]?;
+    let args = JoinArgs {
+        validation: JoinValidation::ManyToOne,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+
+    // Null keys don't match, so they aren't duplicates.
+    let err = left
+        .join(&right, ["a"], ["a"], args.clone(), None)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("m:1 validation: key 3 occurs 3 times in the right dataset")
+    );
+
+    let err = left
+        .join(&right, ["a", "b"], ["a", "b"], args, None)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains(r#"key (3, "z") occurs 3 times in the right dataset"#)
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "asof_join")]
+fn asof_join_auto_sort() -> PolarsResult<()> {
+    let left = df![
+        "g" => ["x", "y", "x"],
+        "a" => [3, 1, 1],
+    ]?;
+    let right = df![
+        "g" => ["x", "y", "x", "y"],
+        "a" => [2, 0, 1, 3],
+        "val" => [20, 0, 10, 30],
+    ]?;
+    let args = |by: bool, maintain_order| JoinArgs {
+        maintain_order,
+        ..JoinArgs::new(JoinType::AsOf(Box::new(AsOfOptions {
+            left_by: by.then(|| vec!["g".into()]),
+            right_by: by.then(|| vec!["g".into()]),
+            allow_eq: true,
+            auto_sort: true,
+            ..Default::default()
+        })))
+    };
+
+    let out = left.join(
+        &right,
+        ["a"],
+        ["a"],
+        args(false, MaintainOrderJoin::Left),
+        None,
+    )?;
+    let expected = df![
+        "g" => ["x", "y", "x"],
+        "a" => [3, 1, 1],
+        "g_right" => ["y", "x", "x"],
+        "val" => [30, 10, 10],
+    ]?;
+    assert!(out.equals(&expected), "{out} != {expected}");
+
+    let out = left.join(
+        &right,
+        ["a"],
+        ["a"],
+        args(true, MaintainOrderJoin::Left),
+        None,
+    )?;
+    let expected = df![
+        "g" => ["x", "y", "x"],
+        "a" => [3, 1, 1],
+        "val" => [20, 0, 10],
+    ]?;
+    assert!(out.equals(&expected), "{out} != {expected}");
+
+    // Without maintaining the order the output follows the sorted left rows.
+    let out = left.join(
+        &right,
+        ["a"],
+        ["a"],
+        args(true, MaintainOrderJoin::None),
+        None,
+    )?;
+    let expected = df![
+        "g" => ["y", "x", "x"],
+        "a" => [1, 1, 3],
+        "val" => [0, 10, 20],
+    ]?;
+    assert!(out.equals(&expected), "{out} != {expected}");
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "asof_join")]
+fn asof_join_relative_tolerance() -> PolarsResult<()> {
+    let left = df!["price" => [100.0, 200.0, 1000.0]]?;
+    let right = df![
+        "price" => [99.5, 190.0, 995.0],
+        "quote" => ["a", "b", "c"],
+    ]?;
+    let args = JoinArgs {
+        coalesce: JoinCoalesce::KeepColumns,
+        ..JoinArgs::new(JoinType::AsOf(Box::new(AsOfOptions {
+            tolerance: Some(Scalar::from(0.01)),
+            tolerance_relative: true,
+            ..Default::default()
+        })))
+    };
+
+    // Within 1% of 100 and 1000, but 190 is 5% below 200.
+    let out = left.join(&right, ["price"], ["price"], args, None)?;
+    let expected = df![
+        "price" => [100.0, 200.0, 1000.0],
+        "price_right" => [Some(99.5), None, Some(995.0)],
+        "quote" => [Some("a"), None, Some("c")],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "asof_join")]
+fn asof_join_string_collation() -> PolarsResult<()> {
+    let left = df!["name" => ["b", "C", "d"]]?;
+    let right = df![
+        "name" => ["A", "c"],
+        "val" => [1, 3],
+    ]?;
+    let args = |string_collation| {
+        JoinArgs::new(JoinType::AsOf(Box::new(AsOfOptions {
+            string_collation,
+            allow_eq: true,
+            ..Default::default()
+        })))
+    };
+
+    // Case-insensitively "C" matches "c", but in byte order "C" sorts before "c".
+    let out = left.join(
+        &right,
+        ["name"],
+        ["name"],
+        args(AsofStringCollation::CaseInsensitive),
+        None,
+    )?;
+    let expected = df![
+        "name" => ["b", "C", "d"],
+        "val" => [Some(1), Some(3), Some(3)],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    // A custom collation that orders by the reversed string.
+    let reversed = AsofCollationKey(Arc::new(|s: &str, buf: &mut Vec<u8>| {
+        buf.extend(s.bytes().rev())
+    }));
+    let left = df!["name" => ["ax", "by", "az"]]?;
+    let right = df![
+        "name" => ["cx", "ay"],
+        "val" => [1, 2],
+    ]?;
+    let out = left.join(
+        &right,
+        ["name"],
+        ["name"],
+        args(AsofStringCollation::Custom(reversed)),
+        None,
+    )?;
+    let expected = df![
+        "name" => ["ax", "by", "az"],
+        "val" => [None, Some(2), Some(2)],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "asof_join")]
+fn asof_join_index_column() -> PolarsResult<()> {
+    let left = df![
+        "g" => ["x", "y", "x"],
+        "a" => [1, 5, 9],
+    ]?;
+    let right = df![
+        "g" => ["y", "x", "x"],
+        "a" => [4, 0, 8],
+        "val" => [40, 0, 80],
+    ]?;
+    let args = |by: bool, auto_sort: bool| {
+        JoinArgs::new(JoinType::AsOf(Box::new(AsOfOptions {
+            left_by: by.then(|| vec!["g".into()]),
+            right_by: by.then(|| vec!["g".into()]),
+            tolerance: Some(Scalar::from(2)),
+            auto_sort,
+            index_column: Some("idx".into()),
+            ..Default::default()
+        })))
+    };
+
+    let out = left.join(
+        &right.sort(["a"], Default::default())?,
+        ["a"],
+        ["a"],
+        args(false, false),
+        None,
+    )?;
+    let expected = df![
+        "g" => ["x", "y", "x"],
+        "a" => [1, 5, 9],
+        "g_right" => [Some("x"), Some("y"), Some("x")],
+        "val" => [Some(0), Some(40), Some(80)],
+        "idx" => [Some(0 as IdxSize), Some(1), Some(2)],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    // The index refers to the rows of the right frame before it is sorted.
+    let out = left.join(&right, ["a"], ["a"], args(true, true), None)?;
+    let expected = df![
+        "g" => ["x", "y", "x"],
+        "a" => [1, 5, 9],
+        "val" => [Some(0), Some(40), Some(80)],
+        "idx" => [Some(1 as IdxSize), Some(0), Some(2)],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    // A left row without a match gets a null index.
+    let right = right.slice(1, 2);
+    let out = left.join(&right, ["a"], ["a"], args(true, true), None)?;
+    let expected = df![
+        "g" => ["x", "y", "x"],
+        "a" => [1, 5, 9],
+        "val" => [Some(0), None, Some(80)],
+        "idx" => [Some(0 as IdxSize), None, Some(1)],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out} != {expected}");
+
+    Ok(())
+}
+
+#[test]
+fn inner_join_output_columns() -> PolarsResult<()> {
+    let left = df![
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"],
+        "c" => [1.0, 2.0, 3.0],
+    ]?;
+    let right = df![
+        "a" => [3, 1, 4],
+        "b" => ["p", "q", "r"],
+        "d" => [true, false, true],
+    ]?;
+    let args = JoinArgs {
+        output_columns: Some(vec!["b_right".into(), "a".into(), "d".into()]),
+        maintain_order: MaintainOrderJoin::Left,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+
+    let out = left.join(&right, ["a"], ["a"], args.clone(), None)?;
+    let expected = df![
+        "b_right" => ["q", "p"],
+        "a" => [1, 3],
+        "d" => [false, true],
+    ]?;
+    assert!(out.equals(&expected), "{out} != {expected}");
+
+    #[cfg(feature = "lazy")]
+    {
+        let out = left
+            .clone()
+            .lazy()
+            .join(right.clone().lazy(), [col("a")], [col("a")], args.clone())
+            .collect()?;
+        assert!(out.equals(&expected), "{out} != {expected}");
+    }
+
+    let out = left.join(
+        &right,
+        ["a"],
+        ["a"],
+        JoinArgs {
+            how: JoinType::Left,
+            ..args
+        },
+        None,
+    );
+    assert!(out.is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dtype-i8")]
+fn multi_key_join_fixed_width_keys() -> PolarsResult<()> {
+    let left = df![
+        "a" => [Some(-1i8), Some(1), Some(-1), None],
+        "b" => [2i32, 2, 3, 2],
+        "c" => [i64::MIN, 0, i64::MIN, 0],
+        "v" => [1, 2, 3, 4],
+    ]?;
+    let right = df![
+        "a" => [Some(-1i8), Some(1), Some(1), None],
+        "b" => [2i32, 2, 3, 2],
+        "c" => [i64::MIN, 0, 0, 0],
+        "w" => [10, 20, 30, 40],
+    ]?;
+    let args = JoinArgs {
+        maintain_order: MaintainOrderJoin::Left,
+        ..JoinArgs::new(JoinType::Inner)
+    };
+
+    // Packed into a `UInt64` and, with the third key, into an `Int128`.
+    for on in [&["a", "b"][..], &["a", "b", "c"][..]] {
+        let on = on.iter().copied();
+        let out = left.join(&right, on.clone(), on.clone(), args.clone(), None)?;
+        assert_eq!(out.column("v")?.i32()?.to_vec(), [Some(1), Some(2)]);
+        assert_eq!(out.column("w")?.i32()?.to_vec(), [Some(10), Some(20)]);
+
+        // Null keys that match each other are row encoded.
+        let out = left.join(
+            &right,
+            on.clone(),
+            on,
+            JoinArgs {
+                nulls_equal: true,
+                ..args.clone()
+            },
+            None,
+        )?;
+        assert_eq!(
+            out.column("v")?.i32()?.to_vec(),
+            [Some(1), Some(2), Some(4)]
+        );
+        assert_eq!(
+            out.column("w")?.i32()?.to_vec(),
+            [Some(10), Some(20), Some(40)]
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn join_schema_matches_join() -> PolarsResult<()> {
+    let left = df![
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"],
+        "c" => [1.0, 2.0, 3.0],
+    ]?;
+    let right = df![
+        "a" => [3, 1, 4],
+        "b" => ["p", "q", "r"],
+        "d" => [true, false, true],
+    ]?;
+
+    let mut all_args = vec![];
+    for how in [
+        JoinType::Inner,
+        JoinType::Left,
+        JoinType::Right,
+        JoinType::Full,
+    ] {
+        for coalesce in [JoinCoalesce::CoalesceColumns, JoinCoalesce::KeepColumns] {
+            all_args.push(JoinArgs::new(how.clone()).with_coalesce(coalesce));
+        }
+    }
+    all_args.push(JoinArgs::new(JoinType::Inner).with_suffix(Some("right_{}".into())));
+    all_args.push(JoinArgs {
+        output_columns: Some(vec!["d".into(), "c".into(), "a".into()]),
+        ..JoinArgs::new(JoinType::Inner)
+    });
+
+    for args in all_args {
+        for on in [&["a"][..], &["a", "b"][..]] {
+            let on = on.iter().copied();
+            let out = left.join(&right, on.clone(), on.clone(), args.clone(), None)?;
+            let schema = left.join_schema(right.schema(), on.clone(), on, &args)?;
+            assert_eq!(&schema, out.schema().as_ref(), "{args:?}");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dtype-struct")]
+fn inner_join_key_struct() -> PolarsResult<()> {
+    let left = df![
+        "a" => [1, 2, 3],
+        "b" => ["x", "y", "z"],
+        "c" => [1.0, 2.0, 3.0],
+    ]?;
+    let right = df![
+        "a" => [3, 1, 4],
+        "b" => ["z", "q", "r"],
+        "d" => [true, false, true],
+    ]?;
+    let args = JoinArgs {
+        key_struct: Some("_join_keys".into()),
+        maintain_order: MaintainOrderJoin::Left,
+        ..JoinArgs::new(JoinType::Inner).with_coalesce(JoinCoalesce::KeepColumns)
+    };
+
+    let out = left.join(&right, ["a"], ["a"], args.clone(), None)?;
+    let keys = df![
+        "left" => [1, 3],
+        "right" => [1, 3],
+    ]?
+    .into_struct("_join_keys".into())
+    .into_column();
+    let mut expected = df![
+        "b" => ["x", "z"],
+        "c" => [1.0, 3.0],
+        "b_right" => ["q", "z"],
+        "d" => [false, true],
+    ]?;
+    expected.insert_column(0, keys)?;
+    assert!(out.equals(&expected), "{out} != {expected}");
+
+    for on in [&["a"][..], &["a", "b"][..]] {
+        let on = on.iter().copied();
+        let out = left.join(&right, on.clone(), on.clone(), args.clone(), None)?;
+        let schema = left.join_schema(right.schema(), on.clone(), on, &args)?;
+        assert_eq!(&schema, out.schema().as_ref());
+    }
+    let out = left.join(&right, ["a", "b"], ["a", "b"], args.clone(), None)?;
+    assert_eq!(out.get_column_names(), ["_join_keys", "c", "d"]);
+    assert_eq!(out.height(), 1);
+
+    // The keys of a coalescing join can't be nested.
+    let args = JoinArgs {
+        key_struct: Some("_join_keys".into()),
+        ..JoinArgs::new(JoinType::Inner)
+    };
+    assert!(left.join(&right, ["a"], ["a"], args, None).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn left_join_dedup_right() -> PolarsResult<()> {
+    let left = df![
+        "id" => [1, 2, 3],
+        "a" => ["x", "y", "z"],
+    ]?;
+    let right = df![
+        "id" => [2, 1, 2, 1],
+        "b" => [10, 20, 10, 30],
+    ]?;
+    let join = |dedup_right: JoinDedupRight, right: &DataFrame| {
+        let args = JoinArgs {
+            dedup_right,
+            maintain_order: MaintainOrderJoin::Left,
+            ..JoinArgs::new(JoinType::Left)
+        };
+        left.join(right, ["id"], ["id"], args, None)
+    };
+
+    assert_eq!(join(JoinDedupRight::None, &right)?.height(), 5);
+
+    let out = join(JoinDedupRight::KeepFirst, &right)?;
+    let expected = df![
+        "id" => [1, 2, 3],
+        "a" => ["x", "y", "z"],
+        "b" => [Some(20), Some(10), None],
+    ]?;
+    assert!(out.equals_missing(&expected));
+
+    // The rows of id 1 disagree on `b`.
+    assert!(join(JoinDedupRight::KeepFirstIfEqual, &right).is_err());
+    let right = right.filter(&right.column("b")?.as_materialized_series().lt(30)?)?;
+    let out = join(JoinDedupRight::KeepFirstIfEqual, &right)?;
+    assert!(out.equals_missing(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn join_rechunk() -> PolarsResult<()> {
+    let mut left = df![
+        "id" => [1, 2],
+        "a" => ["x", "y"],
+    ]?;
+    left.vstack_mut(&df!["id" => [3], "a" => ["z"]]?)?;
+    // Misalign the chunks of the columns.
+    left.with_column(Series::new("b".into(), [1, 2, 3]))?;
+    let right = df![
+        "id" => [3, 1, 2],
+        "c" => [30, 10, 20],
+    ]?;
+    assert!(left.should_rechunk());
+
+    let expected = df![
+        "id" => [1, 2, 3],
+        "a" => ["x", "y", "z"],
+        "b" => [1, 2, 3],
+        "c" => [10, 20, 30],
+    ]?;
+    for rechunk in [
+        JoinRechunk::Auto,
+        JoinRechunk::MinChunks(1),
+        JoinRechunk::MinChunks(10),
+        JoinRechunk::Always,
+        JoinRechunk::Never,
+    ] {
+        for how in [JoinType::Inner, JoinType::Left] {
+            let args = JoinArgs {
+                rechunk,
+                maintain_order: MaintainOrderJoin::Left,
+                ..JoinArgs::new(how)
+            };
+            let out = left.join(&right, ["id"], ["id"], args, None)?;
+            assert!(out.equals(&expected));
+        }
+    }
+
+    Ok(())
+}
@@ -11841,13 +11841,20 @@ class DataFrame:
             correlation_matrix = np.array([correlation_matrix])
         return DataFrame(correlation_matrix, schema=self.columns)
 
-    def merge_sorted(self, other: DataFrame, key: str) -> DataFrame:
+    def merge_sorted(
+        self,
+        other: DataFrame,
+        key: str,
+        *,
+        descending: bool = False,
+        nulls_last: bool = False,
+    ) -> DataFrame:
         """
         Take two sorted DataFrames and merge them by the sorted key.
 
         The output of this operation will also be sorted.
         It is the callers responsibility that the frames
-        are sorted in ascending order by that key otherwise
+        are sorted in the given order by that key otherwise
         the output will not make sense.
 
         The schemas of both DataFrames must be equal.
@@ -11858,6 +11865,10 @@ class DataFrame:
             Other DataFrame that must be merged
         key
             Key that is sorted.
+        descending
+            Whether both frames are sorted in descending order by the key.
+        nulls_last
+            Whether the nulls of the key are placed last in both frames.
 
         Examples
         --------
@@ -11911,7 +11922,9 @@ class DataFrame:
         No guarantee is given over the output row order when the key is equal
         between the both dataframes.
 
-        The key must be sorted in ascending order.
+        The key must be sorted in ascending order, or in descending order if
+        `descending` is set. The nulls of the key must be placed first, or last
+        if `nulls_last` is set.
         """
         from polars.lazyframe.opt_flags import QueryOptFlags
 
@@ -11919,7 +11932,9 @@ class DataFrame:
 
         return (
             self.lazy()
-            .merge_sorted(other.lazy(), key)
+            .merge_sorted(
+                other.lazy(), key, descending=descending, nulls_last=nulls_last
+            )
             .collect(optimizations=QueryOptFlags._eager())
         )
 
@@ -10292,6 +10292,38 @@ Consider using {self}.implode() instead"""
         """
         return wrap_expr(self._pyexpr.set_sorted_flag(descending))
 
+    @unstable()
+    def no_pushdown(self) -> Expr:
+        """
+        Prevent the optimizer from pushing a filter on this expression down.
+
+        A filter whose predicate contains this expression is applied where it is
+        written in the query, e.g. after a join instead of on one of its inputs.
+        The values are passed through unchanged.
+
+        .. warning::
+            This functionality is considered **unstable**. It may be changed
+            at any point without it being considered a breaking change.
+
+        Examples
+        --------
+        >>> left = pl.LazyFrame({"id": [1, 2, 3], "a": [1, 5, 9]})
+        >>> right = pl.LazyFrame({"id": [1, 2, 3], "b": ["x", "y", "z"]})
+        >>> left.join(right, on="id", maintain_order="left").filter(
+        ...     (pl.col("a") > 2).no_pushdown()
+        ... ).collect()
+        shape: (2, 3)
+        ┌─────┬─────┬─────┐
+        │ id  ┆ a   ┆ b   │
+        │ --- ┆ --- ┆ --- │
+        │ i64 ┆ i64 ┆ str │
+        ╞═════╪═════╪═════╡
+        │ 2   ┆ 5   ┆ y   │
+        │ 3   ┆ 9   ┆ z   │
+        └─────┴─────┴─────┘
+        """
+        return wrap_expr(self._pyexpr.no_pushdown())
+
     def shrink_dtype(self) -> Expr:
         """
         Shrink numeric columns to the minimal required datatype.
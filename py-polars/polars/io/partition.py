@@ -123,9 +123,11 @@ class BasePartitionContext:
 
 
 def _cast_base_file_path_cb(
-    file_path_cb: Callable[[BasePartitionContext], Path | str | IO[bytes] | IO[str]]
+    file_path_cb: Callable[
+        [BasePartitionContext], Path | str | IO[bytes] | IO[str] | None
+    ]
     | None,
-) -> Callable[[BasePartitionContext], Path | str | IO[bytes] | IO[str]] | None:
+) -> Callable[[BasePartitionContext], Path | str | IO[bytes] | IO[str] | None] | None:
     if file_path_cb is None:
         return None
     return lambda ctx: file_path_cb(
@@ -138,9 +140,11 @@ def _cast_base_file_path_cb(
 
 
 def _cast_keyed_file_path_cb(
-    file_path_cb: Callable[[KeyedPartitionContext], Path | str | IO[bytes] | IO[str]]
+    file_path_cb: Callable[
+        [KeyedPartitionContext], Path | str | IO[bytes] | IO[str] | None
+    ]
     | None,
-) -> Callable[[KeyedPartitionContext], Path | str | IO[bytes] | IO[str]] | None:
+) -> Callable[[KeyedPartitionContext], Path | str | IO[bytes] | IO[str] | None] | None:
     if file_path_cb is None:
         return None
     return lambda ctx: file_path_cb(
@@ -217,7 +221,8 @@ class PartitionMaxSize(PartitioningScheme):
         A callback to register or modify the output path for each partition
         relative to the `base_path`. The callback provides a
         :class:`polars.io.partition.BasePartitionContext` that contains information
-        about the partition.
+        about the partition. If the callback returns `None`, the file is
+        skipped and its rows are discarded.
 
         If no callback is given, it defaults to `{ctx.file_idx}.{EXT}`.
     max_size : int
@@ -251,7 +256,9 @@ class PartitionMaxSize(PartitioningScheme):
         self,
         base_path: str | Path,
         *,
-        file_path: Callable[[BasePartitionContext], Path | str | IO[bytes] | IO[str]]
+        file_path: Callable[
+            [BasePartitionContext], Path | str | IO[bytes] | IO[str] | None
+        ]
         | None = None,
         max_size: int,
         per_partition_sort_by: str | Expr | Iterable[str | Expr] | None = None,
@@ -322,7 +329,8 @@ class PartitionByKey(PartitioningScheme):
         A callback to register or modify the output path for each partition
         relative to the `base_path`. The callback provides a
         :class:`polars.io.partition.KeyedPartitionContext` that contains information
-        about the partition.
+        about the partition. If the callback returns `None`, the file is
+        skipped and its rows are discarded.
 
         If no callback is given, it defaults to
         `{ctx.keys.hive_dirs()}/{ctx.in_part_idx}.{EXT}`.
@@ -339,6 +347,34 @@ class PartitionByKey(PartitioningScheme):
 
         For parquet files, the callback is given a dataframe with metrics about all
         files written files.
+    expected_keys
+        A dataframe with the values of the keys that should always get a file, with
+        a column per key. A key that doesn't occur in the data gets a file without
+        any rows.
+    max_rows_per_file
+        The maximum number of rows in a file. The rows of a key that doesn't fit
+        into one file are split over several files, which are numbered by
+        `ctx.in_part_idx`.
+    dry_run
+        Only resolve the partitions and their file paths, without writing any
+        files. The `finish_callback` is still called, with the partitions that
+        would have been written.
+    separate_null_keys
+        Route the rows whose keys are all null to their own partition, and name
+        the null keys `__NULL__` in the default file paths, e.g. `a=__NULL__`,
+        instead of `__HIVE_DEFAULT_PARTITION__`. This also applies to the null
+        keys of a partition whose other keys aren't null. Note that hive scans
+        read these keys back as the string `"__NULL__"`.
+    flush_morsel_rows
+        The number of rows of the chunks that the rows of a partition that was
+        buffered in memory are written in. By default they are written in the
+        chunks they were buffered in. This only applies to the partitions that are
+        buffered because too many partitions are open at once.
+    strict_order
+        Write the rows of every file in the order of the input, regardless of the
+        `maintain_order` of the sink. The rows are put back in their input order
+        before they are partitioned, which adds latency and holds more rows in
+        memory while waiting for earlier rows.
 
     Examples
     --------
@@ -378,12 +414,20 @@ class PartitionByKey(PartitioningScheme):
         self,
         base_path: str | Path,
         *,
-        file_path: Callable[[KeyedPartitionContext], Path | str | IO[bytes] | IO[str]]
+        file_path: Callable[
+            [KeyedPartitionContext], Path | str | IO[bytes] | IO[str] | None
+        ]
         | None = None,
         by: str | Expr | Sequence[str | Expr] | Mapping[str, Expr],
         include_key: bool = True,
         per_partition_sort_by: str | Expr | Iterable[str | Expr] | None = None,
         finish_callback: Callable[[DataFrame], None] | None = None,
+        expected_keys: DataFrame | None = None,
+        max_rows_per_file: int | None = None,
+        dry_run: bool = False,
+        separate_null_keys: bool = False,
+        flush_morsel_rows: int | None = None,
+        strict_order: bool = False,
     ) -> None:
         issue_unstable_warning("partitioning strategies are considered unstable.")
 
@@ -398,6 +442,12 @@ class PartitionByKey(PartitioningScheme):
                     per_partition_sort_by
                 ),
                 finish_callback=_prepare_finish_callback(finish_callback),
+                expected_keys=None if expected_keys is None else expected_keys._df,
+                max_rows_per_file=max_rows_per_file,
+                dry_run=dry_run,
+                separate_null_keys=separate_null_keys,
+                flush_morsel_rows=flush_morsel_rows,
+                strict_order=strict_order,
             )
         )
 
@@ -429,7 +479,8 @@ class PartitionParted(PartitioningScheme):
         A callback to register or modify the output path for each partition
         relative to the `base_path`.The callback provides a
         :class:`polars.io.partition.KeyedPartitionContext` that contains information
-        about the partition.
+        about the partition. If the callback returns `None`, the file is
+        skipped and its rows are discarded.
 
         If no callback is given, it defaults to
         `{ctx.keys.hive_dirs()}/{ctx.in_part_idx}.{EXT}`.
@@ -467,7 +518,9 @@ class PartitionParted(PartitioningScheme):
         self,
         base_path: str | Path,
         *,
-        file_path: Callable[[KeyedPartitionContext], Path | str | IO[bytes] | IO[str]]
+        file_path: Callable[
+            [KeyedPartitionContext], Path | str | IO[bytes] | IO[str] | None
+        ]
         | None = None,
         by: str | Expr | Sequence[str | Expr] | Mapping[str, Expr],
         include_key: bool = True,
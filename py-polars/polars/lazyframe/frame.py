@@ -7539,13 +7539,20 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         )
         return self._from_pyldf(self._ldf.unnest(subset._pyselector))
 
-    def merge_sorted(self, other: LazyFrame, key: str) -> LazyFrame:
+    def merge_sorted(
+        self,
+        other: LazyFrame,
+        key: str,
+        *,
+        descending: bool = False,
+        nulls_last: bool = False,
+    ) -> LazyFrame:
         """
         Take two sorted DataFrames and merge them by the sorted key.
 
         The output of this operation will also be sorted.
         It is the callers responsibility that the frames
-        are sorted in ascending order by that key otherwise
+        are sorted in the given order by that key otherwise
         the output will not make sense.
 
         The schemas of both LazyFrames must be equal.
@@ -7556,6 +7563,10 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
             Other DataFrame that must be merged
         key
             Key that is sorted.
+        descending
+            Whether both frames are sorted in descending order by the key.
+        nulls_last
+            Whether the nulls of the key are placed last in both frames.
 
         Examples
         --------
@@ -7609,10 +7620,14 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         No guarantee is given over the output row order when the key is equal
         between the both dataframes.
 
-        The key must be sorted in ascending order.
+        The key must be sorted in ascending order, or in descending order if
+        `descending` is set. The nulls of the key must be placed first, or last
+        if `nulls_last` is set.
         """
         require_same_type(self, other)
-        return self._from_pyldf(self._ldf.merge_sorted(other._ldf, key))
+        return self._from_pyldf(
+            self._ldf.merge_sorted(other._ldf, key, descending, nulls_last)
+        )
 
     def set_sorted(
         self,
@@ -2893,6 +2893,43 @@ def test_equality_filter(
     pl.read_parquet(f)
 
 
+@pytest.mark.parametrize("strategy", ["columns", "prefiltered"])
+@pytest.mark.parametrize(
+    "predicate",
+    [
+        pl.col.a.str.starts_with("key_1"),
+        pl.col.a.str.starts_with("ü"),
+        pl.col.a.str.contains("_ü9", literal=True),
+        pl.col.a.str.contains("long_strings", literal=True),
+        # A regex is not pushed into the decoder.
+        pl.col.a.str.contains("^key_1.*9$"),
+    ],
+)
+def test_string_pattern_filter(strategy: ParallelStrategy, predicate: pl.Expr) -> None:
+    df = pl.DataFrame(
+        {
+            "a": [
+                f"key_{i}_ü{i % 13}" if i % 3 else f"ü_long_strings_with_the_number_{i}"
+                for i in range(1000)
+            ],
+            "b": range(1000),
+        }
+    )
+
+    f = io.BytesIO()
+    df.write_parquet(f)
+
+    for columns in [["b"], ["a", "b"]]:
+        f.seek(0)
+        assert_frame_equal(
+            pl.scan_parquet(f, parallel=strategy)
+            .filter(predicate)
+            .select(columns)
+            .collect(),
+            df.filter(predicate).select(columns),
+        )
+
+
 def test_nested_string_slice_utf8_21202() -> None:
     s = pl.Series(
         "a",
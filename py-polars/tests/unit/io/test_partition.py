@@ -546,3 +546,202 @@ def test_parquet_preserve_order_within_partition_23376(tmp_path: Path) -> None:
     df.lazy().sink_parquet(pl.PartitionMaxSize(tmp_path, max_size=1))
     out = pl.scan_parquet(tmp_path).collect().to_series().to_list()
     assert ll == out
+
+
+@pytest.mark.parametrize("separate_null_keys", [False, True])
+@pytest.mark.write_disk
+def test_partition_by_key_null_keys(tmp_path: Path, separate_null_keys: bool) -> None:
+    df = pl.DataFrame({"a": ["x", "", None, "x", None], "b": [1, 2, 3, 4, 5]})
+    df.lazy().sink_parquet(
+        PartitionByKey(tmp_path, by="a", separate_null_keys=separate_null_keys),
+        mkdir=True,
+    )
+
+    null_dir = "a=__NULL__" if separate_null_keys else "a=__HIVE_DEFAULT_PARTITION__"
+    assert_frame_equal(
+        pl.read_parquet(tmp_path / null_dir / "0.parquet"),
+        pl.DataFrame({"a": [None, None], "b": [3, 5]}, schema=df.schema),
+    )
+    assert_frame_equal(
+        pl.read_parquet(tmp_path / "a=" / "0.parquet"),
+        pl.DataFrame({"a": [""], "b": [2]}),
+    )
+
+
+@pytest.mark.write_disk
+def test_partition_by_key_separate_null_keys_partially_null(tmp_path: Path) -> None:
+    df = pl.DataFrame({"a": ["x", None, None], "b": [None, 1, None], "c": [1, 2, 3]})
+    df.lazy().sink_parquet(
+        PartitionByKey(tmp_path, by=["a", "b"], separate_null_keys=True),
+        mkdir=True,
+    )
+
+    files = sorted(p.relative_to(tmp_path).as_posix() for p in tmp_path.rglob("*.*"))
+    assert files == [
+        "a=__NULL__/b=1/0.parquet",
+        "a=__NULL__/b=__NULL__/0.parquet",
+        "a=x/b=__NULL__/0.parquet",
+    ]
+
+
+@pytest.mark.parametrize("io_type", io_types)
+@pytest.mark.write_disk
+def test_partition_by_key_expected_keys(tmp_path: Path, io_type: IOType) -> None:
+    df = pl.DataFrame({"a": [1, 2, 1], "b": ["x", "y", "z"]})
+    expected_keys = pl.DataFrame({"a": [1, 3]})
+    io_type["sink"](
+        df.lazy(),
+        PartitionByKey(tmp_path, by="a", expected_keys=expected_keys),
+        mkdir=True,
+    )
+
+    ext = io_type["ext"]
+    assert sorted(p.parent.name for p in tmp_path.glob(f"*/*.{ext}")) == [
+        "a=1",
+        "a=2",
+        "a=3",
+    ]
+    if ext in ("parquet", "ipc"):
+        out = io_type["scan"](tmp_path / "a=3" / f"0.{ext}").collect()
+        assert_frame_equal(out, df.clear())
+
+
+@pytest.mark.write_disk
+def test_partition_by_key_flush_morsel_rows(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    # Only one partition can be open, the others are buffered until the end.
+    monkeypatch.setenv("POLARS_MAX_OPEN_PARTITIONS", "1")
+
+    df = pl.DataFrame({"a": [i % 3 for i in range(20)], "b": list(range(20))})
+    df.lazy().sink_parquet(
+        PartitionByKey(tmp_path, by="a", flush_morsel_rows=2), mkdir=True
+    )
+
+    for key in range(3):
+        assert_frame_equal(
+            pl.read_parquet(tmp_path / f"a={key}" / "0.parquet"),
+            df.filter(pl.col("a") == key),
+        )
+
+    # An invalid size is rejected when the query is planned.
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="flush_morsel_rows"):
+        df.lazy().sink_parquet(
+            PartitionByKey(tmp_path, by="a", flush_morsel_rows=0), mkdir=True
+        )
+
+
+@pytest.mark.parametrize("max_open_partitions", [None, "1"])
+@pytest.mark.write_disk
+def test_partition_by_key_strict_order(
+    tmp_path: Path, max_open_partitions: str | None, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "10")
+    if max_open_partitions is not None:
+        monkeypatch.setenv("POLARS_MAX_OPEN_PARTITIONS", max_open_partitions)
+
+    df = pl.DataFrame({"a": [i % 3 for i in range(1000)], "b": list(range(1000))})
+    df.lazy().sink_parquet(
+        PartitionByKey(tmp_path, by="a", strict_order=True),
+        mkdir=True,
+        maintain_order=False,
+    )
+
+    for key in range(3):
+        assert_frame_equal(
+            pl.read_parquet(tmp_path / f"a={key}" / "0.parquet"),
+            df.filter(pl.col("a") == key),
+        )
+
+
+@pytest.mark.parametrize("max_open_partitions", [None, "1"])
+@pytest.mark.write_disk
+def test_partition_by_key_max_rows_per_file(
+    tmp_path: Path, max_open_partitions: str | None, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    if max_open_partitions is not None:
+        monkeypatch.setenv("POLARS_MAX_OPEN_PARTITIONS", max_open_partitions)
+
+    df = pl.DataFrame({"a": [0] * 7 + [1] * 2, "b": list(range(9))})
+    metrics: list[pl.DataFrame] = []
+    df.lazy().sink_parquet(
+        PartitionByKey(
+            tmp_path,
+            by="a",
+            max_rows_per_file=3,
+            finish_callback=metrics.append,
+        ),
+        mkdir=True,
+        maintain_order=True,
+    )
+
+    assert sorted(p.relative_to(tmp_path).as_posix() for p in tmp_path.glob("*/*")) == [
+        "a=0/0.parquet",
+        "a=0/1.parquet",
+        "a=0/2.parquet",
+        "a=1/0.parquet",
+    ]
+    for key, num_files in [(0, 3), (1, 1)]:
+        parts = [
+            pl.read_parquet(tmp_path / f"a={key}" / f"{i}.parquet")
+            for i in range(num_files)
+        ]
+        assert all(part.height <= 3 for part in parts)
+        assert_frame_equal(pl.concat(parts), df.filter(pl.col("a") == key))
+
+    (metrics_df,) = metrics
+    assert metrics_df.height == 4
+    assert metrics_df["num_rows"].sum() == df.height
+
+
+@pytest.mark.write_disk
+def test_partition_by_key_dry_run(tmp_path: Path) -> None:
+    df = pl.DataFrame({"a": [1, 2, 1], "b": ["x", "y", "z"]})
+    metrics: list[pl.DataFrame] = []
+    df.lazy().sink_parquet(
+        PartitionByKey(tmp_path, by="a", dry_run=True, finish_callback=metrics.append),
+        mkdir=True,
+    )
+
+    assert list(tmp_path.iterdir()) == []
+
+    (metrics_df,) = metrics
+    metrics_df = metrics_df.sort("path")
+    assert metrics_df["path"].str.ends_with(".parquet").all()
+    assert metrics_df["path"].str.contains("a=1").to_list() == [True, False]
+    assert metrics_df["num_rows"].to_list() == [2, 1]
+    assert not metrics_df["skipped"].any()
+
+
+@pytest.mark.parametrize("max_open_partitions", [None, "1"])
+@pytest.mark.write_disk
+def test_partition_by_key_skip(
+    tmp_path: Path, max_open_partitions: str | None, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    if max_open_partitions is not None:
+        monkeypatch.setenv("POLARS_MAX_OPEN_PARTITIONS", max_open_partitions)
+
+    def file_path_cb(ctx: KeyedPartitionContext) -> Path | None:
+        if ctx.keys[0].raw_value == 2:
+            return None
+        return ctx.file_path
+
+    df = pl.DataFrame({"a": [1, 2, 3, 2, 1], "b": list(range(5))})
+    metrics: list[pl.DataFrame] = []
+    df.lazy().sink_parquet(
+        PartitionByKey(
+            tmp_path, file_path=file_path_cb, by="a", finish_callback=metrics.append
+        ),
+        mkdir=True,
+    )
+
+    assert sorted(p.parent.name for p in tmp_path.glob("*/*.parquet")) == [
+        "a=1",
+        "a=3",
+    ]
+
+    (metrics_df,) = metrics
+    skipped = metrics_df.filter("skipped")
+    assert skipped["num_rows"].to_list() == [2]
+    assert skipped["keys"].struct.field("a").to_list() == [2]
+    assert metrics_df.filter(~pl.col("skipped"))["num_rows"].sum() == 3
@@ -12,7 +12,7 @@ from polars.exceptions import DuplicateError, InvalidOperationError
 from polars.testing import assert_frame_equal
 
 if TYPE_CHECKING:
-    from polars._typing import AsofJoinStrategy, PolarsIntegerType
+    from polars._typing import AsofJoinStrategy, PolarsDataType, PolarsIntegerType
 
 
 def test_asof_join_singular_right_11966() -> None:
@@ -1134,6 +1134,43 @@ def test_asof_join_string() -> None:
     assert_frame_equal(backward, backward_expected)
 
 
+@pytest.mark.parametrize("dtype", [pl.String, pl.Binary])
+def test_asof_join_string_version_keys(dtype: PolarsDataType) -> None:
+    versions = pl.DataFrame(
+        {
+            "pkg": ["a", "a", "a", "b", "b"],
+            "version": ["1.0.0", "1.2.0", "2.0.0", "0.1.0", "0.9.0"],
+            "released": [1, 2, 3, 4, 5],
+        }
+    ).with_columns(pl.col("version").cast(dtype))
+    requests = pl.DataFrame(
+        {
+            "pkg": ["a", "b", "a", "b"],
+            "version": ["0.9.0", "0.5.0", "1.9.9", "1.0.0"],
+        }
+    ).with_columns(pl.col("version").cast(dtype))
+
+    out = requests.sort("version").join_asof(
+        versions.sort("version"), on="version", by="pkg", check_sortedness=False
+    )
+    assert out.sort("pkg", "version").to_dict(as_series=False) == {
+        "pkg": ["a", "a", "b", "b"],
+        "version": pl.Series(["0.9.0", "1.9.9", "0.5.0", "1.0.0"])
+        .cast(dtype)
+        .to_list(),
+        "released": [None, 2, 4, 5],
+    }
+
+    with pytest.raises(InvalidOperationError, match="'nearest' strategy"):
+        requests.sort("version").join_asof(
+            versions.sort("version"), on="version", strategy="nearest"
+        )
+    with pytest.raises(InvalidOperationError, match="tolerance"):
+        requests.sort("version").join_asof(
+            versions.sort("version"), on="version", tolerance=1
+        )
+
+
 def test_join_asof_by_argument_parsing() -> None:
     df1 = pl.DataFrame(
         {
@@ -1408,3 +1445,20 @@ def test_join_asof_nosuffix_dup_col_23834() -> None:
     b = pl.DataFrame({"b": [1, 2, 3], "c": [9, 10, 11]})
     with pytest.raises(DuplicateError):
         a.join_asof(b, left_on="a", right_on="b", suffix="")
+
+
+@pytest.mark.parametrize(
+    ("strategy", "expected"),
+    [
+        ("backward", [None, 0, 0, 3, 4]),
+        ("forward", [1, 4, 4, 5, 5]),
+        ("nearest", [3, 4, 4, 3, 5]),
+    ],
+)
+def test_join_asof_disallow_exact_matches_duplicates(
+    strategy: AsofJoinStrategy, expected: list[int | None]
+) -> None:
+    left = pl.DataFrame({"a": [1, 2, 2, 3, 4]})
+    right = pl.DataFrame({"a": [1, 2, 2, 2, 3, 5]}).with_row_index()
+    out = left.join_asof(right, on="a", strategy=strategy, allow_exact_matches=False)
+    assert out["index"].to_list() == expected
@@ -27,6 +27,40 @@ def test_merge_sorted(streaming: bool) -> None:
     )
 
 
+@pytest.mark.parametrize("streaming", [False, True])
+def test_merge_sorted_descending(streaming: bool) -> None:
+    left = pl.DataFrame({"a": [9, 6, 4, 2, 1]}).set_sorted("a", descending=True)
+    right = pl.DataFrame({"a": [10, 5, 4, 3, 2]}).set_sorted("a", descending=True)
+    result = (
+        left.lazy()
+        .merge_sorted(right.lazy(), "a", descending=True)
+        .collect(engine="streaming" if streaming else "in-memory")
+    )
+    assert_frame_equal(result, pl.DataFrame({"a": [10, 9, 6, 5, 4, 4, 3, 2, 2, 1]}))
+    assert result["a"].flags["SORTED_DESC"]
+
+
+@pytest.mark.parametrize("streaming", [False, True])
+@pytest.mark.parametrize("nulls_last", [False, True])
+def test_merge_sorted_nulls_last(streaming: bool, nulls_last: bool) -> None:
+    engine = "streaming" if streaming else "in-memory"
+    left = pl.DataFrame({"a": [None, 1, 4]}).sort("a", nulls_last=nulls_last)
+    right = pl.DataFrame({"a": [None, None, 2]}).sort("a", nulls_last=nulls_last)
+    result = (
+        left.lazy()
+        .merge_sorted(right.lazy(), "a", nulls_last=nulls_last)
+        .collect(engine=engine)
+    )
+    expected = pl.DataFrame({"a": [None, None, None, 1, 2, 4]})
+    assert_frame_equal(result, expected.sort("a", nulls_last=nulls_last))
+
+    # Nulls that are placed at the other end are rejected.
+    with pytest.raises(pl.exceptions.InvalidOperationError):
+        left.lazy().merge_sorted(
+            right.lazy(), "a", nulls_last=not nulls_last
+        ).collect(engine=engine)
+
+
 def test_merge_sorted_pred_pd() -> None:
     assert_frame_equal(
         lf.filter(pl.col.b > 30).collect(),
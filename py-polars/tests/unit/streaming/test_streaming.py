@@ -380,3 +380,23 @@ def test_i128_sum_reduction() -> None:
         .item()
         == 6
     )
+
+
+@pytest.mark.parametrize("dtype", [pl.Int8, pl.Int64, pl.Float64, pl.Boolean])
+def test_streaming_cum_agg(dtype: pl.DataType) -> None:
+    values = [1, None, 0, 1, None, 1, 0, 0, 1, None] * 5
+    df = pl.concat(
+        [
+            pl.DataFrame({"a": values[i : i + 7]}, schema={"a": dtype})
+            for i in range(0, 50, 7)
+        ],
+        rechunk=False,
+    )
+
+    q = df.lazy().select(
+        pl.col("a").cum_sum().alias("sum"),
+        pl.col("a").cum_min().alias("min"),
+        pl.col("a").cum_max().alias("max"),
+        pl.col("a").cum_count().alias("count"),
+    )
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
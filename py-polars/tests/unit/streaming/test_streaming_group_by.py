@@ -292,6 +292,32 @@ def test_streaming_group_by_ooc_q3(
     assert_frame_equal(result, expected)
 
 
+@pytest.mark.write_disk
+def test_streaming_group_by_spill_high_cardinality(
+    tmp_path: Path,
+    monkeypatch: Any,
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_GROUP_BY_SPILL_BYTES", "1000")
+    monkeypatch.setenv("POLARS_HOT_TABLE_SIZE", "2")
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+
+    n = 10_000
+    lf = pl.LazyFrame({"a": [(i * 7919) % 2500 for i in range(n)], "b": range(n)})
+    q = (
+        lf.group_by("a")
+        .agg(
+            pl.col("b").sum().alias("sum"),
+            pl.col("b").first().alias("first"),
+            pl.col("b").last().alias("last"),
+            pl.col("a").len().alias("len"),
+        )
+        .sort("a")
+    )
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
 def test_streaming_group_by_struct_key() -> None:
     df = pl.DataFrame(
         {"A": [1, 2, 3, 2], "B": ["google", "ms", "apple", "ms"], "C": [2, 3, 4, 3]}
@@ -13,7 +13,7 @@ from polars.testing import assert_frame_equal, assert_series_equal
 if TYPE_CHECKING:
     from pathlib import Path
 
-    from polars._typing import JoinStrategy
+    from polars._typing import JoinStrategy, MaintainOrderJoin
 
 pytestmark = pytest.mark.xdist_group("streaming")
 
@@ -115,6 +115,33 @@ def test_streaming_cross_join_empty() -> None:
     assert out.columns == ["col1", "col1_right"]
 
 
+@pytest.mark.parametrize("maintain_order", ["none", "left", "right"])
+def test_streaming_cross_join_group_by(maintain_order: MaintainOrderJoin) -> None:
+    queries = pl.LazyFrame({"q": [1, 2, 3], "x": [0.0, 5.0, 9.0]})
+    points = pl.LazyFrame({"p": [10, 20, 30, 40], "x": [1.0, 4.0, 8.0, 10.0]})
+
+    q = (
+        queries.join(points, how="cross", maintain_order=maintain_order)
+        .with_columns(score=-(pl.col("x") - pl.col("x_right")).abs())
+        .group_by("q")
+        .agg(pl.col("score").max(), pl.len(), pl.col("p").min())
+    )
+    expected = pl.DataFrame(
+        {"q": [1, 2, 3], "score": [-1.0, -1.0, -1.0], "len": [4, 4, 4], "p": [10] * 3},
+        schema_overrides={"len": pl.get_index_type()},
+    )
+    assert_frame_equal(q.collect(engine="streaming"), expected, check_row_order=False)
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+        check_row_order=False,
+    )
+
+    empty = points.clear().join(queries, how="cross", maintain_order=maintain_order)
+    out = empty.group_by("q").agg(pl.len()).collect(engine="streaming")
+    assert out.height == 0
+
+
 def test_streaming_join_rechunk_12498() -> None:
     rows = pl.int_range(0, 2)
 
@@ -386,3 +413,75 @@ def test_invert_order_full_join_22295() -> None:
     lf.join(lf, on=["value", "value_at"], how="full", coalesce=True).collect(
         engine="streaming"
     )
+
+
+@pytest.mark.parametrize("strategy", ["backward", "forward", "nearest"])
+@pytest.mark.parametrize("by", [None, "g"])
+@pytest.mark.parametrize("tolerance", [None, 3])
+def test_streaming_join_asof(
+    strategy: Literal["backward", "forward", "nearest"],
+    by: str | None,
+    tolerance: int | None,
+) -> None:
+    left = pl.LazyFrame(
+        {
+            "t": [None, 1, 2, 4, 7, 7, 9, 12, 15, 16, 20, 21, 22, 30, 31, 40],
+            "g": ["a", "b"] * 8,
+            "lv": range(16),
+        }
+    )
+    right = pl.LazyFrame(
+        {
+            "t": [None, 0, 2, 2, 3, 5, 7, 7, 10, 10, 14, 18, 18, 25, 33, 35],
+            "g": ["b", "a"] * 8,
+            "rv": range(100, 116),
+        }
+    )
+
+    q = left.join_asof(
+        right,
+        on="t",
+        by=by,
+        strategy=strategy,
+        tolerance=tolerance,
+        check_sortedness=False,
+    )
+    assert_frame_equal(q.collect(engine="streaming"), q.collect())
+    assert_frame_equal(
+        q.slice(3, 5).collect(engine="streaming"), q.slice(3, 5).collect()
+    )
+
+
+@pytest.mark.parametrize("nulls_equal", [False, True])
+@pytest.mark.parametrize("on", [["k"], ["k", "s"]])
+def test_streaming_index_join(
+    nulls_equal: bool, on: list[str], monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("POLARS_STREAMING_INDEX_JOIN", "1")
+    left = pl.LazyFrame(
+        {
+            "k": [1, 2, None, 3, 1, 5] * 3,
+            "s": ["a", "b", "c", "d", "a", "e"] * 3,
+            "v": range(18),
+        }
+    )
+    right = pl.LazyFrame(
+        {
+            "k": [1, None, 3, 1, 7],
+            "s": ["a", "c", "d", "x", "a"],
+            "v": range(100, 105),
+        }
+    )
+
+    q = left.join(
+        right,
+        on=on,
+        how="inner",
+        nulls_equal=nulls_equal,
+        suffix="_r",
+        maintain_order="left_right",
+    )
+    assert_frame_equal(q.collect(engine="streaming"), q.collect())
+    assert_frame_equal(
+        q.slice(2, 4).collect(engine="streaming"), q.slice(2, 4).collect()
+    )
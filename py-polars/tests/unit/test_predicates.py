@@ -1156,3 +1156,19 @@ def test_predicate_pushdown_map_elements_io_plugin_22860() -> None:
     assert plan.index("SELECTION") > plan.index("PYTHON SCAN")
 
     assert_frame_equal(q.collect(), pl.DataFrame({"row_nr": [2, 4, 5], "y": [1, 1, 1]}))
+
+
+def test_predicate_no_pushdown() -> None:
+    left = pl.LazyFrame({"id": [1, 2, 3], "a": [1, 5, 9]})
+    right = pl.LazyFrame({"id": [1, 2, 3], "b": ["x", "y", "z"]})
+    q = left.join(right, on="id", maintain_order="left")
+
+    plan = q.filter(pl.col("a") > 2).explain()
+    assert plan.index("FILTER") > plan.index("JOIN")
+
+    q = q.filter((pl.col("a") > 2).no_pushdown())
+    plan = q.explain()
+    assert plan.index("FILTER") < plan.index("JOIN")
+
+    expected = pl.DataFrame({"id": [2, 3], "a": [5, 9], "b": ["y", "z"]})
+    assert_frame_equal(q.collect(), expected)